@@ -866,7 +866,7 @@ fn read_text(value: Option<&Value>) -> Option<String> {
 }
 
 #[cfg(feature = "native-codex-runtime")]
-fn next_approval_action_id(native: &mut crate::NativeSessionHandles) -> String {
+pub(crate) fn next_approval_action_id(native: &mut crate::NativeSessionHandles) -> String {
     let value = native.next_approval_id;
     native.next_approval_id += 1;
     format!("approval-{value}")
@@ -51,10 +51,12 @@ const ALICIA_NATIVE_INTERNAL_PROFILE: &str = "__alicia_native_internal";
 
 #[cfg(feature = "native-codex-runtime")]
 fn native_internal_profile_cli_overrides() -> Vec<(String, toml::Value)> {
-    vec![(
+    let mut overrides = vec![(
         format!("profiles.{ALICIA_NATIVE_INTERNAL_PROFILE}"),
         toml::Value::Table(TomlMap::new()),
-    )]
+    )];
+    overrides.extend(crate::neuro_mcp_server_runtime::neuro_mcp_server_cli_overrides());
+    overrides
 }
 
 #[cfg(feature = "native-codex-runtime")]
@@ -32,19 +32,47 @@ use crate::account_runtime::{
 };
 #[cfg(feature = "native-codex-runtime")]
 use crate::app_server_runtime::request_app_server_method;
+use crate::commit_hook_runtime::{build_commit_hook_report, GIT_COMMIT_HOOKS};
+use crate::commit_message_runtime::{
+    append_co_author_trailers, is_valid_git_identity, validate_conventional_commit_message,
+    ConventionalCommitRules,
+};
+#[cfg(feature = "native-codex-runtime")]
+use crate::commit_message_runtime::{
+    build_commit_message_prompt, commit_message_output_schema, parse_generated_commit_message,
+};
+use crate::git_conflict_runtime::{
+    build_conflict_resolution_prompt, contains_conflict_markers, parse_git_unmerged_entries,
+    GitConflictFile, GitConflictReadRequest, GitConflictReadResponse, GitConflictResolveRequest,
+    GitConflictResolveResponse, GitUnmergedEntry,
+};
+use crate::git_history_runtime::{
+    parse_git_blame_porcelain, parse_git_file_history, validate_line_range, GitBlameRequest,
+    GitBlameResponse, GitFileHistoryRequest, GitFileHistoryResponse, DEFAULT_FILE_HISTORY_LIMIT,
+    GIT_FILE_HISTORY_FORMAT, MAX_FILE_HISTORY_LIMIT,
+};
 use crate::mcp_runtime::{
     parse_mcp_server_list_runtime_result, McpLoginRequest, McpLoginResponse, McpReloadResponse,
     McpServerListResponse, McpStartupWarmupResponse,
 };
 use crate::models_runtime::fetch_models_for_picker;
+#[cfg(feature = "native-codex-runtime")]
+use crate::neuro_agent_runtime::NEURO_AGENT_SERVER_NAME;
+#[cfg(feature = "native-codex-runtime")]
+use crate::neuro_mcp_server_runtime::neuro_mcp_endpoint;
+use crate::thread_export_runtime::{
+    build_thread_export_summary, export_file_stem, is_safe_revision_range, load_thread_commits,
+    parse_exported_commits, record_thread_commit, thread_commits_path, GitExportedCommit,
+    GitThreadExportFormat, GitThreadExportRequest, GitThreadExportResponse,
+};
 use crate::{
     default_codex_binary, lock_active_session, resolve_codex_launch, ActiveSessionTransport,
-    AppState, CodexModelListResponse, CodexWorkspaceReadFileRequest,
-    CodexWorkspaceReadFileResponse, CodexWorkspaceWriteFileRequest,
-    CodexWorkspaceWriteFileResponse, GitCommandExecutionResult, GitCommitApprovedReviewRequest,
-    GitCommitApprovedReviewResponse, GitWorkspaceChange, GitWorkspaceChangesRequest,
-    GitWorkspaceChangesResponse, RunCodexCommandResponse, RuntimeCapabilitiesResponse,
-    RuntimeContractMetadata,
+    AppState, CodexInputItem, CodexModelListResponse, CodexThreadReadRequest, CodexThreadSummary,
+    CodexTurnRunRequest, CodexWorkspaceReadFileRequest, CodexWorkspaceReadFileResponse,
+    CodexWorkspaceWriteFileRequest, CodexWorkspaceWriteFileResponse, GitCommandExecutionResult,
+    GitCommitApprovedReviewRequest, GitCommitApprovedReviewResponse, GitCommitSigningOptions,
+    GitWorkspaceChange, GitWorkspaceChangesRequest, GitWorkspaceChangesResponse,
+    RunCodexCommandResponse, RuntimeCapabilitiesResponse, RuntimeContractMetadata,
 };
 
 #[cfg(feature = "native-codex-runtime")]
@@ -115,10 +143,12 @@ const ALICIA_NATIVE_INTERNAL_PROFILE: &str = "__alicia_native_internal";
 
 #[cfg(feature = "native-codex-runtime")]
 fn native_internal_profile_cli_overrides() -> Vec<(String, toml::Value)> {
-    vec![(
+    let mut overrides = vec![(
         format!("profiles.{ALICIA_NATIVE_INTERNAL_PROFILE}"),
         toml::Value::Table(TomlMap::new()),
-    )]
+    )];
+    overrides.extend(crate::neuro_mcp_server_runtime::neuro_mcp_server_cli_overrides());
+    overrides
 }
 
 #[cfg(feature = "native-codex-runtime")]
@@ -262,6 +292,22 @@ async fn collect_native_mcp_server_list(
         }));
     }
 
+    // The `neuro` server is registered through config overrides while it runs; otherwise
+    // it is listed as disconnected with the reason it could not start.
+    if !config_servers.contains_key(NEURO_AGENT_SERVER_NAME) {
+        if let Err(reason) = neuro_mcp_endpoint() {
+            data.push(json!({
+                "name": NEURO_AGENT_SERVER_NAME,
+                "transport": "streamable-http",
+                "status": "disconnected",
+                "statusReason": reason,
+                "authStatus": auth_status_label(McpAuthStatus::Unsupported),
+                "tools": Vec::<String>::new(),
+                "url": Value::Null,
+            }));
+        }
+    }
+
     let result = json!({
         "data": data,
         "total": data.len(),
@@ -272,11 +318,11 @@ async fn collect_native_mcp_server_list(
     ))
 }
 
-fn disable_methods_for_native_transport(methods: &mut HashMap<String, bool>) {
-    const NATIVE_UNSUPPORTED_METHODS: &[&str] = &["tool.call.dynamic"];
+fn enable_methods_for_native_transport(methods: &mut HashMap<String, bool>) {
+    const NATIVE_ONLY_METHODS: &[&str] = &["tool.call.dynamic"];
 
-    for method in NATIVE_UNSUPPORTED_METHODS {
-        methods.insert((*method).to_string(), false);
+    for method in NATIVE_ONLY_METHODS {
+        methods.insert((*method).to_string(), true);
     }
 }
 
@@ -469,7 +515,10 @@ fn to_literal_pathspec(path: &str) -> String {
 const WORKSPACE_READ_OPERATION: &str = "codex_workspace_read_file";
 const WORKSPACE_WRITE_OPERATION: &str = "codex_workspace_write_file";
 
-fn active_workspace_cwd(state: &State<'_, AppState>, operation: &str) -> Result<PathBuf, String> {
+pub(crate) fn active_workspace_cwd(
+    state: &State<'_, AppState>,
+    operation: &str,
+) -> Result<PathBuf, String> {
     let active = lock_active_session(state.inner())?;
     let Some(session) = active.as_ref() else {
         return Err(format!("{operation} requires an active codex session"));
@@ -478,7 +527,10 @@ fn active_workspace_cwd(state: &State<'_, AppState>, operation: &str) -> Result<
     Ok(session.cwd.clone())
 }
 
-fn normalize_workspace_relative_path(operation: &str, path: &str) -> Result<PathBuf, String> {
+pub(crate) fn normalize_workspace_relative_path(
+    operation: &str,
+    path: &str,
+) -> Result<PathBuf, String> {
     if path.is_empty() {
         return Err(format!("{operation} rejected empty path"));
     }
@@ -607,11 +659,191 @@ fn ensure_secure_workspace_parent(
         }
     }
 
+    let parent_dir = fs::canonicalize(&parent_dir).map_err(|error| {
+        format!(
+            "{operation} failed to canonicalize parent '{}': {error}",
+            parent_dir.display()
+        )
+    })?;
     ensure_path_within_workspace(workspace_root, &parent_dir, operation, "parent")?;
     Ok(parent_dir)
 }
 
-pub(crate) fn git_commit_approved_review_impl(
+fn skipped_git_commit_result(reason: String) -> GitCommandExecutionResult {
+    GitCommandExecutionResult {
+        stdout: String::new(),
+        stderr: reason,
+        status: -1,
+        success: false,
+    }
+}
+
+struct GitCommitSigning {
+    format: Option<&'static str>,
+    key: Option<String>,
+}
+
+fn resolve_git_commit_signing(
+    options: &GitCommitSigningOptions,
+) -> Result<GitCommitSigning, String> {
+    let format = match options
+        .format
+        .as_deref()
+        .map(|entry| entry.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("") => None,
+        Some("gpg") | Some("openpgp") => Some("openpgp"),
+        Some("ssh") => Some("ssh"),
+        Some("x509") => Some("x509"),
+        Some(other) => {
+            return Err(format!(
+                "unsupported commit signing format `{other}` (expected gpg, ssh or x509)"
+            ));
+        }
+    };
+
+    let key = options
+        .key
+        .as_deref()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string);
+    if key
+        .as_deref()
+        .is_some_and(|entry| entry.starts_with('-') || entry.chars().any(char::is_control))
+    {
+        return Err(
+            "commit signing key must not start with `-` or contain control characters".to_string(),
+        );
+    }
+
+    Ok(GitCommitSigning { format, key })
+}
+
+fn installed_git_commit_hooks(cwd: &Path) -> Vec<String> {
+    let mut git_hooks_path = Command::new("git");
+    git_hooks_path
+        .current_dir(cwd)
+        .arg("rev-parse")
+        .arg("--git-path")
+        .arg("hooks");
+    let hooks_dir = match run_git_command_impl(git_hooks_path, "rev-parse") {
+        Ok(result) if result.success => cwd.join(result.stdout.trim()),
+        _ => return Vec::new(),
+    };
+
+    GIT_COMMIT_HOOKS
+        .iter()
+        .filter(|hook| hooks_dir.join(hook).is_file())
+        .map(|hook| (*hook).to_string())
+        .collect()
+}
+
+fn git_head_sha(cwd: &Path) -> Option<String> {
+    let mut git_rev_parse = Command::new("git");
+    git_rev_parse
+        .current_dir(cwd)
+        .arg("rev-parse")
+        .arg("--verify")
+        .arg("HEAD");
+    run_git_command_impl(git_rev_parse, "rev-parse")
+        .ok()
+        .filter(|result| result.success)
+        .map(|result| result.stdout.trim().to_string())
+}
+
+/// Tree id of the current index, so a failed approved-review commit can undo its `git add`.
+fn snapshot_git_index(cwd: &Path) -> Option<String> {
+    let mut git_write_tree = Command::new("git");
+    git_write_tree.current_dir(cwd).arg("write-tree");
+    run_git_command_impl(git_write_tree, "write-tree")
+        .ok()
+        .filter(|result| result.success)
+        .map(|result| result.stdout.trim().to_string())
+        .filter(|tree| !tree.is_empty())
+}
+
+fn restore_git_index(cwd: &Path, snapshot: Option<&str>, literal_pathspecs: &[String]) -> bool {
+    let mut git_restore = Command::new("git");
+    git_restore.current_dir(cwd);
+    match snapshot {
+        Some(tree) => git_restore.arg("read-tree").arg(tree),
+        None => git_restore
+            .arg("reset")
+            .arg("-q")
+            .arg("--")
+            .args(literal_pathspecs.iter()),
+    };
+    run_git_command_impl(git_restore, "restore index").is_ok_and(|result| result.success)
+}
+
+fn staged_changes_note(restored: bool) -> &'static str {
+    if restored {
+        "staged changes were reverted"
+    } else {
+        "failed to revert staged changes"
+    }
+}
+
+async fn generate_approved_review_commit_message(
+    state: &State<'_, AppState>,
+    cwd: &Path,
+    literal_pathspecs: &[String],
+    rules: Option<&ConventionalCommitRules>,
+) -> Result<String, String> {
+    let mut git_diff = Command::new("git");
+    git_diff
+        .current_dir(cwd)
+        .arg("diff")
+        .arg("--cached")
+        .arg("--no-color")
+        .arg("--no-ext-diff")
+        .arg("--")
+        .args(literal_pathspecs.iter());
+    let diff = run_git_command_impl(git_diff, "diff")?;
+    if !diff.success {
+        return Err(format!(
+            "git diff --cached failed: {}",
+            git_result_details(&diff)
+        ));
+    }
+    if diff.stdout.trim().is_empty() {
+        return Err("no staged changes found for the approved paths".to_string());
+    }
+
+    #[cfg(feature = "native-codex-runtime")]
+    {
+        let runtime = {
+            let active = lock_active_session(state.inner())?;
+            active
+                .as_ref()
+                .and_then(native_reload_context_from_session)
+                .map(|(runtime, _)| runtime)
+        }
+        .ok_or_else(|| "commit message generation requires an active codex session".to_string())?;
+        let runtime_config = crate::lock_runtime_config(state.inner())?.clone();
+
+        let raw = crate::session_turn_runtime::run_ephemeral_structured_turn(
+            runtime,
+            &runtime_config,
+            cwd,
+            build_commit_message_prompt(&diff.stdout, rules),
+            commit_message_output_schema(),
+        )
+        .await?;
+        Ok(parse_generated_commit_message(&raw)?.to_message())
+    }
+
+    #[cfg(not(feature = "native-codex-runtime"))]
+    {
+        let _ = (state, rules);
+        Err("commit message generation requires native runtime support in this build".to_string())
+    }
+}
+
+pub(crate) async fn git_commit_approved_review_impl(
+    state: State<'_, AppState>,
     request: GitCommitApprovedReviewRequest,
 ) -> Result<GitCommitApprovedReviewResponse, String> {
     let mut paths: Vec<String> = Vec::new();
@@ -635,11 +867,65 @@ pub(crate) fn git_commit_approved_review_impl(
     let literal_pathspecs: Vec<String> =
         paths.iter().map(|path| to_literal_pathspec(path)).collect();
 
-    let message = request.message.trim().to_string();
-    if message.is_empty() {
-        return Err("git_commit_approved_review requires a non-empty message".to_string());
+    let provided_message = request
+        .message
+        .as_deref()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string);
+    if provided_message.is_none() && !request.generate_message && !request.amend {
+        return Err(
+            "git_commit_approved_review requires a non-empty message or generateMessage"
+                .to_string(),
+        );
+    }
+    if provided_message.is_some() && request.generate_message {
+        return Err(
+            "git_commit_approved_review accepts either message or generateMessage, not both"
+                .to_string(),
+        );
     }
 
+    let author = request
+        .author
+        .as_deref()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string);
+    if let Some(author) = author.as_deref() {
+        if !is_valid_git_identity(author) {
+            return Err(format!(
+                "git_commit_approved_review requires author as `Name <email>`: {author}"
+            ));
+        }
+    }
+
+    let co_authors: Vec<String> = request
+        .co_authors
+        .iter()
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    if let Some(invalid) = co_authors
+        .iter()
+        .find(|entry| !is_valid_git_identity(entry))
+    {
+        return Err(format!(
+            "git_commit_approved_review requires co-authors as `Name <email>`: {invalid}"
+        ));
+    }
+    if !co_authors.is_empty() && provided_message.is_none() && !request.generate_message {
+        return Err(
+            "git_commit_approved_review requires a message to add co-author trailers".to_string(),
+        );
+    }
+
+    let signing = request
+        .signing
+        .as_ref()
+        .map(resolve_git_commit_signing)
+        .transpose()?;
+
     let cwd = request
         .cwd
         .as_deref()
@@ -656,6 +942,36 @@ pub(crate) fn git_commit_approved_review_impl(
         return Err("git_commit_approved_review requires an absolute cwd".to_string());
     }
 
+    // A provided message is checked before anything is staged.
+    let provided_validation = request
+        .conventional_commits
+        .as_ref()
+        .zip(provided_message.as_deref())
+        .map(|(rules, message)| validate_conventional_commit_message(message, rules));
+    if provided_validation
+        .as_ref()
+        .is_some_and(|entry| !entry.valid)
+    {
+        return Ok(GitCommitApprovedReviewResponse {
+            success: false,
+            add: skipped_git_commit_result(
+                "git add skipped because commit message failed conventional-commit validation"
+                    .to_string(),
+            ),
+            commit: skipped_git_commit_result(
+                "git commit skipped because commit message failed conventional-commit validation"
+                    .to_string(),
+            ),
+            message: provided_message
+                .map(|message| append_co_author_trailers(&message, &co_authors)),
+            generated_message: false,
+            validation: provided_validation,
+            hooks: None,
+            warnings: Vec::new(),
+        });
+    }
+
+    let index_snapshot = snapshot_git_index(&cwd);
     let mut git_add = Command::new("git");
     git_add
         .current_dir(&cwd)
@@ -665,29 +981,159 @@ pub(crate) fn git_commit_approved_review_impl(
         .args(literal_pathspecs.iter());
     let add = run_git_command_impl(git_add, "add")?;
 
-    let commit = if add.success {
-        let mut git_commit = Command::new("git");
-        git_commit
-            .current_dir(&cwd)
-            .arg("commit")
-            .arg("-m")
-            .arg(&message)
-            .arg("--")
-            .args(literal_pathspecs.iter());
-        run_git_command_impl(git_commit, "commit")?
+    if !add.success {
+        return Ok(GitCommitApprovedReviewResponse {
+            success: false,
+            add,
+            commit: skipped_git_commit_result(
+                "git commit skipped because git add failed".to_string(),
+            ),
+            message: provided_message,
+            generated_message: false,
+            validation: None,
+            hooks: None,
+            warnings: Vec::new(),
+        });
+    }
+
+    let (message, validation) = if request.generate_message {
+        match generate_approved_review_commit_message(
+            &state,
+            &cwd,
+            &literal_pathspecs,
+            request.conventional_commits.as_ref(),
+        )
+        .await
+        {
+            Ok(message) => {
+                let validation = request
+                    .conventional_commits
+                    .as_ref()
+                    .map(|rules| validate_conventional_commit_message(&message, rules));
+                (Some(message), validation)
+            }
+            Err(error) => {
+                let restored =
+                    restore_git_index(&cwd, index_snapshot.as_deref(), &literal_pathspecs);
+                return Ok(GitCommitApprovedReviewResponse {
+                    success: false,
+                    add,
+                    commit: skipped_git_commit_result(format!(
+                        "git commit skipped because commit message generation failed: {error} ({})",
+                        staged_changes_note(restored)
+                    )),
+                    message: None,
+                    generated_message: false,
+                    validation: None,
+                    hooks: None,
+                    warnings: Vec::new(),
+                });
+            }
+        }
     } else {
-        GitCommandExecutionResult {
-            stdout: String::new(),
-            stderr: "git commit skipped because git add failed".to_string(),
-            status: -1,
+        (provided_message, provided_validation)
+    };
+    let message = message.map(|message| append_co_author_trailers(&message, &co_authors));
+
+    if validation.as_ref().is_some_and(|entry| !entry.valid) {
+        let restored = restore_git_index(&cwd, index_snapshot.as_deref(), &literal_pathspecs);
+        return Ok(GitCommitApprovedReviewResponse {
             success: false,
+            add,
+            commit: skipped_git_commit_result(format!(
+                "git commit skipped because commit message failed conventional-commit validation ({})",
+                staged_changes_note(restored)
+            )),
+            message,
+            generated_message: request.generate_message,
+            validation,
+            hooks: None,
+            warnings: Vec::new(),
+        });
+    }
+
+    let mut git_commit = Command::new("git");
+    git_commit.current_dir(&cwd);
+    if let Some(signing) = signing.as_ref() {
+        if let Some(format) = signing.format {
+            git_commit.arg("-c").arg(format!("gpg.format={format}"));
+        }
+    }
+    git_commit.arg("commit");
+    if request.amend {
+        git_commit.arg("--amend");
+    }
+    if request.no_verify {
+        git_commit.arg("--no-verify");
+    }
+    if let Some(author) = author.as_deref() {
+        git_commit.arg(format!("--author={author}"));
+    }
+    if let Some(signing) = signing.as_ref() {
+        match signing.key.as_deref() {
+            Some(key) => git_commit.arg(format!("--gpg-sign={key}")),
+            None => git_commit.arg("--gpg-sign"),
+        };
+    }
+    match message.as_deref() {
+        Some(message) => git_commit.arg("-m").arg(message),
+        None => git_commit.arg("--no-edit"),
+    };
+    git_commit.arg("--").args(literal_pathspecs.iter());
+    let thread_id = request
+        .thread_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty());
+    let amended_sha = if request.amend && thread_id.is_some() {
+        git_head_sha(&cwd)
+    } else {
+        None
+    };
+    let mut commit = match run_git_command_impl(git_commit, "commit") {
+        Ok(commit) => commit,
+        Err(error) => {
+            restore_git_index(&cwd, index_snapshot.as_deref(), &literal_pathspecs);
+            return Err(error);
         }
     };
+    if !commit.success {
+        let restored = restore_git_index(&cwd, index_snapshot.as_deref(), &literal_pathspecs);
+        if !commit.stderr.is_empty() && !commit.stderr.ends_with('\n') {
+            commit.stderr.push('\n');
+        }
+        commit.stderr.push_str(staged_changes_note(restored));
+    }
+    let mut warnings = Vec::new();
+    if let (true, Some(thread_id), Some(sha)) = (commit.success, thread_id, git_head_sha(&cwd)) {
+        if let Err(error) = record_thread_commit(
+            &thread_commits_path(),
+            thread_id,
+            &sha,
+            amended_sha.as_deref(),
+        ) {
+            warnings.push(format!(
+                "commit {sha} was not recorded for thread {thread_id}, so git_thread_export will not include it: {error}"
+            ));
+        }
+    }
+
+    let hooks = build_commit_hook_report(
+        installed_git_commit_hooks(&cwd),
+        request.no_verify,
+        commit.success,
+        &format!("{}\n{}", commit.stdout, commit.stderr),
+    );
 
     Ok(GitCommitApprovedReviewResponse {
         success: add.success && commit.success,
         add,
         commit,
+        message,
+        generated_message: request.generate_message,
+        validation,
+        hooks: Some(hooks),
+        warnings,
     })
 }
 
@@ -953,8 +1399,21 @@ pub(crate) fn codex_workspace_read_file_impl(
     let relative_path = normalize_workspace_relative_path(operation, &requested_path)?;
 
     let workspace_cwd = active_workspace_cwd(&state, operation)?;
-    let workspace_root = canonicalize_workspace_root(&workspace_cwd, operation)?;
-    let target_path = workspace_cwd.join(&relative_path);
+    let content = read_workspace_file(&workspace_cwd, &relative_path, operation)?;
+
+    Ok(CodexWorkspaceReadFileResponse {
+        path: requested_path,
+        content,
+    })
+}
+
+pub(crate) fn read_workspace_file(
+    workspace_cwd: &Path,
+    relative_path: &Path,
+    operation: &str,
+) -> Result<String, String> {
+    let workspace_root = canonicalize_workspace_root(workspace_cwd, operation)?;
+    let target_path = workspace_cwd.join(relative_path);
 
     let metadata = fs::metadata(&target_path).map_err(|error| {
         if error.kind() == ErrorKind::NotFound {
@@ -985,29 +1444,21 @@ pub(crate) fn codex_workspace_read_file_impl(
     })?;
     ensure_path_within_workspace(&workspace_root, &canonical_target, operation, "target")?;
 
-    let content = fs::read_to_string(&canonical_target).map_err(|error| {
+    fs::read_to_string(&canonical_target).map_err(|error| {
         format!(
             "{operation} failed to read file '{}': {error}",
             relative_path.display()
         )
-    })?;
-
-    Ok(CodexWorkspaceReadFileResponse {
-        path: requested_path,
-        content,
     })
 }
 
-pub(crate) fn codex_workspace_write_file_impl(
-    state: State<'_, AppState>,
-    request: CodexWorkspaceWriteFileRequest,
-) -> Result<CodexWorkspaceWriteFileResponse, String> {
-    let operation = WORKSPACE_WRITE_OPERATION;
-    let requested_path = request.path;
-    let relative_path = normalize_workspace_relative_path(operation, &requested_path)?;
-
-    let workspace_cwd = active_workspace_cwd(&state, operation)?;
-    let workspace_root = canonicalize_workspace_root(&workspace_cwd, operation)?;
+pub(crate) fn write_workspace_file(
+    workspace_cwd: &Path,
+    relative_path: &Path,
+    content: String,
+    operation: &str,
+) -> Result<(), String> {
+    let workspace_root = canonicalize_workspace_root(workspace_cwd, operation)?;
 
     let file_name = relative_path.file_name().ok_or_else(|| {
         format!(
@@ -1015,16 +1466,21 @@ pub(crate) fn codex_workspace_write_file_impl(
             relative_path.display()
         )
     })?;
-    let parent_dir = ensure_secure_workspace_parent(&workspace_root, &relative_path, operation)?;
+    let parent_dir = ensure_secure_workspace_parent(&workspace_root, relative_path, operation)?;
     let target_path = parent_dir.join(file_name);
 
-    if target_path.exists() {
-        let metadata = fs::symlink_metadata(&target_path).map_err(|error| {
-            format!(
+    // Not `exists()`: it follows symlinks, so a dangling link would be written through.
+    let metadata = match fs::symlink_metadata(&target_path) {
+        Ok(metadata) => Some(metadata),
+        Err(error) if error.kind() == ErrorKind::NotFound => None,
+        Err(error) => {
+            return Err(format!(
                 "{operation} failed to inspect existing path '{}': {error}",
                 target_path.display()
-            )
-        })?;
+            ))
+        }
+    };
+    if let Some(metadata) = metadata {
         if metadata.file_type().is_symlink() {
             let canonical_target = fs::canonicalize(&target_path).map_err(|error| {
                 format!(
@@ -1047,18 +1503,613 @@ pub(crate) fn codex_workspace_write_file_impl(
         }
     }
 
-    fs::write(&target_path, request.content).map_err(|error| {
+    fs::write(&target_path, content).map_err(|error| {
         format!(
             "{operation} failed to write file '{}': {error}",
             relative_path.display()
         )
-    })?;
+    })
+}
+
+pub(crate) fn codex_workspace_write_file_impl(
+    state: State<'_, AppState>,
+    request: CodexWorkspaceWriteFileRequest,
+) -> Result<CodexWorkspaceWriteFileResponse, String> {
+    let operation = WORKSPACE_WRITE_OPERATION;
+    let requested_path = request.path;
+    let relative_path = normalize_workspace_relative_path(operation, &requested_path)?;
+
+    let workspace_cwd = active_workspace_cwd(&state, operation)?;
+    write_workspace_file(&workspace_cwd, &relative_path, request.content, operation)?;
 
     Ok(CodexWorkspaceWriteFileResponse {
         path: requested_path,
     })
 }
 
+const GIT_CONFLICT_READ_OPERATION: &str = "git_conflict_read";
+const GIT_CONFLICT_RESOLVE_OPERATION: &str = "git_conflict_resolve";
+
+fn list_git_unmerged_entries(
+    cwd: &Path,
+    literal_pathspecs: &[String],
+) -> Result<Vec<GitUnmergedEntry>, String> {
+    let mut git_ls_files = Command::new("git");
+    git_ls_files
+        .current_dir(cwd)
+        .arg("ls-files")
+        .arg("--unmerged")
+        .arg("-z")
+        .arg("--")
+        .args(literal_pathspecs.iter());
+    let result = run_git_command_impl(git_ls_files, "ls-files")?;
+    if !result.success {
+        return Err(format!(
+            "failed to list unmerged paths at '{}': {}",
+            cwd.display(),
+            git_result_details(&result)
+        ));
+    }
+
+    parse_git_unmerged_entries(result.stdout.as_bytes())
+}
+
+fn read_git_conflict_stage(cwd: &Path, object_id: Option<&str>) -> Result<Option<String>, String> {
+    let Some(object_id) = object_id else {
+        return Ok(None);
+    };
+
+    let mut git_cat_file = Command::new("git");
+    git_cat_file
+        .current_dir(cwd)
+        .arg("cat-file")
+        .arg("blob")
+        .arg(object_id);
+    let result = run_git_command_impl(git_cat_file, "cat-file")?;
+    if !result.success {
+        return Err(format!(
+            "failed to read conflict stage {object_id}: {}",
+            git_result_details(&result)
+        ));
+    }
+
+    Ok(Some(result.stdout))
+}
+
+pub(crate) async fn git_conflict_read_impl(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: GitConflictReadRequest,
+) -> Result<GitConflictReadResponse, String> {
+    let operation = GIT_CONFLICT_READ_OPERATION;
+    let cwd = active_workspace_cwd(&state, operation)?;
+    validate_workspace_cwd(&cwd)?;
+    ensure_git_repository(&cwd)?;
+
+    let mut literal_pathspecs = Vec::new();
+    for entry in &request.paths {
+        let normalized = entry.trim();
+        if normalized.is_empty() {
+            continue;
+        }
+        if !is_safe_git_path(normalized) {
+            return Err(format!("{operation} rejected unsafe path: {normalized}"));
+        }
+        literal_pathspecs.push(to_literal_pathspec(normalized));
+    }
+
+    let mut files = Vec::new();
+    for entry in list_git_unmerged_entries(&cwd, &literal_pathspecs)? {
+        let base = read_git_conflict_stage(&cwd, entry.base.as_deref())?;
+        let ours = read_git_conflict_stage(&cwd, entry.ours.as_deref())?;
+        let theirs = read_git_conflict_stage(&cwd, entry.theirs.as_deref())?;
+        let working = fs::read(cwd.join(&entry.path))
+            .ok()
+            .map(|bytes| String::from_utf8_lossy(&bytes).to_string());
+        let binary = [&base, &ours, &theirs, &working]
+            .into_iter()
+            .flatten()
+            .any(|content| content.contains('\0'));
+
+        files.push(if binary {
+            GitConflictFile {
+                path: entry.path,
+                base: None,
+                ours: None,
+                theirs: None,
+                working: None,
+                binary,
+            }
+        } else {
+            GitConflictFile {
+                path: entry.path,
+                base,
+                ours,
+                theirs,
+                working,
+                binary,
+            }
+        });
+    }
+
+    let turn = if request.start_agent_turn && !files.is_empty() {
+        let turn_request = CodexTurnRunRequest {
+            thread_id: request.thread_id,
+            input_items: vec![CodexInputItem {
+                item_type: "text".to_string(),
+                text: Some(build_conflict_resolution_prompt(&files)),
+                path: None,
+                image_url: None,
+                name: None,
+            }],
+            output_schema: None,
+        };
+        Some(crate::session_runtime::codex_turn_run_impl(app, state, turn_request).await?)
+    } else {
+        None
+    };
+
+    Ok(GitConflictReadResponse {
+        cwd: cwd.to_string_lossy().to_string(),
+        files,
+        turn,
+    })
+}
+
+pub(crate) fn git_conflict_resolve_impl(
+    state: State<'_, AppState>,
+    request: GitConflictResolveRequest,
+) -> Result<GitConflictResolveResponse, String> {
+    let operation = GIT_CONFLICT_RESOLVE_OPERATION;
+    let requested_path = request.path.trim().to_string();
+    if !is_safe_git_path(&requested_path) {
+        return Err(format!(
+            "{operation} rejected unsafe path: {requested_path}"
+        ));
+    }
+    let relative_path = normalize_workspace_relative_path(operation, &requested_path)?;
+
+    let cwd = active_workspace_cwd(&state, operation)?;
+    validate_workspace_cwd(&cwd)?;
+    ensure_git_repository(&cwd)?;
+
+    // Exact match: a directory pathspec would also match the conflicted files below it.
+    if !list_git_unmerged_entries(&cwd, &[])?
+        .iter()
+        .any(|entry| Path::new(&entry.path) == relative_path)
+    {
+        return Err(format!(
+            "{operation} path '{requested_path}' is not in a conflicted state"
+        ));
+    }
+    let literal_pathspec = to_literal_pathspec(&requested_path);
+    if contains_conflict_markers(&request.content) {
+        return Err(format!(
+            "{operation} rejected content for '{requested_path}': conflict markers are still present"
+        ));
+    }
+
+    write_workspace_file(&cwd, &relative_path, request.content, operation)?;
+
+    let mut git_add = Command::new("git");
+    git_add
+        .current_dir(&cwd)
+        .arg("add")
+        .arg("--")
+        .arg(&literal_pathspec);
+    let add = run_git_command_impl(git_add, "add")?;
+
+    let remaining = list_git_unmerged_entries(&cwd, &[])?
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect::<Vec<_>>();
+
+    Ok(GitConflictResolveResponse {
+        path: requested_path.clone(),
+        resolved: add.success && !remaining.contains(&requested_path),
+        add,
+        remaining,
+    })
+}
+
+const GIT_THREAD_EXPORT_OPERATION: &str = "git_thread_export";
+
+/// Either an explicit range or the commits recorded for the thread by `git_commit_approved_review`.
+enum ThreadExportRevisions {
+    Range(String),
+    Recorded(Vec<String>),
+}
+
+fn git_thread_export_revisions(
+    thread: &CodexThreadSummary,
+    requested_thread_id: &str,
+    revision_range: Option<&str>,
+) -> Result<ThreadExportRevisions, String> {
+    if let Some(range) = revision_range
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        if !is_safe_revision_range(range) {
+            return Err(format!(
+                "{GIT_THREAD_EXPORT_OPERATION} rejected unsafe revision range: {range}"
+            ));
+        }
+        return Ok(ThreadExportRevisions::Range(range.to_string()));
+    }
+
+    let path = thread_commits_path();
+    for thread_id in [
+        Some(thread.id.as_str()),
+        Some(requested_thread_id.trim()),
+        thread.codex_thread_id.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|entry| !entry.is_empty())
+    {
+        let commits = load_thread_commits(&path, thread_id)?;
+        if !commits.is_empty() {
+            return Ok(ThreadExportRevisions::Recorded(commits));
+        }
+    }
+    Err(format!(
+        "{GIT_THREAD_EXPORT_OPERATION} has no commits recorded for thread `{}`; commit through the review panel or pass a revisionRange",
+        thread.id
+    ))
+}
+
+/// Bundles need a ref and a contiguous history, so recorded commits interleaved with others are refused.
+fn recorded_commits_bundle_args(
+    cwd: &Path,
+    thread_id: &str,
+    commits: &[GitExportedCommit],
+    export_ref: &str,
+) -> Result<Vec<String>, String> {
+    let operation = GIT_THREAD_EXPORT_OPERATION;
+    let (Some(first), Some(last)) = (commits.first(), commits.last()) else {
+        return Err(format!("{operation} has no commits to bundle"));
+    };
+    let mut git_parent = Command::new("git");
+    git_parent
+        .current_dir(cwd)
+        .arg("rev-parse")
+        .arg("--verify")
+        .arg("--quiet")
+        .arg(format!("{}^", first.sha));
+    let parent = run_git_command_impl(git_parent, "rev-parse")?;
+    let exclude_parent = parent.success.then(|| format!("^{}", parent.stdout.trim()));
+
+    let mut git_rev_list = Command::new("git");
+    git_rev_list
+        .current_dir(cwd)
+        .arg("rev-list")
+        .arg("--reverse")
+        .arg(&last.sha)
+        .args(exclude_parent.iter())
+        .arg("--");
+    let history = run_git_export_step(git_rev_list, "rev-list")?;
+    let history = history.lines().map(str::trim).collect::<Vec<_>>();
+    if history.len() != commits.len()
+        || history
+            .iter()
+            .zip(commits)
+            .any(|(entry, commit)| *entry != commit.sha)
+    {
+        return Err(format!(
+            "{operation} cannot bundle thread `{thread_id}`: its commits are interleaved with other commits; use the `patch` format"
+        ));
+    }
+
+    let mut git_update_ref = Command::new("git");
+    git_update_ref
+        .current_dir(cwd)
+        .arg("update-ref")
+        .arg(export_ref)
+        .arg(&last.sha);
+    run_git_export_step(git_update_ref, "update-ref")?;
+    let mut args = vec![export_ref.to_string()];
+    args.extend(exclude_parent);
+    Ok(args)
+}
+
+fn run_git_export_step(command: Command, operation: &str) -> Result<String, String> {
+    let result = run_git_command_impl(command, operation)?;
+    if !result.success {
+        return Err(format!(
+            "{GIT_THREAD_EXPORT_OPERATION} failed to run git {operation}: {}",
+            git_result_details(&result)
+        ));
+    }
+    Ok(result.stdout)
+}
+
+pub(crate) async fn git_thread_export_impl(
+    state: State<'_, AppState>,
+    request: GitThreadExportRequest,
+) -> Result<GitThreadExportResponse, String> {
+    let operation = GIT_THREAD_EXPORT_OPERATION;
+    let format = GitThreadExportFormat::parse(&request.format)?;
+    let output_dir = PathBuf::from(request.output_dir.trim());
+    if !output_dir.is_absolute() {
+        return Err(format!("{operation} requires an absolute outputDir"));
+    }
+
+    let workspace_cwd = active_workspace_cwd(&state, operation)?;
+    let thread = crate::session_runtime::codex_thread_read_impl(
+        state,
+        CodexThreadReadRequest {
+            thread_id: request.thread_id.clone(),
+            include_turns: Some(true),
+        },
+    )
+    .await?
+    .thread;
+
+    let thread_cwd = PathBuf::from(thread.cwd.trim());
+    let cwd = if thread_cwd.is_absolute() && thread_cwd.is_dir() {
+        thread_cwd
+    } else {
+        workspace_cwd
+    };
+    validate_workspace_cwd(&cwd)?;
+    ensure_git_repository(&cwd)?;
+
+    fs::create_dir_all(&output_dir).map_err(|error| {
+        format!(
+            "{operation} failed to create output directory '{}': {error}",
+            output_dir.display()
+        )
+    })?;
+
+    let revisions = git_thread_export_revisions(
+        &thread,
+        &request.thread_id,
+        request.revision_range.as_deref(),
+    )?;
+    let commits = match &revisions {
+        ThreadExportRevisions::Range(range) => {
+            let mut git_log = Command::new("git");
+            git_log
+                .current_dir(&cwd)
+                .arg("log")
+                .arg("--reverse")
+                .arg("--format=%H%x09%s")
+                .arg(range)
+                .arg("--");
+            parse_exported_commits(&run_git_export_step(git_log, "log")?)
+        }
+        ThreadExportRevisions::Recorded(shas) => {
+            let mut git_log = Command::new("git");
+            git_log
+                .current_dir(&cwd)
+                .arg("log")
+                .arg("--no-walk=unsorted")
+                .arg("--format=%H%x09%s")
+                .args(shas.iter())
+                .arg("--");
+            parse_exported_commits(&run_git_export_step(git_log, "log")?)
+        }
+    };
+    if commits.is_empty() {
+        return Err(format!(
+            "{operation} found no commits for thread `{}`",
+            thread.id
+        ));
+    }
+
+    let file_stem = export_file_stem(&thread.id);
+    let mut files = Vec::new();
+    match format {
+        GitThreadExportFormat::Patch => {
+            for (index, commit) in commits.iter().enumerate() {
+                let mut git_format_patch = Command::new("git");
+                git_format_patch
+                    .current_dir(&cwd)
+                    .arg("format-patch")
+                    .arg("--output-directory")
+                    .arg(&output_dir)
+                    .arg(format!("--start-number={}", index + 1))
+                    .arg("-1")
+                    .arg(&commit.sha);
+                let stdout = run_git_export_step(git_format_patch, "format-patch")?;
+                files.extend(
+                    stdout
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .map(str::to_string),
+                );
+            }
+        }
+        GitThreadExportFormat::Bundle => {
+            let bundle_path = output_dir.join(format!("{file_stem}.bundle"));
+            let export_ref = format!("refs/alicia/export/{file_stem}");
+            let revision_args = match &revisions {
+                ThreadExportRevisions::Range(range) => vec![range.clone()],
+                ThreadExportRevisions::Recorded(_) => {
+                    recorded_commits_bundle_args(&cwd, &thread.id, &commits, &export_ref)?
+                }
+            };
+            let mut git_bundle = Command::new("git");
+            git_bundle
+                .current_dir(&cwd)
+                .arg("bundle")
+                .arg("create")
+                .arg(&bundle_path)
+                .args(revision_args.iter());
+            let bundled = run_git_export_step(git_bundle, "bundle");
+            if revision_args.first() == Some(&export_ref) {
+                let mut git_delete_ref = Command::new("git");
+                git_delete_ref
+                    .current_dir(&cwd)
+                    .arg("update-ref")
+                    .arg("-d")
+                    .arg(&export_ref);
+                let _ = run_git_command_impl(git_delete_ref, "update-ref");
+            }
+            bundled?;
+            files.push(bundle_path.to_string_lossy().to_string());
+        }
+        GitThreadExportFormat::Diff => {
+            // One diff per exported commit, in order, so `git apply` replays only the thread.
+            let mut diff = String::new();
+            for commit in &commits {
+                let mut git_diff_tree = Command::new("git");
+                git_diff_tree
+                    .current_dir(&cwd)
+                    .arg("diff-tree")
+                    .arg("-p")
+                    .arg("--binary")
+                    .arg("--no-color")
+                    .arg("--no-ext-diff")
+                    .arg("--no-commit-id")
+                    .arg("--root")
+                    .arg(&commit.sha);
+                diff.push_str(&run_git_export_step(git_diff_tree, "diff-tree")?);
+            }
+            if diff.trim().is_empty() {
+                return Err(format!(
+                    "{operation} found no changes in the commits of thread `{}`",
+                    thread.id
+                ));
+            }
+            let diff_path = output_dir.join(format!("{file_stem}.diff"));
+            fs::write(&diff_path, diff).map_err(|error| {
+                format!(
+                    "{operation} failed to write '{}': {error}",
+                    diff_path.display()
+                )
+            })?;
+            files.push(diff_path.to_string_lossy().to_string());
+        }
+    }
+
+    let summary_path = if request.include_summary {
+        let summary_path = output_dir.join(format!("{file_stem}.md"));
+        let summary = build_thread_export_summary(&thread, format, &commits, &files);
+        fs::write(&summary_path, summary).map_err(|error| {
+            format!(
+                "{operation} failed to write '{}': {error}",
+                summary_path.display()
+            )
+        })?;
+        Some(summary_path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(GitThreadExportResponse {
+        thread_id: thread.id,
+        format: format.as_str().to_string(),
+        output_dir: output_dir.to_string_lossy().to_string(),
+        files,
+        commits,
+        summary_path,
+    })
+}
+
+const GIT_BLAME_OPERATION: &str = "git_blame";
+const GIT_FILE_HISTORY_OPERATION: &str = "git_file_history";
+
+fn normalize_git_file_path(operation: &str, path: &str) -> Result<String, String> {
+    let normalized = path.trim().to_string();
+    if !is_safe_git_path(&normalized) {
+        return Err(format!("{operation} rejected unsafe path: {normalized}"));
+    }
+    Ok(normalized)
+}
+
+fn git_history_workspace_cwd(
+    state: &State<'_, AppState>,
+    operation: &str,
+) -> Result<PathBuf, String> {
+    let cwd = active_workspace_cwd(state, operation)?;
+    validate_workspace_cwd(&cwd)?;
+    ensure_git_repository(&cwd)?;
+    Ok(cwd)
+}
+
+pub(crate) fn git_blame_in_cwd(
+    cwd: &Path,
+    request: GitBlameRequest,
+) -> Result<GitBlameResponse, String> {
+    let operation = GIT_BLAME_OPERATION;
+    let path = normalize_git_file_path(operation, &request.path)?;
+
+    let mut git_blame = Command::new("git");
+    git_blame.current_dir(cwd).arg("blame").arg("--porcelain");
+    if let Some(range) = request.range.as_ref() {
+        validate_line_range(range)?;
+        git_blame
+            .arg("-L")
+            .arg(format!("{},{}", range.start, range.end));
+    }
+    git_blame.arg("--").arg(&path);
+    let result = run_git_command_impl(git_blame, "blame")?;
+    if !result.success {
+        return Err(format!(
+            "{operation} failed for '{path}': {}",
+            git_result_details(&result)
+        ));
+    }
+
+    Ok(GitBlameResponse {
+        lines: parse_git_blame_porcelain(&result.stdout)?,
+        path,
+    })
+}
+
+pub(crate) fn git_file_history_in_cwd(
+    cwd: &Path,
+    request: GitFileHistoryRequest,
+) -> Result<GitFileHistoryResponse, String> {
+    let operation = GIT_FILE_HISTORY_OPERATION;
+    let path = normalize_git_file_path(operation, &request.path)?;
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_FILE_HISTORY_LIMIT)
+        .clamp(1, MAX_FILE_HISTORY_LIMIT);
+
+    let mut git_log = Command::new("git");
+    git_log
+        .current_dir(cwd)
+        .arg("log")
+        .arg("--follow")
+        .arg("--name-only")
+        .arg(format!("--max-count={limit}"))
+        .arg(GIT_FILE_HISTORY_FORMAT)
+        .arg("--")
+        .arg(&path);
+    let result = run_git_command_impl(git_log, "log")?;
+    if !result.success {
+        return Err(format!(
+            "{operation} failed for '{path}': {}",
+            git_result_details(&result)
+        ));
+    }
+
+    Ok(GitFileHistoryResponse {
+        entries: parse_git_file_history(&result.stdout),
+        path,
+    })
+}
+
+pub(crate) fn git_blame_impl(
+    state: State<'_, AppState>,
+    request: GitBlameRequest,
+) -> Result<GitBlameResponse, String> {
+    let cwd = git_history_workspace_cwd(&state, GIT_BLAME_OPERATION)?;
+    git_blame_in_cwd(&cwd, request)
+}
+
+pub(crate) fn git_file_history_impl(
+    state: State<'_, AppState>,
+    request: GitFileHistoryRequest,
+) -> Result<GitFileHistoryResponse, String> {
+    let cwd = git_history_workspace_cwd(&state, GIT_FILE_HISTORY_OPERATION)?;
+    git_file_history_in_cwd(&cwd, request)
+}
+
 pub(crate) fn codex_models_list_impl(
     state: State<'_, AppState>,
 ) -> Result<CodexModelListResponse, String> {
@@ -1089,7 +2140,7 @@ pub(crate) async fn codex_runtime_capabilities_impl(
             .is_some_and(|session| matches!(session.transport, ActiveSessionTransport::Native(_)))
     };
     if native_transport_active {
-        disable_methods_for_native_transport(&mut methods);
+        enable_methods_for_native_transport(&mut methods);
     }
 
     Ok(RuntimeCapabilitiesResponse {
@@ -1573,7 +2624,7 @@ pub(crate) async fn codex_mcp_reload_impl(
 #[cfg(test)]
 mod tests {
     use super::{
-        classify_git_status, default_runtime_capabilities, disable_methods_for_native_transport,
+        classify_git_status, default_runtime_capabilities, enable_methods_for_native_transport,
         extract_capabilities_contract_version, parse_git_status_porcelain,
     };
     use serde_json::json;
@@ -1698,7 +2749,7 @@ mod tests {
     fn native_transport_keeps_supported_methods_enabled() {
         let mut methods = default_runtime_capabilities();
 
-        disable_methods_for_native_transport(&mut methods);
+        enable_methods_for_native_transport(&mut methods);
 
         for method in [
             "mcp.warmup",
@@ -1721,12 +2772,13 @@ mod tests {
     }
 
     #[test]
-    fn native_transport_still_disables_unsupported_methods() {
+    fn native_transport_enables_local_dynamic_tools() {
         let mut methods = default_runtime_capabilities();
+        assert_eq!(methods.get("tool.call.dynamic"), Some(&false));
 
-        disable_methods_for_native_transport(&mut methods);
+        enable_methods_for_native_transport(&mut methods);
 
-        assert_eq!(methods.get("tool.call.dynamic"), Some(&false));
+        assert_eq!(methods.get("tool.call.dynamic"), Some(&true));
     }
 
     #[test]
@@ -0,0 +1,215 @@
+use serde::Serialize;
+
+pub const GIT_COMMIT_HOOKS: &[&str] = &[
+    "pre-commit",
+    "prepare-commit-msg",
+    "commit-msg",
+    "post-commit",
+];
+const GIT_VERIFY_HOOKS: &[&str] = &["pre-commit", "commit-msg"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookCheck {
+    pub name: String,
+    pub status: String,
+    pub hook_id: Option<String>,
+    pub exit_code: Option<i32>,
+    pub details: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitHookReport {
+    pub installed: Vec<String>,
+    pub verify_skipped: bool,
+    pub failed_hook: Option<String>,
+    pub checks: Vec<GitHookCheck>,
+}
+
+fn parse_check_line(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim_end();
+    let status = ["Passed", "Failed", "Skipped"]
+        .into_iter()
+        .find(|status| trimmed.ends_with(status))?;
+    let head = &trimmed[..trimmed.len() - status.len()];
+    let head = match head.rfind("(no files to check)") {
+        Some(index) => &head[..index],
+        None => head,
+    };
+    let name_end = head.find("...")?;
+    let name = head[..name_end].trim();
+    if name.is_empty()
+        || !head[name_end..]
+            .trim_end()
+            .chars()
+            .all(|entry| entry == '.')
+    {
+        return None;
+    }
+    Some((name.to_string(), status.to_ascii_lowercase()))
+}
+
+fn parse_husky_failed_hook(line: &str) -> Option<String> {
+    let rest = line.trim().strip_prefix("husky - ")?;
+    let (hook, tail) = rest.split_once(' ')?;
+    if tail.starts_with("hook exited with code") || tail.starts_with("script failed") {
+        Some(hook.to_string())
+    } else {
+        None
+    }
+}
+
+pub fn parse_commit_hook_checks(output: &str) -> Vec<GitHookCheck> {
+    let mut checks: Vec<GitHookCheck> = Vec::new();
+    let mut in_check_details = false;
+
+    for line in output.lines() {
+        if let Some((name, status)) = parse_check_line(line) {
+            checks.push(GitHookCheck {
+                name,
+                status,
+                hook_id: None,
+                exit_code: None,
+                details: Vec::new(),
+            });
+            in_check_details = true;
+            continue;
+        }
+
+        let Some(current) = checks.last_mut().filter(|_| in_check_details) else {
+            continue;
+        };
+        let trimmed = line.trim();
+        if let Some(hook_id) = trimmed.strip_prefix("- hook id:") {
+            current.hook_id = Some(hook_id.trim().to_string());
+        } else if let Some(exit_code) = trimmed.strip_prefix("- exit code:") {
+            current.exit_code = exit_code.trim().parse().ok();
+        } else if let Some(detail) = trimmed.strip_prefix("- ") {
+            current.details.push(detail.to_string());
+        } else if !trimmed.is_empty() && current.status == "failed" {
+            current.details.push(line.trim_end().to_string());
+        }
+    }
+
+    checks
+}
+
+fn reported_git_error(output: &str) -> bool {
+    output.lines().any(|line| {
+        let trimmed = line.trim_start();
+        trimmed.starts_with("fatal:")
+            || trimmed.starts_with("error: ")
+            || trimmed.starts_with("nothing to commit")
+            || trimmed.starts_with("no changes added to commit")
+    })
+}
+
+pub fn build_commit_hook_report(
+    installed: Vec<String>,
+    verify_skipped: bool,
+    commit_succeeded: bool,
+    output: &str,
+) -> GitCommitHookReport {
+    let checks = parse_commit_hook_checks(output);
+    let failed_hook = if commit_succeeded {
+        None
+    } else {
+        let husky = output.lines().find_map(parse_husky_failed_hook);
+        let verify_hooks = installed
+            .iter()
+            .filter(|hook| !verify_skipped && GIT_VERIFY_HOOKS.contains(&hook.as_str()))
+            .collect::<Vec<_>>();
+        husky.or_else(|| {
+            if checks.iter().any(|check| check.status == "failed")
+                || (verify_hooks.len() == 1 && !reported_git_error(output))
+            {
+                verify_hooks.first().map(|hook| (*hook).clone())
+            } else {
+                None
+            }
+        })
+    };
+
+    GitCommitHookReport {
+        installed,
+        verify_skipped,
+        failed_hook,
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_commit_hook_report, parse_commit_hook_checks, GitHookCheck};
+
+    const PRE_COMMIT_OUTPUT: &str = "\
+trim trailing whitespace.................................................Passed
+check yaml...........................................(no files to check)Skipped
+black....................................................................Failed
+- hook id: black
+- exit code: 1
+- files were modified by this hook
+
+reformatted src/app.py
+";
+
+    #[test]
+    fn parses_pre_commit_framework_checks() {
+        let checks = parse_commit_hook_checks(PRE_COMMIT_OUTPUT);
+        assert_eq!(checks.len(), 3);
+        assert_eq!(checks[0].status, "passed");
+        assert_eq!(checks[1].name, "check yaml");
+        assert_eq!(checks[1].status, "skipped");
+        assert_eq!(
+            checks[2],
+            GitHookCheck {
+                name: "black".to_string(),
+                status: "failed".to_string(),
+                hook_id: Some("black".to_string()),
+                exit_code: Some(1),
+                details: vec![
+                    "files were modified by this hook".to_string(),
+                    "reformatted src/app.py".to_string(),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn attributes_failed_checks_to_pre_commit_hook() {
+        let report = build_commit_hook_report(
+            vec!["pre-commit".to_string(), "commit-msg".to_string()],
+            false,
+            false,
+            PRE_COMMIT_OUTPUT,
+        );
+        assert_eq!(report.failed_hook.as_deref(), Some("pre-commit"));
+    }
+
+    #[test]
+    fn uses_husky_hook_name_when_reported() {
+        let report = build_commit_hook_report(
+            vec!["pre-commit".to_string(), "commit-msg".to_string()],
+            false,
+            false,
+            "subject may not be empty\nhusky - commit-msg hook exited with code 1 (error)\n",
+        );
+        assert_eq!(report.failed_hook.as_deref(), Some("commit-msg"));
+    }
+
+    #[test]
+    fn does_not_blame_hooks_for_git_errors_or_skipped_verification() {
+        let report = build_commit_hook_report(
+            vec!["pre-commit".to_string()],
+            false,
+            false,
+            "fatal: cannot lock ref 'HEAD'\n",
+        );
+        assert_eq!(report.failed_hook, None);
+
+        let report =
+            build_commit_hook_report(vec!["pre-commit".to_string()], true, false, "lint failed\n");
+        assert_eq!(report.failed_hook, None);
+    }
+}
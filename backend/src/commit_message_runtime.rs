@@ -0,0 +1,486 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+const DEFAULT_CONVENTIONAL_COMMIT_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+const DEFAULT_MAX_SUBJECT_LENGTH: usize = 72;
+const MAX_DIFF_CHARS_FOR_PROMPT: usize = 60_000;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConventionalCommitRules {
+    #[serde(default)]
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub require_scope: bool,
+    pub max_subject_length: Option<usize>,
+    #[serde(default = "default_allow_breaking")]
+    pub allow_breaking: bool,
+    #[serde(default)]
+    pub require_body: bool,
+}
+
+fn default_allow_breaking() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitMessageValidation {
+    pub valid: bool,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedCommitMessage {
+    pub subject: String,
+    pub body: String,
+}
+
+impl GeneratedCommitMessage {
+    pub fn to_message(&self) -> String {
+        let subject = self.subject.trim();
+        let body = self.body.trim();
+        if body.is_empty() {
+            subject.to_string()
+        } else {
+            format!("{subject}\n\n{body}")
+        }
+    }
+}
+
+fn allowed_types(rules: &ConventionalCommitRules) -> Vec<String> {
+    let configured = rules
+        .types
+        .iter()
+        .map(|entry| entry.trim().to_ascii_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .collect::<Vec<_>>();
+    if configured.is_empty() {
+        DEFAULT_CONVENTIONAL_COMMIT_TYPES
+            .iter()
+            .map(|entry| (*entry).to_string())
+            .collect()
+    } else {
+        configured
+    }
+}
+
+fn allowed_scopes(rules: &ConventionalCommitRules) -> Vec<String> {
+    rules
+        .scopes
+        .iter()
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+fn max_subject_length(rules: &ConventionalCommitRules) -> usize {
+    rules
+        .max_subject_length
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_MAX_SUBJECT_LENGTH)
+}
+
+pub fn validate_conventional_commit_message(
+    message: &str,
+    rules: &ConventionalCommitRules,
+) -> CommitMessageValidation {
+    let mut errors = Vec::new();
+    let mut lines = message.lines();
+    let subject = lines.next().unwrap_or("").trim_end();
+
+    if subject.trim().is_empty() {
+        errors.push("subject line is empty".to_string());
+        return CommitMessageValidation {
+            valid: false,
+            errors,
+        };
+    }
+
+    let max_length = max_subject_length(rules);
+    let subject_length = subject.chars().count();
+    if subject_length > max_length {
+        errors.push(format!(
+            "subject line is {subject_length} characters long (max {max_length})"
+        ));
+    }
+
+    match subject.split_once(':') {
+        None => errors.push(
+            "subject must follow `type(scope): description` (missing `:` separator)".to_string(),
+        ),
+        Some((header, description)) => {
+            let (header, breaking) = match header.strip_suffix('!') {
+                Some(stripped) => (stripped, true),
+                None => (header, false),
+            };
+            if breaking && !rules.allow_breaking {
+                errors.push("breaking change marker `!` is not allowed".to_string());
+            }
+
+            let (commit_type, scope) = match header.split_once('(') {
+                Some((commit_type, rest)) => match rest.strip_suffix(')') {
+                    Some(scope) => (commit_type, Some(scope)),
+                    None => {
+                        errors.push("scope must be wrapped in parentheses".to_string());
+                        (commit_type, None)
+                    }
+                },
+                None => (header, None),
+            };
+
+            let types = allowed_types(rules);
+            if commit_type.is_empty() {
+                errors.push("commit type is missing".to_string());
+            } else if !types.iter().any(|entry| entry == commit_type) {
+                errors.push(format!(
+                    "commit type `{commit_type}` is not allowed (expected one of: {})",
+                    types.join(", ")
+                ));
+            }
+
+            let scopes = allowed_scopes(rules);
+            match scope.map(str::trim) {
+                Some("") => errors.push("scope must not be empty".to_string()),
+                Some(scope) => {
+                    if !scopes.is_empty() && !scopes.iter().any(|entry| entry == scope) {
+                        errors.push(format!(
+                            "scope `{scope}` is not allowed (expected one of: {})",
+                            scopes.join(", ")
+                        ));
+                    }
+                }
+                None => {
+                    if rules.require_scope {
+                        errors.push("scope is required".to_string());
+                    }
+                }
+            }
+
+            if !description.starts_with(' ') || description.trim().is_empty() {
+                errors.push("description must follow `: ` and must not be empty".to_string());
+            }
+        }
+    }
+
+    let remaining = lines.collect::<Vec<_>>();
+    if let Some(separator) = remaining.first() {
+        if !separator.trim().is_empty() {
+            errors.push("body must be separated from the subject by a blank line".to_string());
+        }
+    }
+    if rules.require_body && remaining.iter().all(|line| line.trim().is_empty()) {
+        errors.push("commit body is required".to_string());
+    }
+
+    CommitMessageValidation {
+        valid: errors.is_empty(),
+        errors,
+    }
+}
+
+pub fn commit_message_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "subject": {
+                "type": "string",
+                "description": "Single-line commit subject."
+            },
+            "body": {
+                "type": "string",
+                "description": "Commit body explaining what changed and why. May be empty."
+            }
+        },
+        "required": ["subject", "body"],
+        "additionalProperties": false
+    })
+}
+
+fn truncate_diff_for_prompt(diff: &str) -> (&str, bool) {
+    if diff.len() <= MAX_DIFF_CHARS_FOR_PROMPT {
+        return (diff, false);
+    }
+
+    let mut end = MAX_DIFF_CHARS_FOR_PROMPT;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    (&diff[..end], true)
+}
+
+pub fn build_commit_message_prompt(diff: &str, rules: Option<&ConventionalCommitRules>) -> String {
+    let mut prompt = String::from(
+        "Write a git commit message for the staged changes below. \
+         Do not run any tools; answer only with the requested JSON object.\n",
+    );
+
+    match rules {
+        Some(rules) => {
+            prompt.push_str(&format!(
+                "The subject must follow Conventional Commits (`type(scope): description`), \
+                 use one of these types: {}, and be at most {} characters long.\n",
+                allowed_types(rules).join(", "),
+                max_subject_length(rules)
+            ));
+            let scopes = allowed_scopes(rules);
+            if !scopes.is_empty() {
+                prompt.push_str(&format!("Allowed scopes: {}.\n", scopes.join(", ")));
+            }
+            if rules.require_scope {
+                prompt.push_str("A scope is required.\n");
+            }
+            if !rules.allow_breaking {
+                prompt.push_str("Do not use the `!` breaking change marker.\n");
+            }
+            if rules.require_body {
+                prompt.push_str("The body must not be empty.\n");
+            }
+        }
+        None => prompt.push_str(&format!(
+            "Keep the subject imperative and at most {DEFAULT_MAX_SUBJECT_LENGTH} characters long.\n"
+        )),
+    }
+
+    let (diff, truncated) = truncate_diff_for_prompt(diff);
+    prompt.push_str("\n```diff\n");
+    prompt.push_str(diff);
+    if !diff.ends_with('\n') {
+        prompt.push('\n');
+    }
+    prompt.push_str("```\n");
+    if truncated {
+        prompt.push_str("(diff truncated)\n");
+    }
+    prompt
+}
+
+pub fn parse_generated_commit_message(raw: &str) -> Result<GeneratedCommitMessage, String> {
+    let trimmed = raw.trim();
+    let json_text = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(trimmed);
+
+    let parsed: Value = serde_json::from_str(json_text)
+        .map_err(|error| format!("generated commit message is not valid JSON: {error}"))?;
+    let subject = parsed
+        .get("subject")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| "generated commit message is missing `subject`".to_string())?;
+    let body = parsed
+        .get("body")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or_default();
+
+    Ok(GeneratedCommitMessage {
+        subject: subject.lines().next().unwrap_or(subject).trim().to_string(),
+        body: body.to_string(),
+    })
+}
+
+pub fn is_valid_git_identity(identity: &str) -> bool {
+    let trimmed = identity.trim();
+    if trimmed.starts_with('-') || trimmed.chars().any(char::is_control) {
+        return false;
+    }
+    let Some((name, rest)) = trimmed.split_once('<') else {
+        return false;
+    };
+    let Some(email) = rest.strip_suffix('>') else {
+        return false;
+    };
+    !name.trim().is_empty() && !email.trim().is_empty() && !email.contains(['<', '>'])
+}
+
+pub fn append_co_author_trailers(message: &str, co_authors: &[String]) -> String {
+    let mut result = message.trim_end().to_string();
+    let trailers = co_authors
+        .iter()
+        .map(|entry| format!("Co-authored-by: {}", entry.trim()))
+        .filter(|trailer| !result.lines().any(|line| line.trim() == trailer))
+        .collect::<Vec<_>>();
+    if trailers.is_empty() {
+        return result;
+    }
+
+    let ends_with_trailer_block = result
+        .lines()
+        .last()
+        .is_some_and(|line| line.starts_with("Co-authored-by:"));
+    result.push_str(if ends_with_trailer_block {
+        "\n"
+    } else {
+        "\n\n"
+    });
+    result.push_str(&trailers.join("\n"));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        append_co_author_trailers, build_commit_message_prompt, is_valid_git_identity,
+        parse_generated_commit_message, validate_conventional_commit_message,
+        ConventionalCommitRules, GeneratedCommitMessage,
+    };
+
+    fn default_rules() -> ConventionalCommitRules {
+        ConventionalCommitRules {
+            allow_breaking: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accepts_basic_conventional_commit() {
+        let validation =
+            validate_conventional_commit_message("feat(review): add commit step", &default_rules());
+        assert!(validation.valid, "{:?}", validation.errors);
+    }
+
+    #[test]
+    fn accepts_breaking_marker_and_body() {
+        let validation = validate_conventional_commit_message(
+            "refactor!: drop legacy bridge\n\nThe bridge is no longer used.",
+            &default_rules(),
+        );
+        assert!(validation.valid, "{:?}", validation.errors);
+    }
+
+    #[test]
+    fn rejects_unknown_type_and_missing_separator() {
+        let validation =
+            validate_conventional_commit_message("feature: add thing", &default_rules());
+        assert!(!validation.valid);
+        assert!(validation.errors[0].contains("`feature`"));
+
+        let validation = validate_conventional_commit_message("add thing", &default_rules());
+        assert!(!validation.valid);
+        assert!(validation.errors[0].contains("missing `:`"));
+    }
+
+    #[test]
+    fn enforces_configured_scopes_and_subject_length() {
+        let rules = ConventionalCommitRules {
+            scopes: vec!["backend".to_string()],
+            require_scope: true,
+            max_subject_length: Some(20),
+            allow_breaking: false,
+            ..Default::default()
+        };
+
+        let validation =
+            validate_conventional_commit_message("fix: a subject that is too long", &rules);
+        assert!(!validation.valid);
+        assert!(validation
+            .errors
+            .iter()
+            .any(|error| error.contains("max 20")));
+        assert!(validation
+            .errors
+            .iter()
+            .any(|error| error == "scope is required"));
+
+        let validation = validate_conventional_commit_message("fix(ui)!: tweak", &rules);
+        assert!(validation
+            .errors
+            .iter()
+            .any(|error| error.contains("`ui` is not allowed")));
+        assert!(validation
+            .errors
+            .iter()
+            .any(|error| error.contains("breaking change marker")));
+    }
+
+    #[test]
+    fn requires_blank_line_before_body() {
+        let validation =
+            validate_conventional_commit_message("fix: tweak\nbody text", &default_rules());
+        assert!(!validation.valid);
+        assert!(validation.errors[0].contains("blank line"));
+    }
+
+    #[test]
+    fn parses_generated_message_from_plain_or_fenced_json() {
+        let parsed = parse_generated_commit_message(
+            r#"{"subject":"fix(git): handle renames","body":"Stage both paths."}"#,
+        )
+        .expect("plain json should parse");
+        assert_eq!(
+            parsed,
+            GeneratedCommitMessage {
+                subject: "fix(git): handle renames".to_string(),
+                body: "Stage both paths.".to_string(),
+            }
+        );
+        assert_eq!(
+            parsed.to_message(),
+            "fix(git): handle renames\n\nStage both paths."
+        );
+
+        let parsed = parse_generated_commit_message(
+            "```json\n{\"subject\":\"chore: bump\",\"body\":\"\"}\n```",
+        )
+        .expect("fenced json should parse");
+        assert_eq!(parsed.to_message(), "chore: bump");
+    }
+
+    #[test]
+    fn rejects_generated_message_without_subject() {
+        let error = parse_generated_commit_message(r#"{"subject":"  ","body":"x"}"#)
+            .expect_err("blank subject must fail");
+        assert!(error.contains("subject"));
+    }
+
+    #[test]
+    fn prompt_mentions_rules_and_truncates_large_diffs() {
+        let rules = ConventionalCommitRules {
+            types: vec!["feat".to_string()],
+            scopes: vec!["neuro".to_string()],
+            allow_breaking: true,
+            ..Default::default()
+        };
+        let diff = "+".repeat(70_000);
+        let prompt = build_commit_message_prompt(&diff, Some(&rules));
+        assert!(prompt.contains("use one of these types: feat"));
+        assert!(prompt.contains("Allowed scopes: neuro."));
+        assert!(prompt.contains("(diff truncated)"));
+    }
+
+    #[test]
+    fn validates_git_identities() {
+        assert!(is_valid_git_identity("Alicia Bot <bot@example.com>"));
+        assert!(!is_valid_git_identity("bot@example.com"));
+        assert!(!is_valid_git_identity("--amend <x@y>"));
+        assert!(!is_valid_git_identity(" <bot@example.com>"));
+        assert!(!is_valid_git_identity(
+            "Bot <bot@example.com>\nSigned-off-by: x"
+        ));
+    }
+
+    #[test]
+    fn appends_co_author_trailers_once() {
+        let co_authors = vec!["Ana <ana@example.com>".to_string()];
+        let message = append_co_author_trailers("fix: tweak\n", &co_authors);
+        assert_eq!(
+            message,
+            "fix: tweak\n\nCo-authored-by: Ana <ana@example.com>"
+        );
+        assert_eq!(append_co_author_trailers(&message, &co_authors), message);
+
+        let message = append_co_author_trailers(&message, &["Rui <rui@example.com>".to_string()]);
+        assert!(message.ends_with(
+            "Co-authored-by: Ana <ana@example.com>\nCo-authored-by: Rui <rui@example.com>"
+        ));
+    }
+}
@@ -149,7 +149,7 @@ fn read_codex_config_file(path: &Path) -> Result<Option<CodexConfigToml>, String
         .map_err(|error| format!("failed to parse `{}`: {error}", path.display()))
 }
 
-fn resolve_codex_home() -> PathBuf {
+pub(crate) fn resolve_codex_home() -> PathBuf {
     if let Some(path) = env::var_os("CODEX_HOME") {
         if !path.is_empty() {
             return PathBuf::from(path);
@@ -0,0 +1,192 @@
+use serde_json::{json, Value};
+use std::path::Path;
+
+#[cfg(feature = "native-codex-runtime")]
+use codex_core::CodexThread;
+#[cfg(feature = "native-codex-runtime")]
+use codex_protocol::dynamic_tools::{
+    DynamicToolCallOutputContentItem, DynamicToolCallRequest, DynamicToolResponse, DynamicToolSpec,
+};
+#[cfg(feature = "native-codex-runtime")]
+use codex_protocol::protocol::Op;
+
+use crate::git_history_runtime::{GitBlameRequest, GitFileHistoryRequest};
+
+pub(crate) const GIT_BLAME_TOOL: &str = "git_blame";
+pub(crate) const GIT_FILE_HISTORY_TOOL: &str = "git_file_history";
+
+pub(crate) struct LocalDynamicTool {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub input_schema: Value,
+}
+
+pub(crate) fn local_dynamic_tools() -> Vec<LocalDynamicTool> {
+    vec![
+        LocalDynamicTool {
+            name: GIT_BLAME_TOOL,
+            description: "Show who last changed each line of a file in the workspace git repository.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "File path relative to the workspace root."
+                    },
+                    "range": {
+                        "type": "object",
+                        "properties": {
+                            "start": { "type": "integer", "minimum": 1 },
+                            "end": { "type": "integer", "minimum": 1 }
+                        },
+                        "required": ["start", "end"],
+                        "additionalProperties": false
+                    }
+                },
+                "required": ["path"],
+                "additionalProperties": false
+            }),
+        },
+        LocalDynamicTool {
+            name: GIT_FILE_HISTORY_TOOL,
+            description: "List the commits that touched a file in the workspace git repository, following renames.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "File path relative to the workspace root."
+                    },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": 500 }
+                },
+                "required": ["path"],
+                "additionalProperties": false
+            }),
+        },
+    ]
+}
+
+pub(crate) fn parse_tool_arguments<T: serde::de::DeserializeOwned>(
+    tool: &str,
+    arguments: Value,
+) -> Result<T, String> {
+    serde_json::from_value(arguments)
+        .map_err(|error| format!("invalid arguments for `{tool}`: {error}"))
+}
+
+pub(crate) fn invoke_local_dynamic_tool(
+    cwd: &Path,
+    tool: &str,
+    arguments: Value,
+) -> Result<Value, String> {
+    match tool {
+        GIT_BLAME_TOOL => {
+            let request: GitBlameRequest = parse_tool_arguments(tool, arguments)?;
+            let response = crate::command_runtime::git_blame_in_cwd(cwd, request)?;
+            serde_json::to_value(response).map_err(|error| error.to_string())
+        }
+        GIT_FILE_HISTORY_TOOL => {
+            let request: GitFileHistoryRequest = parse_tool_arguments(tool, arguments)?;
+            let response = crate::command_runtime::git_file_history_in_cwd(cwd, request)?;
+            serde_json::to_value(response).map_err(|error| error.to_string())
+        }
+        other => Err(format!("unknown dynamic tool `{other}`")),
+    }
+}
+
+#[cfg(feature = "native-codex-runtime")]
+pub(crate) fn native_dynamic_tool_specs() -> Vec<DynamicToolSpec> {
+    local_dynamic_tools()
+        .into_iter()
+        .map(|tool| DynamicToolSpec {
+            name: tool.name.to_string(),
+            description: tool.description.to_string(),
+            input_schema: tool.input_schema,
+        })
+        .collect()
+}
+
+#[cfg(feature = "native-codex-runtime")]
+pub(crate) async fn respond_to_native_dynamic_tool_call(
+    thread: &CodexThread,
+    cwd: &Path,
+    request: &DynamicToolCallRequest,
+) -> Result<Vec<Value>, String> {
+    let started = json!({
+        "type": "item.started",
+        "item": {
+            "type": "dynamic_tool_call",
+            "id": request.call_id.clone(),
+            "tool": request.tool.clone(),
+            "status": "in_progress",
+            "arguments": request.arguments.clone(),
+        },
+    });
+
+    let result = invoke_local_dynamic_tool(cwd, &request.tool, request.arguments.clone());
+    let (success, text) = match &result {
+        Ok(value) => (true, value.to_string()),
+        Err(error) => (false, error.clone()),
+    };
+
+    thread
+        .submit(Op::DynamicToolResponse {
+            id: request.call_id.clone(),
+            response: DynamicToolResponse {
+                content_items: vec![DynamicToolCallOutputContentItem::InputText { text }],
+                success,
+            },
+        })
+        .await
+        .map_err(|error| format!("failed to submit dynamic tool response: {error}"))?;
+
+    let (result, error) = match result {
+        Ok(value) => (value, Value::Null),
+        Err(message) => (Value::Null, json!({ "message": message })),
+    };
+    Ok(vec![
+        started,
+        json!({
+            "type": "item.completed",
+            "item": {
+                "type": "dynamic_tool_call",
+                "id": request.call_id.clone(),
+                "tool": request.tool.clone(),
+                "status": if success { "completed" } else { "failed" },
+                "arguments": request.arguments.clone(),
+                "result": result,
+                "error": error,
+            },
+        }),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{invoke_local_dynamic_tool, local_dynamic_tools};
+    use serde_json::json;
+    use std::path::Path;
+
+    #[test]
+    fn local_tools_expose_object_schemas() {
+        let tools = local_dynamic_tools();
+        let names = tools.iter().map(|tool| tool.name).collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["git_blame", "git_file_history"]);
+        for tool in tools {
+            assert_eq!(tool.input_schema["type"], "object");
+            assert_eq!(tool.input_schema["required"], json!(["path"]));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_tools_and_bad_arguments() {
+        let error = invoke_local_dynamic_tool(Path::new("/"), "rm_rf", json!({}))
+            .expect_err("unknown tool must fail");
+        assert!(error.contains("unknown dynamic tool"));
+
+        let error = invoke_local_dynamic_tool(Path::new("/"), "git_blame", json!({ "line": 1 }))
+            .expect_err("missing path must fail");
+        assert!(error.contains("invalid arguments for `git_blame`"));
+    }
+}
@@ -29,6 +29,14 @@ struct LifecycleEventPayload {
     message: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NeuroLifecycleEventPayload<'a> {
+    connection_id: &'a str,
+    status: &'static str,
+    message: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct TerminalDataPayload {
@@ -63,6 +71,38 @@ pub(crate) fn emit_lifecycle(
     let _ = app.emit("codex://lifecycle", payload);
 }
 
+pub(crate) fn emit_neuro_lifecycle(
+    app: &AppHandle,
+    connection_id: &str,
+    status: &'static str,
+    message: Option<String>,
+) {
+    let payload = NeuroLifecycleEventPayload {
+        connection_id,
+        status,
+        message,
+    };
+    let _ = app.emit("neuro://lifecycle", payload);
+}
+
+pub(crate) fn emit_neuro_health(
+    app: &AppHandle,
+    event: &crate::neuro_health_runtime::NeuroHealthEvent,
+) {
+    let _ = app.emit("neuro://health", event);
+}
+
+pub(crate) fn emit_neuro_ws(
+    app: &AppHandle,
+    event: &crate::neuro_ws_subscription_runtime::NeuroWsEvent<'_>,
+) {
+    let _ = app.emit("neuro://ws", event);
+}
+
+pub(crate) fn emit_neuro_telemetry_event(app: &AppHandle, payload: &Value) {
+    let _ = app.emit("neuro://telemetry", payload);
+}
+
 fn emit_stream(app: &AppHandle, channel: &str, session_id: u64, chunk: String) {
     let payload = StreamEventPayload { session_id, chunk };
     let _ = app.emit(channel, payload);
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+
+use crate::CodexTurnRunResponse;
+
+const MAX_CONFLICT_STAGE_CHARS_FOR_PROMPT: usize = 20_000;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConflictReadRequest {
+    #[serde(default)]
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub start_agent_turn: bool,
+    pub thread_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConflictFile {
+    pub path: String,
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+    pub working: Option<String>,
+    pub binary: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConflictReadResponse {
+    pub cwd: String,
+    pub files: Vec<GitConflictFile>,
+    pub turn: Option<CodexTurnRunResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConflictResolveRequest {
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConflictResolveResponse {
+    pub path: String,
+    pub resolved: bool,
+    pub add: crate::GitCommandExecutionResult,
+    pub remaining: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitUnmergedEntry {
+    pub path: String,
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+pub fn parse_git_unmerged_entries(output: &[u8]) -> Result<Vec<GitUnmergedEntry>, String> {
+    let mut entries: Vec<GitUnmergedEntry> = Vec::new();
+
+    for record in output.split(|byte| *byte == b'\0') {
+        if record.is_empty() {
+            continue;
+        }
+
+        let record = String::from_utf8_lossy(record);
+        let Some((meta, path)) = record.split_once('\t') else {
+            return Err(format!("malformed git ls-files entry: '{record}'"));
+        };
+        let mut fields = meta.split(' ');
+        let (Some(_mode), Some(object_id), Some(stage), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(format!("malformed git ls-files entry: '{record}'"));
+        };
+        if path.is_empty() {
+            return Err(format!(
+                "malformed git ls-files entry (missing path): '{record}'"
+            ));
+        }
+
+        let index = match entries.iter().position(|entry| entry.path == path) {
+            Some(index) => index,
+            None => {
+                entries.push(GitUnmergedEntry {
+                    path: path.to_string(),
+                    base: None,
+                    ours: None,
+                    theirs: None,
+                });
+                entries.len() - 1
+            }
+        };
+        let entry = &mut entries[index];
+        let object_id = Some(object_id.to_string());
+        match stage {
+            "1" => entry.base = object_id,
+            "2" => entry.ours = object_id,
+            "3" => entry.theirs = object_id,
+            _ => {
+                return Err(format!(
+                    "malformed git ls-files entry (unexpected stage `{stage}`): '{record}'"
+                ));
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+pub fn contains_conflict_markers(content: &str) -> bool {
+    content
+        .lines()
+        .any(|line| line.starts_with("<<<<<<< ") || line.starts_with(">>>>>>> "))
+}
+
+fn push_stage_section(prompt: &mut String, label: &str, content: Option<&str>) {
+    let Some(content) = content else {
+        prompt.push_str(&format!("{label}: (absent)\n"));
+        return;
+    };
+
+    let mut end = content.len().min(MAX_CONFLICT_STAGE_CHARS_FOR_PROMPT);
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    prompt.push_str(&format!("{label}:\n```\n{}", &content[..end]));
+    if !content[..end].ends_with('\n') {
+        prompt.push('\n');
+    }
+    prompt.push_str("```\n");
+    if end < content.len() {
+        prompt.push_str("(truncated)\n");
+    }
+}
+
+pub fn build_conflict_resolution_prompt(files: &[GitConflictFile]) -> String {
+    let mut prompt = String::from(
+        "Resolve the merge conflicts in the files below. For each file, combine the intent of \
+         both sides, write the resolved content to the file without conflict markers, and do not \
+         run `git add` or `git commit`.\n",
+    );
+
+    for file in files {
+        prompt.push_str(&format!("\n## {}\n", file.path));
+        if file.binary {
+            prompt.push_str("Binary file; choose one side and explain the choice.\n");
+            continue;
+        }
+        push_stage_section(&mut prompt, "Base", file.base.as_deref());
+        push_stage_section(&mut prompt, "Ours", file.ours.as_deref());
+        push_stage_section(&mut prompt, "Theirs", file.theirs.as_deref());
+    }
+
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_conflict_resolution_prompt, contains_conflict_markers, parse_git_unmerged_entries,
+        GitConflictFile, GitUnmergedEntry,
+    };
+
+    #[test]
+    fn parses_unmerged_stages_grouped_by_path() {
+        let output = [
+            "100644 aaa 1\tsrc/lib.rs",
+            "100644 bbb 2\tsrc/lib.rs",
+            "100644 ccc 3\tsrc/lib.rs",
+            "100644 ddd 2\tnew file.rs",
+            "",
+        ]
+        .join("\0");
+        let entries = parse_git_unmerged_entries(output.as_bytes()).expect("entries should parse");
+
+        assert_eq!(
+            entries,
+            vec![
+                GitUnmergedEntry {
+                    path: "src/lib.rs".to_string(),
+                    base: Some("aaa".to_string()),
+                    ours: Some("bbb".to_string()),
+                    theirs: Some("ccc".to_string()),
+                },
+                GitUnmergedEntry {
+                    path: "new file.rs".to_string(),
+                    base: None,
+                    ours: Some("ddd".to_string()),
+                    theirs: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_unmerged_entries() {
+        assert!(parse_git_unmerged_entries(b"100644 aaa\tsrc/lib.rs\0").is_err());
+        assert!(parse_git_unmerged_entries(b"100644 aaa 0\tsrc/lib.rs\0").is_err());
+    }
+
+    #[test]
+    fn detects_conflict_markers() {
+        assert!(contains_conflict_markers(
+            "a\n<<<<<<< HEAD\nb\n=======\nc\n>>>>>>> topic\n"
+        ));
+        assert!(!contains_conflict_markers("a\n=======\nb\n"));
+    }
+
+    #[test]
+    fn conflict_prompt_lists_each_stage() {
+        let prompt = build_conflict_resolution_prompt(&[GitConflictFile {
+            path: "src/lib.rs".to_string(),
+            base: None,
+            ours: Some("fn a() {}".to_string()),
+            theirs: Some("fn b() {}\n".to_string()),
+            working: None,
+            binary: false,
+        }]);
+
+        assert!(prompt.contains("## src/lib.rs"));
+        assert!(prompt.contains("Base: (absent)"));
+        assert!(prompt.contains("Ours:\n```\nfn a() {}\n```"));
+        assert!(prompt.contains("Theirs:\n```\nfn b() {}\n```"));
+    }
+}
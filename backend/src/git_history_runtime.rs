@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub const DEFAULT_FILE_HISTORY_LIMIT: usize = 50;
+pub const MAX_FILE_HISTORY_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLineRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitBlameRequest {
+    pub path: String,
+    pub range: Option<GitLineRange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitBlameLine {
+    pub line: u32,
+    pub commit: String,
+    pub author: String,
+    pub author_email: String,
+    pub authored_at: i64,
+    pub summary: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitBlameResponse {
+    pub path: String,
+    pub lines: Vec<GitBlameLine>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileHistoryRequest {
+    pub path: String,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileHistoryEntry {
+    pub commit: String,
+    pub author: String,
+    pub author_email: String,
+    pub authored_at: i64,
+    pub subject: String,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileHistoryResponse {
+    pub path: String,
+    pub entries: Vec<GitFileHistoryEntry>,
+}
+
+pub const GIT_FILE_HISTORY_FORMAT: &str = "--format=%x1e%H%x1f%an%x1f%ae%x1f%at%x1f%s";
+
+pub fn validate_line_range(range: &GitLineRange) -> Result<(), String> {
+    if range.start == 0 || range.end < range.start {
+        return Err(format!(
+            "invalid line range {}-{}: start must be >= 1 and end >= start",
+            range.start, range.end
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Clone)]
+struct BlameCommitInfo {
+    author: String,
+    author_email: String,
+    authored_at: i64,
+    summary: String,
+}
+
+fn is_blame_header(line: &str) -> Option<(String, u32)> {
+    let mut fields = line.split(' ');
+    let commit = fields.next()?;
+    if commit.len() < 40 || !commit.chars().all(|entry| entry.is_ascii_hexdigit()) {
+        return None;
+    }
+    let _original_line = fields.next()?.parse::<u32>().ok()?;
+    let final_line = fields.next()?.parse::<u32>().ok()?;
+    Some((commit.to_string(), final_line))
+}
+
+pub fn parse_git_blame_porcelain(output: &str) -> Result<Vec<GitBlameLine>, String> {
+    let mut commits: HashMap<String, BlameCommitInfo> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current: Option<(String, u32)> = None;
+
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            let Some((commit, final_line)) = current.take() else {
+                return Err("malformed git blame output: content without header".to_string());
+            };
+            let info = commits.get(&commit).cloned().unwrap_or_default();
+            lines.push(GitBlameLine {
+                line: final_line,
+                commit,
+                author: info.author,
+                author_email: info.author_email,
+                authored_at: info.authored_at,
+                summary: info.summary,
+                content: content.to_string(),
+            });
+            continue;
+        }
+
+        if current.is_none() {
+            let Some(header) = is_blame_header(line) else {
+                return Err(format!("malformed git blame header: '{line}'"));
+            };
+            commits.entry(header.0.clone()).or_default();
+            current = Some(header);
+            continue;
+        }
+
+        let Some((commit, _)) = current.as_ref() else {
+            continue;
+        };
+        let info = commits.entry(commit.clone()).or_default();
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "author" => info.author = value.to_string(),
+            "author-mail" => {
+                info.author_email = value
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            }
+            "author-time" => info.authored_at = value.parse().unwrap_or_default(),
+            "summary" => info.summary = value.to_string(),
+            _ => {}
+        }
+    }
+
+    Ok(lines)
+}
+
+pub fn parse_git_file_history(output: &str) -> Vec<GitFileHistoryEntry> {
+    output
+        .split('\u{1e}')
+        .filter_map(|record| {
+            let mut record_lines = record.lines();
+            let header = record_lines.next()?;
+            let mut fields = header.split('\u{1f}');
+            let commit = fields.next()?.trim();
+            if commit.is_empty() {
+                return None;
+            }
+            let author = fields.next().unwrap_or_default();
+            let author_email = fields.next().unwrap_or_default();
+            let authored_at = fields
+                .next()
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or_default();
+            let subject = fields.next().unwrap_or_default();
+            let path = record_lines
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string);
+
+            Some(GitFileHistoryEntry {
+                commit: commit.to_string(),
+                author: author.to_string(),
+                author_email: author_email.to_string(),
+                authored_at,
+                subject: subject.to_string(),
+                path,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_git_blame_porcelain, parse_git_file_history, validate_line_range, GitLineRange,
+    };
+
+    const COMMIT_A: &str = "1111111111111111111111111111111111111111";
+    const COMMIT_B: &str = "2222222222222222222222222222222222222222";
+
+    #[test]
+    fn parses_blame_porcelain_with_repeated_commits() {
+        let output = format!(
+            "{COMMIT_A} 1 1 2\nauthor Ana\nauthor-mail <ana@example.com>\nauthor-time 1700000000\nauthor-tz +0000\nsummary feat: first\nfilename src/lib.rs\n\tfn a() {{}}\n\
+             {COMMIT_A} 2 2\n\tfn b() {{}}\n\
+             {COMMIT_B} 3 3 1\nauthor Rui\nauthor-mail <rui@example.com>\nauthor-time 1700000100\nsummary fix: second\nprevious {COMMIT_A} src/lib.rs\nfilename src/lib.rs\n\t\n"
+        );
+        let lines = parse_git_blame_porcelain(&output).expect("blame should parse");
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].line, 1);
+        assert_eq!(lines[0].author_email, "ana@example.com");
+        assert_eq!(lines[1].commit, COMMIT_A);
+        assert_eq!(lines[1].summary, "feat: first");
+        assert_eq!(lines[1].content, "fn b() {}");
+        assert_eq!(lines[2].author, "Rui");
+        assert_eq!(lines[2].authored_at, 1_700_000_100);
+        assert_eq!(lines[2].content, "");
+    }
+
+    #[test]
+    fn rejects_malformed_blame_output() {
+        assert!(parse_git_blame_porcelain("not a header\n").is_err());
+    }
+
+    #[test]
+    fn parses_file_history_records_with_renamed_paths() {
+        let output = format!(
+            "\u{1e}{COMMIT_B}\u{1f}Rui\u{1f}rui@example.com\u{1f}1700000100\u{1f}fix: move\n\nsrc/new.rs\n\
+             \u{1e}{COMMIT_A}\u{1f}Ana\u{1f}ana@example.com\u{1f}1700000000\u{1f}feat: add\n\nsrc/old.rs\n"
+        );
+        let entries = parse_git_file_history(&output);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].commit, COMMIT_B);
+        assert_eq!(entries[0].path.as_deref(), Some("src/new.rs"));
+        assert_eq!(entries[1].subject, "feat: add");
+        assert_eq!(entries[1].authored_at, 1_700_000_000);
+        assert_eq!(entries[1].path.as_deref(), Some("src/old.rs"));
+    }
+
+    #[test]
+    fn validates_line_ranges() {
+        assert!(validate_line_range(&GitLineRange { start: 1, end: 1 }).is_ok());
+        assert!(validate_line_range(&GitLineRange { start: 0, end: 3 }).is_err());
+        assert!(validate_line_range(&GitLineRange { start: 5, end: 2 }).is_err());
+    }
+}
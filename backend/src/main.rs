@@ -14,27 +14,66 @@ mod app_server_runtime;
 mod codex_event_translator;
 mod codex_native_runtime;
 mod command_runtime;
+mod commit_hook_runtime;
+mod commit_message_runtime;
 mod config_runtime;
+mod dynamic_tool_runtime;
 mod events_runtime;
+mod git_conflict_runtime;
+mod git_history_runtime;
 mod launch_runtime;
 mod mcp_runtime;
 mod models_runtime;
+mod neuro_adt_rest;
+mod neuro_agent_runtime;
+mod neuro_atc_runtime;
+mod neuro_audit_runtime;
+mod neuro_cache_runtime;
+mod neuro_check_runtime;
+mod neuro_connection_runtime;
+mod neuro_data_preview_runtime;
+mod neuro_ddic_runtime;
+mod neuro_diff_runtime;
+mod neuro_health_runtime;
+mod neuro_lock_runtime;
+mod neuro_mcp_server_runtime;
+mod neuro_navigation_runtime;
+mod neuro_package_runtime;
 mod neuro_runtime;
+mod neuro_secret_runtime;
+mod neuro_telemetry_runtime;
+#[cfg(test)]
+mod neuro_test_fixtures;
+mod neuro_transport_runtime;
+mod neuro_unit_runtime;
+mod neuro_version_runtime;
+mod neuro_ws_subscription_runtime;
 mod session_lifecycle_runtime;
 mod session_runtime;
 mod session_turn_runtime;
 mod status_runtime;
 mod terminal_runtime;
+mod thread_export_runtime;
 use crate::account_runtime::{
     AccountLoginStartRequest, AccountLoginStartResponse, AccountLogoutResponse,
     AccountRateLimitsReadResponse, AccountReadRequest, AccountReadResponse, AppListRequest,
     AppListResponse,
 };
+use crate::commit_hook_runtime::GitCommitHookReport;
+use crate::commit_message_runtime::{CommitMessageValidation, ConventionalCommitRules};
 use crate::config_runtime::{load_runtime_config_from_codex, normalize_runtime_config};
+use crate::git_conflict_runtime::{
+    GitConflictReadRequest, GitConflictReadResponse, GitConflictResolveRequest,
+    GitConflictResolveResponse,
+};
+use crate::git_history_runtime::{
+    GitBlameRequest, GitBlameResponse, GitFileHistoryRequest, GitFileHistoryResponse,
+};
 use crate::mcp_runtime::{
     McpLoginRequest, McpLoginResponse, McpReloadResponse, McpServerListResponse,
     McpStartupWarmupResponse,
 };
+use crate::thread_export_runtime::{GitThreadExportRequest, GitThreadExportResponse};
 #[cfg(feature = "native-codex-runtime")]
 use codex_core::CodexThread;
 
@@ -173,7 +212,7 @@ fn spawn_neuro_startup_probe(app_handle: AppHandle) {
 
         let state = app_handle.state::<AppState>();
         let report =
-            crate::neuro_runtime::neuro_runtime_diagnose_for_app_state(state.inner()).await;
+            crate::neuro_runtime::neuro_runtime_diagnose_for_app_state(state.inner(), None).await;
 
         eprintln!(
             "[neuro-startup] overall_status={}",
@@ -287,8 +326,33 @@ struct RunCodexCommandResponse {
 #[serde(rename_all = "camelCase")]
 struct GitCommitApprovedReviewRequest {
     paths: Vec<String>,
-    message: String,
+    #[serde(default)]
+    message: Option<String>,
     cwd: Option<String>,
+    #[serde(default)]
+    generate_message: bool,
+    #[serde(default)]
+    conventional_commits: Option<ConventionalCommitRules>,
+    #[serde(default)]
+    signing: Option<GitCommitSigningOptions>,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    co_authors: Vec<String>,
+    #[serde(default)]
+    no_verify: bool,
+    #[serde(default)]
+    amend: bool,
+    /// Thread whose commit history records the new commit, for `git_thread_export`.
+    #[serde(default)]
+    thread_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GitCommitSigningOptions {
+    format: Option<String>,
+    key: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -306,6 +370,11 @@ struct GitCommitApprovedReviewResponse {
     success: bool,
     add: GitCommandExecutionResult,
     commit: GitCommandExecutionResult,
+    message: Option<String>,
+    generated_message: bool,
+    validation: Option<CommitMessageValidation>,
+    hooks: Option<GitCommitHookReport>,
+    warnings: Vec<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -709,6 +778,16 @@ enum SessionTransport {
 enum NativeApprovalKind {
     CommandExecution,
     FileChange,
+    DynamicToolCall,
+    NeuroWrite,
+}
+
+#[cfg(feature = "native-codex-runtime")]
+impl NativeApprovalKind {
+    /// Approvals raised by the app itself, answered without going through codex core.
+    fn is_local(self) -> bool {
+        matches!(self, Self::DynamicToolCall | Self::NeuroWrite)
+    }
 }
 
 #[cfg(feature = "native-codex-runtime")]
@@ -738,6 +817,9 @@ struct NativeSessionHandles {
     active_turns: HashMap<String, String>,
     pending_approvals: HashMap<String, NativePendingApproval>,
     pending_user_inputs: HashMap<String, NativePendingUserInput>,
+    local_approvals:
+        HashMap<String, tokio::sync::oneshot::Sender<codex_protocol::protocol::ReviewDecision>>,
+    approved_dynamic_tools: std::collections::HashSet<String>,
     next_approval_id: u64,
     next_user_input_id: u64,
 }
@@ -776,8 +858,11 @@ struct AppState {
     native_codex_runtime: AsyncMutex<Option<Arc<codex_native_runtime::NativeCodexRuntime>>>,
     #[cfg(feature = "native-codex-runtime")]
     native_codex_runtime_init_gate: AsyncMutex<()>,
-    neuro_runtime: AsyncMutex<Option<Arc<neuro_runtime::NeuroRuntime>>>,
+    neuro_runtimes: AsyncMutex<HashMap<String, Arc<neuro_runtime::NeuroRuntime>>>,
     neuro_runtime_init_gate: AsyncMutex<()>,
+    neuro_secrets: AsyncMutex<neuro_secret_runtime::NeuroSecretSession>,
+    neuro_health: Mutex<neuro_health_runtime::NeuroHealthRegistry>,
+    neuro_ws_subscriptions: neuro_ws_subscription_runtime::NeuroWsSubscriptionRegistry,
 }
 
 impl Default for AppState {
@@ -794,8 +879,12 @@ impl Default for AppState {
             native_codex_runtime: AsyncMutex::new(None),
             #[cfg(feature = "native-codex-runtime")]
             native_codex_runtime_init_gate: AsyncMutex::new(()),
-            neuro_runtime: AsyncMutex::new(None),
+            neuro_runtimes: AsyncMutex::new(HashMap::new()),
             neuro_runtime_init_gate: AsyncMutex::new(()),
+            neuro_secrets: AsyncMutex::new(neuro_secret_runtime::NeuroSecretSession::default()),
+            neuro_health: Mutex::new(neuro_health_runtime::NeuroHealthRegistry::default()),
+            neuro_ws_subscriptions:
+                neuro_ws_subscription_runtime::NeuroWsSubscriptionRegistry::default(),
         }
     }
 }
@@ -854,9 +943,198 @@ async fn codex_native_runtime_diagnose(
 #[tauri::command]
 async fn neuro_runtime_diagnose(
     state: State<'_, AppState>,
+    connection_id: Option<String>,
 ) -> Result<neuro_types::NeuroCommandResponse<neuro_types::RuntimeDiagnoseResponse>, String> {
     Ok(
-        match crate::neuro_runtime::neuro_runtime_diagnose_impl(state).await {
+        match crate::neuro_runtime::neuro_runtime_diagnose_impl(state, connection_id).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_runtime_reload(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<neuro_types::NeuroCommandResponse<neuro_runtime::NeuroRuntimeLifecycleResponse>, String>
+{
+    Ok(
+        match crate::neuro_runtime::neuro_runtime_reload_impl(app, state, connection_id).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_runtime_reset(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<neuro_types::NeuroCommandResponse<neuro_runtime::NeuroRuntimeLifecycleResponse>, String>
+{
+    Ok(
+        match crate::neuro_runtime::neuro_runtime_reset_impl(app, state, connection_id).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_connection_list() -> Result<
+    neuro_types::NeuroCommandResponse<Vec<neuro_connection_runtime::NeuroConnectionProfile>>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_connection_list_impl().await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_connection_create(
+    profile: neuro_connection_runtime::NeuroConnectionProfile,
+) -> Result<
+    neuro_types::NeuroCommandResponse<neuro_connection_runtime::NeuroConnectionProfile>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_connection_create_impl(profile).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_connection_update(
+    state: State<'_, AppState>,
+    profile: neuro_connection_runtime::NeuroConnectionProfile,
+) -> Result<
+    neuro_types::NeuroCommandResponse<neuro_connection_runtime::NeuroConnectionProfile>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_connection_update_impl(state, profile).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_connection_delete(
+    state: State<'_, AppState>,
+    connection_id: String,
+) -> Result<neuro_types::NeuroCommandResponse<bool>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_connection_delete_impl(state, connection_id).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_health_history(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<neuro_types::NeuroCommandResponse<neuro_health_runtime::NeuroHealthHistory>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_health_history_impl(state, connection_id).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_telemetry_summary(
+) -> Result<neuro_types::NeuroCommandResponse<neuro_telemetry_runtime::NeuroTelemetrySummary>, String>
+{
+    Ok(
+        match crate::neuro_runtime::neuro_telemetry_summary_impl().await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_secret_status(
+    state: State<'_, AppState>,
+) -> Result<neuro_types::NeuroCommandResponse<neuro_secret_runtime::NeuroSecretStatus>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_secret_status_impl(state).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_secret_create(
+    state: State<'_, AppState>,
+    passphrase: String,
+) -> Result<neuro_types::NeuroCommandResponse<neuro_secret_runtime::NeuroSecretStatus>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_secret_create_impl(state, passphrase).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_secret_unlock(
+    state: State<'_, AppState>,
+    passphrase: String,
+) -> Result<neuro_types::NeuroCommandResponse<neuro_secret_runtime::NeuroSecretStatus>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_secret_unlock_impl(state, passphrase).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_secret_lock(
+    state: State<'_, AppState>,
+) -> Result<neuro_types::NeuroCommandResponse<neuro_secret_runtime::NeuroSecretStatus>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_secret_lock_impl(state).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_secret_set(
+    state: State<'_, AppState>,
+    request: neuro_secret_runtime::NeuroSecretSetRequest,
+) -> Result<neuro_types::NeuroCommandResponse<neuro_secret_runtime::NeuroSecretStatus>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_secret_set_impl(state, request).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_secret_clear(
+    state: State<'_, AppState>,
+    request: neuro_secret_runtime::NeuroSecretClearRequest,
+) -> Result<neuro_types::NeuroCommandResponse<bool>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_secret_clear_impl(state, request).await {
             Ok(data) => neuro_types::NeuroCommandResponse::success(data),
             Err(error) => neuro_types::NeuroCommandResponse::failure(error),
         },
@@ -866,11 +1144,19 @@ async fn neuro_runtime_diagnose(
 #[tauri::command]
 async fn neuro_search_objects(
     state: State<'_, AppState>,
+    connection_id: Option<String>,
     query: String,
     max_results: Option<u32>,
 ) -> Result<neuro_types::NeuroCommandResponse<Vec<neuro_types::AdtObjectSummary>>, String> {
     Ok(
-        match crate::neuro_runtime::neuro_search_objects_impl(state, query, max_results).await {
+        match crate::neuro_runtime::neuro_search_objects_impl(
+            state,
+            connection_id,
+            query,
+            max_results,
+        )
+        .await
+        {
             Ok(data) => neuro_types::NeuroCommandResponse::success(data),
             Err(error) => neuro_types::NeuroCommandResponse::failure(error),
         },
@@ -880,10 +1166,239 @@ async fn neuro_search_objects(
 #[tauri::command]
 async fn neuro_get_source(
     state: State<'_, AppState>,
+    connection_id: Option<String>,
     object_uri: String,
 ) -> Result<neuro_types::NeuroCommandResponse<neuro_types::AdtSourceResponse>, String> {
     Ok(
-        match crate::neuro_runtime::neuro_get_source_impl(state, object_uri).await {
+        match crate::neuro_runtime::neuro_get_source_impl(state, connection_id, object_uri).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_get_sources(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uris: Vec<String>,
+) -> Result<
+    neuro_types::NeuroCommandResponse<Vec<crate::neuro_cache_runtime::NeuroSourceEntry>>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_get_sources_impl(state, connection_id, object_uris).await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_list_versions(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_version_runtime::NeuroVersionListResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_list_versions_impl(state, connection_id, object_uri).await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_get_version(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+    version_id: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_version_runtime::NeuroVersionSourceResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_get_version_impl(
+            state,
+            connection_id,
+            object_uri,
+            version_id,
+        )
+        .await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_diff_source(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+    local_source: String,
+    version: Option<crate::neuro_version_runtime::NeuroServerVersion>,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_version_runtime::NeuroSourceDiffResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_diff_source_impl(
+            state,
+            connection_id,
+            object_uri,
+            local_source,
+            version,
+        )
+        .await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_where_used(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_navigation_runtime::NeuroWhereUsedResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_where_used_impl(state, connection_id, object_uri).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_navigate_definition(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_navigation_runtime::NeuroNavigateRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_navigation_runtime::NeuroNavigationResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_navigate_definition_impl(state, connection_id, request)
+            .await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_object_structure(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<
+        crate::neuro_navigation_runtime::NeuroObjectStructureResponse,
+    >,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_object_structure_impl(state, connection_id, object_uri)
+            .await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_ddic_table(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    name: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_ddic_runtime::NeuroDdicTableResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_ddic_table_impl(state, connection_id, name).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_ddic_data_element(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    name: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_ddic_runtime::NeuroDataElementResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_ddic_data_element_impl(state, connection_id, name).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_ddic_domain(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    name: String,
+) -> Result<neuro_types::NeuroCommandResponse<crate::neuro_ddic_runtime::NeuroDomainResponse>, String>
+{
+    Ok(
+        match crate::neuro_runtime::neuro_ddic_domain_impl(state, connection_id, name).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_cds_view(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    name: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_ddic_runtime::NeuroCdsViewResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_cds_view_impl(state, connection_id, name).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_data_preview(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_data_preview_runtime::NeuroDataPreviewRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_data_preview_runtime::NeuroDataPreviewResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_data_preview_impl(state, connection_id, request).await {
             Ok(data) => neuro_types::NeuroCommandResponse::success(data),
             Err(error) => neuro_types::NeuroCommandResponse::failure(error),
         },
@@ -893,10 +1408,217 @@ async fn neuro_get_source(
 #[tauri::command]
 async fn neuro_update_source(
     state: State<'_, AppState>,
-    request: neuro_types::AdtUpdateSourceRequest,
+    connection_id: Option<String>,
+    request: crate::neuro_transport_runtime::NeuroUpdateSourceRequest,
+    syntax_check: Option<bool>,
+    confirmed: Option<bool>,
 ) -> Result<neuro_types::NeuroCommandResponse<neuro_types::AdtUpdateSourceResponse>, String> {
     Ok(
-        match crate::neuro_runtime::neuro_update_source_impl(state, request).await {
+        match crate::neuro_runtime::neuro_update_source_impl(
+            state,
+            connection_id,
+            request,
+            syntax_check,
+            crate::neuro_runtime::SapWriteConfirmation::Caller {
+                confirmed: confirmed.unwrap_or(false),
+            },
+        )
+        .await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_syntax_check(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_check_runtime::NeuroSyntaxCheckRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_check_runtime::NeuroSyntaxCheckResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_syntax_check_impl(state, connection_id, request).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_activate(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_check_runtime::NeuroActivateRequest,
+    confirmed: Option<bool>,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_check_runtime::NeuroActivateResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_activate_impl(
+            state,
+            connection_id,
+            request,
+            crate::neuro_runtime::SapWriteConfirmation::Caller {
+                confirmed: confirmed.unwrap_or(false),
+            },
+        )
+        .await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_atc_run(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_atc_runtime::NeuroAtcRunRequest,
+) -> Result<neuro_types::NeuroCommandResponse<crate::neuro_atc_runtime::NeuroAtcRunResponse>, String>
+{
+    Ok(
+        match crate::neuro_runtime::neuro_atc_run_impl(state, connection_id, request).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_run_unit_tests(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_unit_runtime::NeuroUnitTestRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_unit_runtime::NeuroUnitTestResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_run_unit_tests_impl(state, connection_id, request).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_list_transports(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<
+    neuro_types::NeuroCommandResponse<Vec<crate::neuro_transport_runtime::NeuroTransportRequest>>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_list_transports_impl(state, connection_id).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_create_transport(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_transport_runtime::NeuroTransportCreateRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_transport_runtime::NeuroTransportAssignment>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_create_transport_impl(state, connection_id, request).await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_assign_transport(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_transport_runtime::NeuroTransportAssignRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_transport_runtime::NeuroTransportAssignment>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_assign_transport_impl(state, connection_id, request).await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_sync_package(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_package_runtime::NeuroSyncPackageRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_package_runtime::NeuroSyncPackageResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_sync_package_impl(state, connection_id, request).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_push_changes(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_package_runtime::NeuroPushChangesRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_package_runtime::NeuroPushChangesResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_push_changes_impl(state, connection_id, request).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_lock_object(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<neuro_types::NeuroCommandResponse<crate::neuro_lock_runtime::NeuroObjectLock>, String> {
+    Ok(
+        match crate::neuro_runtime::neuro_lock_object_impl(state, connection_id, object_uri).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_unlock_object(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_lock_runtime::NeuroObjectUnlockResponse>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_unlock_object_impl(state, connection_id, object_uri).await
+        {
             Ok(data) => neuro_types::NeuroCommandResponse::success(data),
             Err(error) => neuro_types::NeuroCommandResponse::failure(error),
         },
@@ -906,13 +1628,52 @@ async fn neuro_update_source(
 #[tauri::command]
 async fn neuro_ws_request(
     state: State<'_, AppState>,
+    connection_id: Option<String>,
     request: neuro_types::WsDomainRequest,
 ) -> Result<
     neuro_types::NeuroCommandResponse<neuro_types::WsMessageEnvelope<serde_json::Value>>,
     String,
 > {
     Ok(
-        match crate::neuro_runtime::neuro_ws_request_impl(state, request).await {
+        match crate::neuro_runtime::neuro_ws_request_impl(state, connection_id, request).await {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_ws_subscribe(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: crate::neuro_ws_subscription_runtime::NeuroWsSubscribeRequest,
+) -> Result<
+    neuro_types::NeuroCommandResponse<crate::neuro_ws_subscription_runtime::NeuroWsSubscription>,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_ws_subscribe_impl(app, state, connection_id, request)
+            .await
+        {
+            Ok(data) => neuro_types::NeuroCommandResponse::success(data),
+            Err(error) => neuro_types::NeuroCommandResponse::failure(error),
+        },
+    )
+}
+
+#[tauri::command]
+async fn neuro_ws_unsubscribe(
+    state: State<'_, AppState>,
+    subscription_id: String,
+) -> Result<
+    neuro_types::NeuroCommandResponse<
+        crate::neuro_ws_subscription_runtime::NeuroWsUnsubscribeResponse,
+    >,
+    String,
+> {
+    Ok(
+        match crate::neuro_runtime::neuro_ws_unsubscribe_impl(state, subscription_id).await {
             Ok(data) => neuro_types::NeuroCommandResponse::success(data),
             Err(error) => neuro_types::NeuroCommandResponse::failure(error),
         },
@@ -922,9 +1683,10 @@ async fn neuro_ws_request(
 #[tauri::command]
 async fn neuro_list_tools(
     state: State<'_, AppState>,
+    connection_id: Option<String>,
 ) -> Result<neuro_types::NeuroCommandResponse<Vec<neuro_mcp::NeuroToolSpec>>, String> {
     Ok(
-        match crate::neuro_runtime::neuro_list_tools_impl(state).await {
+        match crate::neuro_runtime::neuro_list_tools_impl(state, connection_id).await {
             Ok(data) => neuro_types::NeuroCommandResponse::success(data),
             Err(error) => neuro_types::NeuroCommandResponse::failure(error),
         },
@@ -934,11 +1696,21 @@ async fn neuro_list_tools(
 #[tauri::command]
 async fn neuro_invoke_tool(
     state: State<'_, AppState>,
+    connection_id: Option<String>,
     tool_name: String,
     arguments: serde_json::Value,
+    confirmed: Option<bool>,
 ) -> Result<neuro_types::NeuroCommandResponse<serde_json::Value>, String> {
     Ok(
-        match crate::neuro_runtime::neuro_invoke_tool_impl(state, tool_name, arguments).await {
+        match crate::neuro_runtime::neuro_invoke_tool_impl(
+            state,
+            connection_id,
+            tool_name,
+            arguments,
+            confirmed.unwrap_or(false),
+        )
+        .await
+        {
             Ok(data) => neuro_types::NeuroCommandResponse::success(data),
             Err(error) => neuro_types::NeuroCommandResponse::failure(error),
         },
@@ -1173,7 +1945,7 @@ fn run_codex_command(
 }
 
 #[tauri::command]
-fn git_commit_approved_review(
+async fn git_commit_approved_review(
     state: State<'_, AppState>,
     mut request: GitCommitApprovedReviewRequest,
 ) -> Result<GitCommitApprovedReviewResponse, String> {
@@ -1190,7 +1962,48 @@ fn git_commit_approved_review(
 
     request.cwd = Some(cwd);
 
-    crate::command_runtime::git_commit_approved_review_impl(request)
+    crate::command_runtime::git_commit_approved_review_impl(state, request).await
+}
+
+#[tauri::command]
+async fn git_conflict_read(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    request: Option<GitConflictReadRequest>,
+) -> Result<GitConflictReadResponse, String> {
+    crate::command_runtime::git_conflict_read_impl(app, state, request.unwrap_or_default()).await
+}
+
+#[tauri::command]
+fn git_conflict_resolve(
+    state: State<'_, AppState>,
+    request: GitConflictResolveRequest,
+) -> Result<GitConflictResolveResponse, String> {
+    crate::command_runtime::git_conflict_resolve_impl(state, request)
+}
+
+#[tauri::command]
+async fn git_thread_export(
+    state: State<'_, AppState>,
+    request: GitThreadExportRequest,
+) -> Result<GitThreadExportResponse, String> {
+    crate::command_runtime::git_thread_export_impl(state, request).await
+}
+
+#[tauri::command]
+fn git_blame(
+    state: State<'_, AppState>,
+    request: GitBlameRequest,
+) -> Result<GitBlameResponse, String> {
+    crate::command_runtime::git_blame_impl(state, request)
+}
+
+#[tauri::command]
+fn git_file_history(
+    state: State<'_, AppState>,
+    request: GitFileHistoryRequest,
+) -> Result<GitFileHistoryResponse, String> {
+    crate::command_runtime::git_file_history_impl(state, request)
 }
 
 #[tauri::command]
@@ -1326,7 +2139,10 @@ fn main() {
     tauri::Builder::default()
         .manage(AppState::default())
         .setup(|app| {
+            neuro_telemetry_runtime::install_telemetry_sink(app.handle().clone());
             spawn_neuro_startup_probe(app.handle().clone());
+            crate::neuro_runtime::spawn_neuro_health_monitor(app.handle().clone());
+            crate::neuro_mcp_server_runtime::start_neuro_mcp_server(app.handle().clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -1353,10 +2169,49 @@ fn main() {
             codex_runtime_capabilities,
             codex_native_runtime_diagnose,
             neuro_runtime_diagnose,
+            neuro_runtime_reload,
+            neuro_runtime_reset,
+            neuro_connection_list,
+            neuro_connection_create,
+            neuro_connection_update,
+            neuro_connection_delete,
+            neuro_health_history,
+            neuro_telemetry_summary,
+            neuro_secret_status,
+            neuro_secret_create,
+            neuro_secret_unlock,
+            neuro_secret_lock,
+            neuro_secret_set,
+            neuro_secret_clear,
             neuro_search_objects,
             neuro_get_source,
+            neuro_get_sources,
+            neuro_list_versions,
+            neuro_get_version,
+            neuro_diff_source,
+            neuro_where_used,
+            neuro_navigate_definition,
+            neuro_object_structure,
+            neuro_ddic_table,
+            neuro_ddic_data_element,
+            neuro_ddic_domain,
+            neuro_cds_view,
+            neuro_data_preview,
             neuro_update_source,
+            neuro_syntax_check,
+            neuro_activate,
+            neuro_atc_run,
+            neuro_run_unit_tests,
+            neuro_list_transports,
+            neuro_create_transport,
+            neuro_assign_transport,
+            neuro_sync_package,
+            neuro_push_changes,
+            neuro_lock_object,
+            neuro_unlock_object,
             neuro_ws_request,
+            neuro_ws_subscribe,
+            neuro_ws_unsubscribe,
             neuro_list_tools,
             neuro_invoke_tool,
             load_codex_default_config,
@@ -1370,6 +2225,11 @@ fn main() {
             run_codex_command,
             git_commit_approved_review,
             git_workspace_changes,
+            git_conflict_read,
+            git_conflict_resolve,
+            git_thread_export,
+            git_blame,
+            git_file_history,
             codex_workspace_read_file,
             codex_workspace_write_file,
             codex_models_list,
@@ -1386,6 +2246,14 @@ fn main() {
             pick_mention_file,
             codex_help_snapshot
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                let state = app.state::<AppState>();
+                tauri::async_runtime::block_on(crate::neuro_runtime::release_all_object_locks(
+                    state.inner(),
+                ));
+            }
+        });
 }
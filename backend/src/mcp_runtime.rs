@@ -171,7 +171,7 @@ pub fn parse_mcp_server_list_runtime_result(
                         .map(str::trim)
                         .filter(|value| !value.is_empty())
                         .map(|value| match value {
-                            "stdio" | "sse" | "streamable-http" => value.to_string(),
+                            "stdio" | "sse" | "streamable-http" | "in-process" => value.to_string(),
                             _ => "stdio".to_string(),
                         })
                         .unwrap_or_else(|| "stdio".to_string());
@@ -476,4 +476,18 @@ mod tests {
         assert_eq!(parsed.data.len(), 1);
         assert_eq!(parsed.data[0].auth_status, "not_logged_in");
     }
+
+    #[test]
+    fn parse_mcp_server_list_keeps_in_process_transport() {
+        let result = json!({
+            "data": [
+                { "name": "neuro", "transport": "in-process" },
+                { "name": "custom", "transport": "websocket" }
+            ]
+        });
+
+        let parsed = parse_mcp_server_list_runtime_result(&result, 3);
+        assert_eq!(parsed.data[0].transport, "in-process");
+        assert_eq!(parsed.data[1].transport, "stdio");
+    }
 }
@@ -0,0 +1,433 @@
+use neuro_types::{AdtAuth, AdtHttpConfig};
+use quick_xml::events::{BytesRef, BytesStart, Event};
+use quick_xml::Reader;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ETAG};
+use reqwest::{Client, Method, StatusCode};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const CSRF_TOKEN_HEADER: &str = "x-csrf-token";
+const SESSION_TYPE_HEADER: &str = "x-sap-adt-sessiontype";
+
+#[derive(Debug, Clone)]
+pub(crate) struct AdtRestConfig {
+    pub base_url: String,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    pub client: Option<String>,
+    pub language: Option<String>,
+    pub insecure_tls: bool,
+    pub timeout_secs: u64,
+    pub csrf_fetch_path: String,
+}
+
+impl AdtRestConfig {
+    /// Uses the connection settings the engine's ADT client is built from.
+    pub(crate) fn from_engine(config: &AdtHttpConfig) -> Self {
+        let (user, password) = if let AdtAuth::Basic { username, password } = &config.auth {
+            (Some(username.clone()), Some(password.clone()))
+        } else {
+            (None, None)
+        };
+        Self {
+            base_url: config.base_url.clone(),
+            user,
+            password,
+            client: config.sap_client.clone(),
+            language: config.sap_language.clone(),
+            insecure_tls: config.insecure_tls,
+            timeout_secs: config.timeout_secs,
+            csrf_fetch_path: config.csrf_fetch_path.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum AdtRestError {
+    Transport(String),
+    UnexpectedStatus {
+        operation: String,
+        status: u16,
+        body: String,
+    },
+    MissingCsrfToken,
+    InvalidResponse {
+        operation: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for AdtRestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(message) => write!(f, "ADT transport error: {message}"),
+            Self::UnexpectedStatus {
+                operation, status, ..
+            } => write!(f, "ADT operation `{operation}` failed with status {status}"),
+            Self::MissingCsrfToken => write!(f, "missing CSRF token from ADT response"),
+            Self::InvalidResponse { operation, message } => {
+                write!(f, "invalid ADT response for `{operation}`: {message}")
+            }
+        }
+    }
+}
+
+impl AdtRestError {
+    pub(crate) fn invalid_response(operation: &str, message: impl Into<String>) -> Self {
+        Self::InvalidResponse {
+            operation: operation.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AdtRequest {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    headers: Vec<(&'static str, String)>,
+    body: Option<String>,
+    stateful: bool,
+}
+
+impl AdtRequest {
+    fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            query: Vec::new(),
+            headers: Vec::new(),
+            body: None,
+            stateful: false,
+        }
+    }
+
+    pub(crate) fn get(path: impl Into<String>) -> Self {
+        Self::new(Method::GET, path)
+    }
+
+    pub(crate) fn post(path: impl Into<String>) -> Self {
+        Self::new(Method::POST, path)
+    }
+
+    pub(crate) fn put(path: impl Into<String>) -> Self {
+        Self::new(Method::PUT, path)
+    }
+
+    pub(crate) fn query(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.query.push((key.to_string(), value.into()));
+        self
+    }
+
+    pub(crate) fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    pub(crate) fn accept(self, value: impl Into<String>) -> Self {
+        self.header("accept", value)
+    }
+
+    pub(crate) fn content_type(self, value: impl Into<String>) -> Self {
+        self.header("content-type", value)
+    }
+
+    pub(crate) fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Runs the request in the ADT stateful session that owns object locks.
+    pub(crate) fn stateful(mut self) -> Self {
+        self.stateful = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AdtResponse {
+    pub status: u16,
+    pub etag: Option<String>,
+    pub body: String,
+}
+
+/// Thin ADT REST client for endpoints the engine does not cover yet.
+pub(crate) struct AdtRestClient {
+    http: Client,
+    config: AdtRestConfig,
+    csrf_token: Mutex<Option<String>>,
+}
+
+impl AdtRestClient {
+    pub(crate) fn new(config: AdtRestConfig) -> Result<Self, AdtRestError> {
+        let http = Client::builder()
+            .cookie_store(true)
+            .danger_accept_invalid_certs(config.insecure_tls)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|error| AdtRestError::Transport(error.to_string()))?;
+        Ok(Self {
+            http,
+            config,
+            csrf_token: Mutex::new(None),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            return path.to_string();
+        }
+        let base = self.config.base_url.trim_end_matches('/');
+        if path.starts_with('/') {
+            format!("{base}{path}")
+        } else {
+            format!("{base}/{path}")
+        }
+    }
+
+    fn base_request(&self, method: Method, path: &str, stateful: bool) -> reqwest::RequestBuilder {
+        let mut query = Vec::new();
+        if let Some(client) = self.config.client.as_deref() {
+            query.push(("sap-client", client));
+        }
+        if let Some(language) = self.config.language.as_deref() {
+            query.push(("sap-language", language));
+        }
+
+        let mut builder = self.http.request(method, self.url(path)).query(&query);
+        if let Some(user) = self.config.user.as_deref() {
+            builder = builder.basic_auth(user, self.config.password.as_deref());
+        }
+        if stateful {
+            builder = builder.header(SESSION_TYPE_HEADER, "stateful");
+        }
+        builder
+    }
+
+    async fn fetch_csrf_token(&self, stateful: bool) -> Result<String, AdtRestError> {
+        let response = self
+            .base_request(Method::GET, &self.config.csrf_fetch_path, stateful)
+            .header(CSRF_TOKEN_HEADER, "Fetch")
+            .send()
+            .await
+            .map_err(|error| AdtRestError::Transport(error.to_string()))?;
+
+        let status = response.status();
+        let token = header_value(response.headers(), CSRF_TOKEN_HEADER);
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(AdtRestError::UnexpectedStatus {
+                operation: "csrf_fetch".to_string(),
+                status: status.as_u16(),
+                body,
+            });
+        }
+
+        let token = token
+            .filter(|value| !value.is_empty() && !value.eq_ignore_ascii_case("required"))
+            .ok_or(AdtRestError::MissingCsrfToken)?;
+        *self.csrf_token.lock().await = Some(token.clone());
+        Ok(token)
+    }
+
+    async fn csrf_token(&self, stateful: bool) -> Result<String, AdtRestError> {
+        if let Some(token) = self.csrf_token.lock().await.clone() {
+            return Ok(token);
+        }
+        self.fetch_csrf_token(stateful).await
+    }
+
+    async fn send_once(
+        &self,
+        request: &AdtRequest,
+        csrf_token: Option<&str>,
+    ) -> Result<reqwest::Response, AdtRestError> {
+        let mut builder = self
+            .base_request(request.method.clone(), &request.path, request.stateful)
+            .query(&request.query);
+        for (name, value) in &request.headers {
+            let name = HeaderName::from_static(name);
+            let value = HeaderValue::from_str(value)
+                .map_err(|error| AdtRestError::Transport(error.to_string()))?;
+            builder = builder.header(name, value);
+        }
+        if let Some(token) = csrf_token {
+            builder = builder.header(CSRF_TOKEN_HEADER, token);
+        }
+        if let Some(body) = request.body.clone() {
+            builder = builder.body(body);
+        }
+        builder
+            .send()
+            .await
+            .map_err(|error| AdtRestError::Transport(error.to_string()))
+    }
+
+    pub(crate) async fn send(
+        &self,
+        operation: &str,
+        request: AdtRequest,
+    ) -> Result<AdtResponse, AdtRestError> {
+        let needs_csrf = request.method != Method::GET;
+        let token = if needs_csrf {
+            Some(self.csrf_token(request.stateful).await?)
+        } else {
+            None
+        };
+
+        let mut response = self.send_once(&request, token.as_deref()).await?;
+        if needs_csrf && csrf_token_rejected(response.status(), response.headers()) {
+            *self.csrf_token.lock().await = None;
+            let token = self.fetch_csrf_token(request.stateful).await?;
+            response = self.send_once(&request, Some(&token)).await?;
+        }
+
+        let status = response.status();
+        let etag = header_value(response.headers(), ETAG.as_str());
+        let body = response
+            .text()
+            .await
+            .map_err(|error| AdtRestError::Transport(error.to_string()))?;
+        if !status.is_success() {
+            return Err(AdtRestError::UnexpectedStatus {
+                operation: operation.to_string(),
+                status: status.as_u16(),
+                body,
+            });
+        }
+
+        Ok(AdtResponse {
+            status: status.as_u16(),
+            etag,
+            body,
+        })
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+}
+
+fn csrf_token_rejected(status: StatusCode, headers: &HeaderMap) -> bool {
+    status == StatusCode::FORBIDDEN
+        && header_value(headers, CSRF_TOKEN_HEADER)
+            .is_some_and(|value| value.eq_ignore_ascii_case("required"))
+}
+
+/// Renders an inclusive object set; the caller declares the `adtcore` namespace.
+pub(crate) fn adt_object_set_xml(object_uris: &[String]) -> String {
+    let references = object_uris
+        .iter()
+        .map(|object_uri| {
+            format!(
+                "<adtcore:objectReference adtcore:uri=\"{}\"/>",
+                quick_xml::escape::escape(object_uri)
+            )
+        })
+        .collect::<String>();
+    format!(
+        "<objectSet kind=\"inclusive\"><adtcore:objectReferences>{references}</adtcore:objectReferences></objectSet>"
+    )
+}
+
+/// Resolves a character or predefined XML entity reference to its text.
+pub(crate) fn xml_reference_text(reference: &BytesRef<'_>) -> String {
+    if let Ok(Some(character)) = reference.resolve_char_ref() {
+        return character.to_string();
+    }
+    let name = reference.decode().unwrap_or_default();
+    quick_xml::escape::resolve_predefined_entity(&name)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("&{name};"))
+}
+
+/// Reads an attribute by qualified name, falling back to its local name.
+pub(crate) fn xml_attribute(element: &BytesStart<'_>, name: &str) -> Option<String> {
+    element.attributes().flatten().find_map(|attribute| {
+        let key = attribute.key;
+        let matches =
+            key.as_ref() == name.as_bytes() || key.local_name().as_ref() == name.as_bytes();
+        matches.then(|| {
+            attribute
+                .unescape_value()
+                .map(|value| value.into_owned())
+                .unwrap_or_default()
+        })
+    })
+}
+
+/// Collects the text of the first element with each requested local name.
+pub(crate) fn collect_xml_text(
+    xml: &str,
+    names: &[&str],
+) -> Result<BTreeMap<String, String>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut values = BTreeMap::new();
+    let mut current: Option<String> = None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => {
+                let local = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                if names.contains(&local.as_str()) && !values.contains_key(&local) {
+                    current = Some(local);
+                    text.clear();
+                }
+            }
+            Ok(Event::Text(value)) if current.is_some() => {
+                text.push_str(&value.decode().map_err(|error| error.to_string())?);
+            }
+            Ok(Event::CData(value)) if current.is_some() => {
+                text.push_str(&value.decode().map_err(|error| error.to_string())?);
+            }
+            Ok(Event::GeneralRef(reference)) if current.is_some() => {
+                text.push_str(&xml_reference_text(&reference));
+            }
+            Ok(Event::End(element)) => {
+                let local = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                if current.as_deref() == Some(local.as_str()) {
+                    values.insert(local, text.trim().to_string());
+                    current = None;
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(error) => {
+                return Err(format!(
+                    "malformed XML at position {}: {error}",
+                    reader.error_position()
+                ))
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::collect_xml_text;
+
+    #[test]
+    fn collects_first_text_per_element_with_entities() {
+        let xml = r#"<?xml version="1.0"?>
+            <asx:abap xmlns:asx="http://www.sap.com/abapxml"><asx:values><DATA>
+              <LOCK_HANDLE>A&amp;B&#67;</LOCK_HANDLE><CORRNR/><IS_LOCAL>X</IS_LOCAL>
+              <IS_LOCAL>ignored</IS_LOCAL>
+            </DATA></asx:values></asx:abap>"#;
+        let values =
+            collect_xml_text(xml, &["LOCK_HANDLE", "CORRNR", "IS_LOCAL"]).expect("valid xml");
+
+        assert_eq!(values.get("LOCK_HANDLE").map(String::as_str), Some("A&BC"));
+        assert_eq!(values.get("IS_LOCAL").map(String::as_str), Some("X"));
+        assert_eq!(values.get("CORRNR"), None);
+        assert!(collect_xml_text("<a><b></a>", &["b"]).is_err());
+    }
+}
@@ -0,0 +1,544 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::dynamic_tool_runtime::parse_tool_arguments;
+use crate::neuro_atc_runtime::NeuroAtcRunRequest;
+use crate::neuro_check_runtime::{NeuroActivateRequest, NeuroSyntaxCheckRequest};
+use crate::neuro_data_preview_runtime::NeuroDataPreviewRequest;
+use crate::neuro_runtime::SapWriteConfirmation;
+use crate::neuro_transport_runtime::NeuroUpdateSourceRequest;
+use crate::neuro_unit_runtime::NeuroUnitTestRequest;
+use crate::AppState;
+
+/// Name of the MCP server that serves the Neuro tools to native threads.
+pub(crate) const NEURO_AGENT_SERVER_NAME: &str = "neuro";
+
+const SEARCH_OBJECTS_TOOL: &str = "neuro_search_objects";
+const GET_SOURCE_TOOL: &str = "neuro_get_source";
+const UPDATE_SOURCE_TOOL: &str = "neuro_update_source";
+const SYNTAX_CHECK_TOOL: &str = "neuro_syntax_check";
+const ACTIVATE_TOOL: &str = "neuro_activate";
+const ATC_RUN_TOOL: &str = "neuro_atc_run";
+const RUN_UNIT_TESTS_TOOL: &str = "neuro_run_unit_tests";
+const WHERE_USED_TOOL: &str = "neuro_where_used";
+const OBJECT_STRUCTURE_TOOL: &str = "neuro_object_structure";
+const DDIC_TABLE_TOOL: &str = "neuro_ddic_table";
+const DDIC_DATA_ELEMENT_TOOL: &str = "neuro_ddic_data_element";
+const DDIC_DOMAIN_TOOL: &str = "neuro_ddic_domain";
+const CDS_VIEW_TOOL: &str = "neuro_cds_view";
+const DATA_PREVIEW_TOOL: &str = "neuro_data_preview";
+
+pub(crate) struct NeuroAgentTool {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub input_schema: Value,
+    /// Write tools change the SAP system and wait for user approval.
+    pub writes: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchObjectsArguments {
+    query: String,
+    max_results: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectUriArguments {
+    object_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DdicNameArguments {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateSourceArguments {
+    object_uri: String,
+    source: String,
+    etag: Option<String>,
+    transport: Option<String>,
+    syntax_check: Option<bool>,
+}
+
+fn object_uri_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "ADT object URI, e.g. /sap/bc/adt/programs/programs/ztest."
+    })
+}
+
+fn ddic_name_schema(example: &str) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "name": {
+                "type": "string",
+                "description": format!("DDIC name, e.g. {example}.")
+            }
+        },
+        "required": ["name"],
+        "additionalProperties": false
+    })
+}
+
+fn object_uris_property() -> Value {
+    json!({
+        "type": "array",
+        "items": object_uri_property(),
+        "minItems": 1
+    })
+}
+
+pub(crate) fn neuro_agent_tools() -> Vec<NeuroAgentTool> {
+    vec![
+        NeuroAgentTool {
+            name: SEARCH_OBJECTS_TOOL,
+            description: "Search ABAP repository objects on the connected SAP system by name pattern.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Object name or pattern, `*` as wildcard."
+                    },
+                    "maxResults": { "type": "integer", "minimum": 1, "maximum": 200 }
+                },
+                "required": ["query"],
+                "additionalProperties": false
+            }),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: GET_SOURCE_TOOL,
+            description: "Read the source code and ETag of an ABAP object.",
+            input_schema: json!({
+                "type": "object",
+                "properties": { "objectUri": object_uri_property() },
+                "required": ["objectUri"],
+                "additionalProperties": false
+            }),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: UPDATE_SOURCE_TOOL,
+            description: "Replace the source code of an ABAP object. Pass the ETag from neuro_get_source to avoid overwriting concurrent changes.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "objectUri": object_uri_property(),
+                    "source": { "type": "string" },
+                    "etag": { "type": "string" },
+                    "transport": {
+                        "type": "string",
+                        "description": "Transport request to record the change in, e.g. DEVK900123."
+                    },
+                    "syntaxCheck": { "type": "boolean" }
+                },
+                "required": ["objectUri", "source"],
+                "additionalProperties": false
+            }),
+            writes: true,
+        },
+        NeuroAgentTool {
+            name: SYNTAX_CHECK_TOOL,
+            description: "Run the ABAP syntax check on an object, optionally against unsaved source.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "objectUri": object_uri_property(),
+                    "source": { "type": "string" }
+                },
+                "required": ["objectUri"],
+                "additionalProperties": false
+            }),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: ACTIVATE_TOOL,
+            description: "Activate inactive ABAP objects.",
+            input_schema: json!({
+                "type": "object",
+                "properties": { "objectUris": object_uris_property() },
+                "required": ["objectUris"],
+                "additionalProperties": false
+            }),
+            writes: true,
+        },
+        NeuroAgentTool {
+            name: ATC_RUN_TOOL,
+            description: "Run ABAP Test Cockpit checks on objects or a package and return the findings.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "objectUris": { "type": "array", "items": object_uri_property() },
+                    "package": { "type": "string" },
+                    "checkVariant": { "type": "string" },
+                    "maxFindings": { "type": "integer", "minimum": 1 }
+                },
+                "additionalProperties": false
+            }),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: RUN_UNIT_TESTS_TOOL,
+            description: "Run ABAP Unit tests of objects, optionally with statement coverage.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "objectUris": object_uris_property(),
+                    "withCoverage": { "type": "boolean" }
+                },
+                "required": ["objectUris"],
+                "additionalProperties": false
+            }),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: WHERE_USED_TOOL,
+            description: "List the ABAP objects that use an object, with type and package.",
+            input_schema: json!({
+                "type": "object",
+                "properties": { "objectUri": object_uri_property() },
+                "required": ["objectUri"],
+                "additionalProperties": false
+            }),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: OBJECT_STRUCTURE_TOOL,
+            description: "List the methods, attributes and includes of an ABAP class, interface or program with their source positions.",
+            input_schema: json!({
+                "type": "object",
+                "properties": { "objectUri": object_uri_property() },
+                "required": ["objectUri"],
+                "additionalProperties": false
+            }),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: DDIC_TABLE_TOOL,
+            description: "Read the fields, types, keys and includes of a DDIC table or structure. Use the field names when writing SQL.",
+            input_schema: ddic_name_schema("SFLIGHT"),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: DDIC_DATA_ELEMENT_TOOL,
+            description: "Read a DDIC data element: type, length, domain and field labels.",
+            input_schema: ddic_name_schema("MATNR"),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: DDIC_DOMAIN_TOOL,
+            description: "Read a DDIC domain: type, length, value table and fixed values.",
+            input_schema: ddic_name_schema("BUKRS"),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: CDS_VIEW_TOOL,
+            description: "Read the source, annotations and elements of a CDS view or entity.",
+            input_schema: ddic_name_schema("I_COUNTRY"),
+            writes: false,
+        },
+        NeuroAgentTool {
+            name: DATA_PREVIEW_TOOL,
+            description: "Run a read-only ABAP SQL SELECT, or read the first rows of a table or CDS entity, and return columns and rows. Results are capped.",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "ABAP SQL SELECT, e.g. SELECT COUNT(*) FROM vbak WHERE gbstk = 'A'."
+                    },
+                    "entity": {
+                        "type": "string",
+                        "description": "Table or CDS entity name, instead of a query."
+                    },
+                    "maxRows": { "type": "integer", "minimum": 1 }
+                },
+                "additionalProperties": false
+            }),
+            writes: false,
+        },
+    ]
+}
+
+/// `None` when `tool` is not a Neuro tool.
+pub(crate) fn neuro_agent_tool_writes(tool: &str) -> Option<bool> {
+    neuro_agent_tools()
+        .into_iter()
+        .find(|entry| entry.name == tool)
+        .map(|entry| entry.writes)
+}
+
+/// Facade tools that only read from SAP. Every other facade tool counts as a write.
+const NEURO_FACADE_READ_TOOLS: &[&str] = &["adt_search_objects", "adt_get_source"];
+
+/// Whether a Neuro agent or facade tool can change the SAP system; unknown tools are writes.
+pub(crate) fn neuro_tool_writes(tool: &str) -> bool {
+    neuro_agent_tool_writes(tool).unwrap_or_else(|| !NEURO_FACADE_READ_TOOLS.contains(&tool))
+}
+
+/// Whether the tool's write path already asks for confirmation with a diff, which makes a
+/// tool-call approval a second prompt for the same write.
+pub(crate) async fn neuro_agent_tool_confirms_write(
+    app: &AppHandle,
+    tool: &str,
+    arguments: &Value,
+) -> bool {
+    if tool != UPDATE_SOURCE_TOOL && tool != ACTIVATE_TOOL {
+        return false;
+    }
+    let connection_id = arguments.get("connectionId").and_then(Value::as_str);
+    crate::neuro_runtime::writes_require_confirmation(
+        app.state::<AppState>().inner(),
+        connection_id,
+    )
+    .await
+}
+
+/// Short description of a write call, shown in the approval prompt.
+pub(crate) fn neuro_agent_approval_reason(tool: &str, arguments: &Value) -> String {
+    let text = |key: &str| {
+        arguments
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    match tool {
+        UPDATE_SOURCE_TOOL => {
+            let object_uri = text("objectUri").unwrap_or("unknown object");
+            match text("transport") {
+                Some(transport) => {
+                    format!("Update ABAP source of {object_uri} in transport {transport}")
+                }
+                None => format!("Update ABAP source of {object_uri}"),
+            }
+        }
+        ACTIVATE_TOOL => {
+            let object_uris = arguments
+                .get("objectUris")
+                .and_then(Value::as_array)
+                .map(|uris| {
+                    uris.iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_default();
+            format!("Activate {object_uris}")
+        }
+        other => format!("Run {other} on the SAP system"),
+    }
+}
+
+fn to_tool_result<T: serde::Serialize>(
+    result: Result<T, neuro_types::NeuroRuntimeError>,
+) -> Result<Value, String> {
+    match result {
+        Ok(value) => serde_json::to_value(value).map_err(|error| error.to_string()),
+        Err(error) => Err(serde_json::to_string(&error).unwrap_or(error.message)),
+    }
+}
+
+pub(crate) async fn invoke_neuro_agent_tool(
+    app: &AppHandle,
+    tool: &str,
+    mut arguments: Value,
+) -> Result<Value, String> {
+    let state = app.state::<AppState>();
+    let connection_id = arguments
+        .as_object_mut()
+        .and_then(|object| object.remove("connectionId"))
+        .and_then(|value| value.as_str().map(str::to_string));
+
+    match tool {
+        SEARCH_OBJECTS_TOOL => {
+            let request: SearchObjectsArguments = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_search_objects_impl(
+                    state,
+                    connection_id,
+                    request.query,
+                    request.max_results,
+                )
+                .await,
+            )
+        }
+        GET_SOURCE_TOOL => {
+            let request: ObjectUriArguments = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_get_source_impl(
+                    state,
+                    connection_id,
+                    request.object_uri,
+                )
+                .await,
+            )
+        }
+        UPDATE_SOURCE_TOOL => {
+            let request: UpdateSourceArguments = parse_tool_arguments(tool, arguments)?;
+            let update = NeuroUpdateSourceRequest {
+                update: neuro_types::AdtUpdateSourceRequest {
+                    object_uri: request.object_uri,
+                    source: request.source,
+                    etag: request.etag,
+                },
+                transport: request.transport,
+            };
+            to_tool_result(
+                crate::neuro_runtime::neuro_update_source_impl(
+                    state,
+                    connection_id,
+                    update,
+                    request.syntax_check,
+                    SapWriteConfirmation::Session(app.clone()),
+                )
+                .await,
+            )
+        }
+        SYNTAX_CHECK_TOOL => {
+            let request: NeuroSyntaxCheckRequest = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_syntax_check_impl(state, connection_id, request).await,
+            )
+        }
+        ACTIVATE_TOOL => {
+            let request: NeuroActivateRequest = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_activate_impl(
+                    state,
+                    connection_id,
+                    request,
+                    SapWriteConfirmation::Session(app.clone()),
+                )
+                .await,
+            )
+        }
+        ATC_RUN_TOOL => {
+            let request: NeuroAtcRunRequest = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_atc_run_impl(state, connection_id, request).await,
+            )
+        }
+        RUN_UNIT_TESTS_TOOL => {
+            let request: NeuroUnitTestRequest = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_run_unit_tests_impl(state, connection_id, request)
+                    .await,
+            )
+        }
+        WHERE_USED_TOOL => {
+            let request: ObjectUriArguments = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_where_used_impl(
+                    state,
+                    connection_id,
+                    request.object_uri,
+                )
+                .await,
+            )
+        }
+        OBJECT_STRUCTURE_TOOL => {
+            let request: ObjectUriArguments = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_object_structure_impl(
+                    state,
+                    connection_id,
+                    request.object_uri,
+                )
+                .await,
+            )
+        }
+        DDIC_TABLE_TOOL => {
+            let request: DdicNameArguments = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_ddic_table_impl(state, connection_id, request.name)
+                    .await,
+            )
+        }
+        DDIC_DATA_ELEMENT_TOOL => {
+            let request: DdicNameArguments = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_ddic_data_element_impl(
+                    state,
+                    connection_id,
+                    request.name,
+                )
+                .await,
+            )
+        }
+        DDIC_DOMAIN_TOOL => {
+            let request: DdicNameArguments = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_ddic_domain_impl(state, connection_id, request.name)
+                    .await,
+            )
+        }
+        CDS_VIEW_TOOL => {
+            let request: DdicNameArguments = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_cds_view_impl(state, connection_id, request.name).await,
+            )
+        }
+        DATA_PREVIEW_TOOL => {
+            let request: NeuroDataPreviewRequest = parse_tool_arguments(tool, arguments)?;
+            to_tool_result(
+                crate::neuro_runtime::neuro_data_preview_impl(state, connection_id, request).await,
+            )
+        }
+        other => Err(format!("unknown neuro tool `{other}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        neuro_agent_approval_reason, neuro_agent_tool_writes, neuro_agent_tools, neuro_tool_writes,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn only_write_tools_need_approval() {
+        let writes = neuro_agent_tools()
+            .into_iter()
+            .filter(|tool| tool.writes)
+            .map(|tool| tool.name)
+            .collect::<Vec<_>>();
+        assert_eq!(writes, vec!["neuro_update_source", "neuro_activate"]);
+        assert_eq!(neuro_agent_tool_writes("neuro_get_source"), Some(false));
+        assert_eq!(neuro_agent_tool_writes("git_blame"), None);
+        assert!(neuro_tool_writes("neuro_activate"));
+        assert!(!neuro_tool_writes("neuro_where_used"));
+        assert!(!neuro_tool_writes("adt_get_source"));
+        assert!(neuro_tool_writes("adt_update_source"));
+        assert!(neuro_tool_writes("adt_unknown_tool"));
+        for tool in neuro_agent_tools() {
+            assert_eq!(tool.input_schema["type"], "object");
+        }
+    }
+
+    #[test]
+    fn describes_write_calls_for_approval() {
+        assert_eq!(
+            neuro_agent_approval_reason(
+                "neuro_update_source",
+                &json!({ "objectUri": "/sap/bc/adt/programs/programs/ztest", "transport": "DEVK900123" }),
+            ),
+            "Update ABAP source of /sap/bc/adt/programs/programs/ztest in transport DEVK900123"
+        );
+        assert_eq!(
+            neuro_agent_approval_reason(
+                "neuro_activate",
+                &json!({ "objectUris": ["/sap/bc/adt/oo/classes/zcl_a", "/sap/bc/adt/oo/classes/zcl_b"] }),
+            ),
+            "Activate /sap/bc/adt/oo/classes/zcl_a, /sap/bc/adt/oo/classes/zcl_b"
+        );
+    }
+}
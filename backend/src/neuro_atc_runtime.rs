@@ -0,0 +1,373 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::time::Duration;
+
+use crate::neuro_adt_rest::{adt_object_set_xml, collect_xml_text, xml_attribute};
+use crate::neuro_check_runtime::parse_uri_position;
+use crate::neuro_lock_runtime::normalize_object_uri;
+
+pub const ATC_WORKLISTS_PATH: &str = "/sap/bc/adt/atc/worklists";
+pub const ATC_RUNS_PATH: &str = "/sap/bc/adt/atc/runs";
+pub const ATC_WORKLIST_ACCEPT: &str = "application/atc.worklist.v1+xml";
+pub const DEFAULT_ATC_CHECK_VARIANT: &str = "DEFAULT";
+pub const DEFAULT_ATC_TIMEOUT_SECS: u64 = 300;
+pub const ATC_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_ATC_MAX_FINDINGS: u32 = 100;
+const MAX_ATC_MAX_FINDINGS: u32 = 5000;
+const PACKAGES_PATH: &str = "/sap/bc/adt/packages/";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroAtcRunRequest {
+    #[serde(default)]
+    pub object_uris: Vec<String>,
+    pub package: Option<String>,
+    pub check_variant: Option<String>,
+    pub max_findings: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroAtcQuickFixHints {
+    pub automatic: bool,
+    pub manual: bool,
+    pub pseudo: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroAtcFinding {
+    pub finding_uri: Option<String>,
+    pub object_uri: Option<String>,
+    pub object_name: Option<String>,
+    pub object_type: Option<String>,
+    /// ATC priority: 1 (very high) to 4 (low).
+    pub priority: u8,
+    pub check_id: Option<String>,
+    pub check_title: Option<String>,
+    pub message_id: Option<String>,
+    pub message: String,
+    pub location_uri: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub quick_fixes: NeuroAtcQuickFixHints,
+    pub documentation_uri: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroAtcRunResponse {
+    pub worklist_id: String,
+    pub check_variant: String,
+    /// False when polling stopped at the timeout before the worklist was complete.
+    pub complete: bool,
+    pub findings: Vec<NeuroAtcFinding>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct AtcWorklist {
+    pub complete: bool,
+    pub findings: Vec<NeuroAtcFinding>,
+}
+
+pub(crate) fn resolve_check_variant(check_variant: Option<&str>) -> String {
+    check_variant
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            env::var("NEURO_ATC_CHECK_VARIANT")
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        })
+        .unwrap_or_else(|| DEFAULT_ATC_CHECK_VARIANT.to_string())
+        .to_ascii_uppercase()
+}
+
+pub(crate) fn atc_timeout_from_env() -> Duration {
+    let secs = env::var("NEURO_ATC_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_ATC_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+}
+
+pub(crate) fn resolve_max_findings(max_findings: Option<u32>) -> u32 {
+    max_findings
+        .unwrap_or(DEFAULT_ATC_MAX_FINDINGS)
+        .clamp(1, MAX_ATC_MAX_FINDINGS)
+}
+
+fn package_uri(package: &str) -> String {
+    let encoded = package
+        .trim()
+        .to_ascii_lowercase()
+        .chars()
+        .map(|entry| match entry {
+            '/' => "%2f".to_string(),
+            other => other.to_string(),
+        })
+        .collect::<String>();
+    format!("{PACKAGES_PATH}{encoded}")
+}
+
+/// Resolves the ATC object set: explicit object URIs, a package, or both.
+pub(crate) fn atc_object_set(request: &NeuroAtcRunRequest) -> Result<Vec<String>, String> {
+    let mut object_uris = Vec::new();
+    for object_uri in &request.object_uris {
+        let object_uri = normalize_object_uri(object_uri)?;
+        if !object_uris.contains(&object_uri) {
+            object_uris.push(object_uri);
+        }
+    }
+    if let Some(package) = request
+        .package
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        object_uris.push(package_uri(package));
+    }
+    if object_uris.is_empty() {
+        return Err("ATC run requires objectUris or a package".to_string());
+    }
+    Ok(object_uris)
+}
+
+pub(crate) fn atc_run_request_xml(object_uris: &[String], max_findings: u32) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><atc:run maximumVerdicts=\"{max_findings}\" xmlns:atc=\"http://www.sap.com/adt/atc\"><objectSets xmlns:adtcore=\"http://www.sap.com/adt/core\">{}</objectSets></atc:run>",
+        adt_object_set_xml(object_uris)
+    )
+}
+
+/// The worklist endpoint answers with the id as plain text; some releases wrap it in XML.
+pub(crate) fn parse_worklist_id(body: &str) -> Result<String, String> {
+    let trimmed = body.trim();
+    if trimmed.starts_with('<') {
+        return collect_xml_text(trimmed, &["worklistId"])?
+            .remove("worklistId")
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| "ATC response does not contain a worklist id".to_string());
+    }
+    if trimmed.is_empty() {
+        return Err("ATC response does not contain a worklist id".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+fn non_empty_attribute(element: &BytesStart<'_>, name: &str) -> Option<String> {
+    xml_attribute(element, name).filter(|value| !value.trim().is_empty())
+}
+
+fn finding_from_element(
+    element: &BytesStart<'_>,
+    object: &(Option<String>, Option<String>, Option<String>),
+) -> NeuroAtcFinding {
+    let location_uri = non_empty_attribute(element, "location");
+    let (location_object, line, column) = location_uri
+        .as_deref()
+        .map(parse_uri_position)
+        .unwrap_or((None, None, None));
+    NeuroAtcFinding {
+        finding_uri: non_empty_attribute(element, "uri"),
+        object_uri: object.0.clone().or(location_object),
+        object_name: object.1.clone(),
+        object_type: object.2.clone(),
+        priority: xml_attribute(element, "priority")
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(4),
+        check_id: non_empty_attribute(element, "checkId"),
+        check_title: non_empty_attribute(element, "checkTitle"),
+        message_id: non_empty_attribute(element, "messageId"),
+        message: xml_attribute(element, "messageTitle").unwrap_or_default(),
+        location_uri,
+        line,
+        column,
+        quick_fixes: NeuroAtcQuickFixHints::default(),
+        documentation_uri: None,
+    }
+}
+
+fn is_true(element: &BytesStart<'_>, name: &str) -> bool {
+    xml_attribute(element, name).is_some_and(|value| value.eq_ignore_ascii_case("true"))
+}
+
+pub(crate) fn parse_atc_worklist(xml: &str) -> Result<AtcWorklist, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut worklist = AtcWorklist::default();
+    let mut object: (Option<String>, Option<String>, Option<String>) = (None, None, None);
+    let mut finding: Option<NeuroAtcFinding> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        let (element, is_empty) = match &event {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(element) => {
+                match element.local_name().as_ref() {
+                    b"finding" => worklist.findings.extend(finding.take()),
+                    b"object" => object = (None, None, None),
+                    _ => {}
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match element.local_name().as_ref() {
+            b"worklist" => {
+                worklist.complete = is_true(element, "objectSetIsComplete");
+            }
+            b"object" if finding.is_none() => {
+                object = (
+                    non_empty_attribute(element, "uri")
+                        .map(|uri| normalize_object_uri(&uri).unwrap_or(uri)),
+                    non_empty_attribute(element, "name"),
+                    non_empty_attribute(element, "type"),
+                );
+            }
+            b"finding" => {
+                let parsed = finding_from_element(element, &object);
+                if is_empty {
+                    worklist.findings.push(parsed);
+                } else {
+                    finding = Some(parsed);
+                }
+            }
+            b"quickfixes" => {
+                if let Some(current) = finding.as_mut() {
+                    current.quick_fixes = NeuroAtcQuickFixHints {
+                        automatic: is_true(element, "automatic"),
+                        manual: is_true(element, "manual"),
+                        pseudo: is_true(element, "pseudo"),
+                    };
+                }
+            }
+            b"link" => {
+                let is_documentation = xml_attribute(element, "rel")
+                    .is_some_and(|rel| rel.ends_with("/documentation"));
+                if let Some(current) = finding.as_mut().filter(|_| is_documentation) {
+                    current.documentation_uri = non_empty_attribute(element, "href");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    worklist.findings.sort_by_key(|finding| finding.priority);
+    Ok(worklist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        atc_object_set, atc_run_request_xml, parse_atc_worklist, parse_worklist_id,
+        NeuroAtcRunRequest,
+    };
+
+    const PROGRAM: &str = "/sap/bc/adt/programs/programs/ztest";
+
+    #[test]
+    fn builds_object_sets_from_objects_and_packages() {
+        let request = NeuroAtcRunRequest {
+            object_uris: vec![format!("{PROGRAM}/source/main"), PROGRAM.to_string()],
+            package: Some("/NEO/CORE".to_string()),
+            check_variant: None,
+            max_findings: None,
+        };
+        let object_set = atc_object_set(&request).expect("valid object set");
+        assert_eq!(
+            object_set,
+            vec![
+                PROGRAM.to_string(),
+                "/sap/bc/adt/packages/%2fneo%2fcore".to_string()
+            ]
+        );
+
+        let body = atc_run_request_xml(&object_set, 50);
+        assert!(body.contains("maximumVerdicts=\"50\""));
+        assert!(body.contains("adtcore:uri=\"/sap/bc/adt/packages/%2fneo%2fcore\""));
+
+        let empty = NeuroAtcRunRequest {
+            object_uris: Vec::new(),
+            package: Some(" ".to_string()),
+            check_variant: None,
+            max_findings: None,
+        };
+        assert!(atc_object_set(&empty).is_err());
+    }
+
+    #[test]
+    fn parses_worklist_ids() {
+        assert_eq!(parse_worklist_id(" 0242AC11 \n").unwrap(), "0242AC11");
+        assert_eq!(
+            parse_worklist_id(
+                "<atcworklist:worklistRun xmlns:atcworklist=\"http://www.sap.com/adt/atc/worklist\"><atcworklist:worklistId>0242AC12</atcworklist:worklistId></atcworklist:worklistRun>"
+            )
+            .unwrap(),
+            "0242AC12"
+        );
+        assert!(parse_worklist_id("").is_err());
+    }
+
+    #[test]
+    fn parses_findings_with_locations_and_quick_fixes() {
+        let xml = format!(
+            r#"<atcworklist:worklist atcworklist:id="0242AC11" atcworklist:objectSetIsComplete="true"
+                xmlns:atcworklist="http://www.sap.com/adt/atc/worklist" xmlns:atcobject="http://www.sap.com/adt/atc/object"
+                xmlns:atcfinding="http://www.sap.com/adt/atc/finding" xmlns:adtcore="http://www.sap.com/adt/core" xmlns:atom="http://www.w3.org/2005/Atom">
+              <atcworklist:objects>
+                <atcobject:object adtcore:uri="{PROGRAM}" adtcore:type="PROG/P" adtcore:name="ZTEST">
+                  <atcobject:findings>
+                    <atcfinding:finding adtcore:uri="/sap/bc/adt/atc/findings/itemid/1" atcfinding:location="{PROGRAM}/source/main#start=14,2"
+                        atcfinding:priority="2" atcfinding:checkId="CL_CI_TEST_SELECT" atcfinding:checkTitle="Search problematic statements"
+                        atcfinding:messageId="0001" atcfinding:messageTitle="SELECT * in loop">
+                      <atom:link href="/sap/bc/adt/documentation/atc/1" rel="http://www.sap.com/adt/relations/documentation"/>
+                      <atcfinding:quickfixes atcfinding:manual="false" atcfinding:automatic="true" atcfinding:pseudo="false"/>
+                    </atcfinding:finding>
+                    <atcfinding:finding atcfinding:location="{PROGRAM}/source/main#start=3,0" atcfinding:priority="1" atcfinding:messageTitle="Syntax error"/>
+                  </atcobject:findings>
+                </atcobject:object>
+              </atcworklist:objects>
+            </atcworklist:worklist>"#
+        );
+        let worklist = parse_atc_worklist(&xml).expect("valid worklist");
+
+        assert!(worklist.complete);
+        assert_eq!(worklist.findings.len(), 2);
+        let first = &worklist.findings[0];
+        assert_eq!(first.priority, 1);
+        assert_eq!(first.message, "Syntax error");
+        assert_eq!(first.object_name.as_deref(), Some("ZTEST"));
+        assert_eq!(first.line, Some(3));
+
+        let second = &worklist.findings[1];
+        assert_eq!(second.object_uri.as_deref(), Some(PROGRAM));
+        assert_eq!(second.object_type.as_deref(), Some("PROG/P"));
+        assert_eq!(second.check_id.as_deref(), Some("CL_CI_TEST_SELECT"));
+        assert_eq!((second.line, second.column), (Some(14), Some(2)));
+        assert!(second.quick_fixes.automatic);
+        assert!(!second.quick_fixes.manual);
+        assert_eq!(
+            second.documentation_uri.as_deref(),
+            Some("/sap/bc/adt/documentation/atc/1")
+        );
+
+        let pending = parse_atc_worklist(
+            "<atcworklist:worklist xmlns:atcworklist=\"x\" atcworklist:objectSetIsComplete=\"false\"/>",
+        )
+        .unwrap();
+        assert!(!pending.complete);
+    }
+}
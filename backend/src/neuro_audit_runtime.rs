@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const AUDIT_LOG_FILE_NAME: &str = "neuro-audit.jsonl";
+
+/// One SAP write attempt; the log is only ever appended to.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroAuditEntry {
+    pub timestamp_epoch_secs: u64,
+    pub operation: String,
+    pub connection_id: String,
+    /// SAP base URL and client, e.g. `https://sap.example.internal (100)`.
+    pub system: Option<String>,
+    pub user: Option<String>,
+    pub object_uri: Option<String>,
+    /// ETag sent with the write.
+    pub etag: Option<String>,
+    /// ETag returned by SAP after a successful write.
+    pub new_etag: Option<String>,
+    pub source_sha256: Option<String>,
+    pub transport: Option<String>,
+    /// `succeeded`, `failed` or `declined`.
+    pub outcome: String,
+    pub error: Option<String>,
+}
+
+pub(crate) fn audit_log_path() -> PathBuf {
+    if let Some(path) = env::var_os("NEURO_AUDIT_LOG_PATH") {
+        if !path.is_empty() {
+            return PathBuf::from(path);
+        }
+    }
+    crate::config_runtime::resolve_codex_home().join(AUDIT_LOG_FILE_NAME)
+}
+
+pub(crate) fn append_audit_entry(path: &Path, entry: &NeuroAuditEntry) -> Result<(), String> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create audit log directory: {error}"))?;
+    }
+    let mut line = serde_json::to_string(entry)
+        .map_err(|error| format!("failed to serialize audit entry: {error}"))?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|error| format!("failed to append to audit log {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{append_audit_entry, NeuroAuditEntry};
+    use std::fs;
+
+    #[test]
+    fn appends_one_json_line_per_entry() {
+        let dir = std::env::temp_dir().join(format!("neuro-audit-test-{}", std::process::id()));
+        let path = dir.join("audit").join("neuro-audit.jsonl");
+        let _ = fs::remove_dir_all(&dir);
+
+        let entry = NeuroAuditEntry {
+            timestamp_epoch_secs: 1_700_000_000,
+            operation: "update_source".to_string(),
+            connection_id: "dev".to_string(),
+            system: Some("https://sap.example.internal (100)".to_string()),
+            user: Some("DEVELOPER".to_string()),
+            object_uri: Some("/sap/bc/adt/programs/programs/ztest".to_string()),
+            etag: Some("etag-1".to_string()),
+            new_etag: Some("etag-2".to_string()),
+            source_sha256: Some("ab".repeat(32)),
+            transport: None,
+            outcome: "succeeded".to_string(),
+            error: None,
+        };
+        append_audit_entry(&path, &entry).unwrap();
+        append_audit_entry(
+            &path,
+            &NeuroAuditEntry {
+                outcome: "declined".to_string(),
+                ..entry.clone()
+            },
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let entries = content
+            .lines()
+            .map(|line| serde_json::from_str::<NeuroAuditEntry>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], entry);
+        assert_eq!(entries[1].outcome, "declined");
+        assert!(content.contains("\"sourceSha256\""));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
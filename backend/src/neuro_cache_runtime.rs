@@ -0,0 +1,196 @@
+use neuro_types::{AdtObjectSummary, AdtSourceResponse, NeuroRuntimeError};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::env;
+use std::time::{Duration, Instant};
+
+const DEFAULT_CACHE_MAX_ENTRIES: usize = 256;
+const DEFAULT_CACHE_TTL_SECS: u64 = 300;
+const DEFAULT_SOURCE_FETCH_CONCURRENCY: usize = 4;
+const MAX_SOURCE_FETCH_CONCURRENCY: usize = 16;
+pub(crate) const MAX_SOURCE_BATCH_SIZE: usize = 200;
+
+/// One object of a `neuro_get_sources` batch; a failed object does not fail the batch.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSourceEntry {
+    pub object_uri: String,
+    pub source: Option<AdtSourceResponse>,
+    pub error: Option<NeuroRuntimeError>,
+    /// Served from the cache, possibly after an `If-None-Match` check.
+    pub cached: bool,
+}
+
+/// `NEURO_SOURCE_FETCH_CONCURRENCY`, clamped to 1..=16.
+pub(crate) fn source_fetch_concurrency_from_env() -> usize {
+    env::var("NEURO_SOURCE_FETCH_CONCURRENCY")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_SOURCE_FETCH_CONCURRENCY)
+        .clamp(1, MAX_SOURCE_FETCH_CONCURRENCY)
+}
+
+#[derive(Debug)]
+struct LruEntry<V> {
+    value: V,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+/// Least-recently-used map; entries older than the TTL are reported as stale, not dropped.
+#[derive(Debug)]
+pub(crate) struct LruCache<V> {
+    capacity: usize,
+    ttl: Duration,
+    tick: u64,
+    entries: HashMap<String, LruEntry<V>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CacheLookup<V> {
+    Fresh(V),
+    Stale(V),
+}
+
+impl<V: Clone> LruCache<V> {
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &str, now: Instant) -> Option<CacheLookup<V>> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = self.tick;
+        let value = entry.value.clone();
+        if now.saturating_duration_since(entry.stored_at) < self.ttl {
+            Some(CacheLookup::Fresh(value))
+        } else {
+            Some(CacheLookup::Stale(value))
+        }
+    }
+
+    pub(crate) fn insert(&mut self, key: String, value: V, now: Instant) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            if let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(
+            key,
+            LruEntry {
+                value,
+                stored_at: now,
+                last_used: self.tick,
+            },
+        );
+    }
+
+    /// Marks an entry as fresh again, e.g. after SAP answered `304 Not Modified`.
+    pub(crate) fn touch(&mut self, key: &str, now: Instant) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.stored_at = now;
+        }
+    }
+
+    pub(crate) fn remove(&mut self, key: &str) {
+        self.entries.remove(key);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Per-connection cache of search results and sources.
+pub(crate) struct NeuroResponseCache {
+    pub search: LruCache<Vec<AdtObjectSummary>>,
+    /// Keyed by normalized object URI; every hit is revalidated with the stored ETag.
+    pub sources: LruCache<AdtSourceResponse>,
+}
+
+impl NeuroResponseCache {
+    pub(crate) fn new(max_entries: usize, ttl: Duration) -> Self {
+        Self {
+            search: LruCache::new(max_entries, ttl),
+            sources: LruCache::new(max_entries, ttl),
+        }
+    }
+
+    /// `NEURO_CACHE_MAX_ENTRIES` (0 disables the cache) and `NEURO_CACHE_TTL_SECS`.
+    pub(crate) fn from_env() -> Self {
+        let max_entries = env::var("NEURO_CACHE_MAX_ENTRIES")
+            .ok()
+            .and_then(|raw| raw.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_CACHE_MAX_ENTRIES);
+        let ttl_secs = env::var("NEURO_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|raw| raw.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_CACHE_TTL_SECS);
+        Self::new(max_entries, Duration::from_secs(ttl_secs))
+    }
+}
+
+/// SAP quick search ignores case, so `ztest*` and `ZTEST*` share an entry.
+pub(crate) fn search_cache_key(query: &str, max_results: Option<u32>) -> String {
+    format!(
+        "{}|{}",
+        query.trim().to_ascii_uppercase(),
+        max_results
+            .map(|value| value.to_string())
+            .unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{search_cache_key, CacheLookup, LruCache};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn evicts_least_recently_used_and_reports_stale_entries() {
+        let start = Instant::now();
+        let mut cache = LruCache::new(2, Duration::from_secs(60));
+        cache.insert("a".to_string(), 1, start);
+        cache.insert("b".to_string(), 2, start);
+        assert_eq!(cache.get("a", start), Some(CacheLookup::Fresh(1)));
+
+        cache.insert("c".to_string(), 3, start);
+        assert_eq!(cache.get("b", start), None);
+        assert_eq!(cache.get("a", start), Some(CacheLookup::Fresh(1)));
+
+        let later = start + Duration::from_secs(61);
+        assert_eq!(cache.get("c", later), Some(CacheLookup::Stale(3)));
+        cache.touch("c", later);
+        assert_eq!(cache.get("c", later), Some(CacheLookup::Fresh(3)));
+
+        cache.remove("c");
+        assert_eq!(cache.get("c", later), None);
+
+        cache.clear();
+        assert_eq!(cache.get("a", later), None);
+    }
+
+    #[test]
+    fn zero_capacity_disables_caching() {
+        let now = Instant::now();
+        let mut cache = LruCache::new(0, Duration::from_secs(60));
+        cache.insert("a".to_string(), 1, now);
+        assert_eq!(cache.get("a", now), None);
+        assert_eq!(search_cache_key(" ztest* ", Some(50)), "ZTEST*|50");
+        assert_eq!(search_cache_key("ZTEST*", None), "ZTEST*|");
+    }
+}
@@ -0,0 +1,426 @@
+use base64::Engine as _;
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+use crate::neuro_adt_rest::{xml_attribute, xml_reference_text};
+use crate::neuro_lock_runtime::normalize_object_uri;
+
+pub const SYNTAX_CHECK_PATH: &str = "/sap/bc/adt/checkruns";
+pub const SYNTAX_CHECK_CONTENT_TYPE: &str = "application/vnd.sap.adt.checkobjects+xml";
+pub const SYNTAX_CHECK_ACCEPT: &str = "application/vnd.sap.adt.checkmessages+xml";
+pub const ACTIVATION_PATH: &str = "/sap/bc/adt/activation";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NeuroCheckSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroCheckMessage {
+    pub object_uri: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub severity: NeuroCheckSeverity,
+    pub text: String,
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSyntaxCheckRequest {
+    pub object_uri: String,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSyntaxCheckResponse {
+    pub object_uri: String,
+    pub has_errors: bool,
+    pub messages: Vec<NeuroCheckMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroActivateRequest {
+    pub object_uris: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroActivationObjectResult {
+    pub object_uri: String,
+    pub activated: bool,
+    pub messages: Vec<NeuroCheckMessage>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroActivateResponse {
+    pub activated: bool,
+    pub objects: Vec<NeuroActivationObjectResult>,
+    /// Messages SAP did not attribute to one of the requested objects.
+    pub messages: Vec<NeuroCheckMessage>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ActivationResult {
+    pub messages: Vec<NeuroCheckMessage>,
+    pub inactive_object_uris: Vec<String>,
+}
+
+pub(crate) fn severity_from_type(message_type: &str) -> NeuroCheckSeverity {
+    match message_type.trim().to_ascii_uppercase().as_str() {
+        "E" | "A" | "X" | "ERROR" => NeuroCheckSeverity::Error,
+        "W" | "WARNING" => NeuroCheckSeverity::Warning,
+        _ => NeuroCheckSeverity::Info,
+    }
+}
+
+pub(crate) fn has_errors(messages: &[NeuroCheckMessage]) -> bool {
+    messages
+        .iter()
+        .any(|message| message.severity == NeuroCheckSeverity::Error)
+}
+
+/// Splits an ADT message link such as `.../source/main#start=12,4` into object URI and position.
+pub(crate) fn parse_uri_position(uri: &str) -> (Option<String>, Option<u32>, Option<u32>) {
+    let (path, fragment) = uri.split_once('#').unwrap_or((uri, ""));
+    let object_uri = normalize_object_uri(path).ok();
+    let position = fragment
+        .split(['&', ';'])
+        .find_map(|entry| entry.strip_prefix("start="));
+    let Some(position) = position else {
+        return (object_uri, None, None);
+    };
+    let mut parts = position.split(',');
+    let line = parts.next().and_then(|value| value.trim().parse().ok());
+    let column = parts.next().and_then(|value| value.trim().parse().ok());
+    (object_uri, line, column)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Derives the ADT object name from the last URI segment, e.g. `%2fneo%2fzprog` -> `/NEO/ZPROG`.
+pub(crate) fn object_name_from_uri(object_uri: &str) -> String {
+    let segment = object_uri.rsplit('/').next().unwrap_or(object_uri);
+    percent_decode(segment).to_ascii_uppercase()
+}
+
+pub(crate) fn syntax_check_request_xml(object_uri: &str, source: Option<&str>) -> String {
+    let artifacts = source
+        .map(|source| {
+            let content = base64::engine::general_purpose::STANDARD.encode(source);
+            format!(
+                "<chkrun:artifacts><chkrun:artifact chkrun:contentType=\"text/plain; charset=utf-8\" chkrun:uri=\"{}/source/main\"><chkrun:content>{content}</chkrun:content></chkrun:artifact></chkrun:artifacts>",
+                escape(object_uri)
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><chkrun:checkObjectList xmlns:chkrun=\"http://www.sap.com/adt/checkrun\" xmlns:adtcore=\"http://www.sap.com/adt/core\"><chkrun:checkObject adtcore:uri=\"{}\" chkrun:version=\"inactive\">{artifacts}</chkrun:checkObject></chkrun:checkObjectList>",
+        escape(object_uri)
+    )
+}
+
+pub(crate) fn activation_request_xml(object_uris: &[String]) -> String {
+    let references = object_uris
+        .iter()
+        .map(|object_uri| {
+            format!(
+                "<adtcore:objectReference adtcore:uri=\"{}\" adtcore:name=\"{}\"/>",
+                escape(object_uri),
+                escape(object_name_from_uri(object_uri))
+            )
+        })
+        .collect::<String>();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><adtcore:objectReferences xmlns:adtcore=\"http://www.sap.com/adt/core\">{references}</adtcore:objectReferences>"
+    )
+}
+
+fn xml_error(reader: &Reader<&[u8]>, error: quick_xml::Error) -> String {
+    format!(
+        "malformed XML at position {}: {error}",
+        reader.error_position()
+    )
+}
+
+pub(crate) fn parse_check_run_reports(xml: &str) -> Result<Vec<NeuroCheckMessage>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut messages = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) | Ok(Event::Empty(element))
+                if element.local_name().as_ref() == b"checkMessage" =>
+            {
+                let (object_uri, line, column) = xml_attribute(&element, "uri")
+                    .map(|uri| parse_uri_position(&uri))
+                    .unwrap_or((None, None, None));
+                messages.push(NeuroCheckMessage {
+                    object_uri,
+                    line,
+                    column,
+                    severity: severity_from_type(
+                        &xml_attribute(&element, "type").unwrap_or_default(),
+                    ),
+                    text: xml_attribute(&element, "shortText").unwrap_or_default(),
+                    code: xml_attribute(&element, "code").filter(|code| !code.is_empty()),
+                });
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(error) => return Err(xml_error(&reader, error)),
+        }
+    }
+
+    Ok(messages)
+}
+
+pub(crate) fn parse_activation_result(xml: &str) -> Result<ActivationResult, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut result = ActivationResult::default();
+    let mut current: Option<NeuroCheckMessage> = None;
+    let mut in_text = false;
+    let mut in_inactive_objects = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => match element.local_name().as_ref() {
+                b"msg" => {
+                    let (object_uri, line, column) = xml_attribute(&element, "href")
+                        .map(|href| parse_uri_position(&href))
+                        .unwrap_or((None, None, None));
+                    current = Some(NeuroCheckMessage {
+                        object_uri,
+                        line: line.or_else(|| {
+                            xml_attribute(&element, "line").and_then(|value| value.parse().ok())
+                        }),
+                        column,
+                        severity: severity_from_type(
+                            &xml_attribute(&element, "type").unwrap_or_default(),
+                        ),
+                        text: String::new(),
+                        code: None,
+                    });
+                }
+                b"txt" => in_text = current.is_some(),
+                b"inactiveObjects" => in_inactive_objects = true,
+                b"ref" if in_inactive_objects => {
+                    if let Some(uri) = xml_attribute(&element, "uri") {
+                        result.inactive_object_uris.push(uri);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(element)) => {
+                if in_inactive_objects && element.local_name().as_ref() == b"ref" {
+                    if let Some(uri) = xml_attribute(&element, "uri") {
+                        result.inactive_object_uris.push(uri);
+                    }
+                }
+            }
+            Ok(Event::Text(text)) if in_text => {
+                if let Some(message) = current.as_mut() {
+                    message
+                        .text
+                        .push_str(&text.decode().map_err(|error| error.to_string())?);
+                }
+            }
+            Ok(Event::GeneralRef(reference)) if in_text => {
+                if let Some(message) = current.as_mut() {
+                    message.text.push_str(&xml_reference_text(&reference));
+                }
+            }
+            Ok(Event::End(element)) => match element.local_name().as_ref() {
+                b"txt" => in_text = false,
+                b"msg" => {
+                    if let Some(mut message) = current.take() {
+                        message.text = message.text.trim().to_string();
+                        result.messages.push(message);
+                    }
+                }
+                b"inactiveObjects" => in_inactive_objects = false,
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(error) => return Err(xml_error(&reader, error)),
+        }
+    }
+
+    Ok(result)
+}
+
+pub(crate) fn group_activation_result(
+    object_uris: &[String],
+    result: ActivationResult,
+) -> NeuroActivateResponse {
+    let inactive = result
+        .inactive_object_uris
+        .iter()
+        .filter_map(|uri| normalize_object_uri(uri).ok())
+        .collect::<Vec<_>>();
+    let mut objects = object_uris
+        .iter()
+        .map(|object_uri| NeuroActivationObjectResult {
+            object_uri: object_uri.clone(),
+            activated: !inactive.contains(object_uri),
+            messages: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    let mut unassigned = Vec::new();
+
+    for message in result.messages {
+        let target = message.object_uri.as_ref().and_then(|object_uri| {
+            objects
+                .iter_mut()
+                .find(|object| &object.object_uri == object_uri)
+        });
+        match target {
+            Some(object) => {
+                if message.severity == NeuroCheckSeverity::Error {
+                    object.activated = false;
+                }
+                object.messages.push(message);
+            }
+            None => unassigned.push(message),
+        }
+    }
+
+    let activated = objects.iter().all(|object| object.activated) && !has_errors(&unassigned);
+    NeuroActivateResponse {
+        activated,
+        objects,
+        messages: unassigned,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        activation_request_xml, group_activation_result, parse_activation_result,
+        parse_check_run_reports, parse_uri_position, syntax_check_request_xml, NeuroCheckSeverity,
+    };
+
+    const PROGRAM: &str = "/sap/bc/adt/programs/programs/ztest";
+    const CLASS: &str = "/sap/bc/adt/oo/classes/zcl_demo";
+
+    #[test]
+    fn parses_positions_from_message_links() {
+        assert_eq!(
+            parse_uri_position(&format!("{PROGRAM}/source/main#start=12,4")),
+            (Some(PROGRAM.to_string()), Some(12), Some(4))
+        );
+        assert_eq!(
+            parse_uri_position(&format!("{PROGRAM}/source/main#type=REPS;start=3")),
+            (Some(PROGRAM.to_string()), Some(3), None)
+        );
+        assert_eq!(parse_uri_position("ztest"), (None, None, None));
+    }
+
+    #[test]
+    fn builds_request_bodies_with_encoded_source() {
+        let body = syntax_check_request_xml(PROGRAM, Some("REPORT ztest."));
+        assert!(body.contains("adtcore:uri=\"/sap/bc/adt/programs/programs/ztest\""));
+        assert!(body.contains("<chkrun:content>UkVQT1JUIHp0ZXN0Lg==</chkrun:content>"));
+        assert!(!syntax_check_request_xml(PROGRAM, None).contains("chkrun:artifacts"));
+
+        let body = activation_request_xml(&[PROGRAM.to_string(), CLASS.to_string()]);
+        assert!(body.contains("adtcore:name=\"ZTEST\""));
+        assert!(body.contains("adtcore:name=\"ZCL_DEMO\""));
+
+        let body = activation_request_xml(&["/sap/bc/adt/programs/programs/%2fneo%2fzprog".into()]);
+        assert!(body.contains("adtcore:name=\"/NEO/ZPROG\""));
+    }
+
+    #[test]
+    fn parses_check_run_messages() {
+        let xml = format!(
+            r#"<chkrun:checkRunReports xmlns:chkrun="http://www.sap.com/adt/checkrun">
+              <chkrun:checkReport chkrun:reporter="abapCheckRun" chkrun:status="processed">
+                <chkrun:checkMessageList>
+                  <chkrun:checkMessage chkrun:uri="{PROGRAM}/source/main#start=7,10" chkrun:type="E" chkrun:shortText="Field &quot;LV_X&quot; is unknown." chkrun:code="MESSAGEG[K"/>
+                  <chkrun:checkMessage chkrun:uri="{PROGRAM}/source/main#start=2,0" chkrun:type="W" chkrun:shortText="Unused variable"/>
+                </chkrun:checkMessageList>
+              </chkrun:checkReport>
+            </chkrun:checkRunReports>"#
+        );
+        let messages = parse_check_run_reports(&xml).expect("valid report");
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].line, Some(7));
+        assert_eq!(messages[0].column, Some(10));
+        assert_eq!(messages[0].severity, NeuroCheckSeverity::Error);
+        assert_eq!(messages[0].text, "Field \"LV_X\" is unknown.");
+        assert_eq!(messages[1].severity, NeuroCheckSeverity::Warning);
+        assert_eq!(messages[1].code, None);
+    }
+
+    #[test]
+    fn groups_activation_messages_per_object() {
+        let xml = format!(
+            r#"<chkl:messages xmlns:chkl="http://www.sap.com/abapxml/checklist">
+              <msg objDescr="Class ZCL_DEMO" type="E" line="1" href="{CLASS}/source/main#start=20,5" forceSupported="true">
+                <shortText><txt>Method &quot;RUN&quot; is not implemented.</txt></shortText>
+              </msg>
+              <msg objDescr="System" type="W"><shortText><txt>Check the log</txt></shortText></msg>
+            </chkl:messages>"#
+        );
+        let result = parse_activation_result(&xml).expect("valid activation result");
+        let response = group_activation_result(&[PROGRAM.to_string(), CLASS.to_string()], result);
+
+        assert!(!response.activated);
+        assert!(response.objects[0].activated);
+        assert!(!response.objects[1].activated);
+        assert_eq!(response.objects[1].messages[0].line, Some(20));
+        assert_eq!(
+            response.objects[1].messages[0].text,
+            "Method \"RUN\" is not implemented."
+        );
+        assert_eq!(response.messages.len(), 1);
+    }
+
+    #[test]
+    fn marks_objects_left_inactive() {
+        let xml = format!(
+            r#"<ioc:inactiveObjects xmlns:ioc="http://www.sap.com/abapxml/inactiveCtsObjects" xmlns:adtcore="http://www.sap.com/adt/core">
+              <ioc:entry><ioc:object><ioc:ref adtcore:uri="{PROGRAM}" adtcore:name="ZTEST"/></ioc:object></ioc:entry>
+            </ioc:inactiveObjects>"#
+        );
+        let result = parse_activation_result(&xml).expect("valid activation result");
+        let response = group_activation_result(&[PROGRAM.to_string()], result);
+
+        assert!(!response.activated);
+        assert!(!response.objects[0].activated);
+        assert!(parse_activation_result("").unwrap().messages.is_empty());
+    }
+}
@@ -0,0 +1,333 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const ENV_CONNECTION_ID: &str = "env";
+const CONNECTIONS_FILE_NAME: &str = "neuro-connections.json";
+const MAX_CONNECTION_ID_LEN: usize = 64;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroConnectionSafety {
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub blocked_source_patterns: Vec<String>,
+    /// Table or CDS names refused by data preview; `*` matches any run of characters.
+    #[serde(default)]
+    pub blocked_entity_patterns: Vec<String>,
+    #[serde(default)]
+    pub allowed_ws_domains: Vec<String>,
+    #[serde(default)]
+    pub require_etag_for_updates: bool,
+    #[serde(default)]
+    pub syntax_check_before_update: bool,
+    #[serde(default)]
+    pub require_transport_for_updates: bool,
+    #[serde(default)]
+    pub require_confirmation_for_updates: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroConnectionProfile {
+    pub id: String,
+    pub name: String,
+    pub sap_url: String,
+    pub user: Option<String>,
+    /// Name of the environment variable holding the password for `user`.
+    pub password_env: Option<String>,
+    pub client: Option<String>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub insecure_tls: bool,
+    pub timeout_secs: Option<u64>,
+    pub csrf_fetch_path: Option<String>,
+    pub search_objects_path: Option<String>,
+    pub ws_url: Option<String>,
+    pub ws_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub safety: NeuroConnectionSafety,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NeuroConnectionsFile {
+    #[serde(default)]
+    profiles: Vec<NeuroConnectionProfile>,
+}
+
+pub fn resolve_connection_id(connection_id: Option<&str>) -> String {
+    connection_id
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(ENV_CONNECTION_ID)
+        .to_string()
+}
+
+pub fn connections_file_path() -> PathBuf {
+    if let Some(path) = env::var_os("NEURO_CONNECTIONS_PATH") {
+        if !path.is_empty() {
+            return PathBuf::from(path);
+        }
+    }
+    crate::config_runtime::resolve_codex_home().join(CONNECTIONS_FILE_NAME)
+}
+
+fn trim_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+}
+
+pub fn normalize_connection_profile(
+    profile: NeuroConnectionProfile,
+) -> Result<NeuroConnectionProfile, String> {
+    let id = profile.id.trim().to_string();
+    if id.is_empty() || id.len() > MAX_CONNECTION_ID_LEN {
+        return Err(format!(
+            "connection id must be between 1 and {MAX_CONNECTION_ID_LEN} characters"
+        ));
+    }
+    if !id
+        .chars()
+        .all(|entry| entry.is_ascii_alphanumeric() || entry == '-' || entry == '_')
+    {
+        return Err(format!(
+            "connection id `{id}` may only contain letters, digits, `-` and `_`"
+        ));
+    }
+    if id.eq_ignore_ascii_case(ENV_CONNECTION_ID) {
+        return Err(format!(
+            "connection id `{ENV_CONNECTION_ID}` is reserved for the environment connection"
+        ));
+    }
+
+    let name = profile.name.trim().to_string();
+    if name.is_empty() {
+        return Err(format!("connection `{id}` requires a name"));
+    }
+
+    let sap_url = profile.sap_url.trim().trim_end_matches('/').to_string();
+    if !(sap_url.starts_with("https://") || sap_url.starts_with("http://")) {
+        return Err(format!(
+            "connection `{id}` requires an http(s) SAP URL, got `{sap_url}`"
+        ));
+    }
+
+    let ws_url = trim_optional(profile.ws_url);
+    if let Some(ws_url) = ws_url.as_deref() {
+        if !(ws_url.starts_with("wss://") || ws_url.starts_with("ws://")) {
+            return Err(format!(
+                "connection `{id}` requires a ws(s) WebSocket URL, got `{ws_url}`"
+            ));
+        }
+    }
+
+    if profile.timeout_secs == Some(0) || profile.ws_timeout_secs == Some(0) {
+        return Err(format!(
+            "connection `{id}` timeouts must be greater than zero"
+        ));
+    }
+
+    Ok(NeuroConnectionProfile {
+        id,
+        name,
+        sap_url,
+        user: trim_optional(profile.user),
+        password_env: trim_optional(profile.password_env),
+        client: trim_optional(profile.client),
+        language: trim_optional(profile.language),
+        insecure_tls: profile.insecure_tls,
+        timeout_secs: profile.timeout_secs,
+        csrf_fetch_path: trim_optional(profile.csrf_fetch_path),
+        search_objects_path: trim_optional(profile.search_objects_path),
+        ws_url,
+        ws_timeout_secs: profile.ws_timeout_secs,
+        safety: profile.safety,
+    })
+}
+
+pub fn load_connection_profiles(path: &Path) -> Result<Vec<NeuroConnectionProfile>, String> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let raw = fs::read_to_string(path)
+        .map_err(|error| format!("failed to read `{}`: {error}", path.display()))?;
+    if raw.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    serde_json::from_str::<NeuroConnectionsFile>(&raw)
+        .map(|file| file.profiles)
+        .map_err(|error| format!("failed to parse `{}`: {error}", path.display()))
+}
+
+fn save_connection_profiles(
+    path: &Path,
+    profiles: &[NeuroConnectionProfile],
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create `{}`: {error}", parent.display()))?;
+    }
+
+    let serialized = serde_json::to_string_pretty(&NeuroConnectionsFile {
+        profiles: profiles.to_vec(),
+    })
+    .map_err(|error| format!("failed to serialize neuro connections: {error}"))?;
+
+    let staging = path.with_extension("json.tmp");
+    fs::write(&staging, serialized)
+        .map_err(|error| format!("failed to write `{}`: {error}", staging.display()))?;
+    fs::rename(&staging, path)
+        .map_err(|error| format!("failed to replace `{}`: {error}", path.display()))
+}
+
+pub fn find_connection_profile(
+    path: &Path,
+    connection_id: &str,
+) -> Result<Option<NeuroConnectionProfile>, String> {
+    Ok(load_connection_profiles(path)?
+        .into_iter()
+        .find(|profile| profile.id == connection_id))
+}
+
+pub fn create_connection_profile(
+    path: &Path,
+    profile: NeuroConnectionProfile,
+) -> Result<NeuroConnectionProfile, String> {
+    let profile = normalize_connection_profile(profile)?;
+    let mut profiles = load_connection_profiles(path)?;
+    if profiles.iter().any(|entry| entry.id == profile.id) {
+        return Err(format!("connection `{}` already exists", profile.id));
+    }
+
+    profiles.push(profile.clone());
+    save_connection_profiles(path, &profiles)?;
+    Ok(profile)
+}
+
+pub fn update_connection_profile(
+    path: &Path,
+    profile: NeuroConnectionProfile,
+) -> Result<NeuroConnectionProfile, String> {
+    let profile = normalize_connection_profile(profile)?;
+    let mut profiles = load_connection_profiles(path)?;
+    let Some(existing) = profiles.iter_mut().find(|entry| entry.id == profile.id) else {
+        return Err(format!("connection `{}` does not exist", profile.id));
+    };
+
+    *existing = profile.clone();
+    save_connection_profiles(path, &profiles)?;
+    Ok(profile)
+}
+
+pub fn delete_connection_profile(path: &Path, connection_id: &str) -> Result<bool, String> {
+    let mut profiles = load_connection_profiles(path)?;
+    let before = profiles.len();
+    profiles.retain(|entry| entry.id != connection_id);
+    if profiles.len() == before {
+        return Ok(false);
+    }
+
+    save_connection_profiles(path, &profiles)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        create_connection_profile, delete_connection_profile, find_connection_profile,
+        load_connection_profiles, normalize_connection_profile, resolve_connection_id,
+        update_connection_profile, NeuroConnectionProfile, NeuroConnectionSafety,
+    };
+    use std::path::PathBuf;
+
+    fn profile(id: &str) -> NeuroConnectionProfile {
+        NeuroConnectionProfile {
+            id: id.to_string(),
+            name: format!("{id} system"),
+            sap_url: "https://dev.sap.local/".to_string(),
+            user: Some(" DEVELOPER ".to_string()),
+            password_env: Some("DEV_SAP_PASSWORD".to_string()),
+            client: Some("100".to_string()),
+            language: None,
+            insecure_tls: false,
+            timeout_secs: Some(45),
+            csrf_fetch_path: None,
+            search_objects_path: Some(" ".to_string()),
+            ws_url: None,
+            ws_timeout_secs: None,
+            safety: NeuroConnectionSafety::default(),
+        }
+    }
+
+    fn temp_connections_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "alicia-neuro-connections-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("neuro-connections.json")
+    }
+
+    #[test]
+    fn resolves_missing_connection_id_to_env() {
+        assert_eq!(resolve_connection_id(None), "env");
+        assert_eq!(resolve_connection_id(Some("  ")), "env");
+        assert_eq!(resolve_connection_id(Some(" qas ")), "qas");
+    }
+
+    #[test]
+    fn normalizes_and_validates_profiles() {
+        let normalized = normalize_connection_profile(profile("dev")).expect("profile is valid");
+        assert_eq!(normalized.sap_url, "https://dev.sap.local");
+        assert_eq!(normalized.user.as_deref(), Some("DEVELOPER"));
+        assert_eq!(normalized.search_objects_path, None);
+
+        assert!(normalize_connection_profile(profile("env")).is_err());
+        assert!(normalize_connection_profile(profile("dev/qas")).is_err());
+
+        let mut invalid_url = profile("dev");
+        invalid_url.sap_url = "dev.sap.local".to_string();
+        assert!(normalize_connection_profile(invalid_url).is_err());
+
+        let mut invalid_ws = profile("dev");
+        invalid_ws.ws_url = Some("https://ws.local".to_string());
+        assert!(normalize_connection_profile(invalid_ws).is_err());
+    }
+
+    #[test]
+    fn profiles_round_trip_through_crud_operations() {
+        let path = temp_connections_path("crud");
+        assert!(load_connection_profiles(&path).unwrap().is_empty());
+
+        create_connection_profile(&path, profile("dev")).expect("create dev");
+        create_connection_profile(&path, profile("qas")).expect("create qas");
+        assert!(create_connection_profile(&path, profile("dev")).is_err());
+
+        let mut renamed = profile("qas");
+        renamed.name = "Quality".to_string();
+        update_connection_profile(&path, renamed).expect("update qas");
+        assert!(update_connection_profile(&path, profile("prd")).is_err());
+
+        let qas = find_connection_profile(&path, "qas")
+            .unwrap()
+            .expect("qas should exist");
+        assert_eq!(qas.name, "Quality");
+
+        assert!(delete_connection_profile(&path, "dev").unwrap());
+        assert!(!delete_connection_profile(&path, "dev").unwrap());
+        let ids = load_connection_profiles(&path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["qas"]);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,436 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+use crate::neuro_adt_rest::{xml_attribute, xml_reference_text};
+use crate::neuro_ddic_runtime::normalize_ddic_name;
+
+pub const DATA_PREVIEW_FREESTYLE_PATH: &str = "/sap/bc/adt/datapreview/freestyle";
+pub const DATA_PREVIEW_DDIC_PATH: &str = "/sap/bc/adt/datapreview/ddic";
+pub const DATA_PREVIEW_ACCEPT: &str =
+    "application/xml, application/vnd.sap.adt.datapreview.table.v1+xml";
+const DEFAULT_PREVIEW_ROWS: u32 = 100;
+const DEFAULT_PREVIEW_MAX_ROWS: u32 = 1000;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDataPreviewRequest {
+    /// ABAP SQL `SELECT`; mutually exclusive with `entity`.
+    pub query: Option<String>,
+    /// Table or CDS entity to read without a query.
+    pub entity: Option<String>,
+    pub max_rows: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDataPreviewColumn {
+    pub name: String,
+    pub description: Option<String>,
+    /// DDIC type, e.g. `CHAR` or `DEC`.
+    pub data_type: Option<String>,
+    pub length: Option<u32>,
+    pub key: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDataPreviewResponse {
+    pub columns: Vec<NeuroDataPreviewColumn>,
+    /// One entry per row, in column order; values are formatted by SAP.
+    pub rows: Vec<Vec<String>>,
+    /// Rows matching the query, when SAP reports it.
+    pub total_rows: Option<u64>,
+    pub truncated: bool,
+    pub executed_query: Option<String>,
+    pub execution_time_ms: Option<f64>,
+}
+
+/// Validated preview target.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum DataPreviewTarget {
+    Query(String),
+    Entity(String),
+}
+
+/// `NEURO_DATA_PREVIEW_MAX_ROWS`, the upper bound for `maxRows`.
+pub(crate) fn data_preview_max_rows_from_env() -> u32 {
+    env::var("NEURO_DATA_PREVIEW_MAX_ROWS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_PREVIEW_MAX_ROWS)
+}
+
+pub(crate) fn preview_row_limit(requested: Option<u32>, max_rows: u32) -> u32 {
+    requested
+        .unwrap_or(DEFAULT_PREVIEW_ROWS)
+        .clamp(1, max_rows.max(1))
+}
+
+/// Case-insensitive substring match against `NEURO_SAFETY_BLOCKED_PATTERNS`.
+pub(crate) fn find_blocked_pattern<'a>(patterns: &'a [String], text: &str) -> Option<&'a str> {
+    let text = text.to_ascii_lowercase();
+    patterns
+        .iter()
+        .map(String::as_str)
+        .filter(|pattern| !pattern.trim().is_empty())
+        .find(|pattern| text.contains(&pattern.to_ascii_lowercase()))
+}
+
+/// Tables and CDS entities a preview reads: the entity itself, or every name after `FROM`/`JOIN`.
+pub(crate) fn preview_entities(target: &DataPreviewTarget) -> Vec<String> {
+    let query = match target {
+        DataPreviewTarget::Entity(entity) => return vec![entity.to_ascii_uppercase()],
+        DataPreviewTarget::Query(query) => query,
+    };
+    let tokens = query
+        .split(|ch: char| ch.is_whitespace() || matches!(ch, '(' | ')' | ','))
+        .filter(|token| !token.is_empty())
+        .map(str::to_ascii_uppercase)
+        .collect::<Vec<_>>();
+    let mut entities = Vec::new();
+    for pair in tokens.windows(2) {
+        let (keyword, name) = (pair[0].as_str(), pair[1].as_str());
+        if (keyword == "FROM" || keyword == "JOIN")
+            && name != "SELECT"
+            && !entities.iter().any(|entity| entity == name)
+        {
+            entities.push(name.to_string());
+        }
+    }
+    entities
+}
+
+/// Whether a query follows a CDS association (`\_assoc`). The association target is not named
+/// in the query, so it cannot be checked against blocked entity patterns.
+pub(crate) fn uses_path_expression(target: &DataPreviewTarget) -> bool {
+    matches!(target, DataPreviewTarget::Query(query) if query.contains('\\'))
+}
+
+/// First `NEURO_SAFETY_BLOCKED_ENTITIES` pattern matching one of `entities`, with that entity.
+pub(crate) fn find_blocked_entity<'a>(
+    patterns: &'a [String],
+    entities: &[String],
+) -> Option<(&'a str, String)> {
+    patterns
+        .iter()
+        .map(|pattern| pattern.trim())
+        .filter(|pattern| !pattern.is_empty())
+        .find_map(|pattern| {
+            entities
+                .iter()
+                .find(|entity| entity_matches(&pattern.to_ascii_uppercase(), entity))
+                .map(|entity| (pattern, entity.clone()))
+        })
+}
+
+/// Whole-name match where `*` stands for any run of characters; both sides are uppercase.
+fn entity_matches(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            if rest.is_empty() {
+                return true;
+            }
+            name.char_indices()
+                .map(|(index, _)| index)
+                .chain(std::iter::once(name.len()))
+                .any(|index| entity_matches(rest, &name[index..]))
+        }
+    }
+}
+
+/// Accepts a single `SELECT` (or `WITH`) statement; anything else is refused before SAP sees it.
+pub(crate) fn data_preview_target(
+    request: &NeuroDataPreviewRequest,
+) -> Result<DataPreviewTarget, String> {
+    let query = request
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|query| !query.is_empty());
+    let entity = request
+        .entity
+        .as_deref()
+        .map(str::trim)
+        .filter(|entity| !entity.is_empty());
+    match (query, entity) {
+        (Some(_), Some(_)) => Err("pass either `query` or `entity`, not both".to_string()),
+        (None, None) => Err("data preview requires a `query` or an `entity`".to_string()),
+        (None, Some(entity)) => normalize_ddic_name(entity).map(DataPreviewTarget::Entity),
+        (Some(query), None) => {
+            let query = query.trim_end_matches('.').trim_end();
+            let first = query
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_ascii_uppercase();
+            if first != "SELECT" && first != "WITH" {
+                return Err("data preview only runs SELECT statements".to_string());
+            }
+            if query.contains(';') {
+                return Err("data preview runs a single statement; remove `;`".to_string());
+            }
+            Ok(DataPreviewTarget::Query(query.to_string()))
+        }
+    }
+}
+
+#[derive(Default)]
+struct PreviewColumn {
+    column: Option<NeuroDataPreviewColumn>,
+    values: Vec<String>,
+}
+
+/// Turns the column-wise `dataPreview:tableData` document into rows.
+pub(crate) fn parse_data_preview(
+    xml: &str,
+    row_limit: u32,
+) -> Result<NeuroDataPreviewResponse, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut columns: Vec<PreviewColumn> = Vec::new();
+    let mut total_rows = None;
+    let mut executed_query = None;
+    let mut execution_time_ms = None;
+    let mut text_field: Option<&'static str> = None;
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        match event {
+            Event::Empty(element) if element.local_name().as_ref() == b"data" => {
+                if let Some(current) = columns.last_mut() {
+                    current.values.push(String::new());
+                }
+            }
+            Event::Start(element) | Event::Empty(element) => {
+                match element.local_name().as_ref() {
+                    b"columns" => columns.push(PreviewColumn::default()),
+                    b"metadata" => {
+                        if let Some(current) = columns.last_mut() {
+                            current.column = Some(NeuroDataPreviewColumn {
+                                name: xml_attribute(&element, "name").unwrap_or_default(),
+                                description: xml_attribute(&element, "description")
+                                    .filter(|value| !value.trim().is_empty()),
+                                data_type: xml_attribute(&element, "colType")
+                                    .or_else(|| xml_attribute(&element, "type"))
+                                    .filter(|value| !value.trim().is_empty()),
+                                length: xml_attribute(&element, "length")
+                                    .and_then(|value| value.trim().parse().ok()),
+                                key: xml_attribute(&element, "keyAttribute")
+                                    .is_some_and(|value| value == "true"),
+                            });
+                        }
+                    }
+                    b"data" => text_field = Some("data"),
+                    b"totalRows" => text_field = Some("totalRows"),
+                    b"executedQueryString" => text_field = Some("executedQueryString"),
+                    b"queryExecutionTime" => text_field = Some("queryExecutionTime"),
+                    _ => {}
+                }
+                text.clear();
+            }
+            Event::Text(value) if text_field.is_some() => {
+                text.push_str(&value.decode().map_err(|error| error.to_string())?);
+            }
+            Event::CData(value) if text_field.is_some() => {
+                text.push_str(&value.decode().map_err(|error| error.to_string())?);
+            }
+            Event::GeneralRef(reference) if text_field.is_some() => {
+                text.push_str(&xml_reference_text(&reference));
+            }
+            Event::End(_) => match text_field.take() {
+                Some("data") => {
+                    if let Some(current) = columns.last_mut() {
+                        current.values.push(std::mem::take(&mut text));
+                    }
+                }
+                Some("totalRows") => total_rows = text.trim().parse().ok(),
+                Some("executedQueryString") => {
+                    executed_query = Some(text.trim().to_string()).filter(|value| !value.is_empty())
+                }
+                Some(_) => execution_time_ms = text.trim().parse().ok(),
+                None => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let row_count = columns
+        .iter()
+        .map(|column| column.values.len())
+        .max()
+        .unwrap_or_default()
+        .min(row_limit as usize);
+    let rows = (0..row_count)
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| column.values.get(row).cloned().unwrap_or_default())
+                .collect()
+        })
+        .collect::<Vec<Vec<String>>>();
+    let truncated = total_rows.is_some_and(|total| total > rows.len() as u64);
+    Ok(NeuroDataPreviewResponse {
+        columns: columns
+            .into_iter()
+            .filter_map(|column| column.column)
+            .collect(),
+        rows,
+        total_rows,
+        truncated,
+        executed_query,
+        execution_time_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        data_preview_target, find_blocked_entity, find_blocked_pattern, parse_data_preview,
+        preview_entities, preview_row_limit, uses_path_expression, DataPreviewTarget,
+        NeuroDataPreviewRequest,
+    };
+
+    fn request(query: Option<&str>, entity: Option<&str>) -> NeuroDataPreviewRequest {
+        NeuroDataPreviewRequest {
+            query: query.map(str::to_string),
+            entity: entity.map(str::to_string),
+            max_rows: None,
+        }
+    }
+
+    #[test]
+    fn accepts_only_single_select_statements() {
+        assert_eq!(
+            data_preview_target(&request(
+                Some(" select * from sflight where carrid = 'LH'. "),
+                None
+            )),
+            Ok(DataPreviewTarget::Query(
+                "select * from sflight where carrid = 'LH'".to_string()
+            ))
+        );
+        assert_eq!(
+            data_preview_target(&request(None, Some("i_country"))),
+            Ok(DataPreviewTarget::Entity("I_COUNTRY".to_string()))
+        );
+        assert!(data_preview_target(&request(Some("DELETE FROM sflight"), None)).is_err());
+        assert!(
+            data_preview_target(&request(Some("SELECT * FROM t; DELETE FROM t"), None)).is_err()
+        );
+        assert!(data_preview_target(&request(Some("SELECT 1"), Some("T000"))).is_err());
+        assert!(data_preview_target(&request(None, None)).is_err());
+
+        let patterns = vec!["PA0008".to_string(), " ".to_string()];
+        assert_eq!(
+            find_blocked_pattern(&patterns, "select * from pa0008"),
+            Some("PA0008")
+        );
+        assert_eq!(find_blocked_pattern(&patterns, "select * from t000"), None);
+        assert_eq!(preview_row_limit(None, 1000), 100);
+        assert_eq!(preview_row_limit(Some(5000), 1000), 1000);
+        assert_eq!(preview_row_limit(Some(0), 1000), 1);
+    }
+
+    #[test]
+    fn blocks_entities_by_name_or_wildcard() {
+        let query = DataPreviewTarget::Query(
+            "SELECT a~pernr FROM pa0008 AS a INNER JOIN zcds_emp( p_date = '20240101' ) AS b \
+             ON a~pernr = b~pernr WHERE a~pernr IN ( SELECT pernr FROM t001p )"
+                .to_string(),
+        );
+        let entities = preview_entities(&query);
+        assert_eq!(entities, ["PA0008", "ZCDS_EMP", "T001P"]);
+
+        let patterns = vec!["pa*".to_string(), " ".to_string()];
+        assert_eq!(
+            find_blocked_entity(&patterns, &entities),
+            Some(("pa*", "PA0008".to_string()))
+        );
+        let patterns = vec!["ZCDS_*EMP".to_string(), "T001".to_string()];
+        assert_eq!(
+            find_blocked_entity(&patterns, &entities),
+            Some(("ZCDS_*EMP", "ZCDS_EMP".to_string()))
+        );
+        assert_eq!(
+            find_blocked_entity(
+                &["T001".to_string(), "*PA".to_string()],
+                &preview_entities(&DataPreviewTarget::Entity("T001P".to_string()))
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn detects_association_paths_that_hide_the_target_entity() {
+        let query = DataPreviewTarget::Query(
+            "SELECT FROM zcds_emp AS e FIELDS e~pernr, \\_salary-amount".to_string(),
+        );
+        let entities = preview_entities(&query);
+        assert_eq!(entities, ["ZCDS_EMP"]);
+        assert_eq!(find_blocked_entity(&["PA*".to_string()], &entities), None);
+        assert!(uses_path_expression(&query));
+
+        assert!(!uses_path_expression(&DataPreviewTarget::Query(
+            "SELECT * FROM zcds_emp".to_string()
+        )));
+        assert!(!uses_path_expression(&DataPreviewTarget::Entity(
+            "ZCDS_EMP".to_string()
+        )));
+    }
+
+    #[test]
+    fn transposes_column_data_into_rows() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <dataPreview:tableData xmlns:dataPreview="http://www.sap.com/adt/dataPreview">
+              <dataPreview:totalRows>3</dataPreview:totalRows>
+              <dataPreview:executedQueryString>SELECT CARRID, CARRNAME FROM SCARR</dataPreview:executedQueryString>
+              <dataPreview:queryExecutionTime>4.25</dataPreview:queryExecutionTime>
+              <dataPreview:columns>
+                <dataPreview:metadata dataPreview:name="CARRID" dataPreview:type="C" dataPreview:colType="CHAR" dataPreview:length="3" dataPreview:keyAttribute="true" dataPreview:description="Airline"/>
+                <dataPreview:dataSet>
+                  <dataPreview:data>AA</dataPreview:data>
+                  <dataPreview:data>LH</dataPreview:data>
+                </dataPreview:dataSet>
+              </dataPreview:columns>
+              <dataPreview:columns>
+                <dataPreview:metadata dataPreview:name="CARRNAME" dataPreview:colType="CHAR" dataPreview:keyAttribute="false"/>
+                <dataPreview:dataSet>
+                  <dataPreview:data>American &amp; Co</dataPreview:data>
+                  <dataPreview:data/>
+                </dataPreview:dataSet>
+              </dataPreview:columns>
+            </dataPreview:tableData>"#;
+
+        let preview = parse_data_preview(xml, 100).unwrap();
+        assert_eq!(preview.columns.len(), 2);
+        assert!(preview.columns[0].key);
+        assert_eq!(preview.columns[0].length, Some(3));
+        assert_eq!(preview.columns[1].description, None);
+        assert_eq!(
+            preview.rows,
+            vec![
+                vec!["AA".to_string(), "American & Co".to_string()],
+                vec!["LH".to_string(), String::new()],
+            ]
+        );
+        assert_eq!(preview.total_rows, Some(3));
+        assert!(preview.truncated);
+        assert_eq!(preview.execution_time_ms, Some(4.25));
+
+        let capped = parse_data_preview(xml, 1).unwrap();
+        assert_eq!(capped.rows.len(), 1);
+    }
+}
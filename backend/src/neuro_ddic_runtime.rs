@@ -0,0 +1,761 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::Serialize;
+
+use crate::neuro_adt_rest::{collect_xml_text, xml_attribute, xml_reference_text};
+
+pub const DDIC_TABLES_PATH: &str = "/sap/bc/adt/ddic/tables";
+pub const DDIC_STRUCTURES_PATH: &str = "/sap/bc/adt/ddic/structures";
+pub const DATA_ELEMENTS_PATH: &str = "/sap/bc/adt/ddic/dataelements";
+pub const DATA_ELEMENT_ACCEPT: &str = "application/vnd.sap.adt.dataelements.v2+xml";
+pub const DOMAINS_PATH: &str = "/sap/bc/adt/ddic/domains";
+pub const DOMAIN_ACCEPT: &str = "application/vnd.sap.adt.domains.v2+xml";
+pub const CDS_SOURCES_PATH: &str = "/sap/bc/adt/ddic/ddl/sources";
+const MAX_DDIC_NAME_LEN: usize = 40;
+const LABEL_ANNOTATION: &str = "EndUserText.label";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDdicAnnotation {
+    pub name: String,
+    /// Raw DDL value, e.g. `#TRANSPARENT`, `'Label'` or `[ 'A', 'B' ]`.
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NeuroDdicTableKind {
+    Table,
+    Structure,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDdicField {
+    pub name: String,
+    pub key: bool,
+    /// Type as written in the DDL source, e.g. `abap.char(40)` or `matnr`.
+    pub data_type: String,
+    /// Set when the field is typed with a data element.
+    pub data_element: Option<String>,
+    /// Set for `abap.*` built-in types, e.g. `CHAR`.
+    pub builtin_type: Option<String>,
+    pub length: Option<u32>,
+    pub decimals: Option<u32>,
+    pub not_null: bool,
+    pub annotations: Vec<NeuroDdicAnnotation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDdicTableResponse {
+    pub object_uri: String,
+    pub name: String,
+    pub kind: NeuroDdicTableKind,
+    pub description: Option<String>,
+    pub annotations: Vec<NeuroDdicAnnotation>,
+    pub fields: Vec<NeuroDdicField>,
+    /// Included structures, in source order.
+    pub includes: Vec<String>,
+    pub source: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDataElementResponse {
+    pub object_uri: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// `domain`, `predefinedAbapType` or a reference kind.
+    pub type_kind: Option<String>,
+    pub type_name: Option<String>,
+    pub data_type: Option<String>,
+    pub length: Option<u32>,
+    pub decimals: Option<u32>,
+    pub short_label: Option<String>,
+    pub medium_label: Option<String>,
+    pub long_label: Option<String>,
+    pub heading_label: Option<String>,
+    pub search_help: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDomainFixedValue {
+    pub low: String,
+    pub high: Option<String>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroDomainResponse {
+    pub object_uri: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub data_type: Option<String>,
+    pub length: Option<u32>,
+    pub decimals: Option<u32>,
+    pub output_length: Option<u32>,
+    pub conversion_exit: Option<String>,
+    pub signed: bool,
+    pub lowercase: bool,
+    pub value_table: Option<String>,
+    pub fixed_values: Vec<NeuroDomainFixedValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroCdsElement {
+    /// Alias, or the last path segment when the element has none.
+    pub name: String,
+    pub key: bool,
+    pub expression: String,
+    pub annotations: Vec<NeuroDdicAnnotation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroCdsViewResponse {
+    pub object_uri: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub annotations: Vec<NeuroDdicAnnotation>,
+    pub elements: Vec<NeuroCdsElement>,
+    pub source: String,
+}
+
+/// Validates a DDIC or CDS name and returns it upper-cased.
+pub(crate) fn normalize_ddic_name(name: &str) -> Result<String, String> {
+    let name = name.trim().to_ascii_uppercase();
+    let valid = !name.is_empty()
+        && name.len() <= MAX_DDIC_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '/')
+        && match name.strip_prefix('/') {
+            Some(rest) => rest.split_once('/').is_some_and(|(namespace, object)| {
+                !namespace.is_empty() && !object.is_empty() && !object.contains('/')
+            }),
+            None => !name.contains('/'),
+        };
+    if !valid {
+        return Err(format!(
+            "invalid DDIC name `{name}`: use letters, digits, `_` and an optional /NAMESPACE/ prefix"
+        ));
+    }
+    Ok(name)
+}
+
+/// Returns the normalized name and its ADT URI below `base_path`.
+pub(crate) fn ddic_object_uri(base_path: &str, name: &str) -> Result<(String, String), String> {
+    let name = normalize_ddic_name(name)?;
+    let path_name = name.to_ascii_lowercase().replace('/', "%2f");
+    Ok((name, format!("{base_path}/{path_name}")))
+}
+
+fn parse_number(value: Option<&String>) -> Option<u32> {
+    value.and_then(|value| value.trim().parse().ok())
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn root_attribute(xml: &str, name: &str) -> Result<Option<String>, String> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        match event {
+            Event::Start(element) | Event::Empty(element) => {
+                return Ok(xml_attribute(&element, name).filter(|value| !value.trim().is_empty()))
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn parse_data_element(
+    object_uri: &str,
+    name: &str,
+    xml: &str,
+) -> Result<NeuroDataElementResponse, String> {
+    let values = collect_xml_text(
+        xml,
+        &[
+            "typeKind",
+            "typeName",
+            "dataType",
+            "dataTypeLength",
+            "dataTypeDecimals",
+            "shortFieldLabel",
+            "mediumFieldLabel",
+            "longFieldLabel",
+            "headingFieldLabel",
+            "searchHelp",
+        ],
+    )?;
+    Ok(NeuroDataElementResponse {
+        object_uri: object_uri.to_string(),
+        name: name.to_string(),
+        description: root_attribute(xml, "description")?,
+        type_kind: non_empty(values.get("typeKind")),
+        type_name: non_empty(values.get("typeName")),
+        data_type: non_empty(values.get("dataType")),
+        length: parse_number(values.get("dataTypeLength")),
+        decimals: parse_number(values.get("dataTypeDecimals")),
+        short_label: non_empty(values.get("shortFieldLabel")),
+        medium_label: non_empty(values.get("mediumFieldLabel")),
+        long_label: non_empty(values.get("longFieldLabel")),
+        heading_label: non_empty(values.get("headingFieldLabel")),
+        search_help: non_empty(values.get("searchHelp")),
+    })
+}
+
+pub(crate) fn parse_domain(
+    object_uri: &str,
+    name: &str,
+    xml: &str,
+) -> Result<NeuroDomainResponse, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut domain = NeuroDomainResponse {
+        object_uri: object_uri.to_string(),
+        name: name.to_string(),
+        ..NeuroDomainResponse::default()
+    };
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+    let mut fixed_value: Option<NeuroDomainFixedValue> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        match event {
+            Event::Start(element) => {
+                let local = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                if path.is_empty() {
+                    domain.description = xml_attribute(&element, "description")
+                        .filter(|value| !value.trim().is_empty());
+                }
+                if local == "fixValue" {
+                    fixed_value = Some(NeuroDomainFixedValue {
+                        low: String::new(),
+                        high: None,
+                        text: None,
+                    });
+                }
+                path.push(local);
+                text.clear();
+            }
+            Event::Empty(element) if element.local_name().as_ref() == b"valueTableRef" => {
+                domain.value_table =
+                    xml_attribute(&element, "name").filter(|value| !value.trim().is_empty());
+            }
+            Event::Text(value) => {
+                text.push_str(&value.decode().map_err(|error| error.to_string())?);
+            }
+            Event::GeneralRef(reference) => text.push_str(&xml_reference_text(&reference)),
+            Event::End(_) => {
+                let Some(local) = path.pop() else {
+                    continue;
+                };
+                let parent = path.last().map(String::as_str).unwrap_or_default();
+                let value = Some(text.trim().to_string()).filter(|value| !value.is_empty());
+                text.clear();
+                match (parent, local.as_str()) {
+                    ("typeInformation", "datatype") => domain.data_type = value,
+                    ("typeInformation", "length") => domain.length = parse_number(value.as_ref()),
+                    ("typeInformation", "decimals") => {
+                        domain.decimals = parse_number(value.as_ref())
+                    }
+                    ("outputInformation", "length") => {
+                        domain.output_length = parse_number(value.as_ref())
+                    }
+                    ("outputInformation", "conversionExit") => domain.conversion_exit = value,
+                    ("outputInformation", "signExists") => {
+                        domain.signed = value.as_deref() == Some("true")
+                    }
+                    ("outputInformation", "lowercase") => {
+                        domain.lowercase = value.as_deref() == Some("true")
+                    }
+                    ("fixValue", field) => {
+                        if let Some(fixed) = fixed_value.as_mut() {
+                            match field {
+                                "low" => fixed.low = value.unwrap_or_default(),
+                                "high" => fixed.high = value,
+                                "text" => fixed.text = value,
+                                _ => {}
+                            }
+                        }
+                    }
+                    (_, "fixValue") => domain.fixed_values.extend(fixed_value.take()),
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(domain)
+}
+
+/// Removes `//` and `/* */` comments outside string literals.
+fn strip_ddl_comments(source: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            in_string = c != '\'';
+            output.push(c);
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('\'', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        output.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+                output.push(' ');
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+/// Byte length of the leading string literal or bracketed group, or of the leading bare token.
+fn token_len(text: &str) -> usize {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    for (index, c) in text.char_indices() {
+        if in_string {
+            if c == '\'' {
+                in_string = false;
+                if depth == 0 {
+                    return index + 1;
+                }
+            }
+            continue;
+        }
+        match c {
+            '\'' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    return index + 1;
+                }
+            }
+            c if depth == 0 && (c.is_whitespace() || matches!(c, ',' | ';' | '}' | ')')) => {
+                return index;
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Splits on `separator` outside strings and brackets.
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        if in_string {
+            in_string = c != '\'';
+            continue;
+        }
+        match c {
+            '\'' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            c if c == separator && depth == 0 => {
+                parts.push(&text[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Reads the annotations in front of a definition and returns the remaining text.
+fn take_annotations(text: &str) -> (Vec<NeuroDdicAnnotation>, &str) {
+    let mut annotations = Vec::new();
+    let mut rest = text.trim_start();
+    while let Some(after_at) = rest.strip_prefix('@') {
+        let name_len = after_at
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '<')))
+            .unwrap_or(after_at.len());
+        let name = after_at[..name_len].trim_start_matches('<').to_string();
+        rest = after_at[name_len..].trim_start();
+        let mut value = String::new();
+        if let Some(after_colon) = rest.strip_prefix(':') {
+            let after_colon = after_colon.trim_start();
+            let len = token_len(after_colon);
+            value = after_colon[..len].trim().to_string();
+            rest = after_colon[len..].trim_start();
+        }
+        annotations.push(NeuroDdicAnnotation { name, value });
+    }
+    (annotations, rest)
+}
+
+fn label(annotations: &[NeuroDdicAnnotation]) -> Option<String> {
+    annotations
+        .iter()
+        .find(|annotation| annotation.name.eq_ignore_ascii_case(LABEL_ANNOTATION))
+        .map(|annotation| annotation.value.trim_matches('\'').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns the definition header (`define table zdemo`) and the text between its braces.
+fn definition_body(definition: &str) -> Option<(&str, &str)> {
+    let mut in_string = false;
+    let open = definition.char_indices().find_map(|(index, c)| {
+        if in_string {
+            in_string = c != '\'';
+            return None;
+        }
+        in_string = c == '\'';
+        (c == '{').then_some(index)
+    })?;
+    let block_len = token_len(&definition[open..]);
+    let block = &definition[open..open + block_len];
+    let inner = block.strip_prefix('{')?.strip_suffix('}')?;
+    Some((&definition[..open], inner))
+}
+
+fn parse_ddic_type(data_type: &str) -> (Option<String>, Option<String>, Option<u32>, Option<u32>) {
+    let Some(builtin) = data_type
+        .get(..5)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("abap."))
+        .map(|_| &data_type[5..])
+    else {
+        return (Some(data_type.to_ascii_uppercase()), None, None, None);
+    };
+    let (kind, arguments) = builtin.split_once('(').unwrap_or((builtin, ""));
+    let mut numbers = arguments
+        .trim_end_matches(')')
+        .split(',')
+        .map(|value| value.trim().parse::<u32>().ok());
+    let length = numbers.next().flatten();
+    let decimals = numbers.next().flatten();
+    (
+        None,
+        Some(kind.trim().to_ascii_uppercase()),
+        length,
+        decimals,
+    )
+}
+
+pub(crate) fn parse_ddic_table_source(
+    object_uri: &str,
+    name: &str,
+    kind: NeuroDdicTableKind,
+    source: &str,
+) -> Result<NeuroDdicTableResponse, String> {
+    let stripped = strip_ddl_comments(source);
+    let (annotations, definition) = take_annotations(&stripped);
+    let (_, body) = definition_body(definition)
+        .ok_or_else(|| "DDL source has no `define ... { }` block".to_string())?;
+    let mut fields = Vec::new();
+    let mut includes = Vec::new();
+
+    for member in split_top_level(body, ';') {
+        let (field_annotations, member) = take_annotations(member);
+        let member = member.trim();
+        let mut words = member.split_whitespace();
+        let Some(first) = words.next() else {
+            continue;
+        };
+        if first.eq_ignore_ascii_case("include") || first.eq_ignore_ascii_case(".include") {
+            includes.extend(words.next().map(str::to_ascii_uppercase));
+            continue;
+        }
+        let Some((left, right)) = member.split_once(':') else {
+            continue;
+        };
+        let mut left = left.split_whitespace().peekable();
+        let key = left
+            .next_if(|word| word.eq_ignore_ascii_case("key"))
+            .is_some();
+        let Some(field_name) = left.next() else {
+            continue;
+        };
+        let right = right.trim();
+        let data_type = right[..token_len(right)].to_string();
+        let (data_element, builtin_type, length, decimals) = parse_ddic_type(&data_type);
+        fields.push(NeuroDdicField {
+            name: field_name.to_ascii_uppercase(),
+            key,
+            data_type,
+            data_element,
+            builtin_type,
+            length,
+            decimals,
+            not_null: right.to_ascii_lowercase().contains("not null"),
+            annotations: field_annotations,
+        });
+    }
+
+    Ok(NeuroDdicTableResponse {
+        object_uri: object_uri.to_string(),
+        name: name.to_string(),
+        kind,
+        description: label(&annotations),
+        annotations,
+        fields,
+        includes,
+        source: source.to_string(),
+    })
+}
+
+fn cds_element(member: &str) -> Option<NeuroCdsElement> {
+    let (annotations, expression) = take_annotations(member);
+    let expression = expression.trim();
+    let (key, expression) = match expression.split_once(char::is_whitespace) {
+        Some((first, rest)) if first.eq_ignore_ascii_case("key") => (true, rest.trim()),
+        _ => (false, expression),
+    };
+    if expression.is_empty() {
+        return None;
+    }
+    let typed = split_top_level(expression, ':');
+    let name = if typed.len() > 1 {
+        typed[0].trim().to_string()
+    } else {
+        let words = expression.split_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            [.., as_word, alias] if as_word.eq_ignore_ascii_case("as") => alias.to_string(),
+            [.., last] => last.rsplit('.').next().unwrap_or(last).to_string(),
+            [] => return None,
+        }
+    };
+    Some(NeuroCdsElement {
+        name,
+        key,
+        expression: expression.to_string(),
+        annotations,
+    })
+}
+
+/// Reads header annotations and the select list (or element list) of a CDS definition.
+pub(crate) fn parse_cds_source(object_uri: &str, name: &str, source: &str) -> NeuroCdsViewResponse {
+    let stripped = strip_ddl_comments(source);
+    let (annotations, definition) = take_annotations(&stripped);
+    let elements = definition_body(definition)
+        .map(|(_, body)| {
+            // Entities without a select list (abstract, custom, table functions) use `;`.
+            let separator = if split_top_level(body, ';').len() > 1 {
+                ';'
+            } else {
+                ','
+            };
+            split_top_level(body, separator)
+                .into_iter()
+                .filter_map(cds_element)
+                .collect()
+        })
+        .unwrap_or_default();
+    NeuroCdsViewResponse {
+        object_uri: object_uri.to_string(),
+        name: name.to_string(),
+        description: label(&annotations),
+        annotations,
+        elements,
+        source: source.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ddic_object_uri, parse_cds_source, parse_data_element, parse_ddic_table_source,
+        parse_domain, NeuroDdicTableKind, DDIC_TABLES_PATH,
+    };
+
+    #[test]
+    fn parses_table_fields_keys_and_includes() {
+        let source = r#"@EndUserText.label : 'Demo orders'
+@AbapCatalog.tableCategory : #TRANSPARENT
+define table zorders {
+  key client : abap.clnt not null;
+  key order_id : sysuuid_x16 not null; // technical key
+  include zorder_admin;
+  @Semantics.amount.currencyCode : 'zorders.currency'
+  amount : abap.curr(15,2);
+  currency : waers
+    with foreign key [0..*,1] tcurc
+      where waers = zorders.currency;
+}"#;
+        let table = parse_ddic_table_source(
+            "/sap/bc/adt/ddic/tables/zorders",
+            "ZORDERS",
+            NeuroDdicTableKind::Table,
+            source,
+        )
+        .unwrap();
+
+        assert_eq!(table.description.as_deref(), Some("Demo orders"));
+        assert_eq!(table.annotations[1].value, "#TRANSPARENT");
+        assert_eq!(table.includes, ["ZORDER_ADMIN"]);
+        let names = table
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["CLIENT", "ORDER_ID", "AMOUNT", "CURRENCY"]);
+        assert!(table.fields[0].key && table.fields[0].not_null);
+        assert_eq!(table.fields[1].data_element.as_deref(), Some("SYSUUID_X16"));
+        let amount = &table.fields[2];
+        assert!(!amount.key);
+        assert_eq!(amount.builtin_type.as_deref(), Some("CURR"));
+        assert_eq!((amount.length, amount.decimals), (Some(15), Some(2)));
+        assert_eq!(amount.annotations[0].name, "Semantics.amount.currencyCode");
+        assert_eq!(table.fields[3].data_element.as_deref(), Some("WAERS"));
+    }
+
+    #[test]
+    fn parses_cds_annotations_and_elements() {
+        let source = r#"@AccessControl.authorizationCheck: #NOT_REQUIRED
+@EndUserText.label: 'Open orders, by customer'
+define view entity ZI_OpenOrder
+  as select from zorders
+  association [0..1] to I_Customer as _Customer on $projection.Customer = _Customer.Customer
+{
+  key order_id as OrderId,
+      @Semantics.amount.currencyCode: 'Currency'
+      amount   as Amount,
+      currency as Currency,
+      @UI: { lineItem: [ { position: 10 }, { position: 20 } ] }
+      cast( customer as kunnr ) as Customer,
+      /* exposed association */
+      _Customer
+}
+where status = 'O'"#;
+        let view = parse_cds_source(
+            "/sap/bc/adt/ddic/ddl/sources/zi_openorder",
+            "ZI_OPENORDER",
+            source,
+        );
+
+        assert_eq!(
+            view.description.as_deref(),
+            Some("Open orders, by customer")
+        );
+        let names = view
+            .elements
+            .iter()
+            .map(|element| element.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["OrderId", "Amount", "Currency", "Customer", "_Customer"]
+        );
+        assert!(view.elements[0].key);
+        assert_eq!(view.elements[1].annotations[0].value, "'Currency'");
+        assert_eq!(view.elements[3].annotations[0].name, "UI");
+        assert_eq!(
+            view.elements[3].expression,
+            "cast( customer as kunnr ) as Customer"
+        );
+    }
+
+    #[test]
+    fn parses_data_element_and_domain_xml() {
+        let element = parse_data_element(
+            "/sap/bc/adt/ddic/dataelements/zstatus",
+            "ZSTATUS",
+            r#"<blue:wbobj xmlns:blue="http://www.sap.com/wbobj/dictionary/dtel" xmlns:adtcore="http://www.sap.com/adt/core" xmlns:dtel="http://www.sap.com/adt/dictionary/dataelements" adtcore:name="ZSTATUS" adtcore:description="Order status">
+              <dtel:dataElement>
+                <dtel:typeKind>domain</dtel:typeKind>
+                <dtel:typeName>ZSTATUS</dtel:typeName>
+                <dtel:dataType>CHAR</dtel:dataType>
+                <dtel:dataTypeLength>000001</dtel:dataTypeLength>
+                <dtel:dataTypeDecimals>000000</dtel:dataTypeDecimals>
+                <dtel:shortFieldLabel>Status</dtel:shortFieldLabel>
+                <dtel:searchHelp/>
+              </dtel:dataElement>
+            </blue:wbobj>"#,
+        )
+        .unwrap();
+        assert_eq!(element.description.as_deref(), Some("Order status"));
+        assert_eq!(element.length, Some(1));
+        assert_eq!(element.short_label.as_deref(), Some("Status"));
+        assert_eq!(element.search_help, None);
+
+        let domain = parse_domain(
+            "/sap/bc/adt/ddic/domains/zstatus",
+            "ZSTATUS",
+            r#"<doma:domain xmlns:doma="http://www.sap.com/dictionary/domain" xmlns:adtcore="http://www.sap.com/adt/core" adtcore:name="ZSTATUS" adtcore:description="Order status">
+              <doma:content>
+                <doma:typeInformation><doma:datatype>CHAR</doma:datatype><doma:length>000001</doma:length><doma:decimals>000000</doma:decimals></doma:typeInformation>
+                <doma:outputInformation><doma:length>000001</doma:length><doma:conversionExit/><doma:signExists>false</doma:signExists><doma:lowercase>true</doma:lowercase></doma:outputInformation>
+                <doma:valueInformation>
+                  <doma:valueTableRef adtcore:name="ZSTATUS_T"/>
+                  <doma:fixValues>
+                    <doma:fixValue><doma:position>0001</doma:position><doma:low>O</doma:low><doma:high/><doma:text>Open &amp; new</doma:text></doma:fixValue>
+                    <doma:fixValue><doma:position>0002</doma:position><doma:low>C</doma:low><doma:high/><doma:text>Closed</doma:text></doma:fixValue>
+                  </doma:fixValues>
+                </doma:valueInformation>
+              </doma:content>
+            </doma:domain>"#,
+        )
+        .unwrap();
+        assert_eq!(domain.description.as_deref(), Some("Order status"));
+        assert_eq!(domain.data_type.as_deref(), Some("CHAR"));
+        assert_eq!((domain.length, domain.output_length), (Some(1), Some(1)));
+        assert!(domain.lowercase && !domain.signed);
+        assert_eq!(domain.value_table.as_deref(), Some("ZSTATUS_T"));
+        assert_eq!(domain.fixed_values.len(), 2);
+        assert_eq!(domain.fixed_values[0].text.as_deref(), Some("Open & new"));
+        assert_eq!(domain.fixed_values[1].high, None);
+    }
+
+    #[test]
+    fn validates_ddic_names() {
+        assert_eq!(
+            ddic_object_uri(DDIC_TABLES_PATH, " /abc/orders ").unwrap(),
+            (
+                "/ABC/ORDERS".to_string(),
+                "/sap/bc/adt/ddic/tables/%2fabc%2forders".to_string()
+            )
+        );
+        assert!(ddic_object_uri(DDIC_TABLES_PATH, "../programs").is_err());
+        assert!(ddic_object_uri(DDIC_TABLES_PATH, "zorders?x=1").is_err());
+        assert!(ddic_object_uri(DDIC_TABLES_PATH, "//orders").is_err());
+    }
+}
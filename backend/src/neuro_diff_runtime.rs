@@ -0,0 +1,187 @@
+pub(crate) const DEFAULT_DIFF_CONTEXT: usize = 3;
+/// Above this many LCS cells the changed middle is shown as one replaced block.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffOp {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+impl DiffOp {
+    fn is_change(self) -> bool {
+        !matches!(self, DiffOp::Equal(..))
+    }
+}
+
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut ops = (0..prefix)
+        .map(|index| DiffOp::Equal(index, index))
+        .collect::<Vec<_>>();
+
+    let rows = old_middle.len() + 1;
+    let columns = new_middle.len() + 1;
+    if rows.saturating_mul(columns) > MAX_LCS_CELLS {
+        ops.extend((0..old_middle.len()).map(|index| DiffOp::Delete(prefix + index)));
+        ops.extend((0..new_middle.len()).map(|index| DiffOp::Insert(prefix + index)));
+    } else {
+        // lcs[i * columns + j] is the LCS length of old_middle[i..] and new_middle[j..].
+        let mut lcs = vec![0u32; rows * columns];
+        for i in (0..old_middle.len()).rev() {
+            for j in (0..new_middle.len()).rev() {
+                lcs[i * columns + j] = if old_middle[i] == new_middle[j] {
+                    lcs[(i + 1) * columns + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * columns + j].max(lcs[i * columns + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < old_middle.len() || j < new_middle.len() {
+            if i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j] {
+                ops.push(DiffOp::Equal(prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if j == new_middle.len()
+                || (i < old_middle.len() && lcs[(i + 1) * columns + j] >= lcs[i * columns + j + 1])
+            {
+                ops.push(DiffOp::Delete(prefix + i));
+                i += 1;
+            } else {
+                ops.push(DiffOp::Insert(prefix + j));
+                j += 1;
+            }
+        }
+    }
+
+    let old_offset = old.len() - suffix;
+    let new_offset = new.len() - suffix;
+    ops.extend((0..suffix).map(|index| DiffOp::Equal(old_offset + index, new_offset + index)));
+    ops
+}
+
+fn hunk_range(start: usize, count: usize) -> String {
+    // An empty side points at the line before the hunk, as in `diff -u`.
+    let start = if count == 0 { start } else { start + 1 };
+    if count == 1 {
+        start.to_string()
+    } else {
+        format!("{start},{count}")
+    }
+}
+
+/// `diff -u` style patch from `old` to `new`; empty when both are equal.
+pub(crate) fn unified_diff(
+    old: &str,
+    new: &str,
+    old_label: &str,
+    new_label: &str,
+    context: usize,
+) -> String {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    let ops = diff_lines(&old_lines, &new_lines);
+    let changes = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| op.is_change())
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for index in changes {
+        match groups.last_mut() {
+            Some((_, last)) if index - *last <= 2 * context + 1 => *last = index,
+            _ => groups.push((index, index)),
+        }
+    }
+
+    let mut patch = format!("--- {old_label}\n+++ {new_label}\n");
+    for (first, last) in groups {
+        let start = first.saturating_sub(context);
+        let end = (last + context + 1).min(ops.len());
+        let hunk = &ops[start..end];
+
+        let old_before = ops[..start]
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Insert(_)))
+            .count();
+        let new_before = ops[..start]
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Delete(_)))
+            .count();
+        let old_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Insert(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, DiffOp::Delete(_)))
+            .count();
+        patch.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_before, old_count),
+            hunk_range(new_before, new_count)
+        ));
+        for op in hunk {
+            let (marker, line) = match *op {
+                DiffOp::Equal(index, _) => (' ', old_lines[index]),
+                DiffOp::Delete(index) => ('-', old_lines[index]),
+                DiffOp::Insert(index) => ('+', new_lines[index]),
+            };
+            patch.push(marker);
+            patch.push_str(line);
+            patch.push('\n');
+        }
+    }
+    patch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unified_diff;
+
+    #[test]
+    fn diffs_changed_lines_with_context() {
+        let old = "REPORT ztest.\nDATA lv TYPE i.\nlv = 1.\nWRITE lv.\n";
+        let new = "REPORT ztest.\nDATA lv TYPE i.\nlv = 2.\nWRITE lv.\nWRITE / 'done'.\n";
+        assert_eq!(
+            unified_diff(old, new, "a/ztest", "b/ztest", 1),
+            "--- a/ztest\n+++ b/ztest\n@@ -2,3 +2,4 @@\n DATA lv TYPE i.\n-lv = 1.\n+lv = 2.\n WRITE lv.\n+WRITE / 'done'.\n"
+        );
+        assert_eq!(unified_diff(old, old, "a", "b", 3), "");
+    }
+
+    #[test]
+    fn splits_distant_changes_into_hunks() {
+        let old = (1..=20).map(|n| format!("line {n}\n")).collect::<String>();
+        let new = old
+            .replace("line 2\n", "line two\n")
+            .replace("line 19\n", "");
+        let patch = unified_diff(&old, &new, "a", "b", 2);
+        assert!(patch.contains("@@ -1,4 +1,4 @@\n line 1\n-line 2\n+line two\n"));
+        assert!(patch.contains("@@ -17,4 +17,3 @@\n line 17\n line 18\n-line 19\n line 20\n"));
+        assert_eq!(
+            unified_diff("", "new\n", "a", "b", 3),
+            "--- a\n+++ b\n@@ -0,0 +1 @@\n+new\n"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use neuro_types::DiagnoseStatus;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::time::Duration;
+
+const DEFAULT_HEALTH_INTERVAL_SECS: u64 = 60;
+const MIN_HEALTH_INTERVAL_SECS: u64 = 10;
+const MAX_HEALTH_BACKOFF: Duration = Duration::from_secs(15 * 60);
+const HEALTH_HISTORY_LEN: usize = 120;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroHealthSample {
+    pub checked_at_epoch_secs: u64,
+    pub status: DiagnoseStatus,
+    pub latency_ms: u64,
+}
+
+/// Payload of `neuro://health`, sent when a connection's `overall_status` changes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroHealthEvent {
+    pub connection_id: String,
+    pub status: DiagnoseStatus,
+    /// `None` on the first check of a connection.
+    pub previous_status: Option<DiagnoseStatus>,
+    pub checked_at_epoch_secs: u64,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroHealthHistory {
+    pub connection_id: String,
+    pub status: Option<DiagnoseStatus>,
+    pub since_epoch_secs: Option<u64>,
+    /// Share of recorded checks that were not `unavailable`.
+    pub availability: Option<f64>,
+    pub samples: Vec<NeuroHealthSample>,
+}
+
+#[derive(Debug, Default)]
+struct ConnectionHealth {
+    status: Option<DiagnoseStatus>,
+    since_epoch_secs: Option<u64>,
+    consecutive_unavailable: u32,
+    next_check_epoch_secs: u64,
+    samples: VecDeque<NeuroHealthSample>,
+}
+
+#[derive(Debug, Default)]
+pub struct NeuroHealthRegistry {
+    connections: HashMap<String, ConnectionHealth>,
+}
+
+/// `None` disables the monitor (`NEURO_HEALTH_INTERVAL_SECS=0`).
+pub(crate) fn health_interval_from_env() -> Option<Duration> {
+    let secs = env::var("NEURO_HEALTH_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_HEALTH_INTERVAL_SECS);
+    if secs == 0 {
+        return None;
+    }
+    Some(Duration::from_secs(secs.max(MIN_HEALTH_INTERVAL_SECS)))
+}
+
+/// Doubles the interval for every consecutive `unavailable` check, up to 15 minutes.
+pub(crate) fn health_backoff(interval: Duration, consecutive_unavailable: u32) -> Duration {
+    let factor = 1u32 << consecutive_unavailable.min(8);
+    interval
+        .saturating_mul(factor)
+        .min(MAX_HEALTH_BACKOFF)
+        .max(interval)
+}
+
+impl NeuroHealthRegistry {
+    pub(crate) fn is_due(&self, connection_id: &str, now_epoch_secs: u64) -> bool {
+        self.connections
+            .get(connection_id)
+            .is_none_or(|health| now_epoch_secs >= health.next_check_epoch_secs)
+    }
+
+    /// Returns the event to emit when the status differs from the previous check.
+    pub(crate) fn record(
+        &mut self,
+        connection_id: &str,
+        sample: NeuroHealthSample,
+        detail: Option<String>,
+        interval: Duration,
+    ) -> Option<NeuroHealthEvent> {
+        let health = self
+            .connections
+            .entry(connection_id.to_string())
+            .or_default();
+        if sample.status == DiagnoseStatus::Unavailable {
+            health.consecutive_unavailable = health.consecutive_unavailable.saturating_add(1);
+        } else {
+            health.consecutive_unavailable = 0;
+        }
+        let delay = if health.consecutive_unavailable == 0 {
+            interval
+        } else {
+            health_backoff(interval, health.consecutive_unavailable - 1)
+        };
+        health.next_check_epoch_secs = sample.checked_at_epoch_secs + delay.as_secs();
+
+        let previous_status = health.status;
+        let changed = previous_status != Some(sample.status);
+        if changed {
+            health.status = Some(sample.status);
+            health.since_epoch_secs = Some(sample.checked_at_epoch_secs);
+        }
+        if health.samples.len() == HEALTH_HISTORY_LEN {
+            health.samples.pop_front();
+        }
+        let event = changed.then(|| NeuroHealthEvent {
+            connection_id: connection_id.to_string(),
+            status: sample.status,
+            previous_status,
+            checked_at_epoch_secs: sample.checked_at_epoch_secs,
+            detail,
+        });
+        health.samples.push_back(sample);
+        event
+    }
+
+    pub(crate) fn forget(&mut self, connection_id: &str) {
+        self.connections.remove(connection_id);
+    }
+
+    pub(crate) fn history(&self, connection_id: &str) -> NeuroHealthHistory {
+        let Some(health) = self.connections.get(connection_id) else {
+            return NeuroHealthHistory {
+                connection_id: connection_id.to_string(),
+                status: None,
+                since_epoch_secs: None,
+                availability: None,
+                samples: Vec::new(),
+            };
+        };
+        let available = health
+            .samples
+            .iter()
+            .filter(|sample| sample.status != DiagnoseStatus::Unavailable)
+            .count();
+        NeuroHealthHistory {
+            connection_id: connection_id.to_string(),
+            status: health.status,
+            since_epoch_secs: health.since_epoch_secs,
+            availability: (!health.samples.is_empty())
+                .then(|| available as f64 / health.samples.len() as f64),
+            samples: health.samples.iter().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{health_backoff, NeuroHealthRegistry, NeuroHealthSample};
+    use neuro_types::DiagnoseStatus;
+    use std::time::Duration;
+
+    fn sample(at: u64, status: DiagnoseStatus) -> NeuroHealthSample {
+        NeuroHealthSample {
+            checked_at_epoch_secs: at,
+            status,
+            latency_ms: 12,
+        }
+    }
+
+    #[test]
+    fn emits_only_on_status_change_and_backs_off() {
+        let interval = Duration::from_secs(60);
+        let mut registry = NeuroHealthRegistry::default();
+
+        let first = registry
+            .record(
+                "dev",
+                sample(1_000, DiagnoseStatus::Healthy),
+                None,
+                interval,
+            )
+            .expect("first check reports the status");
+        assert_eq!(first.previous_status, None);
+        assert!(registry
+            .record(
+                "dev",
+                sample(1_060, DiagnoseStatus::Healthy),
+                None,
+                interval
+            )
+            .is_none());
+        assert!(!registry.is_due("dev", 1_100));
+        assert!(registry.is_due("dev", 1_120));
+
+        let down = registry
+            .record(
+                "dev",
+                sample(1_120, DiagnoseStatus::Unavailable),
+                Some("connection refused".to_string()),
+                interval,
+            )
+            .expect("status change");
+        assert_eq!(down.previous_status, Some(DiagnoseStatus::Healthy));
+        assert!(registry.is_due("dev", 1_180));
+        registry.record(
+            "dev",
+            sample(1_180, DiagnoseStatus::Unavailable),
+            None,
+            interval,
+        );
+        assert!(!registry.is_due("dev", 1_240));
+        assert!(registry.is_due("dev", 1_300));
+
+        let history = registry.history("dev");
+        assert_eq!(history.status, Some(DiagnoseStatus::Unavailable));
+        assert_eq!(history.since_epoch_secs, Some(1_120));
+        assert_eq!(history.samples.len(), 4);
+        assert_eq!(history.availability, Some(0.5));
+        assert!(registry.history("qas").availability.is_none());
+    }
+
+    #[test]
+    fn caps_backoff() {
+        let interval = Duration::from_secs(60);
+        assert_eq!(health_backoff(interval, 0), interval);
+        assert_eq!(health_backoff(interval, 2), Duration::from_secs(240));
+        assert_eq!(health_backoff(interval, 30), Duration::from_secs(15 * 60));
+    }
+}
@@ -0,0 +1,300 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+use crate::neuro_adt_rest::{collect_xml_text, AdtRequest, AdtRestClient, AdtRestError};
+
+pub const DEFAULT_LOCK_TTL_SECS: u64 = 900;
+const MIN_LOCK_TTL_SECS: u64 = 60;
+const ADT_OBJECT_PREFIX: &str = "/sap/bc/adt/";
+const LOCK_RESULT_ACCEPT: &str =
+    "application/*,application/vnd.sap.as+xml;charset=UTF-8;dataname=com.sap.adt.lock.result";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroObjectLock {
+    pub object_uri: String,
+    #[serde(skip_serializing)]
+    pub lock_handle: String,
+    pub transport: Option<String>,
+    pub is_local: bool,
+    pub acquired_at_epoch_secs: u64,
+    pub refreshed_at_epoch_secs: u64,
+    pub expires_at_epoch_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroObjectUnlockResponse {
+    pub object_uri: String,
+    pub released: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct LockResult {
+    pub lock_handle: String,
+    pub transport: Option<String>,
+    pub is_local: bool,
+}
+
+fn now_epoch_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Reduces an editor or source URI to the ADT object URI that owns the lock.
+pub(crate) fn normalize_object_uri(object_uri: &str) -> Result<String, String> {
+    let trimmed = object_uri.trim();
+    let path = trimmed.split(['?', '#']).next().unwrap_or_default();
+    let path = path
+        .trim_end_matches('/')
+        .trim_end_matches("/source/main")
+        .trim_end_matches('/');
+    if !path.starts_with(ADT_OBJECT_PREFIX) || path.len() <= ADT_OBJECT_PREFIX.len() {
+        return Err(format!(
+            "object URI must start with `{ADT_OBJECT_PREFIX}`, got `{trimmed}`"
+        ));
+    }
+    Ok(path.to_string())
+}
+
+pub(crate) fn parse_lock_result(xml: &str) -> Result<LockResult, String> {
+    let values = collect_xml_text(xml, &["LOCK_HANDLE", "CORRNR", "IS_LOCAL"])?;
+    let lock_handle = values
+        .get("LOCK_HANDLE")
+        .filter(|value| !value.is_empty())
+        .cloned()
+        .ok_or_else(|| "lock result does not contain LOCK_HANDLE".to_string())?;
+    Ok(LockResult {
+        lock_handle,
+        transport: values
+            .get("CORRNR")
+            .filter(|value| !value.is_empty())
+            .cloned(),
+        is_local: values
+            .get("IS_LOCAL")
+            .is_some_and(|value| value.eq_ignore_ascii_case("x")),
+    })
+}
+
+pub(crate) fn lock_ttl_from_env() -> Duration {
+    let secs = env::var("NEURO_ADT_LOCK_TTL_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_LOCK_TTL_SECS)
+        .max(MIN_LOCK_TTL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Locks are re-acquired well before the stateful ADT session can drop them.
+pub(crate) fn lock_refresh_interval(ttl: Duration) -> Duration {
+    ttl / 3
+}
+
+async fn request_lock(adt: &AdtRestClient, object_uri: &str) -> Result<LockResult, AdtRestError> {
+    let response = adt
+        .send(
+            "lock_object",
+            AdtRequest::post(object_uri)
+                .query("_action", "LOCK")
+                .query("accessMode", "MODIFY")
+                .accept(LOCK_RESULT_ACCEPT)
+                .stateful(),
+        )
+        .await?;
+    parse_lock_result(&response.body)
+        .map_err(|message| AdtRestError::invalid_response("lock_object", message))
+}
+
+async fn request_unlock(
+    adt: &AdtRestClient,
+    object_uri: &str,
+    lock_handle: &str,
+) -> Result<(), AdtRestError> {
+    adt.send(
+        "unlock_object",
+        AdtRequest::post(object_uri)
+            .query("_action", "UNLOCK")
+            .query("lockHandle", lock_handle)
+            .stateful(),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Object locks held by one runtime, keyed by normalized object URI.
+pub(crate) struct ObjectLockRegistry {
+    ttl: Duration,
+    locks: Mutex<BTreeMap<String, NeuroObjectLock>>,
+    refresher_started: AtomicBool,
+}
+
+impl ObjectLockRegistry {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            locks: Mutex::new(BTreeMap::new()),
+            refresher_started: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns true exactly once, for the caller that should start the refresher.
+    pub(crate) fn claim_refresher(&self) -> bool {
+        !self.refresher_started.swap(true, Ordering::AcqRel)
+    }
+
+    pub(crate) async fn held(&self, object_uri: &str) -> Option<NeuroObjectLock> {
+        self.locks.lock().await.get(object_uri).cloned()
+    }
+
+    pub(crate) async fn lock(
+        &self,
+        adt: &AdtRestClient,
+        object_uri: &str,
+    ) -> Result<NeuroObjectLock, AdtRestError> {
+        let mut locks = self.locks.lock().await;
+        if let Some(existing) = locks.get(object_uri) {
+            return Ok(existing.clone());
+        }
+
+        let result = request_lock(adt, object_uri).await?;
+        let now = now_epoch_secs();
+        let lock = NeuroObjectLock {
+            object_uri: object_uri.to_string(),
+            lock_handle: result.lock_handle,
+            transport: result.transport,
+            is_local: result.is_local,
+            acquired_at_epoch_secs: now,
+            refreshed_at_epoch_secs: now,
+            expires_at_epoch_secs: now + self.ttl.as_secs(),
+        };
+        locks.insert(object_uri.to_string(), lock.clone());
+        Ok(lock)
+    }
+
+    pub(crate) async fn unlock(
+        &self,
+        adt: &AdtRestClient,
+        object_uri: &str,
+    ) -> Result<bool, AdtRestError> {
+        let mut locks = self.locks.lock().await;
+        let Some(lock) = locks.remove(object_uri) else {
+            return Ok(false);
+        };
+        if let Err(error) = request_unlock(adt, object_uri, &lock.lock_handle).await {
+            locks.insert(object_uri.to_string(), lock);
+            return Err(error);
+        }
+        Ok(true)
+    }
+
+    /// Re-locks every held object; objects that fail are dropped and reported.
+    pub(crate) async fn refresh_all(&self, adt: &AdtRestClient) -> Vec<(String, AdtRestError)> {
+        let mut locks = self.locks.lock().await;
+        let mut failures = Vec::new();
+        let object_uris = locks.keys().cloned().collect::<Vec<_>>();
+        for object_uri in object_uris {
+            match request_lock(adt, &object_uri).await {
+                Ok(result) => {
+                    if let Some(lock) = locks.get_mut(&object_uri) {
+                        let now = now_epoch_secs();
+                        lock.lock_handle = result.lock_handle;
+                        lock.transport = result.transport;
+                        lock.is_local = result.is_local;
+                        lock.refreshed_at_epoch_secs = now;
+                        lock.expires_at_epoch_secs = now + self.ttl.as_secs();
+                    }
+                }
+                Err(error) => {
+                    locks.remove(&object_uri);
+                    failures.push((object_uri, error));
+                }
+            }
+        }
+        failures
+    }
+
+    /// Releases all held locks and returns how many were released cleanly.
+    pub(crate) async fn release_all(&self, adt: &AdtRestClient) -> usize {
+        let locks = std::mem::take(&mut *self.locks.lock().await);
+        let mut released = 0;
+        for (object_uri, lock) in locks {
+            if request_unlock(adt, &object_uri, &lock.lock_handle)
+                .await
+                .is_ok()
+            {
+                released += 1;
+            }
+        }
+        released
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lock_refresh_interval, normalize_object_uri, parse_lock_result, LockResult};
+    use std::time::Duration;
+
+    #[test]
+    fn normalizes_object_uris_to_lockable_objects() {
+        assert_eq!(
+            normalize_object_uri(
+                " /sap/bc/adt/programs/programs/ztest/source/main?version=active "
+            )
+            .unwrap(),
+            "/sap/bc/adt/programs/programs/ztest"
+        );
+        assert_eq!(
+            normalize_object_uri("/sap/bc/adt/oo/classes/zcl_demo/").unwrap(),
+            "/sap/bc/adt/oo/classes/zcl_demo"
+        );
+        assert!(normalize_object_uri("/sap/bc/adt/").is_err());
+        assert!(normalize_object_uri("programs/ztest").is_err());
+    }
+
+    #[test]
+    fn parses_lock_results() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <asx:abap xmlns:asx="http://www.sap.com/abapxml" version="1.0">
+              <asx:values><DATA>
+                <LOCK_HANDLE>6D4C0A1B2C3D4E5F</LOCK_HANDLE>
+                <CORRNR>DEVK900123</CORRNR>
+                <CORRUSER>DEVELOPER</CORRUSER>
+                <IS_LOCAL/>
+              </DATA></asx:values>
+            </asx:abap>"#;
+        assert_eq!(
+            parse_lock_result(xml).unwrap(),
+            LockResult {
+                lock_handle: "6D4C0A1B2C3D4E5F".to_string(),
+                transport: Some("DEVK900123".to_string()),
+                is_local: false,
+            }
+        );
+
+        let local =
+            "<DATA><LOCK_HANDLE>H</LOCK_HANDLE><CORRNR></CORRNR><IS_LOCAL>X</IS_LOCAL></DATA>";
+        let parsed = parse_lock_result(local).unwrap();
+        assert_eq!(parsed.transport, None);
+        assert!(parsed.is_local);
+
+        assert!(parse_lock_result("<DATA><CORRNR>DEVK900123</CORRNR></DATA>").is_err());
+    }
+
+    #[test]
+    fn refreshes_well_before_ttl() {
+        assert_eq!(
+            lock_refresh_interval(Duration::from_secs(900)),
+            Duration::from_secs(300)
+        );
+    }
+}
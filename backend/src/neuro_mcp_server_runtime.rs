@@ -0,0 +1,564 @@
+//! Serves the Neuro agent tools as the `neuro` MCP server for native Codex threads.
+//!
+//! The server speaks MCP streamable HTTP (JSON responses only) on a loopback port that is
+//! picked at startup. Each launch uses a fresh bearer token, which is handed to Codex through
+//! the `mcp_servers.neuro` config override together with the URL.
+
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::neuro_agent_runtime::{
+    invoke_neuro_agent_tool, neuro_agent_tools, NEURO_AGENT_SERVER_NAME,
+};
+
+const MCP_PATH: &str = "/mcp";
+const MCP_PROTOCOL_VERSION: &str = "2025-06-18";
+const MAX_REQUEST_BYTES: usize = 16 * 1024 * 1024;
+/// Pause after a failed accept, e.g. when the process is out of file descriptors.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+#[derive(Debug, Clone)]
+pub(crate) struct NeuroMcpEndpoint {
+    pub url: String,
+    token: String,
+}
+
+/// Set once by [`start_neuro_mcp_server`]; the error explains why the server is not running.
+static ENDPOINT: OnceLock<Result<NeuroMcpEndpoint, String>> = OnceLock::new();
+
+/// Binds the loopback listener and serves it for the rest of the process.
+pub(crate) fn start_neuro_mcp_server(app: AppHandle) {
+    let result = bind_listener().map(|(listener, endpoint)| {
+        let token = endpoint.token.clone();
+        tauri::async_runtime::spawn(async move {
+            let listener = match TcpListener::from_std(listener) {
+                Ok(listener) => listener,
+                Err(error) => {
+                    eprintln!("[neuro-mcp] failed to register listener: {error}");
+                    return;
+                }
+            };
+            serve(listener, token, move |message| {
+                let app = app.clone();
+                async move { handle_message(&app, message).await }
+            })
+            .await;
+        });
+        endpoint
+    });
+    if let Err(error) = &result {
+        eprintln!("[neuro-mcp] server not started: {error}");
+    }
+    let _ = ENDPOINT.set(result);
+}
+
+/// The running endpoint, or why there is none.
+#[cfg_attr(not(feature = "native-codex-runtime"), allow(dead_code))]
+pub(crate) fn neuro_mcp_endpoint() -> Result<NeuroMcpEndpoint, String> {
+    ENDPOINT
+        .get()
+        .cloned()
+        .unwrap_or_else(|| Err("neuro MCP server has not been started".to_string()))
+}
+
+/// Config overrides registering the server as `mcp_servers.neuro`; empty when it is not running.
+#[cfg_attr(not(feature = "native-codex-runtime"), allow(dead_code))]
+pub(crate) fn neuro_mcp_server_cli_overrides() -> Vec<(String, toml::Value)> {
+    match neuro_mcp_endpoint() {
+        Ok(endpoint) => endpoint_cli_overrides(&endpoint),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn endpoint_cli_overrides(endpoint: &NeuroMcpEndpoint) -> Vec<(String, toml::Value)> {
+    let prefix = format!("mcp_servers.{NEURO_AGENT_SERVER_NAME}");
+    vec![
+        (
+            format!("{prefix}.url"),
+            toml::Value::String(endpoint.url.clone()),
+        ),
+        (
+            format!("{prefix}.http_headers.Authorization"),
+            toml::Value::String(format!("Bearer {}", endpoint.token)),
+        ),
+    ]
+}
+
+fn bind_listener() -> Result<(std::net::TcpListener, NeuroMcpEndpoint), String> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", 0))
+        .map_err(|error| format!("failed to bind loopback port: {error}"))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|error| format!("failed to configure listener: {error}"))?;
+    let address = listener
+        .local_addr()
+        .map_err(|error| format!("failed to read listener address: {error}"))?;
+    let endpoint = NeuroMcpEndpoint {
+        url: format!("http://{address}{MCP_PATH}"),
+        token: new_token()?,
+    };
+    Ok((listener, endpoint))
+}
+
+fn new_token() -> Result<String, String> {
+    use ring::rand::{SecureRandom, SystemRandom};
+    use std::fmt::Write as _;
+
+    let mut bytes = [0_u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "failed to generate server token".to_string())?;
+    Ok(bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    }))
+}
+
+async fn serve<F, Fut>(listener: TcpListener, token: String, handler: F)
+where
+    F: Fn(Value) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = Option<Value>> + Send,
+{
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(error) => {
+                eprintln!("[neuro-mcp] accept failed: {error}");
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let token = token.clone();
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            if let Err(error) = serve_connection(stream, &token, handler).await {
+                eprintln!("[neuro-mcp] connection failed: {error}");
+            }
+        });
+    }
+}
+
+struct HttpRequestHead {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    close: bool,
+    content_length: usize,
+}
+
+async fn read_request_head(
+    reader: &mut BufReader<TcpStream>,
+) -> Result<Option<HttpRequestHead>, std::io::Error> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut authorization = None;
+    let mut close = false;
+    let mut content_length = 0_usize;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "authorization" => authorization = Some(value.to_string()),
+            "connection" => close = value.eq_ignore_ascii_case("close"),
+            "content-length" => content_length = value.parse().unwrap_or(usize::MAX),
+            _ => {}
+        }
+    }
+    Ok(Some(HttpRequestHead {
+        method,
+        path,
+        authorization,
+        close,
+        content_length,
+    }))
+}
+
+async fn read_request_body(
+    reader: &mut BufReader<TcpStream>,
+    content_length: usize,
+) -> Result<Vec<u8>, std::io::Error> {
+    if content_length > MAX_REQUEST_BYTES {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "request body too large",
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(body)
+}
+
+/// Compares without an early exit, so response timing does not tell how much of the token
+/// matched.
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0, |difference, (left, right)| difference | (left ^ right))
+            == 0
+}
+
+async fn serve_connection<F, Fut>(
+    stream: TcpStream,
+    token: &str,
+    handler: F,
+) -> Result<(), std::io::Error>
+where
+    F: Fn(Value) -> Fut,
+    Fut: Future<Output = Option<Value>>,
+{
+    let mut reader = BufReader::new(stream);
+    let expected_authorization = format!("Bearer {token}");
+    while let Some(request) = read_request_head(&mut reader).await? {
+        let authorized = request
+            .authorization
+            .as_deref()
+            .is_some_and(|authorization| {
+                constant_time_eq(authorization.as_bytes(), expected_authorization.as_bytes())
+            });
+        let rejection = if request.path.split('?').next() != Some(MCP_PATH) {
+            Some("404 Not Found")
+        } else if !authorized {
+            Some("401 Unauthorized")
+        } else if request.method != "POST" {
+            // No server-initiated stream: GET and session DELETE are not offered.
+            Some("405 Method Not Allowed")
+        } else {
+            None
+        };
+        // A rejected request's body is never read, so its connection is closed afterwards.
+        let close = request.close || rejection.is_some();
+        let (status, body) = match rejection {
+            Some(status) => (status, None),
+            None => {
+                let body = read_request_body(&mut reader, request.content_length).await?;
+                match serde_json::from_slice::<Value>(&body) {
+                    Ok(message) => match handler(message).await {
+                        Some(response) => ("200 OK", Some(response)),
+                        None => ("202 Accepted", None),
+                    },
+                    Err(error) => (
+                        "200 OK",
+                        Some(rpc_error(
+                            Value::Null,
+                            PARSE_ERROR,
+                            format!("invalid JSON: {error}"),
+                        )),
+                    ),
+                }
+            }
+        };
+        let body = body.map(|value| value.to_string()).unwrap_or_default();
+        let mut response = format!("HTTP/1.1 {status}\r\nContent-Length: {}\r\n", body.len());
+        if !body.is_empty() {
+            response.push_str("Content-Type: application/json\r\n");
+        }
+        if status.starts_with("405") {
+            response.push_str("Allow: POST\r\n");
+        }
+        if close {
+            response.push_str("Connection: close\r\n");
+        }
+        response.push_str("\r\n");
+        response.push_str(&body);
+        let stream = reader.get_mut();
+        stream.write_all(response.as_bytes()).await?;
+        stream.flush().await?;
+        if close {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn rpc_result(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn rpc_error(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn tools_list_result() -> Value {
+    let tools = neuro_agent_tools()
+        .into_iter()
+        .map(|tool| {
+            json!({
+                "name": tool.name,
+                "description": tool.description,
+                "inputSchema": tool.input_schema,
+                "annotations": { "readOnlyHint": !tool.writes },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({ "tools": tools })
+}
+
+/// Answers everything except `tools/call`; `None` means the method is a tool call.
+fn handle_protocol_message(method: &str, id: &Value, params: &Value) -> Option<Value> {
+    let response = match method {
+        "initialize" => rpc_result(
+            id.clone(),
+            json!({
+                "protocolVersion": params
+                    .get("protocolVersion")
+                    .and_then(Value::as_str)
+                    .unwrap_or(MCP_PROTOCOL_VERSION),
+                "capabilities": { "tools": { "listChanged": false } },
+                "serverInfo": {
+                    "name": NEURO_AGENT_SERVER_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+        ),
+        "ping" => rpc_result(id.clone(), json!({})),
+        "tools/list" => rpc_result(id.clone(), tools_list_result()),
+        "tools/call" => return None,
+        other => rpc_error(
+            id.clone(),
+            METHOD_NOT_FOUND,
+            format!("method `{other}` is not supported"),
+        ),
+    };
+    Some(response)
+}
+
+fn tool_call_result(result: Result<Value, String>) -> Value {
+    let (text, is_error) = match result {
+        Ok(value) => (value.to_string(), false),
+        Err(error) => (error, true),
+    };
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+async fn handle_message(app: &AppHandle, message: Value) -> Option<Value> {
+    if !message.is_object() {
+        return Some(rpc_error(
+            Value::Null,
+            INVALID_REQUEST,
+            "expected a single JSON-RPC message".to_string(),
+        ));
+    }
+    // Notifications and responses carry no id and get no reply.
+    let id = message.get("id").cloned()?;
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        return Some(rpc_error(
+            id,
+            INVALID_REQUEST,
+            "missing `method`".to_string(),
+        ));
+    };
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    if let Some(response) = handle_protocol_message(method, &id, &params) {
+        return Some(response);
+    }
+
+    let Some(tool) = params.get("name").and_then(Value::as_str) else {
+        return Some(rpc_error(
+            id,
+            INVALID_PARAMS,
+            "tools/call requires `name`".to_string(),
+        ));
+    };
+    let arguments = params
+        .get("arguments")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    let result =
+        if crate::session_turn_runtime::approve_neuro_tool_call(app, tool, &arguments).await {
+            invoke_neuro_agent_tool(app, tool, arguments).await
+        } else {
+            Err(format!("`{tool}` was declined by the user"))
+        };
+    Some(rpc_result(id, tool_call_result(result)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        constant_time_eq, endpoint_cli_overrides, handle_protocol_message, serve, NeuroMcpEndpoint,
+        MCP_PATH,
+    };
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[test]
+    fn answers_initialize_and_lists_agent_tools() {
+        let initialize = handle_protocol_message(
+            "initialize",
+            &json!(1),
+            &json!({ "protocolVersion": "2025-03-26" }),
+        )
+        .expect("initialize is answered");
+        assert_eq!(initialize["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(initialize["result"]["serverInfo"]["name"], "neuro");
+
+        let list = handle_protocol_message("tools/list", &json!(2), &Value::Null)
+            .expect("tools/list is answered");
+        let tools = list["result"]["tools"].as_array().expect("tool array");
+        let update = tools
+            .iter()
+            .find(|tool| tool["name"] == "neuro_update_source")
+            .expect("update tool is listed");
+        assert_eq!(update["annotations"]["readOnlyHint"], false);
+        assert!(tools.iter().any(|tool| tool["name"] == "neuro_get_source"
+            && tool["annotations"]["readOnlyHint"] == true));
+
+        assert!(handle_protocol_message("tools/call", &json!(3), &Value::Null).is_none());
+        let unknown = handle_protocol_message("resources/list", &json!(4), &Value::Null)
+            .expect("unknown methods are answered");
+        assert_eq!(unknown["error"]["code"], -32601);
+    }
+
+    #[test]
+    fn compares_tokens_by_content_and_length() {
+        assert!(constant_time_eq(b"Bearer abc", b"Bearer abc"));
+        assert!(!constant_time_eq(b"Bearer abc", b"Bearer abd"));
+        assert!(!constant_time_eq(b"Bearer abc", b"Bearer ab"));
+        assert!(!constant_time_eq(b"", b"Bearer abc"));
+    }
+
+    #[test]
+    fn registers_the_endpoint_as_the_neuro_server() {
+        let overrides = endpoint_cli_overrides(&NeuroMcpEndpoint {
+            url: "http://127.0.0.1:4100/mcp".to_string(),
+            token: "abc".to_string(),
+        });
+        assert_eq!(
+            overrides,
+            vec![
+                (
+                    "mcp_servers.neuro.url".to_string(),
+                    toml::Value::String("http://127.0.0.1:4100/mcp".to_string())
+                ),
+                (
+                    "mcp_servers.neuro.http_headers.Authorization".to_string(),
+                    toml::Value::String("Bearer abc".to_string())
+                ),
+            ]
+        );
+    }
+
+    fn response_body(response: &str) -> Value {
+        let (_, body) = response
+            .split_once("\r\n\r\n")
+            .expect("response has a body");
+        serde_json::from_str(body).expect("body is JSON")
+    }
+
+    async fn post(address: &str, token: Option<&str>, body: &str) -> String {
+        let mut stream = TcpStream::connect(address).await.expect("connect");
+        let authorization = token
+            .map(|token| format!("Authorization: Bearer {token}\r\n"))
+            .unwrap_or_default();
+        let request = format!(
+            "POST {MCP_PATH} HTTP/1.1\r\nHost: {address}\r\n{authorization}Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.expect("write");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.expect("read");
+        response
+    }
+
+    #[test]
+    fn requires_the_bearer_token_and_replies_with_json() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        rt.block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let address = listener.local_addr().expect("address").to_string();
+            tokio::spawn(serve(
+                listener,
+                "secret".to_string(),
+                |message: Value| async move {
+                    message
+                        .get("id")
+                        .cloned()
+                        .map(|id| json!({ "jsonrpc": "2.0", "id": id, "result": {} }))
+                },
+            ));
+
+            let response = post(
+                &address,
+                None,
+                r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#,
+            )
+            .await;
+            assert!(response.starts_with("HTTP/1.1 401"), "{response}");
+
+            // Rejected before the announced body arrives.
+            let mut stream = TcpStream::connect(&address).await.expect("connect");
+            let request = format!(
+                "POST {MCP_PATH} HTTP/1.1\r\nHost: {address}\r\nAuthorization: Bearer wrong\r\nContent-Length: 1048576\r\n\r\n"
+            );
+            stream.write_all(request.as_bytes()).await.expect("write");
+            let mut response = String::new();
+            tokio::time::timeout(
+                std::time::Duration::from_secs(5),
+                stream.read_to_string(&mut response),
+            )
+            .await
+            .expect("rejected without waiting for the body")
+            .expect("read");
+            assert!(response.starts_with("HTTP/1.1 401"), "{response}");
+            assert!(response.contains("Connection: close"), "{response}");
+
+            let response = post(
+                &address,
+                Some("secret"),
+                r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#,
+            )
+            .await;
+            assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+            assert_eq!(
+                response_body(&response),
+                json!({ "jsonrpc": "2.0", "id": 1, "result": {} })
+            );
+
+            let response = post(
+                &address,
+                Some("secret"),
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            )
+            .await;
+            assert!(response.starts_with("HTTP/1.1 202"), "{response}");
+
+            let response = post(&address, Some("secret"), "{not json").await;
+            assert_eq!(response_body(&response)["error"]["code"], -32700);
+        });
+    }
+}
@@ -0,0 +1,426 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+use crate::neuro_adt_rest::xml_attribute;
+use crate::neuro_check_runtime::parse_uri_position;
+
+pub const WHERE_USED_PATH: &str = "/sap/bc/adt/repository/informationsystem/usageReferences";
+pub const WHERE_USED_CONTENT_TYPE: &str =
+    "application/vnd.sap.adt.repository.usagereferences.request.v1+xml";
+pub const WHERE_USED_ACCEPT: &str =
+    "application/vnd.sap.adt.repository.usagereferences.result.v1+xml";
+pub const NAVIGATION_PATH: &str = "/sap/bc/adt/navigation/target";
+pub const OBJECT_STRUCTURE_ACCEPT: &str = "application/xml";
+const DEFINITION_RELATION_SUFFIX: &str = "/definitionIdentifier";
+const IMPLEMENTATION_RELATION_SUFFIX: &str = "/implementationIdentifier";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroWhereUsedReference {
+    pub object_uri: String,
+    pub name: Option<String>,
+    pub object_type: Option<String>,
+    pub description: Option<String>,
+    pub package: Option<String>,
+    /// Usage grades SAP reports, e.g. `gradeDirect` or `includeProductive`.
+    pub usage: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroWhereUsedResponse {
+    pub object_uri: String,
+    pub references: Vec<NeuroWhereUsedReference>,
+}
+
+/// `line` is 1-based and `column` 0-based, as in ADT source links.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroNavigateRequest {
+    pub object_uri: String,
+    pub line: u32,
+    pub column: u32,
+    /// Unsaved editor source; the saved source is used when omitted.
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSourcePosition {
+    pub object_uri: String,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroNavigationTarget {
+    pub position: NeuroSourcePosition,
+    pub name: Option<String>,
+    pub object_type: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroNavigationResponse {
+    pub object_uri: String,
+    /// `None` when SAP found no definition at the position.
+    pub target: Option<NeuroNavigationTarget>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NeuroStructureKind {
+    Method,
+    Attribute,
+    Include,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroStructureElement {
+    pub name: String,
+    /// ADT type such as `CLAS/OM`.
+    pub object_type: Option<String>,
+    pub kind: NeuroStructureKind,
+    pub visibility: Option<String>,
+    /// `instance` or `static` for class components.
+    pub level: Option<String>,
+    pub description: Option<String>,
+    pub definition: Option<NeuroSourcePosition>,
+    pub implementation: Option<NeuroSourcePosition>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroObjectStructureResponse {
+    pub object_uri: String,
+    pub name: Option<String>,
+    pub object_type: Option<String>,
+    pub methods: Vec<NeuroStructureElement>,
+    pub attributes: Vec<NeuroStructureElement>,
+    pub includes: Vec<NeuroStructureElement>,
+    /// Types, events, aliases and other components, in source order.
+    pub other: Vec<NeuroStructureElement>,
+}
+
+pub(crate) fn where_used_request_xml() -> String {
+    concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        r#"<usagereferences:usageReferenceRequest xmlns:usagereferences="http://www.sap.com/adt/ris/usageReferences">"#,
+        "<usagereferences:affectedObjects/>",
+        "</usagereferences:usageReferenceRequest>"
+    )
+    .to_string()
+}
+
+pub(crate) fn navigation_source_uri(object_uri: &str, line: u32, column: u32) -> String {
+    format!("{object_uri}/source/main#start={line},{column}")
+}
+
+pub(crate) fn object_structure_path(object_uri: &str) -> String {
+    format!("{object_uri}/objectstructure")
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+fn source_position(href: &str) -> Option<NeuroSourcePosition> {
+    let (object_uri, line, column) = parse_uri_position(href);
+    Some(NeuroSourcePosition {
+        object_uri: object_uri?,
+        line,
+        column,
+    })
+}
+
+/// Keeps only `isResult="true"` nodes; the others group results by package.
+pub(crate) fn parse_where_used_result(xml: &str) -> Result<Vec<NeuroWhereUsedReference>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut references = Vec::new();
+    let mut current: Option<NeuroWhereUsedReference> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        match event {
+            Event::Start(element) | Event::Empty(element) => match element.local_name().as_ref() {
+                b"referencedObject" => {
+                    references.extend(current.take());
+                    let is_result = xml_attribute(&element, "isResult")
+                        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+                    current = match xml_attribute(&element, "uri") {
+                        Some(uri) if is_result => Some(NeuroWhereUsedReference {
+                            object_uri: uri,
+                            name: None,
+                            object_type: None,
+                            description: None,
+                            package: None,
+                            usage: xml_attribute(&element, "usageInformation")
+                                .map(|usage| {
+                                    usage
+                                        .split(',')
+                                        .map(str::trim)
+                                        .filter(|grade| !grade.is_empty())
+                                        .map(str::to_string)
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        }),
+                        _ => None,
+                    };
+                }
+                b"adtObject" => {
+                    if let Some(reference) = current.as_mut() {
+                        reference.name = non_empty(xml_attribute(&element, "name"));
+                        reference.object_type = non_empty(xml_attribute(&element, "type"));
+                        reference.description = non_empty(xml_attribute(&element, "description"));
+                    }
+                }
+                b"packageRef" => {
+                    if let Some(reference) = current.as_mut() {
+                        reference.package = non_empty(xml_attribute(&element, "name"));
+                    }
+                }
+                _ => {}
+            },
+            Event::End(element) if element.local_name().as_ref() == b"referencedObject" => {
+                references.extend(current.take());
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(references)
+}
+
+pub(crate) fn parse_navigation_target(xml: &str) -> Result<Option<NeuroNavigationTarget>, String> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        match event {
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == b"objectReference" =>
+            {
+                let Some(position) =
+                    xml_attribute(&element, "uri").and_then(|uri| source_position(&uri))
+                else {
+                    return Ok(None);
+                };
+                return Ok(Some(NeuroNavigationTarget {
+                    position,
+                    name: non_empty(xml_attribute(&element, "name")),
+                    object_type: non_empty(xml_attribute(&element, "type")),
+                }));
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+fn structure_kind(object_type: Option<&str>) -> NeuroStructureKind {
+    let Some((_, subtype)) = object_type.and_then(|value| value.split_once('/')) else {
+        return NeuroStructureKind::Other;
+    };
+    match subtype.to_ascii_uppercase().as_str() {
+        "OM" | "IO" => NeuroStructureKind::Method,
+        "OA" | "IA" => NeuroStructureKind::Attribute,
+        "I" => NeuroStructureKind::Include,
+        _ => NeuroStructureKind::Other,
+    }
+}
+
+fn structure_element(element: &BytesStart<'_>) -> NeuroStructureElement {
+    let object_type = non_empty(xml_attribute(element, "type"));
+    NeuroStructureElement {
+        name: xml_attribute(element, "name").unwrap_or_default(),
+        kind: structure_kind(object_type.as_deref()),
+        object_type,
+        visibility: non_empty(xml_attribute(element, "visibility")),
+        level: non_empty(xml_attribute(element, "clsLevel")),
+        description: non_empty(xml_attribute(element, "description")),
+        definition: None,
+        implementation: None,
+    }
+}
+
+/// Flattens the `objectstructure` tree below the root element, which is the object itself.
+pub(crate) fn parse_object_structure(
+    object_uri: &str,
+    xml: &str,
+) -> Result<NeuroObjectStructureResponse, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut response = NeuroObjectStructureResponse {
+        object_uri: object_uri.to_string(),
+        ..NeuroObjectStructureResponse::default()
+    };
+    // Open elements; the root is kept so its links do not land on a component.
+    let mut open: Vec<NeuroStructureElement> = Vec::new();
+    let mut components = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        let (element, empty) = match event {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(element) if element.local_name().as_ref() == b"objectStructureElement" => {
+                if let Some(done) = open.pop() {
+                    if open.is_empty() {
+                        response.name = non_empty(Some(done.name));
+                        response.object_type = done.object_type;
+                    } else {
+                        components.push(done);
+                    }
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        match element.local_name().as_ref() {
+            b"objectStructureElement" => {
+                let structure = structure_element(&element);
+                if !empty {
+                    open.push(structure);
+                } else if !open.is_empty() {
+                    components.push(structure);
+                }
+            }
+            b"link" => {
+                let Some(owner) = open.last_mut() else {
+                    continue;
+                };
+                let Some(rel) = xml_attribute(&element, "rel") else {
+                    continue;
+                };
+                let position =
+                    xml_attribute(&element, "href").and_then(|href| source_position(&href));
+                if rel.ends_with(DEFINITION_RELATION_SUFFIX) {
+                    owner.definition = position;
+                } else if rel.ends_with(IMPLEMENTATION_RELATION_SUFFIX) {
+                    owner.implementation = position;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for component in components {
+        match component.kind {
+            NeuroStructureKind::Method => response.methods.push(component),
+            NeuroStructureKind::Attribute => response.attributes.push(component),
+            NeuroStructureKind::Include => response.includes.push(component),
+            NeuroStructureKind::Other => response.other.push(component),
+        }
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_navigation_target, parse_object_structure, parse_where_used_result,
+        NeuroStructureKind,
+    };
+
+    const CLASS: &str = "/sap/bc/adt/oo/classes/zcl_demo";
+
+    #[test]
+    fn parses_where_used_results_and_skips_group_nodes() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <usageReferences:usageReferenceResult xmlns:usageReferences="http://www.sap.com/adt/ris/usageReferences" xmlns:adtcore="http://www.sap.com/adt/core">
+              <usageReferences:referencedObjects>
+                <usageReferences:referencedObject uri="/sap/bc/adt/packages/zdemo" isResult="false" canHaveChildren="true">
+                  <usageReferences:adtObject adtcore:name="ZDEMO" adtcore:type="DEVC/K"/>
+                </usageReferences:referencedObject>
+                <usageReferences:referencedObject uri="/sap/bc/adt/programs/programs/zreport" parentUri="/sap/bc/adt/packages/zdemo" isResult="true" usageInformation="gradeDirect,includeProductive">
+                  <usageReferences:adtObject adtcore:name="ZREPORT" adtcore:type="PROG/P" adtcore:description="Demo report">
+                    <adtcore:packageRef adtcore:uri="/sap/bc/adt/packages/zdemo" adtcore:name="ZDEMO"/>
+                  </usageReferences:adtObject>
+                </usageReferences:referencedObject>
+              </usageReferences:referencedObjects>
+            </usageReferences:usageReferenceResult>"#;
+
+        let references = parse_where_used_result(xml).unwrap();
+        assert_eq!(references.len(), 1);
+        assert_eq!(
+            references[0].object_uri,
+            "/sap/bc/adt/programs/programs/zreport"
+        );
+        assert_eq!(references[0].name.as_deref(), Some("ZREPORT"));
+        assert_eq!(references[0].package.as_deref(), Some("ZDEMO"));
+        assert_eq!(references[0].usage, ["gradeDirect", "includeProductive"]);
+    }
+
+    #[test]
+    fn parses_navigation_target_position() {
+        let xml = format!(
+            r#"<adtcore:objectReference xmlns:adtcore="http://www.sap.com/adt/core" adtcore:uri="{CLASS}/source/main#start=42,9" adtcore:type="CLAS/OM" adtcore:name="RUN"/>"#
+        );
+        let target = parse_navigation_target(&xml).unwrap().unwrap();
+        assert_eq!(target.position.object_uri, CLASS);
+        assert_eq!(target.position.line, Some(42));
+        assert_eq!(target.position.column, Some(9));
+        assert_eq!(target.name.as_deref(), Some("RUN"));
+        assert!(parse_navigation_target("<empty/>").unwrap().is_none());
+    }
+
+    #[test]
+    fn flattens_object_structure_by_kind() {
+        let xml = format!(
+            r#"<abapsource:objectStructureElement xmlns:abapsource="http://www.sap.com/adt/abapsource" xmlns:adtcore="http://www.sap.com/adt/core" xmlns:atom="http://www.w3.org/2005/Atom" adtcore:name="ZCL_DEMO" adtcore:type="CLAS/OC" visibility="public">
+              <atom:link href="{CLASS}/source/main#start=1,6" rel="http://www.sap.com/adt/relations/source/definitionIdentifier"/>
+              <abapsource:objectStructureElement adtcore:name="RUN" adtcore:type="CLAS/OM" visibility="public" clsLevel="instance" description="Run the demo">
+                <atom:link href="{CLASS}/source/main#start=5,12" rel="http://www.sap.com/adt/relations/source/definitionIdentifier"/>
+                <atom:link href="{CLASS}/source/main#start=20,9" rel="http://www.sap.com/adt/relations/source/implementationIdentifier"/>
+              </abapsource:objectStructureElement>
+              <abapsource:objectStructureElement adtcore:name="MV_COUNT" adtcore:type="CLAS/OA" visibility="private" clsLevel="static"/>
+              <abapsource:objectStructureElement adtcore:name="ZCL_DEMO=====CCAU" adtcore:type="CLAS/I">
+                <atom:link href="{CLASS}/includes/testclasses#start=1,0" rel="http://www.sap.com/adt/relations/source/definitionIdentifier"/>
+              </abapsource:objectStructureElement>
+              <abapsource:objectStructureElement adtcore:name="TY_ROW" adtcore:type="CLAS/OT" visibility="public"/>
+            </abapsource:objectStructureElement>"#
+        );
+
+        let structure = parse_object_structure(CLASS, &xml).unwrap();
+        assert_eq!(structure.name.as_deref(), Some("ZCL_DEMO"));
+        assert_eq!(structure.object_type.as_deref(), Some("CLAS/OC"));
+        assert_eq!(structure.methods.len(), 1);
+        let method = &structure.methods[0];
+        assert_eq!(method.kind, NeuroStructureKind::Method);
+        assert_eq!(method.level.as_deref(), Some("instance"));
+        assert_eq!(method.definition.as_ref().and_then(|at| at.line), Some(5));
+        assert_eq!(
+            method.implementation.as_ref().and_then(|at| at.line),
+            Some(20)
+        );
+        assert_eq!(structure.attributes[0].name, "MV_COUNT");
+        assert_eq!(
+            structure.includes[0]
+                .definition
+                .as_ref()
+                .map(|at| at.object_uri.as_str()),
+            Some("/sap/bc/adt/oo/classes/zcl_demo/includes/testclasses")
+        );
+        assert_eq!(structure.other[0].name, "TY_ROW");
+    }
+}
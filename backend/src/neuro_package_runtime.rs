@@ -0,0 +1,265 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::neuro_adt_rest::xml_reference_text;
+
+pub const PACKAGE_NODES_PATH: &str = "/sap/bc/adt/repository/nodestructure";
+pub const PACKAGE_MANIFEST_FILE: &str = ".neuro-package.json";
+const PACKAGE_OBJECT_TYPE: &str = "DEVC/K";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSyncPackageRequest {
+    pub package: String,
+    pub target_dir: String,
+    #[serde(default)]
+    pub include_subpackages: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroPackageManifestEntry {
+    pub object_uri: String,
+    pub object_type: String,
+    pub object_name: String,
+    /// Relative to the manifest directory.
+    pub path: String,
+    pub etag: Option<String>,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroPackageManifest {
+    pub package: String,
+    pub connection_id: Option<String>,
+    pub synced_at_epoch_secs: u64,
+    pub objects: Vec<NeuroPackageManifestEntry>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroPackageSkippedObject {
+    pub object_uri: String,
+    pub object_type: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroPackageSyncFailure {
+    pub object_uri: String,
+    pub object_type: String,
+    pub error: neuro_types::NeuroRuntimeError,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSyncPackageResponse {
+    pub package: String,
+    pub target_dir: String,
+    pub manifest_path: String,
+    pub objects: Vec<NeuroPackageManifestEntry>,
+    pub skipped: Vec<NeuroPackageSkippedObject>,
+    /// Objects and subpackages that could not be read or written; the manifest keeps
+    /// their entries from the previous sync.
+    pub failed: Vec<NeuroPackageSyncFailure>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroPushChangesRequest {
+    pub target_dir: String,
+    #[serde(default)]
+    pub dry_run: bool,
+    pub syntax_check: Option<bool>,
+    pub transport: Option<String>,
+    /// Set after the user reviewed the diffs when writes require confirmation.
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroPushedObject {
+    pub object_uri: String,
+    pub path: String,
+    pub etag: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroPushFailure {
+    pub object_uri: String,
+    pub path: String,
+    pub error: neuro_types::NeuroRuntimeError,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroPushChangesResponse {
+    pub dry_run: bool,
+    /// Changed objects; on a dry run these are the ones that would be uploaded.
+    pub pushed: Vec<NeuroPushedObject>,
+    pub unchanged: usize,
+    pub failed: Vec<NeuroPushFailure>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PackageNode {
+    pub object_type: String,
+    pub object_name: String,
+    pub object_uri: String,
+}
+
+impl PackageNode {
+    pub(crate) fn is_package(&self) -> bool {
+        self.object_type == PACKAGE_OBJECT_TYPE
+    }
+}
+
+pub(crate) fn normalize_package_name(package: &str) -> Result<String, String> {
+    let package = package.trim().to_ascii_uppercase();
+    if package.is_empty() || package.len() > 30 {
+        return Err(format!("invalid package name `{package}`"));
+    }
+    Ok(package)
+}
+
+pub(crate) fn manifest_relative_path(target_dir: &Path) -> PathBuf {
+    target_dir.join(PACKAGE_MANIFEST_FILE)
+}
+
+pub(crate) fn sha256_hex(content: &str) -> String {
+    digest(&SHA256, content.as_bytes())
+        .as_ref()
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// abapGit-style file name for objects with a single main source, e.g. `zcl_demo.clas.abap`.
+pub(crate) fn source_file_name(object_type: &str, object_name: &str) -> Option<String> {
+    let suffix = match object_type {
+        "PROG/P" | "PROG/I" => "prog.abap",
+        "CLAS/OC" => "clas.abap",
+        "INTF/OI" => "intf.abap",
+        "DDLS/DF" => "ddls.asddls",
+        "DCLS/DL" => "dcls.asdcls",
+        "BDEF/BDO" => "bdef.asbdef",
+        _ => return None,
+    };
+    let name = object_name.trim().to_ascii_lowercase().replace('/', "#");
+    if name.is_empty() {
+        return None;
+    }
+    Some(format!("{name}.{suffix}"))
+}
+
+/// Reads the object nodes of a repository node structure response.
+pub(crate) fn parse_package_nodes(xml: &str) -> Result<Vec<PackageNode>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut nodes = Vec::new();
+    let mut node: Option<[String; 3]> = None;
+    let mut field: Option<usize> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        match event {
+            Event::Start(element) => match element.local_name().as_ref() {
+                b"SEU_ADT_REPOSITORY_OBJ_NODE" => node = Some(Default::default()),
+                b"OBJECT_TYPE" if node.is_some() => field = Some(0),
+                b"OBJECT_NAME" if node.is_some() => field = Some(1),
+                b"OBJECT_URI" if node.is_some() => field = Some(2),
+                _ => {}
+            },
+            Event::Text(text) => {
+                if let (Some(node), Some(index)) = (node.as_mut(), field) {
+                    node[index].push_str(&text.decode().map_err(|error| error.to_string())?);
+                }
+            }
+            Event::GeneralRef(reference) => {
+                if let (Some(node), Some(index)) = (node.as_mut(), field) {
+                    node[index].push_str(&xml_reference_text(&reference));
+                }
+            }
+            Event::End(element) => match element.local_name().as_ref() {
+                b"SEU_ADT_REPOSITORY_OBJ_NODE" => {
+                    if let Some([object_type, object_name, object_uri]) = node.take() {
+                        if !object_uri.trim().is_empty() {
+                            nodes.push(PackageNode {
+                                object_type: object_type.trim().to_string(),
+                                object_name: object_name.trim().to_string(),
+                                object_uri: object_uri.trim().to_string(),
+                            });
+                        }
+                    }
+                }
+                b"OBJECT_TYPE" | b"OBJECT_NAME" | b"OBJECT_URI" => field = None,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_package_nodes, sha256_hex, source_file_name};
+
+    #[test]
+    fn names_source_files_like_abapgit() {
+        assert_eq!(
+            source_file_name("CLAS/OC", "ZCL_DEMO").as_deref(),
+            Some("zcl_demo.clas.abap")
+        );
+        assert_eq!(
+            source_file_name("PROG/P", "/NEO/REPORT").as_deref(),
+            Some("#neo#report.prog.abap")
+        );
+        assert_eq!(
+            source_file_name("DDLS/DF", "ZI_DEMO").as_deref(),
+            Some("zi_demo.ddls.asddls")
+        );
+        assert_eq!(source_file_name("TABL/DT", "ZTAB"), None);
+    }
+
+    #[test]
+    fn parses_package_node_structure() {
+        let xml = r#"<asx:abap xmlns:asx="http://www.sap.com/abapxml"><asx:values><DATA><TREE_CONTENT>
+            <SEU_ADT_REPOSITORY_OBJ_NODE><OBJECT_TYPE>CLAS/OC</OBJECT_TYPE><OBJECT_NAME>ZCL_DEMO</OBJECT_NAME>
+              <OBJECT_URI>/sap/bc/adt/oo/classes/zcl_demo</OBJECT_URI></SEU_ADT_REPOSITORY_OBJ_NODE>
+            <SEU_ADT_REPOSITORY_OBJ_NODE><OBJECT_TYPE>DEVC/K</OBJECT_TYPE><OBJECT_NAME>ZDEMO_SUB</OBJECT_NAME>
+              <OBJECT_URI>/sap/bc/adt/packages/zdemo_sub</OBJECT_URI></SEU_ADT_REPOSITORY_OBJ_NODE>
+            <SEU_ADT_REPOSITORY_OBJ_NODE><OBJECT_TYPE>PROG/P</OBJECT_TYPE><OBJECT_NAME>ZTEST</OBJECT_NAME>
+              <OBJECT_URI/></SEU_ADT_REPOSITORY_OBJ_NODE>
+          </TREE_CONTENT></DATA></asx:values></asx:abap>"#;
+        let nodes = parse_package_nodes(xml).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].object_uri, "/sap/bc/adt/oo/classes/zcl_demo");
+        assert!(!nodes[0].is_package());
+        assert!(nodes[1].is_package());
+    }
+
+    #[test]
+    fn hashes_sources() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
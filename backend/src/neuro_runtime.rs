@@ -1,3 +1,4 @@
+use futures_util::{stream, StreamExt};
 use neuro_adt_core::AdtClientError;
 use neuro_adt_ws::NeuroWsClientError;
 use neuro_engine::NeuroEngineError;
@@ -7,16 +8,116 @@ use neuro_types::{
     NeuroRuntimeErrorCode, RuntimeDiagnoseComponent, RuntimeDiagnoseResponse, SafetyPolicy,
     WsClientConfig, WsDomainRequest, WsMessageEnvelope,
 };
+use serde::Serialize;
 use serde_json::{json, Value};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::env;
 use std::future::Future;
-use std::sync::Arc;
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 #[cfg(test)]
-use std::sync::{Mutex, OnceLock};
+use std::sync::OnceLock;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::{OwnedRwLockReadGuard, RwLock};
+use tokio_tungstenite::tungstenite::Message;
 
+use crate::command_runtime::{
+    active_workspace_cwd, normalize_workspace_relative_path, read_workspace_file,
+    write_workspace_file,
+};
+use crate::events_runtime::{emit_neuro_health, emit_neuro_lifecycle, emit_neuro_ws};
+use crate::neuro_adt_rest::{AdtRequest, AdtRestClient, AdtRestConfig, AdtRestError};
+use crate::neuro_agent_runtime::neuro_tool_writes;
+use crate::neuro_atc_runtime::{
+    atc_object_set, atc_run_request_xml, atc_timeout_from_env, parse_atc_worklist,
+    parse_worklist_id, resolve_check_variant, resolve_max_findings, NeuroAtcRunRequest,
+    NeuroAtcRunResponse, ATC_POLL_INTERVAL, ATC_RUNS_PATH, ATC_WORKLISTS_PATH, ATC_WORKLIST_ACCEPT,
+};
+use crate::neuro_audit_runtime::{append_audit_entry, audit_log_path, NeuroAuditEntry};
+use crate::neuro_cache_runtime::{
+    search_cache_key, source_fetch_concurrency_from_env, CacheLookup, NeuroResponseCache,
+    NeuroSourceEntry, MAX_SOURCE_BATCH_SIZE,
+};
+use crate::neuro_check_runtime::{
+    activation_request_xml, group_activation_result, has_errors, parse_activation_result,
+    parse_check_run_reports, syntax_check_request_xml, NeuroActivateRequest, NeuroActivateResponse,
+    NeuroSyntaxCheckRequest, NeuroSyntaxCheckResponse, ACTIVATION_PATH, SYNTAX_CHECK_ACCEPT,
+    SYNTAX_CHECK_CONTENT_TYPE, SYNTAX_CHECK_PATH,
+};
+use crate::neuro_connection_runtime::{
+    connections_file_path, create_connection_profile, delete_connection_profile,
+    find_connection_profile, load_connection_profiles, resolve_connection_id,
+    update_connection_profile, NeuroConnectionProfile, ENV_CONNECTION_ID,
+};
+use crate::neuro_data_preview_runtime::{
+    data_preview_max_rows_from_env, data_preview_target, find_blocked_entity, find_blocked_pattern,
+    parse_data_preview, preview_entities, preview_row_limit, uses_path_expression,
+    DataPreviewTarget, NeuroDataPreviewRequest, NeuroDataPreviewResponse, DATA_PREVIEW_ACCEPT,
+    DATA_PREVIEW_DDIC_PATH, DATA_PREVIEW_FREESTYLE_PATH,
+};
+use crate::neuro_ddic_runtime::{
+    ddic_object_uri, parse_cds_source, parse_data_element, parse_ddic_table_source, parse_domain,
+    NeuroCdsViewResponse, NeuroDataElementResponse, NeuroDdicTableKind, NeuroDdicTableResponse,
+    NeuroDomainResponse, CDS_SOURCES_PATH, DATA_ELEMENTS_PATH, DATA_ELEMENT_ACCEPT,
+    DDIC_STRUCTURES_PATH, DDIC_TABLES_PATH, DOMAINS_PATH, DOMAIN_ACCEPT,
+};
+use crate::neuro_diff_runtime::{unified_diff, DEFAULT_DIFF_CONTEXT};
+use crate::neuro_health_runtime::{
+    health_interval_from_env, NeuroHealthHistory, NeuroHealthRegistry, NeuroHealthSample,
+};
+use crate::neuro_lock_runtime::{
+    lock_refresh_interval, lock_ttl_from_env, normalize_object_uri, NeuroObjectLock,
+    NeuroObjectUnlockResponse, ObjectLockRegistry,
+};
+use crate::neuro_navigation_runtime::{
+    navigation_source_uri, object_structure_path, parse_navigation_target, parse_object_structure,
+    parse_where_used_result, where_used_request_xml, NeuroNavigateRequest, NeuroNavigationResponse,
+    NeuroObjectStructureResponse, NeuroWhereUsedResponse, NAVIGATION_PATH, OBJECT_STRUCTURE_ACCEPT,
+    WHERE_USED_ACCEPT, WHERE_USED_CONTENT_TYPE, WHERE_USED_PATH,
+};
+use crate::neuro_package_runtime::{
+    manifest_relative_path, normalize_package_name, parse_package_nodes, sha256_hex,
+    source_file_name, NeuroPackageManifest, NeuroPackageManifestEntry, NeuroPackageSkippedObject,
+    NeuroPackageSyncFailure, NeuroPushChangesRequest, NeuroPushChangesResponse, NeuroPushFailure,
+    NeuroPushedObject, NeuroSyncPackageRequest, NeuroSyncPackageResponse, PackageNode,
+    PACKAGE_NODES_PATH,
+};
+use crate::neuro_secret_runtime::{
+    clear_secret, create_secret_store, lock_secret_store, read_connection_secrets, secret_status,
+    set_secret, unlock_secret_store, ConnectionSecrets, NeuroSecretClearRequest,
+    NeuroSecretSession, NeuroSecretSetRequest, NeuroSecretStatus,
+};
+use crate::neuro_telemetry_runtime::{
+    record_telemetry, telemetry_summary, NeuroTelemetrySummary, NEURO_CACHE_TELEMETRY_EVENT,
+};
+use crate::neuro_transport_runtime::{
+    create_transport_request_xml, normalize_transport_number, parse_created_transport,
+    parse_transport_check, parse_transport_requests, transport_check_request_xml,
+    NeuroTransportAssignRequest, NeuroTransportAssignment, NeuroTransportCreateRequest,
+    NeuroTransportRequest, NeuroUpdateSourceRequest, TransportAssignments, TransportCheck,
+    CREATE_TRANSPORT_CONTENT_TYPE, CREATE_TRANSPORT_PATH, TRANSPORT_CHECK_CONTENT_TYPE,
+    TRANSPORT_CHECK_PATH, TRANSPORT_REQUESTS_ACCEPT, TRANSPORT_REQUESTS_PATH,
+};
+use crate::neuro_unit_runtime::{
+    coverage_query_xml, parse_coverage_result, parse_unit_test_result, unit_test_object_uris,
+    unit_test_response, unit_test_run_xml, NeuroUnitTestRequest, NeuroUnitTestResponse,
+    UNIT_TEST_RUNS_PATH, UNIT_TEST_RUN_CONTENT_TYPE,
+};
+use crate::neuro_version_runtime::{
+    parse_version_feed, version_content_path, versions_feed_path, NeuroServerVersion,
+    NeuroSourceDiffResponse, NeuroVersionListResponse, NeuroVersionSourceResponse,
+    VERSION_FEED_ACCEPT,
+};
+use crate::neuro_ws_subscription_runtime::{
+    close_sink, connect_subscriber, next_reconnect_delay, normalize_subscription_filter,
+    send_frame, subscription_frame, NeuroWsEvent, NeuroWsSubscribeRequest, NeuroWsSubscription,
+    NeuroWsSubscriptionRegistry, NeuroWsUnsubscribeResponse, WsSinkSlot,
+    WS_RECONNECT_INITIAL_DELAY,
+};
+use crate::session_turn_runtime::request_neuro_write_approval;
 use crate::AppState;
 
 const DEFAULT_ADT_TIMEOUT_SECS: u64 = 30;
@@ -26,6 +127,9 @@ const DEFAULT_ADT_SEARCH_PATH: &str =
     "/sap/bc/adt/repository/informationsystem/search?operation=quickSearch";
 const NEURO_COMMAND_TELEMETRY_EVENT: &str = "neuro.command";
 const NEURO_RUNTIME_INIT_TELEMETRY_EVENT: &str = "neuro.runtime_init";
+const NEURO_LOCK_REFRESH_TELEMETRY_EVENT: &str = "neuro.lock_refresh";
+const NEURO_WS_SUBSCRIPTION_TELEMETRY_EVENT: &str = "neuro.ws_subscription";
+const NEURO_RUNTIME_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone)]
 struct EnvValue {
@@ -68,10 +172,18 @@ struct SafetyConfig {
     read_only_source: Option<&'static str>,
     blocked_source_patterns: Vec<String>,
     blocked_source_patterns_source: Option<&'static str>,
+    blocked_entity_patterns: Vec<String>,
+    blocked_entity_patterns_source: Option<&'static str>,
     allowed_ws_domains: Vec<String>,
     allowed_ws_domains_source: Option<&'static str>,
     require_etag_for_updates: bool,
     require_etag_for_updates_source: Option<&'static str>,
+    syntax_check_before_update: bool,
+    syntax_check_before_update_source: Option<&'static str>,
+    require_transport_for_updates: bool,
+    require_transport_for_updates_source: Option<&'static str>,
+    require_confirmation_for_updates: bool,
+    require_confirmation_for_updates_source: Option<&'static str>,
 }
 
 #[derive(Debug, Clone)]
@@ -82,13 +194,59 @@ struct RuntimeConfig {
 }
 
 pub struct NeuroRuntime {
+    connection_id: String,
     config: RuntimeConfig,
     engine: Arc<neuro_engine::NeuroEngine>,
+    adt: Arc<AdtRestClient>,
+    /// The policy the engine enforces; checked again for writes that bypass the engine.
+    safety: SafetyPolicy,
+    locks: ObjectLockRegistry,
+    transports: TransportAssignments,
+    cache: Mutex<NeuroResponseCache>,
+    in_flight: Arc<RwLock<()>>,
+    retired: AtomicBool,
+}
+
+/// Keeps a runtime from being drained while a command is using it.
+struct NeuroRuntimeLease {
+    runtime: Arc<NeuroRuntime>,
+    _in_flight: OwnedRwLockReadGuard<()>,
+}
+
+impl Deref for NeuroRuntimeLease {
+    type Target = NeuroRuntime;
+
+    fn deref(&self) -> &Self::Target {
+        self.runtime.as_ref()
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroRuntimeLifecycleResponse {
+    pub connection_id: String,
+    pub status: &'static str,
+    pub had_runtime: bool,
+    pub drained: bool,
 }
 
 impl NeuroRuntime {
-    async fn initialize() -> Result<Self, NeuroRuntimeError> {
-        let config = resolve_runtime_config()?;
+    async fn initialize(
+        state: &AppState,
+        connection_id: String,
+    ) -> Result<Self, NeuroRuntimeError> {
+        let mut config = resolve_connection_runtime_config(&connection_id)?;
+        let secrets = {
+            let connection_id = connection_id.clone();
+            with_secret_session(state, move |session| {
+                read_connection_secrets(session, &connection_id)
+            })
+            .await
+            .map_err(|message| {
+                runtime_error(NeuroRuntimeErrorCode::RuntimeInitError, message, None)
+            })?
+        };
+        apply_connection_secrets(&mut config, secrets);
         let base_url = config
             .sap
             .url
@@ -110,43 +268,52 @@ impl NeuroRuntime {
             _ => AdtAuth::Anonymous,
         };
 
-        let ws = config.ws.url.as_ref().map(|url| WsClientConfig {
-            url: url.value.clone(),
-            request_timeout_secs: config.ws.timeout_secs,
-            connect_headers: config.ws.headers.clone(),
-        });
+        let adt_config = AdtHttpConfig {
+            base_url,
+            auth,
+            timeout_secs: config.sap.timeout_secs,
+            csrf_fetch_path: config.sap.csrf_fetch_path.value.clone(),
+            endpoints: AdtHttpEndpoints {
+                search_objects_path: config.sap.search_objects_path.value.clone(),
+            },
+            insecure_tls: config.sap.insecure_tls,
+            sap_client: config.sap.client.as_ref().map(|value| value.value.clone()),
+            sap_language: config
+                .sap
+                .language
+                .as_ref()
+                .map(|value| value.value.clone()),
+        };
+        // Lock-bound writes and endpoints the engine does not cover share its connection
+        // settings and safety policy.
+        let adt = Arc::new(
+            AdtRestClient::new(AdtRestConfig::from_engine(&adt_config))
+                .map_err(map_adt_rest_error)?,
+        );
+        let safety = safety_policy(&config.safety);
 
         let engine = Arc::new(
             neuro_engine::NeuroEngine::new(NeuroEngineConfig {
-                adt: AdtHttpConfig {
-                    base_url,
-                    auth,
-                    timeout_secs: config.sap.timeout_secs,
-                    csrf_fetch_path: config.sap.csrf_fetch_path.value.clone(),
-                    endpoints: AdtHttpEndpoints {
-                        search_objects_path: config.sap.search_objects_path.value.clone(),
-                    },
-                    insecure_tls: config.sap.insecure_tls,
-                    sap_client: config.sap.client.as_ref().map(|value| value.value.clone()),
-                    sap_language: config
-                        .sap
-                        .language
-                        .as_ref()
-                        .map(|value| value.value.clone()),
-                },
-                ws,
-                safety: SafetyPolicy {
-                    read_only: config.safety.read_only,
-                    blocked_source_patterns: config.safety.blocked_source_patterns.clone(),
-                    allowed_ws_domains: config.safety.allowed_ws_domains.clone(),
-                    require_etag_for_updates: config.safety.require_etag_for_updates,
-                },
+                adt: adt_config,
+                ws: ws_client_config(&config),
+                safety: safety_policy(&config.safety),
             })
             .await
             .map_err(map_engine_error)?,
         );
 
-        Ok(Self { config, engine })
+        Ok(Self {
+            connection_id,
+            config,
+            engine,
+            adt,
+            safety,
+            locks: ObjectLockRegistry::new(lock_ttl_from_env()),
+            transports: TransportAssignments::default(),
+            cache: Mutex::new(NeuroResponseCache::from_env()),
+            in_flight: Arc::new(RwLock::new(())),
+            retired: AtomicBool::new(false),
+        })
     }
 }
 
@@ -173,11 +340,15 @@ fn drain_test_telemetry_events() -> Vec<String> {
     )
 }
 
-fn emit_neuro_telemetry(payload: Value) {
+fn emit_neuro_telemetry(mut payload: Value) {
+    if let Some(fields) = payload.as_object_mut() {
+        fields.insert("timestampMs".to_string(), json!(now_epoch_millis()));
+    }
     let line = payload.to_string();
     #[cfg(test)]
     push_test_telemetry_event(line.clone());
     eprintln!("[neuro-telemetry] {line}");
+    record_telemetry(&payload, &line);
 }
 
 fn latency_millis(duration: Duration) -> u64 {
@@ -394,6 +565,12 @@ fn resolve_runtime_config() -> Result<RuntimeConfig, NeuroRuntimeError> {
         parse_env_bool_with_default(&["NEURO_SAFETY_READ_ONLY"], false)?;
     let (require_etag_for_updates, require_etag_for_updates_source) =
         parse_env_bool_with_default(&["NEURO_UPDATE_REQUIRE_ETAG"], false)?;
+    let (syntax_check_before_update, syntax_check_before_update_source) =
+        parse_env_bool_with_default(&["NEURO_UPDATE_SYNTAX_CHECK"], false)?;
+    let (require_transport_for_updates, require_transport_for_updates_source) =
+        parse_env_bool_with_default(&["NEURO_UPDATE_REQUIRE_TRANSPORT"], false)?;
+    let (require_confirmation_for_updates, require_confirmation_for_updates_source) =
+        parse_env_bool_with_default(&["NEURO_UPDATE_REQUIRE_CONFIRMATION"], false)?;
 
     let (blocked_source_patterns, blocked_source_patterns_source) =
         match parse_csv_env(&["NEURO_SAFETY_BLOCKED_PATTERNS"]) {
@@ -401,6 +578,12 @@ fn resolve_runtime_config() -> Result<RuntimeConfig, NeuroRuntimeError> {
             None => (Vec::new(), None),
         };
 
+    let (blocked_entity_patterns, blocked_entity_patterns_source) =
+        match parse_csv_env(&["NEURO_SAFETY_BLOCKED_ENTITIES"]) {
+            Some((patterns, source)) => (patterns, Some(source)),
+            None => (Vec::new(), None),
+        };
+
     let (allowed_ws_domains, allowed_ws_domains_source) =
         match parse_csv_env(&["NEURO_SAFETY_ALLOWED_WS_DOMAINS"]) {
             Some((domains, source)) => (domains, Some(source)),
@@ -412,15 +595,162 @@ fn resolve_runtime_config() -> Result<RuntimeConfig, NeuroRuntimeError> {
         read_only_source,
         blocked_source_patterns,
         blocked_source_patterns_source,
+        blocked_entity_patterns,
+        blocked_entity_patterns_source,
         allowed_ws_domains,
         allowed_ws_domains_source,
         require_etag_for_updates,
         require_etag_for_updates_source,
+        syntax_check_before_update,
+        syntax_check_before_update_source,
+        require_transport_for_updates,
+        require_transport_for_updates_source,
+        require_confirmation_for_updates,
+        require_confirmation_for_updates_source,
     };
 
     Ok(RuntimeConfig { sap, ws, safety })
 }
 
+fn profile_value(value: &Option<String>) -> Option<EnvValue> {
+    value.as_ref().map(|value| EnvValue {
+        value: value.clone(),
+        source: "profile",
+    })
+}
+
+fn profile_value_or_default(value: &Option<String>, default: &'static str) -> EnvValue {
+    profile_value(value).unwrap_or(EnvValue {
+        value: default.to_string(),
+        source: "default",
+    })
+}
+
+fn profile_source<T>(value: &Option<T>) -> Option<&'static str> {
+    value.as_ref().map(|_| "profile")
+}
+
+fn runtime_config_from_profile(profile: &NeuroConnectionProfile) -> RuntimeConfig {
+    let password = profile
+        .password_env
+        .as_deref()
+        .and_then(|key| env::var(key).ok())
+        .map(|raw| raw.trim().to_string())
+        .filter(|raw| !raw.is_empty())
+        .map(|value| EnvValue {
+            value,
+            source: "profile.passwordEnv",
+        });
+
+    RuntimeConfig {
+        sap: SapConfig {
+            url: Some(EnvValue {
+                value: profile.sap_url.clone(),
+                source: "profile",
+            }),
+            user: profile_value(&profile.user),
+            password,
+            client: profile_value(&profile.client),
+            language: profile_value(&profile.language),
+            insecure_tls: profile.insecure_tls,
+            insecure_tls_source: Some("profile"),
+            timeout_secs: profile.timeout_secs.unwrap_or(DEFAULT_ADT_TIMEOUT_SECS),
+            timeout_source: profile_source(&profile.timeout_secs),
+            csrf_fetch_path: profile_value_or_default(
+                &profile.csrf_fetch_path,
+                DEFAULT_ADT_CSRF_FETCH_PATH,
+            ),
+            search_objects_path: profile_value_or_default(
+                &profile.search_objects_path,
+                DEFAULT_ADT_SEARCH_PATH,
+            ),
+        },
+        ws: WsConfig {
+            url: profile_value(&profile.ws_url),
+            timeout_secs: profile.ws_timeout_secs.unwrap_or(DEFAULT_WS_TIMEOUT_SECS),
+            timeout_source: profile_source(&profile.ws_timeout_secs),
+            headers: BTreeMap::new(),
+        },
+        safety: SafetyConfig {
+            read_only: profile.safety.read_only,
+            read_only_source: Some("profile"),
+            blocked_source_patterns: profile.safety.blocked_source_patterns.clone(),
+            blocked_source_patterns_source: Some("profile"),
+            blocked_entity_patterns: profile.safety.blocked_entity_patterns.clone(),
+            blocked_entity_patterns_source: Some("profile"),
+            allowed_ws_domains: profile.safety.allowed_ws_domains.clone(),
+            allowed_ws_domains_source: Some("profile"),
+            require_etag_for_updates: profile.safety.require_etag_for_updates,
+            require_etag_for_updates_source: Some("profile"),
+            syntax_check_before_update: profile.safety.syntax_check_before_update,
+            syntax_check_before_update_source: Some("profile"),
+            require_transport_for_updates: profile.safety.require_transport_for_updates,
+            require_transport_for_updates_source: Some("profile"),
+            require_confirmation_for_updates: profile.safety.require_confirmation_for_updates,
+            require_confirmation_for_updates_source: Some("profile"),
+        },
+    }
+}
+
+fn apply_connection_secrets(config: &mut RuntimeConfig, secrets: ConnectionSecrets) {
+    if config.sap.password.is_none() {
+        config.sap.password = secrets.sap_password.map(|value| EnvValue {
+            value,
+            source: "secret_store",
+        });
+    }
+    config.ws.headers.extend(secrets.ws_headers);
+}
+
+/// Runs a secret store operation on the blocking pool: key derivation and keyring access
+/// can take seconds and must not stall the async executor.
+async fn with_secret_session<T: Send + 'static>(
+    state: &AppState,
+    operation: impl FnOnce(&mut NeuroSecretSession) -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    let mut session = state.neuro_secrets.lock().await;
+    let mut working = session.clone();
+    let (working, result) = tokio::task::spawn_blocking(move || {
+        let result = operation(&mut working);
+        (working, result)
+    })
+    .await
+    .map_err(|error| format!("secret store task failed: {error}"))?;
+    *session = working;
+    result
+}
+
+fn secret_store_error(message: String) -> NeuroRuntimeError {
+    runtime_error(NeuroRuntimeErrorCode::InvalidArgument, message, None)
+}
+
+fn connection_store_error(message: String) -> NeuroRuntimeError {
+    runtime_error(
+        NeuroRuntimeErrorCode::InvalidArgument,
+        message,
+        Some(json!({ "path": connections_file_path().display().to_string() })),
+    )
+}
+
+fn resolve_connection_runtime_config(
+    connection_id: &str,
+) -> Result<RuntimeConfig, NeuroRuntimeError> {
+    if connection_id == ENV_CONNECTION_ID {
+        return resolve_runtime_config();
+    }
+
+    let profile = find_connection_profile(&connections_file_path(), connection_id)
+        .map_err(connection_store_error)?
+        .ok_or_else(|| {
+            runtime_error(
+                NeuroRuntimeErrorCode::InvalidArgument,
+                format!("unknown neuro connection `{connection_id}`"),
+                Some(json!({ "connectionId": connection_id })),
+            )
+        })?;
+    Ok(runtime_config_from_profile(&profile))
+}
+
 fn now_epoch_secs() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -429,138 +759,164 @@ fn now_epoch_secs() -> u64 {
         .unwrap_or(0)
 }
 
+fn now_epoch_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(latency_millis)
+        .unwrap_or(0)
+}
+
 fn metadata_for(runtime: &NeuroRuntime, cached_runtime: bool) -> BTreeMap<String, Value> {
-    let mut metadata = BTreeMap::new();
+    let mut metadata = config_metadata(&runtime.config);
+    metadata.insert("connectionId".to_string(), json!(runtime.connection_id));
     metadata.insert("cachedRuntime".to_string(), json!(cached_runtime));
-    metadata.insert("sapReady".to_string(), json!(runtime.config.sap.is_ready()));
-    metadata.insert(
-        "sapUrlSource".to_string(),
-        json!(source(&runtime.config.sap.url)),
-    );
-    metadata.insert(
-        "sapUserSource".to_string(),
-        json!(source(&runtime.config.sap.user)),
-    );
+    metadata
+}
+
+fn config_metadata(config: &RuntimeConfig) -> BTreeMap<String, Value> {
+    let mut metadata = BTreeMap::new();
+    metadata.insert("sapReady".to_string(), json!(config.sap.is_ready()));
+    metadata.insert("sapUrlSource".to_string(), json!(source(&config.sap.url)));
+    metadata.insert("sapUserSource".to_string(), json!(source(&config.sap.user)));
     metadata.insert(
         "sapPasswordSource".to_string(),
-        json!(source(&runtime.config.sap.password)),
-    );
-    metadata.insert(
-        "sapClient".to_string(),
-        json!(value(&runtime.config.sap.client)),
+        json!(source(&config.sap.password)),
     );
+    metadata.insert("sapClient".to_string(), json!(value(&config.sap.client)));
     metadata.insert(
         "sapClientSource".to_string(),
-        json!(source(&runtime.config.sap.client)),
+        json!(source(&config.sap.client)),
     );
     metadata.insert(
         "sapLanguage".to_string(),
-        json!(value(&runtime.config.sap.language)),
+        json!(value(&config.sap.language)),
     );
     metadata.insert(
         "sapLanguageSource".to_string(),
-        json!(source(&runtime.config.sap.language)),
-    );
-    metadata.insert(
-        "sapInsecureTls".to_string(),
-        json!(runtime.config.sap.insecure_tls),
+        json!(source(&config.sap.language)),
     );
+    metadata.insert("sapInsecureTls".to_string(), json!(config.sap.insecure_tls));
     metadata.insert(
         "sapInsecureTlsSource".to_string(),
-        json!(runtime.config.sap.insecure_tls_source.map(str::to_string)),
-    );
-    metadata.insert(
-        "sapTimeoutSecs".to_string(),
-        json!(runtime.config.sap.timeout_secs),
+        json!(config.sap.insecure_tls_source.map(str::to_string)),
     );
+    metadata.insert("sapTimeoutSecs".to_string(), json!(config.sap.timeout_secs));
     metadata.insert(
         "sapTimeoutSource".to_string(),
-        json!(runtime.config.sap.timeout_source.map(str::to_string)),
+        json!(config.sap.timeout_source.map(str::to_string)),
     );
     metadata.insert(
         "adtCsrfFetchPath".to_string(),
-        json!(runtime.config.sap.csrf_fetch_path.value.clone()),
+        json!(config.sap.csrf_fetch_path.value.clone()),
     );
     metadata.insert(
         "adtCsrfFetchPathSource".to_string(),
-        json!(runtime.config.sap.csrf_fetch_path.source),
+        json!(config.sap.csrf_fetch_path.source),
     );
     metadata.insert(
         "adtSearchPath".to_string(),
-        json!(runtime.config.sap.search_objects_path.value.clone()),
+        json!(config.sap.search_objects_path.value.clone()),
     );
     metadata.insert(
         "adtSearchPathSource".to_string(),
-        json!(runtime.config.sap.search_objects_path.source),
-    );
-    metadata.insert(
-        "wsConfigured".to_string(),
-        json!(runtime.config.ws.url.is_some()),
-    );
-    metadata.insert(
-        "wsUrlSource".to_string(),
-        json!(source(&runtime.config.ws.url)),
-    );
-    metadata.insert(
-        "wsTimeoutSecs".to_string(),
-        json!(runtime.config.ws.timeout_secs),
+        json!(config.sap.search_objects_path.source),
     );
+    metadata.insert("wsConfigured".to_string(), json!(config.ws.url.is_some()));
+    metadata.insert("wsUrlSource".to_string(), json!(source(&config.ws.url)));
+    metadata.insert("wsTimeoutSecs".to_string(), json!(config.ws.timeout_secs));
     metadata.insert(
         "wsTimeoutSource".to_string(),
-        json!(runtime.config.ws.timeout_source.map(str::to_string)),
-    );
-    metadata.insert(
-        "wsHeadersCount".to_string(),
-        json!(runtime.config.ws.headers.len()),
+        json!(config.ws.timeout_source.map(str::to_string)),
     );
+    metadata.insert("wsHeadersCount".to_string(), json!(config.ws.headers.len()));
     metadata.insert(
-        "safetyReadOnly".to_string(),
-        json!(runtime.config.safety.read_only),
+        "wsHeaderNames".to_string(),
+        json!(config.ws.headers.keys().collect::<Vec<_>>()),
     );
+    metadata.insert("safetyReadOnly".to_string(), json!(config.safety.read_only));
     metadata.insert(
         "safetyReadOnlySource".to_string(),
-        json!(runtime.config.safety.read_only_source.map(str::to_string)),
+        json!(config.safety.read_only_source.map(str::to_string)),
     );
     metadata.insert(
         "safetyBlockedPatterns".to_string(),
-        json!(runtime.config.safety.blocked_source_patterns),
+        json!(config.safety.blocked_source_patterns),
     );
     metadata.insert(
         "safetyBlockedPatternsSource".to_string(),
-        json!(runtime
-            .config
+        json!(config
             .safety
             .blocked_source_patterns_source
             .map(str::to_string)),
     );
     metadata.insert(
-        "safetyAllowedWsDomains".to_string(),
-        json!(runtime.config.safety.allowed_ws_domains),
+        "safetyBlockedEntities".to_string(),
+        json!(config.safety.blocked_entity_patterns),
     );
     metadata.insert(
-        "safetyAllowedWsDomainsSource".to_string(),
-        json!(runtime
-            .config
+        "safetyBlockedEntitiesSource".to_string(),
+        json!(config
             .safety
-            .allowed_ws_domains_source
+            .blocked_entity_patterns_source
             .map(str::to_string)),
     );
+    metadata.insert(
+        "safetyAllowedWsDomains".to_string(),
+        json!(config.safety.allowed_ws_domains),
+    );
+    metadata.insert(
+        "safetyAllowedWsDomainsSource".to_string(),
+        json!(config.safety.allowed_ws_domains_source.map(str::to_string)),
+    );
     metadata.insert(
         "safetyRequireEtagForUpdates".to_string(),
-        json!(runtime.config.safety.require_etag_for_updates),
+        json!(config.safety.require_etag_for_updates),
     );
     metadata.insert(
         "safetyRequireEtagForUpdatesSource".to_string(),
-        json!(runtime
-            .config
+        json!(config
             .safety
             .require_etag_for_updates_source
             .map(str::to_string)),
     );
+    metadata.insert(
+        "safetySyntaxCheckBeforeUpdate".to_string(),
+        json!(config.safety.syntax_check_before_update),
+    );
+    metadata.insert(
+        "safetySyntaxCheckBeforeUpdateSource".to_string(),
+        json!(config
+            .safety
+            .syntax_check_before_update_source
+            .map(str::to_string)),
+    );
+    metadata.insert(
+        "safetyRequireTransportForUpdates".to_string(),
+        json!(config.safety.require_transport_for_updates),
+    );
+    metadata.insert(
+        "safetyRequireTransportForUpdatesSource".to_string(),
+        json!(config
+            .safety
+            .require_transport_for_updates_source
+            .map(str::to_string)),
+    );
+    metadata.insert(
+        "safetyRequireConfirmationForUpdates".to_string(),
+        json!(config.safety.require_confirmation_for_updates),
+    );
+    metadata.insert(
+        "safetyRequireConfirmationForUpdatesSource".to_string(),
+        json!(config
+            .safety
+            .require_confirmation_for_updates_source
+            .map(str::to_string)),
+    );
     metadata.insert(
         "sapAuthMode".to_string(),
         json!(
-            if runtime.config.sap.user.is_some() && runtime.config.sap.password.is_some() {
+            if config.sap.user.is_some() && config.sap.password.is_some() {
                 "basic"
             } else {
                 "anonymous"
@@ -633,8 +989,9 @@ async fn success_response(
     response
 }
 
-fn init_error_response(error: NeuroRuntimeError) -> RuntimeDiagnoseResponse {
+fn init_error_response(connection_id: &str, error: NeuroRuntimeError) -> RuntimeDiagnoseResponse {
     let mut metadata = BTreeMap::new();
+    metadata.insert("connectionId".to_string(), json!(connection_id));
     metadata.insert("cachedRuntime".to_string(), json!(false));
     metadata.insert("errorCode".to_string(), json!(error.code));
     metadata.insert("errorMessage".to_string(), json!(error.message.clone()));
@@ -695,6 +1052,36 @@ fn map_adt_error(error: AdtClientError) -> NeuroRuntimeError {
     }
 }
 
+fn map_adt_rest_error(error: AdtRestError) -> NeuroRuntimeError {
+    match error {
+        AdtRestError::MissingCsrfToken => runtime_error(
+            NeuroRuntimeErrorCode::AdtCsrfError,
+            "missing CSRF token from ADT response".to_string(),
+            None,
+        ),
+        AdtRestError::UnexpectedStatus {
+            operation,
+            status,
+            body,
+        } => {
+            let code = match status {
+                401 | 403 => NeuroRuntimeErrorCode::AdtAuthError,
+                _ => NeuroRuntimeErrorCode::AdtHttpError,
+            };
+            runtime_error(
+                code,
+                format!("ADT operation `{operation}` failed with status {status}"),
+                Some(json!({
+                    "operation": operation,
+                    "statusCode": status,
+                    "body": body,
+                })),
+            )
+        }
+        other => runtime_error(NeuroRuntimeErrorCode::AdtHttpError, other.to_string(), None),
+    }
+}
+
 fn map_ws_error(error: NeuroWsClientError) -> NeuroRuntimeError {
     match error {
         NeuroWsClientError::Timeout { timeout_secs } => runtime_error(
@@ -751,12 +1138,16 @@ fn map_mcp_error(error: NeuroMcpError) -> NeuroRuntimeError {
     }
 }
 
-pub async fn get_or_init(state: &AppState) -> Result<Arc<NeuroRuntime>, NeuroRuntimeError> {
+pub async fn get_or_init(
+    state: &AppState,
+    connection_id: Option<&str>,
+) -> Result<Arc<NeuroRuntime>, NeuroRuntimeError> {
     let started_at = Instant::now();
+    let connection_id = resolve_connection_id(connection_id);
 
     if let Some(runtime) = {
-        let cache = state.neuro_runtime.lock().await;
-        cache.clone()
+        let cache = state.neuro_runtimes.lock().await;
+        cache.get(&connection_id).cloned()
     } {
         emit_neuro_runtime_init_telemetry("cache_hit", started_at.elapsed(), None);
         return Ok(runtime);
@@ -765,14 +1156,14 @@ pub async fn get_or_init(state: &AppState) -> Result<Arc<NeuroRuntime>, NeuroRun
     let _init_gate = state.neuro_runtime_init_gate.lock().await;
 
     if let Some(runtime) = {
-        let cache = state.neuro_runtime.lock().await;
-        cache.clone()
+        let cache = state.neuro_runtimes.lock().await;
+        cache.get(&connection_id).cloned()
     } {
         emit_neuro_runtime_init_telemetry("cache_hit_after_gate", started_at.elapsed(), None);
         return Ok(runtime);
     }
 
-    let initialized = match NeuroRuntime::initialize().await {
+    let initialized = match NeuroRuntime::initialize(state, connection_id.clone()).await {
         Ok(runtime) => Arc::new(runtime),
         Err(error) => {
             emit_neuro_runtime_init_telemetry("failed", started_at.elapsed(), Some(&error));
@@ -780,129 +1171,2384 @@ pub async fn get_or_init(state: &AppState) -> Result<Arc<NeuroRuntime>, NeuroRun
         }
     };
 
-    let mut cache = state.neuro_runtime.lock().await;
-    *cache = Some(Arc::clone(&initialized));
+    let mut cache = state.neuro_runtimes.lock().await;
+    cache.insert(connection_id, Arc::clone(&initialized));
     emit_neuro_runtime_init_telemetry("initialized", started_at.elapsed(), None);
     Ok(initialized)
 }
 
-pub async fn neuro_runtime_diagnose_impl(
-    state: State<'_, AppState>,
-) -> Result<RuntimeDiagnoseResponse, NeuroRuntimeError> {
-    run_with_neuro_command_telemetry("neuro_runtime_diagnose", async {
-        Ok(neuro_runtime_diagnose_for_app_state(state.inner()).await)
-    })
-    .await
+async fn acquire_runtime(
+    state: &AppState,
+    connection_id: Option<&str>,
+) -> Result<NeuroRuntimeLease, NeuroRuntimeError> {
+    loop {
+        let runtime = get_or_init(state, connection_id).await?;
+        let in_flight = Arc::clone(&runtime.in_flight).read_owned().await;
+        if !runtime.retired.load(Ordering::Acquire) {
+            return Ok(NeuroRuntimeLease {
+                runtime,
+                _in_flight: in_flight,
+            });
+        }
+    }
 }
 
-pub async fn neuro_runtime_diagnose_for_app_state(state: &AppState) -> RuntimeDiagnoseResponse {
-    if let Some(runtime) = {
-        let cache = state.neuro_runtime.lock().await;
-        cache.clone()
-    } {
-        return success_response(runtime, true).await;
-    }
+async fn drain_in_flight(in_flight: &Arc<RwLock<()>>, timeout: Duration) -> bool {
+    tokio::time::timeout(timeout, in_flight.write())
+        .await
+        .is_ok()
+}
 
-    match get_or_init(state).await {
-        Ok(runtime) => success_response(runtime, false).await,
-        Err(error) => init_error_response(error),
-    }
+/// Removes the cached runtime under the init gate so new calls initialize a fresh one.
+async fn take_cached_runtime(state: &AppState, connection_id: &str) -> Option<Arc<NeuroRuntime>> {
+    let _init_gate = state.neuro_runtime_init_gate.lock().await;
+    let runtime = state.neuro_runtimes.lock().await.remove(connection_id)?;
+    runtime.retired.store(true, Ordering::Release);
+    Some(runtime)
 }
 
-pub async fn neuro_search_objects_impl(
-    state: State<'_, AppState>,
-    query: String,
-    max_results: Option<u32>,
-) -> Result<Vec<neuro_types::AdtObjectSummary>, NeuroRuntimeError> {
-    run_with_neuro_command_telemetry("neuro_search_objects", async {
-        let runtime = get_or_init(state.inner()).await?;
-        runtime
-            .engine
-            .search(query.as_str(), max_results)
-            .await
-            .map_err(map_engine_error)
-    })
-    .await
+/// Waits for in-flight calls on a retired runtime and releases its object locks.
+/// Runs outside the init gate so other connections are not blocked by the drain.
+async fn drain_retired_runtime(runtime: Option<Arc<NeuroRuntime>>) -> (bool, bool) {
+    let Some(runtime) = runtime else {
+        return (false, true);
+    };
+    let drained = drain_in_flight(&runtime.in_flight, NEURO_RUNTIME_DRAIN_TIMEOUT).await;
+    runtime.locks.release_all(&runtime.adt).await;
+    (true, drained)
 }
 
-pub async fn neuro_get_source_impl(
-    state: State<'_, AppState>,
-    object_uri: String,
-) -> Result<neuro_types::AdtSourceResponse, NeuroRuntimeError> {
-    run_with_neuro_command_telemetry("neuro_get_source", async {
-        let runtime = get_or_init(state.inner()).await?;
-        runtime
-            .engine
-            .get_source(object_uri.as_str())
-            .await
-            .map_err(map_engine_error)
-    })
-    .await
+async fn retire_cached_runtime(state: &AppState, connection_id: &str) -> (bool, bool) {
+    drain_retired_runtime(take_cached_runtime(state, connection_id).await).await
 }
 
-pub async fn neuro_update_source_impl(
-    state: State<'_, AppState>,
-    request: neuro_types::AdtUpdateSourceRequest,
-) -> Result<neuro_types::AdtUpdateSourceResponse, NeuroRuntimeError> {
-    run_with_neuro_command_telemetry("neuro_update_source", async {
-        let runtime = get_or_init(state.inner()).await?;
-        runtime
-            .engine
-            .update_source(request)
-            .await
-            .map_err(map_engine_error)
-    })
-    .await
+/// Retires every cached runtime, e.g. after unlocking the secret store: runtimes built
+/// while it was locked carry no stored credentials.
+async fn retire_all_cached_runtimes(state: &AppState) {
+    let connection_ids = state
+        .neuro_runtimes
+        .lock()
+        .await
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    for connection_id in connection_ids {
+        retire_cached_runtime(state, &connection_id).await;
+    }
 }
 
-pub async fn neuro_ws_request_impl(
-    state: State<'_, AppState>,
-    request: WsDomainRequest,
-) -> Result<WsMessageEnvelope<Value>, NeuroRuntimeError> {
-    run_with_neuro_command_telemetry("neuro_ws_request", async {
-        let runtime = get_or_init(state.inner()).await?;
-        runtime
-            .engine
-            .send_domain_request(
-                request.domain.as_str(),
-                request.action.as_str(),
-                request.payload,
-            )
-            .await
-            .map_err(map_engine_error)
-    })
-    .await
+/// Releases every object lock held by cached runtimes; used when the app exits.
+pub async fn release_all_object_locks(state: &AppState) -> usize {
+    let runtimes = state
+        .neuro_runtimes
+        .lock()
+        .await
+        .values()
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut released = 0;
+    for runtime in runtimes {
+        released += runtime.locks.release_all(&runtime.adt).await;
+    }
+    released
 }
 
-pub async fn neuro_list_tools_impl(
-    state: State<'_, AppState>,
-) -> Result<Vec<NeuroToolSpec>, NeuroRuntimeError> {
-    run_with_neuro_command_telemetry("neuro_list_tools", async {
-        let runtime = get_or_init(state.inner()).await?;
-        Ok(NeuroMcpFacade::new(runtime.engine.clone()).list_tools())
+fn lock_health_registry(
+    state: &AppState,
+) -> Result<std::sync::MutexGuard<'_, NeuroHealthRegistry>, NeuroRuntimeError> {
+    state.neuro_health.lock().map_err(|_| {
+        runtime_error(
+            NeuroRuntimeErrorCode::Unknown,
+            "neuro health registry lock poisoned".to_string(),
+            None,
+        )
     })
-    .await
 }
 
-pub async fn neuro_invoke_tool_impl(
-    state: State<'_, AppState>,
-    tool_name: String,
-    arguments: Value,
-) -> Result<Value, NeuroRuntimeError> {
-    run_with_neuro_command_telemetry("neuro_invoke_tool", async {
-        let runtime = get_or_init(state.inner()).await?;
-        NeuroMcpFacade::new(runtime.engine.clone())
-            .invoke(tool_name.as_str(), arguments)
-            .await
-            .map_err(map_mcp_error)
-    })
-    .await
+/// The default connection and every connection with a cached engine.
+async fn monitored_connection_ids(state: &AppState) -> Vec<String> {
+    let mut connection_ids = vec![resolve_connection_id(None)];
+    for connection_id in state.neuro_runtimes.lock().await.keys() {
+        if !connection_ids.contains(connection_id) {
+            connection_ids.push(connection_id.clone());
+        }
+    }
+    connection_ids
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::time::Duration;
+pub(crate) fn spawn_neuro_health_monitor(app: AppHandle) {
+    let Some(interval) = health_interval_from_env() else {
+        eprintln!("[neuro-health] health monitor disabled by NEURO_HEALTH_INTERVAL_SECS");
+        return;
+    };
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            let state = app.state::<AppState>();
+            for connection_id in monitored_connection_ids(state.inner()).await {
+                let checked_at_epoch_secs = now_epoch_secs();
+                let due = lock_health_registry(state.inner())
+                    .map(|registry| registry.is_due(&connection_id, checked_at_epoch_secs))
+                    .unwrap_or(false);
+                if !due {
+                    continue;
+                }
+
+                let started_at = Instant::now();
+                let report =
+                    neuro_runtime_diagnose_for_app_state(state.inner(), Some(&connection_id)).await;
+                let sample = NeuroHealthSample {
+                    checked_at_epoch_secs,
+                    status: report.overall_status,
+                    latency_ms: latency_millis(started_at.elapsed()),
+                };
+                let detail = report
+                    .components
+                    .iter()
+                    .filter(|component| component.status != DiagnoseStatus::Healthy)
+                    .max_by_key(|component| component.status)
+                    .map(|component| format!("{}: {}", component.component, component.detail));
+                let event = lock_health_registry(state.inner())
+                    .ok()
+                    .and_then(|mut registry| {
+                        registry.record(&connection_id, sample, detail, interval)
+                    });
+                if let Some(event) = event {
+                    emit_neuro_health(&app, &event);
+                }
+            }
+        }
+    });
+}
+
+/// The subscriber socket connects with the same settings as the engine's WS client.
+async fn ws_subscriber_target(
+    state: &AppState,
+    connection_id: &str,
+) -> Result<WsClientConfig, NeuroRuntimeError> {
+    let runtime = acquire_runtime(state, Some(connection_id)).await?;
+    ws_client_config(&runtime.config)
+        .ok_or_else(|| map_engine_error(NeuroEngineError::WsUnavailable))
+}
+
+fn forward_ws_envelope(
+    app: &AppHandle,
+    registry: &NeuroWsSubscriptionRegistry,
+    connection_id: &str,
+    text: &str,
+) {
+    let Ok(envelope) = serde_json::from_str::<WsMessageEnvelope<Value>>(text) else {
+        return;
+    };
+    let Ok(envelope) = serde_json::to_value(&envelope) else {
+        return;
+    };
+    let subscription_ids = registry.matching(connection_id, &envelope);
+    if !subscription_ids.is_empty() {
+        emit_neuro_ws(
+            app,
+            &NeuroWsEvent {
+                connection_id,
+                subscription_ids,
+                envelope: &envelope,
+            },
+        );
+    }
+}
+
+/// Keeps one subscriber socket per connection and resubscribes after every reconnect.
+fn spawn_ws_subscription_worker(
+    app: AppHandle,
+    connection_id: String,
+    generation: u64,
+    sink: WsSinkSlot,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut delay = WS_RECONNECT_INITIAL_DELAY;
+        let mut reconnect = false;
+        loop {
+            let state = app.state::<AppState>();
+            let registry = &state.inner().neuro_ws_subscriptions;
+            if !registry.is_current(&connection_id, generation) {
+                break;
+            }
+
+            let connected = match ws_subscriber_target(state.inner(), &connection_id).await {
+                Ok(config) => connect_subscriber(&config).await,
+                Err(error) => Err(error.message),
+            };
+            match connected {
+                Ok(socket) => {
+                    delay = WS_RECONNECT_INITIAL_DELAY;
+                    let (writer, mut reader) = socket.split();
+                    *sink.lock().await = Some(writer);
+                    // Checked after publishing the writer so a concurrent last unsubscribe can close it.
+                    if !registry.is_current(&connection_id, generation) {
+                        close_sink(&sink).await;
+                        break;
+                    }
+                    let subscriptions = registry.subscriptions(&connection_id);
+                    for subscription in &subscriptions {
+                        let _ =
+                            send_frame(&sink, subscription_frame(subscription, "subscribe")).await;
+                    }
+                    emit_neuro_telemetry(json!({
+                        "event": NEURO_WS_SUBSCRIPTION_TELEMETRY_EVENT,
+                        "connectionId": connection_id.as_str(),
+                        "status": if reconnect { "resubscribed" } else { "connected" },
+                        "subscriptions": subscriptions.len(),
+                    }));
+                    while let Some(Ok(message)) = reader.next().await {
+                        if let Message::Text(text) = message {
+                            forward_ws_envelope(&app, registry, &connection_id, text.as_str());
+                        }
+                    }
+                    sink.lock().await.take();
+                }
+                Err(message) => emit_neuro_telemetry(json!({
+                    "event": NEURO_WS_SUBSCRIPTION_TELEMETRY_EVENT,
+                    "connectionId": connection_id.as_str(),
+                    "status": "disconnected",
+                    "errorMessage": message,
+                    "retryInMs": latency_millis(delay),
+                })),
+            }
+
+            if !registry.is_current(&connection_id, generation) {
+                break;
+            }
+            reconnect = true;
+            tokio::time::sleep(delay).await;
+            delay = next_reconnect_delay(delay);
+        }
+    });
+}
+
+fn spawn_lock_refresher(runtime: &Arc<NeuroRuntime>) {
+    if !runtime.locks.claim_refresher() {
+        return;
+    }
+
+    let weak = Arc::downgrade(runtime);
+    let interval = lock_refresh_interval(runtime.locks.ttl());
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            let Some(runtime) = weak.upgrade() else {
+                break;
+            };
+            if runtime.retired.load(Ordering::Acquire) {
+                break;
+            }
+            let Ok(_in_flight) = Arc::clone(&runtime.in_flight).try_read_owned() else {
+                continue;
+            };
+
+            for (object_uri, error) in runtime.locks.refresh_all(&runtime.adt).await {
+                let error = map_adt_rest_error(error);
+                emit_neuro_telemetry(json!({
+                    "event": NEURO_LOCK_REFRESH_TELEMETRY_EVENT,
+                    "connectionId": runtime.connection_id.as_str(),
+                    "objectUri": object_uri,
+                    "success": false,
+                    "errorCode": error_code_value(&error.code),
+                    "errorMessage": error.message,
+                }));
+            }
+        }
+    });
+}
+
+fn safety_policy(safety: &SafetyConfig) -> SafetyPolicy {
+    SafetyPolicy {
+        read_only: safety.read_only,
+        blocked_source_patterns: safety.blocked_source_patterns.clone(),
+        allowed_ws_domains: safety.allowed_ws_domains.clone(),
+        require_etag_for_updates: safety.require_etag_for_updates,
+    }
+}
+
+fn ws_client_config(config: &RuntimeConfig) -> Option<WsClientConfig> {
+    config.ws.url.as_ref().map(|url| WsClientConfig {
+        url: url.value.clone(),
+        request_timeout_secs: config.ws.timeout_secs,
+        connect_headers: config.ws.headers.clone(),
+    })
+}
+
+/// `disabled` names what is refused, e.g. "object locks are disabled".
+fn ensure_writable(safety: &SafetyPolicy, disabled: &str) -> Result<(), NeuroRuntimeError> {
+    if safety.read_only {
+        return Err(runtime_error(
+            NeuroRuntimeErrorCode::SafetyViolation,
+            format!("{disabled}: runtime is read-only"),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_ws_domain_allowed(safety: &SafetyPolicy, domain: &str) -> Result<(), NeuroRuntimeError> {
+    let allowed = &safety.allowed_ws_domains;
+    if !allowed.is_empty()
+        && !allowed
+            .iter()
+            .any(|value| value.eq_ignore_ascii_case(domain))
+    {
+        return Err(runtime_error(
+            NeuroRuntimeErrorCode::SafetyViolation,
+            format!("websocket domain `{domain}` is not allowed"),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+fn check_update_safety(
+    safety: &SafetyPolicy,
+    request: &neuro_types::AdtUpdateSourceRequest,
+) -> Result<(), NeuroRuntimeError> {
+    ensure_writable(safety, "source updates are disabled")?;
+    if safety.require_etag_for_updates
+        && request
+            .etag
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or_default()
+            .is_empty()
+    {
+        return Err(runtime_error(
+            NeuroRuntimeErrorCode::SafetyViolation,
+            "source updates require an ETag".to_string(),
+            None,
+        ));
+    }
+    if let Some(pattern) = find_blocked_pattern(&safety.blocked_source_patterns, &request.source) {
+        return Err(runtime_error(
+            NeuroRuntimeErrorCode::SafetyViolation,
+            format!("source contains blocked pattern `{pattern}`"),
+            None,
+        ));
+    }
+    Ok(())
+}
+
+async fn update_locked_source(
+    runtime: &NeuroRuntime,
+    lock: &NeuroObjectLock,
+    request: neuro_types::AdtUpdateSourceRequest,
+    transport: Option<&str>,
+) -> Result<neuro_types::AdtUpdateSourceResponse, NeuroRuntimeError> {
+    check_update_safety(&runtime.safety, &request)?;
+
+    let mut adt_request = AdtRequest::put(format!("{}/source/main", lock.object_uri))
+        .query("lockHandle", lock.lock_handle.as_str())
+        .content_type("text/plain; charset=utf-8")
+        .body(request.source)
+        .stateful();
+    if let Some(transport) = transport.or(lock.transport.as_deref()) {
+        adt_request = adt_request.query("corrNr", transport);
+    }
+    if let Some(etag) = request.etag.as_deref().filter(|etag| !etag.is_empty()) {
+        adt_request = adt_request.header("if-match", etag);
+    }
+
+    let response = runtime
+        .adt
+        .send("update_source", adt_request)
+        .await
+        .map_err(map_adt_rest_error)?;
+    Ok(neuro_types::AdtUpdateSourceResponse {
+        object_uri: request.object_uri,
+        status_code: response.status,
+        etag: response.etag,
+    })
+}
+
+async fn run_syntax_check(
+    runtime: &NeuroRuntime,
+    object_uri: &str,
+    source: Option<&str>,
+) -> Result<NeuroSyntaxCheckResponse, NeuroRuntimeError> {
+    let response = runtime
+        .adt
+        .send(
+            "syntax_check",
+            AdtRequest::post(SYNTAX_CHECK_PATH)
+                .query("reporters", "abapCheckRun")
+                .content_type(SYNTAX_CHECK_CONTENT_TYPE)
+                .accept(SYNTAX_CHECK_ACCEPT)
+                .body(syntax_check_request_xml(object_uri, source)),
+        )
+        .await
+        .map_err(map_adt_rest_error)?;
+    let messages = parse_check_run_reports(&response.body).map_err(|message| {
+        map_adt_rest_error(AdtRestError::invalid_response("syntax_check", message))
+    })?;
+
+    Ok(NeuroSyntaxCheckResponse {
+        object_uri: object_uri.to_string(),
+        has_errors: has_errors(&messages),
+        messages,
+    })
+}
+
+/// Rejects an update whose source does not pass the ABAP syntax check.
+async fn gate_update_on_syntax_check(
+    runtime: &NeuroRuntime,
+    request: &neuro_types::AdtUpdateSourceRequest,
+) -> Result<(), NeuroRuntimeError> {
+    let object_uri = normalize_object_uri(&request.object_uri).map_err(invalid_argument)?;
+    let check = run_syntax_check(runtime, &object_uri, Some(&request.source)).await?;
+    if !check.has_errors {
+        return Ok(());
+    }
+    Err(runtime_error(
+        NeuroRuntimeErrorCode::SafetyViolation,
+        format!("syntax check failed for `{object_uri}`; source was not updated"),
+        Some(json!({
+            "objectUri": object_uri,
+            "messages": check.messages,
+        })),
+    ))
+}
+
+fn invalid_argument(message: String) -> NeuroRuntimeError {
+    runtime_error(NeuroRuntimeErrorCode::InvalidArgument, message, None)
+}
+
+async fn run_transport_check(
+    runtime: &NeuroRuntime,
+    object_uri: &str,
+) -> Result<TransportCheck, NeuroRuntimeError> {
+    let response = runtime
+        .adt
+        .send(
+            "transport_check",
+            AdtRequest::post(TRANSPORT_CHECK_PATH)
+                .content_type(TRANSPORT_CHECK_CONTENT_TYPE)
+                .accept(TRANSPORT_CHECK_CONTENT_TYPE)
+                .body(transport_check_request_xml(object_uri)),
+        )
+        .await
+        .map_err(map_adt_rest_error)?;
+    parse_transport_check(&response.body).map_err(|message| {
+        map_adt_rest_error(AdtRestError::invalid_response("transport_check", message))
+    })
+}
+
+/// Picks the request an update is recorded in, enforcing the transport safety option.
+async fn resolve_update_transport(
+    runtime: &NeuroRuntime,
+    object_uri: &str,
+    requested: Option<&str>,
+    lock: Option<&NeuroObjectLock>,
+) -> Result<Option<String>, NeuroRuntimeError> {
+    if let Some(transport) = requested.map(str::trim).filter(|value| !value.is_empty()) {
+        return normalize_transport_number(transport)
+            .map(Some)
+            .map_err(invalid_argument);
+    }
+    let transport = match runtime.transports.get(object_uri).await {
+        Some(transport) => Some(transport),
+        None => lock.and_then(|lock| lock.transport.clone()),
+    };
+    if transport.is_some() || !runtime.config.safety.require_transport_for_updates {
+        return Ok(transport);
+    }
+
+    let check = run_transport_check(runtime, object_uri).await?;
+    if check.locked_transport.is_some() || !check.recording_required {
+        return Ok(check.locked_transport);
+    }
+    Err(runtime_error(
+        NeuroRuntimeErrorCode::SafetyViolation,
+        format!(
+            "`{object_uri}` is in transportable package `{}`; updates require a transport request",
+            check.package.as_deref().unwrap_or("?")
+        ),
+        Some(json!({ "objectUri": object_uri, "package": check.package })),
+    ))
+}
+
+pub async fn neuro_runtime_reload_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<NeuroRuntimeLifecycleResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_runtime_reload", async {
+        let connection_id = resolve_connection_id(connection_id.as_deref());
+        let state = state.inner();
+
+        emit_neuro_lifecycle(&app, &connection_id, "reloading", None);
+        let (had_runtime, drained) = retire_cached_runtime(state, &connection_id).await;
+
+        let _init_gate = state.neuro_runtime_init_gate.lock().await;
+        // A call that arrived during the drain may already have initialized a fresh runtime.
+        if !state
+            .neuro_runtimes
+            .lock()
+            .await
+            .contains_key(&connection_id)
+        {
+            let started_at = Instant::now();
+            let runtime = match NeuroRuntime::initialize(state, connection_id.clone()).await {
+                Ok(runtime) => Arc::new(runtime),
+                Err(error) => {
+                    emit_neuro_runtime_init_telemetry(
+                        "reload_failed",
+                        started_at.elapsed(),
+                        Some(&error),
+                    );
+                    emit_neuro_lifecycle(
+                        &app,
+                        &connection_id,
+                        "failed",
+                        Some(error.message.clone()),
+                    );
+                    return Err(error);
+                }
+            };
+            state
+                .neuro_runtimes
+                .lock()
+                .await
+                .insert(connection_id.clone(), runtime);
+            emit_neuro_runtime_init_telemetry("reloaded", started_at.elapsed(), None);
+        }
+        emit_neuro_lifecycle(&app, &connection_id, "reloaded", None);
+
+        Ok(NeuroRuntimeLifecycleResponse {
+            connection_id,
+            status: "reloaded",
+            had_runtime,
+            drained,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_runtime_reset_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<NeuroRuntimeLifecycleResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_runtime_reset", async {
+        let connection_id = resolve_connection_id(connection_id.as_deref());
+        let state = state.inner();
+
+        let (had_runtime, drained) = retire_cached_runtime(state, &connection_id).await;
+        emit_neuro_lifecycle(&app, &connection_id, "reset", None);
+
+        Ok(NeuroRuntimeLifecycleResponse {
+            connection_id,
+            status: "reset",
+            had_runtime,
+            drained,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_runtime_diagnose_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<RuntimeDiagnoseResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_runtime_diagnose", async {
+        Ok(neuro_runtime_diagnose_for_app_state(state.inner(), connection_id.as_deref()).await)
+    })
+    .await
+}
+
+pub async fn neuro_runtime_diagnose_for_app_state(
+    state: &AppState,
+    connection_id: Option<&str>,
+) -> RuntimeDiagnoseResponse {
+    let connection_id = resolve_connection_id(connection_id);
+    if let Some(runtime) = {
+        let cache = state.neuro_runtimes.lock().await;
+        cache.get(&connection_id).cloned()
+    } {
+        return success_response(runtime, true).await;
+    }
+
+    match get_or_init(state, Some(&connection_id)).await {
+        Ok(runtime) => success_response(runtime, false).await,
+        Err(error) => init_error_response(&connection_id, error),
+    }
+}
+
+pub async fn neuro_connection_list_impl() -> Result<Vec<NeuroConnectionProfile>, NeuroRuntimeError>
+{
+    run_with_neuro_command_telemetry("neuro_connection_list", async {
+        load_connection_profiles(&connections_file_path()).map_err(connection_store_error)
+    })
+    .await
+}
+
+pub async fn neuro_connection_create_impl(
+    profile: NeuroConnectionProfile,
+) -> Result<NeuroConnectionProfile, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_connection_create", async {
+        create_connection_profile(&connections_file_path(), profile).map_err(connection_store_error)
+    })
+    .await
+}
+
+pub async fn neuro_connection_update_impl(
+    state: State<'_, AppState>,
+    profile: NeuroConnectionProfile,
+) -> Result<NeuroConnectionProfile, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_connection_update", async {
+        let updated = update_connection_profile(&connections_file_path(), profile)
+            .map_err(connection_store_error)?;
+        retire_cached_runtime(state.inner(), &updated.id).await;
+        Ok(updated)
+    })
+    .await
+}
+
+pub async fn neuro_connection_delete_impl(
+    state: State<'_, AppState>,
+    connection_id: String,
+) -> Result<bool, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_connection_delete", async {
+        let deleted = delete_connection_profile(&connections_file_path(), connection_id.trim())
+            .map_err(connection_store_error)?;
+        retire_cached_runtime(state.inner(), connection_id.trim()).await;
+        lock_health_registry(state.inner())?.forget(connection_id.trim());
+        Ok(deleted)
+    })
+    .await
+}
+
+pub async fn neuro_health_history_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<NeuroHealthHistory, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_health_history", async {
+        let connection_id = resolve_connection_id(connection_id.as_deref());
+        Ok(lock_health_registry(state.inner())?.history(&connection_id))
+    })
+    .await
+}
+
+pub async fn neuro_telemetry_summary_impl() -> Result<NeuroTelemetrySummary, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_telemetry_summary", async { Ok(telemetry_summary()) })
+        .await
+}
+
+pub async fn neuro_secret_status_impl(
+    state: State<'_, AppState>,
+) -> Result<NeuroSecretStatus, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_secret_status", async {
+        with_secret_session(state.inner(), |session| secret_status(session))
+            .await
+            .map_err(secret_store_error)
+    })
+    .await
+}
+
+pub async fn neuro_secret_create_impl(
+    state: State<'_, AppState>,
+    passphrase: String,
+) -> Result<NeuroSecretStatus, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_secret_create", async {
+        let status = with_secret_session(state.inner(), move |session| {
+            create_secret_store(session, &passphrase)?;
+            secret_status(session)
+        })
+        .await
+        .map_err(secret_store_error)?;
+        retire_all_cached_runtimes(state.inner()).await;
+        Ok(status)
+    })
+    .await
+}
+
+pub async fn neuro_secret_unlock_impl(
+    state: State<'_, AppState>,
+    passphrase: String,
+) -> Result<NeuroSecretStatus, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_secret_unlock", async {
+        let status = with_secret_session(state.inner(), move |session| {
+            unlock_secret_store(session, &passphrase)?;
+            secret_status(session)
+        })
+        .await
+        .map_err(secret_store_error)?;
+        retire_all_cached_runtimes(state.inner()).await;
+        Ok(status)
+    })
+    .await
+}
+
+pub async fn neuro_secret_lock_impl(
+    state: State<'_, AppState>,
+) -> Result<NeuroSecretStatus, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_secret_lock", async {
+        with_secret_session(state.inner(), |session| {
+            lock_secret_store(session);
+            secret_status(session)
+        })
+        .await
+        .map_err(secret_store_error)
+    })
+    .await
+}
+
+pub async fn neuro_secret_set_impl(
+    state: State<'_, AppState>,
+    request: NeuroSecretSetRequest,
+) -> Result<NeuroSecretStatus, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_secret_set", async {
+        let connection_id = resolve_connection_id(request.connection_id.as_deref());
+        let status = {
+            let connection_id = connection_id.clone();
+            with_secret_session(state.inner(), move |session| {
+                set_secret(session, &connection_id, &request.name, &request.value)?;
+                secret_status(session)
+            })
+            .await
+            .map_err(secret_store_error)?
+        };
+        retire_cached_runtime(state.inner(), &connection_id).await;
+        Ok(status)
+    })
+    .await
+}
+
+pub async fn neuro_secret_clear_impl(
+    state: State<'_, AppState>,
+    request: NeuroSecretClearRequest,
+) -> Result<bool, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_secret_clear", async {
+        let connection_id = resolve_connection_id(request.connection_id.as_deref());
+        let removed = {
+            let connection_id = connection_id.clone();
+            with_secret_session(state.inner(), move |_| {
+                clear_secret(&connection_id, &request.name)
+            })
+            .await
+            .map_err(secret_store_error)?
+        };
+        if removed {
+            retire_cached_runtime(state.inner(), &connection_id).await;
+        }
+        Ok(removed)
+    })
+    .await
+}
+
+/// `None` when the cache lock is poisoned; callers then treat the lookup as a miss.
+fn with_response_cache<T>(
+    runtime: &NeuroRuntime,
+    operation: impl FnOnce(&mut NeuroResponseCache) -> T,
+) -> Option<T> {
+    runtime
+        .cache
+        .lock()
+        .ok()
+        .map(|mut cache| operation(&mut cache))
+}
+
+fn source_cache_key(object_uri: &str) -> String {
+    normalize_object_uri(object_uri).unwrap_or_else(|_| object_uri.trim().to_string())
+}
+
+/// Drops the written object's source and all search results, which may list changed or
+/// newly active objects.
+fn invalidate_cache_after_write(runtime: &NeuroRuntime, object_uri: Option<&str>) {
+    let key = object_uri.map(source_cache_key);
+    with_response_cache(runtime, |cache| {
+        if let Some(key) = key.as_deref() {
+            cache.sources.remove(key);
+        }
+        cache.search.clear();
+    });
+}
+
+fn emit_neuro_cache_telemetry(runtime: &NeuroRuntime, cache: &'static str, hits: u64, misses: u64) {
+    emit_neuro_telemetry(json!({
+        "event": NEURO_CACHE_TELEMETRY_EVENT,
+        "cache": cache,
+        "connectionId": runtime.connection_id,
+        "hits": hits,
+        "misses": misses,
+    }));
+}
+
+async fn search_objects(
+    runtime: &NeuroRuntime,
+    query: &str,
+    max_results: Option<u32>,
+) -> Result<Vec<neuro_types::AdtObjectSummary>, NeuroRuntimeError> {
+    let key = search_cache_key(query, max_results);
+    if let Some(Some(CacheLookup::Fresh(results))) =
+        with_response_cache(runtime, |cache| cache.search.get(&key, Instant::now()))
+    {
+        emit_neuro_cache_telemetry(runtime, "search", 1, 0);
+        return Ok(results);
+    }
+    emit_neuro_cache_telemetry(runtime, "search", 0, 1);
+    let results = runtime
+        .engine
+        .search(query, max_results)
+        .await
+        .map_err(map_engine_error)?;
+    with_response_cache(runtime, |cache| {
+        cache.search.insert(key, results.clone(), Instant::now())
+    });
+    Ok(results)
+}
+
+/// Returns the source and whether it was served from the cache. Cached entries are always
+/// revalidated with `If-None-Match`, since another user may have changed the object.
+async fn read_source(
+    runtime: &NeuroRuntime,
+    object_uri: &str,
+) -> Result<(neuro_types::AdtSourceResponse, bool), NeuroRuntimeError> {
+    let normalized = normalize_object_uri(object_uri).ok();
+    let key = source_cache_key(object_uri);
+    let cached = with_response_cache(runtime, |cache| cache.sources.get(&key, Instant::now()))
+        .flatten()
+        .map(|lookup| match lookup {
+            CacheLookup::Fresh(source) | CacheLookup::Stale(source) => source,
+        });
+
+    if let (Some(object_path), Some(source)) = (normalized, cached) {
+        if let Some(etag) = source.etag.clone().filter(|etag| !etag.is_empty()) {
+            let response = runtime
+                .adt
+                .send(
+                    "get_source",
+                    AdtRequest::get(format!("{object_path}/source/main"))
+                        .accept("text/plain")
+                        .header("if-none-match", etag),
+                )
+                .await;
+            let source = match response {
+                Err(AdtRestError::UnexpectedStatus { status: 304, .. }) => {
+                    with_response_cache(runtime, |cache| cache.sources.touch(&key, Instant::now()));
+                    return Ok((
+                        neuro_types::AdtSourceResponse {
+                            object_uri: object_uri.to_string(),
+                            ..source
+                        },
+                        true,
+                    ));
+                }
+                Ok(response) => neuro_types::AdtSourceResponse {
+                    object_uri: object_uri.to_string(),
+                    source: response.body,
+                    etag: response.etag,
+                },
+                Err(error) => return Err(map_adt_rest_error(error)),
+            };
+            with_response_cache(runtime, |cache| {
+                cache.sources.insert(key, source.clone(), Instant::now())
+            });
+            return Ok((source, false));
+        }
+    }
+
+    let source = runtime
+        .engine
+        .get_source(object_uri)
+        .await
+        .map_err(map_engine_error)?;
+    with_response_cache(runtime, |cache| {
+        cache.sources.insert(key, source.clone(), Instant::now())
+    });
+    Ok((source, false))
+}
+
+pub async fn neuro_search_objects_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    query: String,
+    max_results: Option<u32>,
+) -> Result<Vec<neuro_types::AdtObjectSummary>, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_search_objects", async {
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        search_objects(&runtime, query.as_str(), max_results).await
+    })
+    .await
+}
+
+pub async fn neuro_get_source_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<neuro_types::AdtSourceResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_get_source", async {
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let result = read_source(&runtime, object_uri.as_str()).await;
+        let cached = matches!(result, Ok((_, true)));
+        emit_neuro_cache_telemetry(&runtime, "source", u64::from(cached), u64::from(!cached));
+        result.map(|(source, _)| source)
+    })
+    .await
+}
+
+pub async fn neuro_get_sources_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uris: Vec<String>,
+) -> Result<Vec<NeuroSourceEntry>, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_get_sources", async {
+        if object_uris.is_empty() {
+            return Err(runtime_error(
+                NeuroRuntimeErrorCode::InvalidArgument,
+                "objectUris must not be empty".to_string(),
+                None,
+            ));
+        }
+        if object_uris.len() > MAX_SOURCE_BATCH_SIZE {
+            return Err(runtime_error(
+                NeuroRuntimeErrorCode::InvalidArgument,
+                format!("at most {MAX_SOURCE_BATCH_SIZE} objects can be read per call"),
+                Some(json!({ "count": object_uris.len() })),
+            ));
+        }
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let runtime: &NeuroRuntime = &runtime;
+        let entries = stream::iter(object_uris)
+            .map(|object_uri| async move {
+                match read_source(runtime, &object_uri).await {
+                    Ok((source, cached)) => NeuroSourceEntry {
+                        object_uri,
+                        source: Some(source),
+                        error: None,
+                        cached,
+                    },
+                    Err(error) => NeuroSourceEntry {
+                        object_uri,
+                        source: None,
+                        error: Some(error),
+                        cached: false,
+                    },
+                }
+            })
+            .buffered(source_fetch_concurrency_from_env())
+            .collect::<Vec<_>>()
+            .await;
+        let hits = entries.iter().filter(|entry| entry.cached).count() as u64;
+        emit_neuro_cache_telemetry(runtime, "source", hits, entries.len() as u64 - hits);
+        Ok(entries)
+    })
+    .await
+}
+
+pub async fn neuro_list_versions_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<NeuroVersionListResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_list_versions", async {
+        let object_uri = normalize_object_uri(&object_uri).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let response = runtime
+            .adt
+            .send(
+                "list_versions",
+                AdtRequest::get(versions_feed_path(&object_uri)).accept(VERSION_FEED_ACCEPT),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        let versions = parse_version_feed(&response.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("list_versions", message))
+        })?;
+        Ok(NeuroVersionListResponse {
+            object_uri,
+            versions,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_get_version_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+    version_id: String,
+) -> Result<NeuroVersionSourceResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_get_version", async {
+        let object_uri = normalize_object_uri(&object_uri).map_err(invalid_argument)?;
+        let path = version_content_path(&object_uri, &version_id).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let response = runtime
+            .adt
+            .send("get_version", AdtRequest::get(path).accept("text/plain"))
+            .await
+            .map_err(map_adt_rest_error)?;
+        Ok(NeuroVersionSourceResponse {
+            object_uri,
+            version_id: version_id.trim().trim_matches('/').to_string(),
+            source: response.body,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_diff_source_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+    local_source: String,
+    version: Option<NeuroServerVersion>,
+) -> Result<NeuroSourceDiffResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_diff_source", async {
+        let object_uri = normalize_object_uri(&object_uri).map_err(invalid_argument)?;
+        let version = version.unwrap_or_default();
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let response = runtime
+            .adt
+            .send(
+                "diff_source",
+                AdtRequest::get(format!("{object_uri}/source/main"))
+                    .query("version", version.as_query())
+                    .accept("text/plain"),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        let diff = source_diff(&object_uri, &response.body, &local_source);
+        Ok(NeuroSourceDiffResponse {
+            changed: !diff.is_empty(),
+            object_uri,
+            version,
+            etag: response.etag,
+            diff,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_where_used_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<NeuroWhereUsedResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_where_used", async {
+        let object_uri = normalize_object_uri(&object_uri).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let response = runtime
+            .adt
+            .send(
+                "where_used",
+                AdtRequest::post(WHERE_USED_PATH)
+                    .query("uri", object_uri.as_str())
+                    .content_type(WHERE_USED_CONTENT_TYPE)
+                    .accept(WHERE_USED_ACCEPT)
+                    .body(where_used_request_xml()),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        let references = parse_where_used_result(&response.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("where_used", message))
+        })?;
+        Ok(NeuroWhereUsedResponse {
+            object_uri,
+            references,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_navigate_definition_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroNavigateRequest,
+) -> Result<NeuroNavigationResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_navigate_definition", async {
+        let object_uri = normalize_object_uri(&request.object_uri).map_err(invalid_argument)?;
+        if request.line == 0 {
+            return Err(invalid_argument("line numbers start at 1".to_string()));
+        }
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let source = match request.source {
+            Some(source) => source,
+            None => read_source(&runtime, &object_uri).await?.0.source,
+        };
+        let response = runtime
+            .adt
+            .send(
+                "navigate_definition",
+                AdtRequest::post(NAVIGATION_PATH)
+                    .query(
+                        "uri",
+                        navigation_source_uri(&object_uri, request.line, request.column),
+                    )
+                    .query("filter", "definition")
+                    .content_type("text/plain")
+                    .accept("application/xml")
+                    .body(source),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        let target = parse_navigation_target(&response.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response(
+                "navigate_definition",
+                message,
+            ))
+        })?;
+        Ok(NeuroNavigationResponse { object_uri, target })
+    })
+    .await
+}
+
+pub async fn neuro_object_structure_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<NeuroObjectStructureResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_object_structure", async {
+        let object_uri = normalize_object_uri(&object_uri).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let response = runtime
+            .adt
+            .send(
+                "object_structure",
+                AdtRequest::get(object_structure_path(&object_uri))
+                    .query("version", "active")
+                    .query("withShortDescriptions", "true")
+                    .accept(OBJECT_STRUCTURE_ACCEPT),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        parse_object_structure(&object_uri, &response.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("object_structure", message))
+        })
+    })
+    .await
+}
+
+pub async fn neuro_ddic_table_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    name: String,
+) -> Result<NeuroDdicTableResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_ddic_table", async {
+        let (name, table_uri) =
+            ddic_object_uri(DDIC_TABLES_PATH, &name).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let mut kind = NeuroDdicTableKind::Table;
+        let mut object_uri = table_uri;
+        let mut response = runtime
+            .adt
+            .send(
+                "ddic_table",
+                AdtRequest::get(format!("{object_uri}/source/main")).accept("text/plain"),
+            )
+            .await;
+        // Tables and structures share the DDIC namespace; a 404 means it is not a table.
+        if let Err(AdtRestError::UnexpectedStatus { status: 404, .. }) = response {
+            kind = NeuroDdicTableKind::Structure;
+            object_uri = ddic_object_uri(DDIC_STRUCTURES_PATH, &name)
+                .map_err(invalid_argument)?
+                .1;
+            response = runtime
+                .adt
+                .send(
+                    "ddic_structure",
+                    AdtRequest::get(format!("{object_uri}/source/main")).accept("text/plain"),
+                )
+                .await;
+        }
+        let source = response.map_err(map_adt_rest_error)?.body;
+        parse_ddic_table_source(&object_uri, &name, kind, &source).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("ddic_table", message))
+        })
+    })
+    .await
+}
+
+pub async fn neuro_ddic_data_element_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    name: String,
+) -> Result<NeuroDataElementResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_ddic_data_element", async {
+        let (name, object_uri) =
+            ddic_object_uri(DATA_ELEMENTS_PATH, &name).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let response = runtime
+            .adt
+            .send(
+                "ddic_data_element",
+                AdtRequest::get(object_uri.as_str()).accept(DATA_ELEMENT_ACCEPT),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        parse_data_element(&object_uri, &name, &response.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("ddic_data_element", message))
+        })
+    })
+    .await
+}
+
+pub async fn neuro_ddic_domain_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    name: String,
+) -> Result<NeuroDomainResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_ddic_domain", async {
+        let (name, object_uri) = ddic_object_uri(DOMAINS_PATH, &name).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let response = runtime
+            .adt
+            .send(
+                "ddic_domain",
+                AdtRequest::get(object_uri.as_str()).accept(DOMAIN_ACCEPT),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        parse_domain(&object_uri, &name, &response.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("ddic_domain", message))
+        })
+    })
+    .await
+}
+
+pub async fn neuro_cds_view_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    name: String,
+) -> Result<NeuroCdsViewResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_cds_view", async {
+        let (name, object_uri) =
+            ddic_object_uri(CDS_SOURCES_PATH, &name).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let response = runtime
+            .adt
+            .send(
+                "cds_view",
+                AdtRequest::get(format!("{object_uri}/source/main")).accept("text/plain"),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        Ok(parse_cds_source(&object_uri, &name, &response.body))
+    })
+    .await
+}
+
+pub async fn neuro_data_preview_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroDataPreviewRequest,
+) -> Result<NeuroDataPreviewResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_data_preview", async {
+        let target = data_preview_target(&request).map_err(invalid_argument)?;
+        let row_limit = preview_row_limit(request.max_rows, data_preview_max_rows_from_env());
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        if let Some((pattern, entity)) = find_blocked_entity(
+            &runtime.config.safety.blocked_entity_patterns,
+            &preview_entities(&target),
+        ) {
+            return Err(runtime_error(
+                NeuroRuntimeErrorCode::SafetyViolation,
+                format!("data preview of `{entity}` is blocked by `{pattern}`"),
+                Some(json!({ "entity": entity, "pattern": pattern })),
+            ));
+        }
+        let blocked_patterns = &runtime.config.safety.blocked_entity_patterns;
+        if uses_path_expression(&target)
+            && blocked_patterns
+                .iter()
+                .any(|pattern| !pattern.trim().is_empty())
+        {
+            return Err(runtime_error(
+                NeuroRuntimeErrorCode::SafetyViolation,
+                "data preview queries with association paths are refused while blocked entities \
+                 are configured"
+                    .to_string(),
+                Some(json!({ "pathExpression": true })),
+            ));
+        }
+
+        let adt_request = match target {
+            DataPreviewTarget::Query(query) => AdtRequest::post(DATA_PREVIEW_FREESTYLE_PATH)
+                .query("rowNumber", row_limit.to_string())
+                .body(query),
+            DataPreviewTarget::Entity(entity) => AdtRequest::post(DATA_PREVIEW_DDIC_PATH)
+                .query("rowNumber", row_limit.to_string())
+                .query("ddicEntityName", entity)
+                .body(""),
+        };
+        let response = runtime
+            .adt
+            .send(
+                "data_preview",
+                adt_request
+                    .content_type("text/plain")
+                    .accept(DATA_PREVIEW_ACCEPT),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        parse_data_preview(&response.body, row_limit).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("data_preview", message))
+        })
+    })
+    .await
+}
+
+/// How a SAP write is confirmed when `requireConfirmationForUpdates` is on.
+pub enum SapWriteConfirmation {
+    /// Agent tool calls ask through the active session's approval flow.
+    Session(AppHandle),
+    /// UI commands show the returned diff themselves and retry with `confirmed`.
+    Caller { confirmed: bool },
+}
+
+/// Whether writes on the connection are confirmed with a diff before they run.
+pub(crate) async fn writes_require_confirmation(
+    state: &AppState,
+    connection_id: Option<&str>,
+) -> bool {
+    get_or_init(state, connection_id)
+        .await
+        .is_ok_and(|runtime| runtime.config.safety.require_confirmation_for_updates)
+}
+
+pub async fn neuro_update_source_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroUpdateSourceRequest,
+    syntax_check: Option<bool>,
+    confirmation: SapWriteConfirmation,
+) -> Result<neuro_types::AdtUpdateSourceResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_update_source", async {
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        update_source(
+            &confirmation,
+            &runtime,
+            request.update,
+            request.transport,
+            syntax_check,
+        )
+        .await
+    })
+    .await
+}
+
+async fn update_source(
+    confirmation: &SapWriteConfirmation,
+    runtime: &NeuroRuntime,
+    update: neuro_types::AdtUpdateSourceRequest,
+    transport: Option<String>,
+    syntax_check: Option<bool>,
+) -> Result<neuro_types::AdtUpdateSourceResponse, NeuroRuntimeError> {
+    let mut audit = SapWriteAudit::new("update_source", Some(&update.object_uri));
+    audit.etag = update.etag.clone();
+    audit.source_sha256 = Some(sha256_hex(&update.source));
+    audit.transport = transport.clone();
+    let object_uri = update.object_uri.clone();
+    let result = write_source(confirmation, runtime, update, transport, syntax_check).await;
+    // Also after a failed write: SAP may have applied it before the error.
+    invalidate_cache_after_write(runtime, Some(&object_uri));
+    audit.record(runtime, result.as_ref().map(|updated| updated.etag.clone()));
+    result
+}
+
+async fn write_source(
+    confirmation: &SapWriteConfirmation,
+    runtime: &NeuroRuntime,
+    update: neuro_types::AdtUpdateSourceRequest,
+    transport: Option<String>,
+    syntax_check: Option<bool>,
+) -> Result<neuro_types::AdtUpdateSourceResponse, NeuroRuntimeError> {
+    if syntax_check.unwrap_or(runtime.config.safety.syntax_check_before_update) {
+        gate_update_on_syntax_check(runtime, &update).await?;
+    }
+    if runtime.config.safety.require_confirmation_for_updates {
+        check_update_safety(&runtime.safety, &update)?;
+        let current = runtime
+            .engine
+            .get_source(update.object_uri.as_str())
+            .await
+            .map_err(map_engine_error)?;
+        confirm_sap_write(
+            confirmation,
+            &update.object_uri,
+            format!("Update ABAP source of {}", update.object_uri),
+            source_diff(&update.object_uri, &current.source, &update.source),
+        )
+        .await?;
+    }
+    let object_uri = match normalize_object_uri(&update.object_uri) {
+        Ok(object_uri) => object_uri,
+        Err(_) if transport.is_none() => {
+            return runtime
+                .engine
+                .update_source(update)
+                .await
+                .map_err(map_engine_error);
+        }
+        Err(message) => return Err(invalid_argument(message)),
+    };
+    let lock = runtime.locks.held(&object_uri).await;
+    let transport =
+        resolve_update_transport(runtime, &object_uri, transport.as_deref(), lock.as_ref()).await?;
+
+    if let Some(lock) = lock {
+        return update_locked_source(runtime, &lock, update, transport.as_deref()).await;
+    }
+    if transport.is_none() {
+        return runtime
+            .engine
+            .update_source(update)
+            .await
+            .map_err(map_engine_error);
+    }
+
+    // Recording in a specific request needs corrNr, which only the REST path sends.
+    check_update_safety(&runtime.safety, &update)?;
+    let lock = runtime
+        .locks
+        .lock(&runtime.adt, &object_uri)
+        .await
+        .map_err(map_adt_rest_error)?;
+    let result = update_locked_source(runtime, &lock, update, transport.as_deref()).await;
+    // A failed unlock stays registered and is released with the runtime.
+    let _ = runtime.locks.unlock(&runtime.adt, &object_uri).await;
+    result
+}
+
+fn source_diff(object_uri: &str, old: &str, new: &str) -> String {
+    unified_diff(
+        old,
+        new,
+        &format!("a{object_uri}"),
+        &format!("b{object_uri}"),
+        DEFAULT_DIFF_CONTEXT,
+    )
+}
+
+fn is_declined_write(error: &NeuroRuntimeError) -> bool {
+    error
+        .details
+        .as_ref()
+        .and_then(|details| details.get("confirmation"))
+        .and_then(Value::as_str)
+        == Some("declined")
+}
+
+async fn confirm_sap_write(
+    confirmation: &SapWriteConfirmation,
+    object_uri: &str,
+    reason: String,
+    diff: String,
+) -> Result<(), NeuroRuntimeError> {
+    let app = match confirmation {
+        SapWriteConfirmation::Session(app) => app,
+        SapWriteConfirmation::Caller { confirmed: true } => return Ok(()),
+        SapWriteConfirmation::Caller { confirmed: false } => {
+            return Err(runtime_error(
+                NeuroRuntimeErrorCode::SafetyViolation,
+                format!("write to `{object_uri}` needs confirmation"),
+                Some(json!({
+                    "objectUri": object_uri,
+                    "confirmation": "required",
+                    "reason": reason,
+                    "diff": diff,
+                })),
+            ))
+        }
+    };
+    let approved = request_neuro_write_approval(app, object_uri, reason, diff)
+        .await
+        .map_err(|message| {
+            runtime_error(
+                NeuroRuntimeErrorCode::SafetyViolation,
+                format!("write needs confirmation: {message}"),
+                None,
+            )
+        })?;
+    if !approved {
+        return Err(runtime_error(
+            NeuroRuntimeErrorCode::SafetyViolation,
+            format!("write to `{object_uri}` was declined"),
+            Some(json!({ "objectUri": object_uri, "confirmation": "declined" })),
+        ));
+    }
+    Ok(())
+}
+
+struct SapWriteAudit {
+    operation: String,
+    object_uri: Option<String>,
+    etag: Option<String>,
+    source_sha256: Option<String>,
+    transport: Option<String>,
+}
+
+impl SapWriteAudit {
+    fn new(operation: impl Into<String>, object_uri: Option<&str>) -> Self {
+        Self {
+            operation: operation.into(),
+            object_uri: object_uri.map(str::to_string),
+            etag: None,
+            source_sha256: None,
+            transport: None,
+        }
+    }
+
+    /// `result` carries the ETag SAP returned, if any. A log that cannot be written does
+    /// not undo the write, so failures are only reported on stderr.
+    fn record(self, runtime: &NeuroRuntime, result: Result<Option<String>, &NeuroRuntimeError>) {
+        let sap = &runtime.config.sap;
+        let system = sap.url.as_ref().map(|url| match sap.client.as_ref() {
+            Some(client) => format!("{} ({})", url.value, client.value),
+            None => url.value.clone(),
+        });
+        let (outcome, new_etag, error) = match result {
+            Ok(new_etag) => ("succeeded", new_etag, None),
+            Err(error) if is_declined_write(error) => ("declined", None, None),
+            Err(error) => ("failed", None, Some(error.message.clone())),
+        };
+        let entry = NeuroAuditEntry {
+            timestamp_epoch_secs: now_epoch_secs(),
+            operation: self.operation,
+            connection_id: runtime.connection_id.clone(),
+            system,
+            user: sap.user.as_ref().map(|user| user.value.clone()),
+            object_uri: self.object_uri,
+            etag: self.etag,
+            new_etag,
+            source_sha256: self.source_sha256,
+            transport: self.transport,
+            outcome: outcome.to_string(),
+            error,
+        };
+        if let Err(message) = append_audit_entry(&audit_log_path(), &entry) {
+            eprintln!("[neuro-audit] {message}");
+        }
+    }
+}
+
+fn workspace_error(message: String) -> NeuroRuntimeError {
+    runtime_error(NeuroRuntimeErrorCode::InvalidArgument, message, None)
+}
+
+async fn list_package_nodes(
+    runtime: &NeuroRuntime,
+    package: &str,
+) -> Result<Vec<PackageNode>, NeuroRuntimeError> {
+    let response = runtime
+        .adt
+        .send(
+            "package_nodes",
+            AdtRequest::post(PACKAGE_NODES_PATH)
+                .query("parent_type", "DEVC/K")
+                .query("parent_name", package)
+                .query("withShortDescriptions", "false")
+                .accept("application/xml"),
+        )
+        .await
+        .map_err(map_adt_rest_error)?;
+    parse_package_nodes(&response.body).map_err(|message| {
+        map_adt_rest_error(AdtRestError::invalid_response("package_nodes", message))
+    })
+}
+
+pub async fn neuro_sync_package_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroSyncPackageRequest,
+) -> Result<NeuroSyncPackageResponse, NeuroRuntimeError> {
+    const OPERATION: &str = "neuro_sync_package";
+    run_with_neuro_command_telemetry(OPERATION, async {
+        let package = normalize_package_name(&request.package).map_err(workspace_error)?;
+        let target_dir = normalize_workspace_relative_path(OPERATION, &request.target_dir)
+            .map_err(workspace_error)?;
+        let workspace_cwd = active_workspace_cwd(&state, OPERATION).map_err(workspace_error)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let manifest_path = manifest_relative_path(&target_dir);
+        let mut previous = read_workspace_file(&workspace_cwd, &manifest_path, OPERATION)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<NeuroPackageManifest>(&raw).ok())
+            .map(|manifest| manifest.objects)
+            .unwrap_or_default();
+
+        let mut packages = VecDeque::from(list_package_nodes(&runtime, &package).await?);
+        let mut visited = vec![package.clone()];
+        let mut objects = Vec::new();
+        let mut skipped = Vec::new();
+        let mut failed = Vec::new();
+        while let Some(node) = packages.pop_front() {
+            if node.is_package() {
+                let name = node.object_name.to_ascii_uppercase();
+                if !request.include_subpackages || visited.contains(&name) {
+                    continue;
+                }
+                visited.push(name.clone());
+                match list_package_nodes(&runtime, &name).await {
+                    Ok(children) => packages.extend(children),
+                    Err(error) => failed.push(NeuroPackageSyncFailure {
+                        object_uri: node.object_uri,
+                        object_type: node.object_type,
+                        error,
+                    }),
+                }
+                continue;
+            }
+            let Some(file_name) = source_file_name(&node.object_type, &node.object_name) else {
+                skipped.push(NeuroPackageSkippedObject {
+                    object_uri: node.object_uri,
+                    object_type: node.object_type,
+                    reason: "object type has no single main source".to_string(),
+                });
+                continue;
+            };
+
+            let synced = match runtime
+                .adt
+                .send(
+                    "get_source",
+                    AdtRequest::get(format!("{}/source/main", node.object_uri))
+                        .accept("text/plain"),
+                )
+                .await
+            {
+                Ok(response) => write_workspace_file(
+                    &workspace_cwd,
+                    &target_dir.join(&file_name),
+                    response.body.clone(),
+                    OPERATION,
+                )
+                .map(|_| response)
+                .map_err(workspace_error),
+                Err(error) => Err(map_adt_rest_error(error)),
+            };
+            match synced {
+                Ok(response) => objects.push(NeuroPackageManifestEntry {
+                    object_uri: node.object_uri,
+                    object_type: node.object_type,
+                    object_name: node.object_name,
+                    path: file_name,
+                    etag: response.etag,
+                    sha256: sha256_hex(&response.body),
+                }),
+                Err(error) => {
+                    if let Some(index) = previous
+                        .iter()
+                        .position(|entry| entry.object_uri == node.object_uri)
+                    {
+                        objects.push(previous.swap_remove(index));
+                    }
+                    failed.push(NeuroPackageSyncFailure {
+                        object_uri: node.object_uri,
+                        object_type: node.object_type,
+                        error,
+                    });
+                }
+            }
+        }
+
+        let manifest = NeuroPackageManifest {
+            package: package.clone(),
+            connection_id: Some(runtime.connection_id.clone()),
+            synced_at_epoch_secs: now_epoch_secs(),
+            objects,
+        };
+        write_package_manifest(&workspace_cwd, &manifest_path, &manifest, OPERATION)?;
+
+        Ok(NeuroSyncPackageResponse {
+            package,
+            target_dir: request.target_dir,
+            manifest_path: manifest_path.to_string_lossy().into_owned(),
+            objects: manifest.objects,
+            skipped,
+            failed,
+        })
+    })
+    .await
+}
+
+/// Reads the current ETag when a write response did not carry one, so the manifest never
+/// keeps the pre-write value.
+async fn fetch_source_etag(runtime: &NeuroRuntime, object_uri: &str) -> Option<String> {
+    runtime
+        .adt
+        .send(
+            "get_source",
+            AdtRequest::get(format!("{object_uri}/source/main")).accept("text/plain"),
+        )
+        .await
+        .ok()
+        .and_then(|response| response.etag)
+}
+
+fn write_package_manifest(
+    workspace_cwd: &Path,
+    manifest_path: &Path,
+    manifest: &NeuroPackageManifest,
+    operation: &str,
+) -> Result<(), NeuroRuntimeError> {
+    let content = serde_json::to_string_pretty(manifest).map_err(|error| {
+        workspace_error(format!("{operation} failed to encode manifest: {error}"))
+    })?;
+    write_workspace_file(workspace_cwd, manifest_path, content, operation).map_err(workspace_error)
+}
+
+pub async fn neuro_push_changes_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroPushChangesRequest,
+) -> Result<NeuroPushChangesResponse, NeuroRuntimeError> {
+    const OPERATION: &str = "neuro_push_changes";
+    run_with_neuro_command_telemetry(OPERATION, async {
+        let target_dir = normalize_workspace_relative_path(OPERATION, &request.target_dir)
+            .map_err(workspace_error)?;
+        let workspace_cwd = active_workspace_cwd(&state, OPERATION).map_err(workspace_error)?;
+        let manifest_path = manifest_relative_path(&target_dir);
+        let mut manifest: NeuroPackageManifest = serde_json::from_str(
+            &read_workspace_file(&workspace_cwd, &manifest_path, OPERATION)
+                .map_err(workspace_error)?,
+        )
+        .map_err(|error| workspace_error(format!("{OPERATION} invalid manifest: {error}")))?;
+        let connection_id = connection_id.or_else(|| manifest.connection_id.clone());
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+
+        let mut response = NeuroPushChangesResponse {
+            dry_run: request.dry_run,
+            pushed: Vec::new(),
+            unchanged: 0,
+            failed: Vec::new(),
+        };
+        for entry in &mut manifest.objects {
+            let source =
+                match normalize_workspace_relative_path(OPERATION, &entry.path).and_then(|path| {
+                    read_workspace_file(&workspace_cwd, &target_dir.join(path), OPERATION)
+                }) {
+                    Ok(source) => source,
+                    Err(message) => {
+                        response.failed.push(NeuroPushFailure {
+                            object_uri: entry.object_uri.clone(),
+                            path: entry.path.clone(),
+                            error: workspace_error(message),
+                        });
+                        continue;
+                    }
+                };
+            let sha256 = sha256_hex(&source);
+            if sha256 == entry.sha256 {
+                response.unchanged += 1;
+                continue;
+            }
+            if request.dry_run {
+                response.pushed.push(NeuroPushedObject {
+                    object_uri: entry.object_uri.clone(),
+                    path: entry.path.clone(),
+                    etag: entry.etag.clone(),
+                });
+                continue;
+            }
+
+            let update = neuro_types::AdtUpdateSourceRequest {
+                object_uri: entry.object_uri.clone(),
+                source,
+                etag: entry.etag.clone(),
+            };
+            match update_source(
+                &SapWriteConfirmation::Caller {
+                    confirmed: request.confirmed,
+                },
+                &runtime,
+                update,
+                request.transport.clone(),
+                request.syntax_check,
+            )
+            .await
+            {
+                Ok(updated) => {
+                    entry.etag = match updated.etag {
+                        Some(etag) => Some(etag),
+                        None => fetch_source_etag(&runtime, &entry.object_uri).await,
+                    };
+                    entry.sha256 = sha256;
+                    response.pushed.push(NeuroPushedObject {
+                        object_uri: entry.object_uri.clone(),
+                        path: entry.path.clone(),
+                        etag: entry.etag.clone(),
+                    });
+                }
+                Err(error) => response.failed.push(NeuroPushFailure {
+                    object_uri: entry.object_uri.clone(),
+                    path: entry.path.clone(),
+                    error,
+                }),
+            }
+        }
+
+        if !request.dry_run && !response.pushed.is_empty() {
+            write_package_manifest(&workspace_cwd, &manifest_path, &manifest, OPERATION)?;
+        }
+        Ok(response)
+    })
+    .await
+}
+
+pub async fn neuro_list_transports_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<Vec<NeuroTransportRequest>, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_list_transports", async {
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let mut request = AdtRequest::get(TRANSPORT_REQUESTS_PATH)
+            .query("targets", "true")
+            .accept(TRANSPORT_REQUESTS_ACCEPT);
+        if let Some(user) = runtime.config.sap.user.as_ref() {
+            request = request.query("user", user.value.trim().to_ascii_uppercase());
+        }
+        let response = runtime
+            .adt
+            .send("list_transports", request)
+            .await
+            .map_err(map_adt_rest_error)?;
+        parse_transport_requests(&response.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("list_transports", message))
+        })
+    })
+    .await
+}
+
+pub async fn neuro_create_transport_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroTransportCreateRequest,
+) -> Result<NeuroTransportAssignment, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_create_transport", async {
+        let object_uri = normalize_object_uri(&request.object_uri).map_err(invalid_argument)?;
+        if request.package.trim().is_empty() || request.description.trim().is_empty() {
+            return Err(invalid_argument(
+                "creating a transport requires a package and a description".to_string(),
+            ));
+        }
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        ensure_writable(&runtime.safety, "transport requests are disabled")?;
+
+        let created: Result<String, NeuroRuntimeError> = async {
+            let response = runtime
+                .adt
+                .send(
+                    "create_transport",
+                    AdtRequest::post(CREATE_TRANSPORT_PATH)
+                        .content_type(CREATE_TRANSPORT_CONTENT_TYPE)
+                        .accept("text/plain")
+                        .body(create_transport_request_xml(
+                            &object_uri,
+                            &request.package,
+                            &request.description,
+                        )),
+                )
+                .await
+                .map_err(map_adt_rest_error)?;
+            parse_created_transport(&response.body).map_err(|message| {
+                map_adt_rest_error(AdtRestError::invalid_response("create_transport", message))
+            })
+        }
+        .await;
+        let mut audit = SapWriteAudit::new("create_transport", Some(&object_uri));
+        audit.transport = created.as_ref().ok().cloned();
+        audit.record(&runtime, created.as_ref().map(|_| None));
+        let transport = created?;
+        runtime.transports.assign(&object_uri, &transport).await;
+        Ok(NeuroTransportAssignment {
+            object_uri,
+            transport,
+            package: Some(request.package.trim().to_ascii_uppercase()),
+            recording_required: true,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_assign_transport_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroTransportAssignRequest,
+) -> Result<NeuroTransportAssignment, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_assign_transport", async {
+        let object_uri = normalize_object_uri(&request.object_uri).map_err(invalid_argument)?;
+        let transport = normalize_transport_number(&request.transport).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        ensure_writable(&runtime.safety, "transport requests are disabled")?;
+
+        let check = run_transport_check(&runtime, &object_uri).await?;
+        if let Some(locked) = check
+            .locked_transport
+            .as_deref()
+            .filter(|locked| *locked != transport)
+        {
+            return Err(runtime_error(
+                NeuroRuntimeErrorCode::InvalidArgument,
+                format!("`{object_uri}` is already locked in transport request `{locked}`"),
+                Some(json!({ "objectUri": object_uri, "transport": locked })),
+            ));
+        }
+        runtime.transports.assign(&object_uri, &transport).await;
+        Ok(NeuroTransportAssignment {
+            object_uri,
+            transport,
+            package: check.package,
+            recording_required: check.recording_required,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_syntax_check_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroSyntaxCheckRequest,
+) -> Result<NeuroSyntaxCheckResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_syntax_check", async {
+        let object_uri = normalize_object_uri(&request.object_uri).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        run_syntax_check(&runtime, &object_uri, request.source.as_deref()).await
+    })
+    .await
+}
+
+pub async fn neuro_activate_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroActivateRequest,
+    confirmation: SapWriteConfirmation,
+) -> Result<NeuroActivateResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_activate", async {
+        let mut object_uris = Vec::new();
+        for object_uri in &request.object_uris {
+            let object_uri = normalize_object_uri(object_uri).map_err(invalid_argument)?;
+            if !object_uris.contains(&object_uri) {
+                object_uris.push(object_uri);
+            }
+        }
+        if object_uris.is_empty() {
+            return Err(invalid_argument(
+                "activation requires at least one object URI".to_string(),
+            ));
+        }
+
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        ensure_writable(&runtime.safety, "activation is disabled")?;
+        if runtime.config.safety.require_confirmation_for_updates {
+            let target = object_uris.join(", ");
+            confirm_sap_write(
+                &confirmation,
+                &target,
+                format!("Activate {target}"),
+                object_uris.join("\n"),
+            )
+            .await?;
+        }
+
+        let activated: Result<NeuroActivateResponse, NeuroRuntimeError> = async {
+            let response = runtime
+                .adt
+                .send(
+                    "activate",
+                    AdtRequest::post(ACTIVATION_PATH)
+                        .query("method", "activate")
+                        .query("preauditRequested", "true")
+                        .content_type("application/xml")
+                        .accept("application/xml")
+                        .body(activation_request_xml(&object_uris)),
+                )
+                .await
+                .map_err(map_adt_rest_error)?;
+            let result = parse_activation_result(&response.body).map_err(|message| {
+                map_adt_rest_error(AdtRestError::invalid_response("activate", message))
+            })?;
+            Ok(group_activation_result(&object_uris, result))
+        }
+        .await;
+        invalidate_cache_after_write(&runtime, None);
+        match &activated {
+            Ok(response) => {
+                for object in &response.objects {
+                    let audit = SapWriteAudit::new("activate", Some(&object.object_uri));
+                    if object.activated {
+                        audit.record(&runtime, Ok(None));
+                    } else {
+                        let inactive = runtime_error(
+                            NeuroRuntimeErrorCode::InvalidArgument,
+                            "object was not activated".to_string(),
+                            None,
+                        );
+                        audit.record(&runtime, Err(&inactive));
+                    }
+                }
+            }
+            Err(error) => {
+                for object_uri in &object_uris {
+                    SapWriteAudit::new("activate", Some(object_uri)).record(&runtime, Err(error));
+                }
+            }
+        }
+        activated
+    })
+    .await
+}
+
+pub async fn neuro_atc_run_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroAtcRunRequest,
+) -> Result<NeuroAtcRunResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_atc_run", async {
+        let object_uris = atc_object_set(&request).map_err(invalid_argument)?;
+        let check_variant = resolve_check_variant(request.check_variant.as_deref());
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+
+        let worklist = runtime
+            .adt
+            .send(
+                "atc_create_worklist",
+                AdtRequest::post(ATC_WORKLISTS_PATH)
+                    .query("checkVariant", &check_variant)
+                    .accept("text/plain"),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        let worklist_id = parse_worklist_id(&worklist.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response(
+                "atc_create_worklist",
+                message,
+            ))
+        })?;
+
+        runtime
+            .adt
+            .send(
+                "atc_run",
+                AdtRequest::post(ATC_RUNS_PATH)
+                    .query("worklistId", &worklist_id)
+                    .content_type("application/xml")
+                    .accept("application/xml")
+                    .body(atc_run_request_xml(
+                        &object_uris,
+                        resolve_max_findings(request.max_findings),
+                    )),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+
+        let deadline = Instant::now() + atc_timeout_from_env();
+        let worklist_path = format!("{ATC_WORKLISTS_PATH}/{worklist_id}");
+        loop {
+            let response = runtime
+                .adt
+                .send(
+                    "atc_worklist",
+                    AdtRequest::get(&worklist_path)
+                        .query("includeExemptedFindings", "false")
+                        .accept(ATC_WORKLIST_ACCEPT),
+                )
+                .await
+                .map_err(map_adt_rest_error)?;
+            let result = parse_atc_worklist(&response.body).map_err(|message| {
+                map_adt_rest_error(AdtRestError::invalid_response("atc_worklist", message))
+            })?;
+            if result.complete || Instant::now() + ATC_POLL_INTERVAL > deadline {
+                return Ok(NeuroAtcRunResponse {
+                    worklist_id,
+                    check_variant,
+                    complete: result.complete,
+                    findings: result.findings,
+                });
+            }
+            tokio::time::sleep(ATC_POLL_INTERVAL).await;
+        }
+    })
+    .await
+}
+
+pub async fn neuro_run_unit_tests_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroUnitTestRequest,
+) -> Result<NeuroUnitTestResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_run_unit_tests", async {
+        let object_uris = unit_test_object_uris(&request.object_uris).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let harmless_only = runtime.safety.read_only;
+
+        let response = runtime
+            .adt
+            .send(
+                "run_unit_tests",
+                AdtRequest::post(UNIT_TEST_RUNS_PATH)
+                    .content_type(UNIT_TEST_RUN_CONTENT_TYPE)
+                    .accept("application/xml")
+                    .body(unit_test_run_xml(
+                        &object_uris,
+                        request.with_coverage,
+                        harmless_only,
+                    )),
+            )
+            .await
+            .map_err(map_adt_rest_error)?;
+        let run = parse_unit_test_result(&response.body).map_err(|message| {
+            map_adt_rest_error(AdtRestError::invalid_response("run_unit_tests", message))
+        })?;
+
+        let mut coverage = Vec::new();
+        if let Some(coverage_uri) = run
+            .coverage_uri
+            .as_deref()
+            .filter(|_| request.with_coverage)
+        {
+            let response = runtime
+                .adt
+                .send(
+                    "unit_test_coverage",
+                    AdtRequest::post(coverage_uri)
+                        .content_type("application/xml")
+                        .accept("application/xml")
+                        .body(coverage_query_xml(&object_uris)),
+                )
+                .await
+                .map_err(map_adt_rest_error)?;
+            coverage = parse_coverage_result(&response.body, &object_uris).map_err(|message| {
+                map_adt_rest_error(AdtRestError::invalid_response(
+                    "unit_test_coverage",
+                    message,
+                ))
+            })?;
+        }
+
+        Ok(unit_test_response(run, coverage))
+    })
+    .await
+}
+
+pub async fn neuro_lock_object_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<NeuroObjectLock, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_lock_object", async {
+        let object_uri = normalize_object_uri(&object_uri).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        ensure_writable(&runtime.safety, "object locks are disabled")?;
+
+        let lock = runtime
+            .locks
+            .lock(&runtime.adt, &object_uri)
+            .await
+            .map_err(map_adt_rest_error)?;
+        spawn_lock_refresher(&runtime.runtime);
+        Ok(lock)
+    })
+    .await
+}
+
+pub async fn neuro_unlock_object_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    object_uri: String,
+) -> Result<NeuroObjectUnlockResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_unlock_object", async {
+        let object_uri = normalize_object_uri(&object_uri).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        let released = runtime
+            .locks
+            .unlock(&runtime.adt, &object_uri)
+            .await
+            .map_err(map_adt_rest_error)?;
+        Ok(NeuroObjectUnlockResponse {
+            object_uri,
+            released,
+        })
+    })
+    .await
+}
+
+pub async fn neuro_ws_request_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: WsDomainRequest,
+) -> Result<WsMessageEnvelope<Value>, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_ws_request", async {
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        runtime
+            .engine
+            .send_domain_request(
+                request.domain.as_str(),
+                request.action.as_str(),
+                request.payload,
+            )
+            .await
+            .map_err(map_engine_error)
+    })
+    .await
+}
+
+pub async fn neuro_ws_subscribe_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    request: NeuroWsSubscribeRequest,
+) -> Result<NeuroWsSubscription, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_ws_subscribe", async {
+        let domain = request.domain.trim().to_string();
+        if domain.is_empty() {
+            return Err(invalid_argument(
+                "websocket subscription requires a `domain`".to_string(),
+            ));
+        }
+        let filter = normalize_subscription_filter(request.filter).map_err(invalid_argument)?;
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        if runtime.config.ws.url.is_none() {
+            return Err(map_engine_error(NeuroEngineError::WsUnavailable));
+        }
+        ensure_ws_domain_allowed(&runtime.safety, &domain)?;
+
+        let outcome =
+            state
+                .neuro_ws_subscriptions
+                .subscribe(&runtime.connection_id, domain, filter);
+        match outcome.start_worker {
+            Some(generation) => spawn_ws_subscription_worker(
+                app,
+                runtime.connection_id.clone(),
+                generation,
+                Arc::clone(&outcome.sink),
+            ),
+            // A disconnected worker sends it when it resubscribes.
+            None => {
+                send_frame(
+                    &outcome.sink,
+                    subscription_frame(&outcome.subscription, "subscribe"),
+                )
+                .await
+                .map_err(|message| {
+                    runtime_error(NeuroRuntimeErrorCode::WsUnavailable, message, None)
+                })?;
+            }
+        }
+        Ok(outcome.subscription)
+    })
+    .await
+}
+
+pub async fn neuro_ws_unsubscribe_impl(
+    state: State<'_, AppState>,
+    subscription_id: String,
+) -> Result<NeuroWsUnsubscribeResponse, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_ws_unsubscribe", async {
+        let subscription_id = subscription_id.trim().to_string();
+        let removed = state.neuro_ws_subscriptions.unsubscribe(&subscription_id);
+        if let Some((subscription, sink, last)) = &removed {
+            let _ = send_frame(sink, subscription_frame(subscription, "unsubscribe")).await;
+            if *last {
+                close_sink(sink).await;
+            }
+        }
+        Ok(NeuroWsUnsubscribeResponse {
+            subscription_id,
+            removed: removed.is_some(),
+        })
+    })
+    .await
+}
+
+pub async fn neuro_list_tools_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+) -> Result<Vec<NeuroToolSpec>, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_list_tools", async {
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        Ok(NeuroMcpFacade::new(runtime.engine.clone()).list_tools())
+    })
+    .await
+}
+
+pub async fn neuro_invoke_tool_impl(
+    state: State<'_, AppState>,
+    connection_id: Option<String>,
+    tool_name: String,
+    arguments: Value,
+    confirmed: bool,
+) -> Result<Value, NeuroRuntimeError> {
+    run_with_neuro_command_telemetry("neuro_invoke_tool", async {
+        let runtime = acquire_runtime(state.inner(), connection_id.as_deref()).await?;
+        if !neuro_tool_writes(&tool_name) {
+            return NeuroMcpFacade::new(runtime.engine.clone())
+                .invoke(tool_name.as_str(), arguments)
+                .await
+                .map_err(map_mcp_error);
+        }
+
+        let argument = |keys: [&str; 2]| {
+            keys.iter()
+                .find_map(|key| arguments.get(*key).and_then(Value::as_str))
+                .map(str::to_string)
+        };
+        let object_uri = argument(["object_uri", "objectUri"]);
+        let source = argument(["source", "content"]);
+        let mut audit =
+            SapWriteAudit::new(format!("invoke_tool:{tool_name}"), object_uri.as_deref());
+        audit.etag = argument(["etag", "eTag"]);
+        audit.source_sha256 = source.as_deref().map(sha256_hex);
+        audit.transport = argument(["transport", "corr_nr"]);
+
+        let result: Result<Value, NeuroRuntimeError> = async {
+            if runtime.config.safety.require_confirmation_for_updates {
+                let target = object_uri.clone().unwrap_or_else(|| tool_name.clone());
+                let diff = match (&object_uri, &source) {
+                    (Some(object_uri), Some(source)) => {
+                        let current = runtime
+                            .engine
+                            .get_source(object_uri.as_str())
+                            .await
+                            .map_err(map_engine_error)?;
+                        source_diff(object_uri, &current.source, source)
+                    }
+                    _ => serde_json::to_string_pretty(&arguments).unwrap_or_default(),
+                };
+                confirm_sap_write(
+                    &SapWriteConfirmation::Caller { confirmed },
+                    &target,
+                    format!("Run {tool_name} on {target}"),
+                    diff,
+                )
+                .await?;
+            }
+            NeuroMcpFacade::new(runtime.engine.clone())
+                .invoke(tool_name.as_str(), arguments.clone())
+                .await
+                .map_err(map_mcp_error)
+        }
+        .await;
+        invalidate_cache_after_write(&runtime, object_uri.as_deref());
+        audit.record(
+            &runtime,
+            result.as_ref().map(|value| {
+                value
+                    .get("etag")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            }),
+        );
+        result
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neuro_test_fixtures::{
+        fake_lock_result_xml, unused_local_url, CsrfMode, FakeAdtServer, FakeResponse, FakeWsMode,
+        FakeWsServer, FAKE_LOCK_HANDLE, FAKE_PROGRAM_ETAG, FAKE_PROGRAM_SOURCE, FAKE_PROGRAM_URI,
+        FAKE_SAP_PASSWORD, FAKE_SAP_USER, FAKE_SEARCH_PATH, FAKE_SEARCH_RESULTS_XML,
+        FAKE_WS_PUSH_ACTION,
+    };
+    use std::time::Duration;
 
     fn env_lock() -> &'static Mutex<()> {
         static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
@@ -920,7 +3566,7 @@ mod tests {
         serde_json::from_str(&events.remove(0)).expect("telemetry payload should be valid JSON")
     }
 
-    fn with_env_overrides(pairs: &[(&str, Option<&str>)], test: impl FnOnce()) {
+    fn with_env_overrides<T>(pairs: &[(&str, Option<&str>)], test: impl FnOnce() -> T) -> T {
         let _guard = env_lock().lock().expect("env lock poisoned");
 
         let previous: Vec<(String, Option<String>)> = pairs
@@ -935,7 +3581,7 @@ mod tests {
             }
         }
 
-        test();
+        let result = test();
 
         for (key, value) in previous {
             match value {
@@ -943,6 +3589,7 @@ mod tests {
                 None => env::remove_var(&key),
             }
         }
+        result
     }
 
     #[test]
@@ -1007,6 +3654,230 @@ mod tests {
         });
     }
 
+    #[test]
+    fn profile_runtime_config_reads_password_from_named_env() {
+        with_env_overrides(&[("ALICIA_TEST_QAS_PASSWORD", Some(" qas-pass "))], || {
+            let profile = NeuroConnectionProfile {
+                id: "qas".to_string(),
+                name: "QAS".to_string(),
+                sap_url: "https://qas.sap.local".to_string(),
+                user: Some("tester".to_string()),
+                password_env: Some("ALICIA_TEST_QAS_PASSWORD".to_string()),
+                client: Some("200".to_string()),
+                language: None,
+                insecure_tls: false,
+                timeout_secs: None,
+                csrf_fetch_path: None,
+                search_objects_path: None,
+                ws_url: None,
+                ws_timeout_secs: Some(5),
+                safety: crate::neuro_connection_runtime::NeuroConnectionSafety {
+                    read_only: true,
+                    ..Default::default()
+                },
+            };
+            let config = runtime_config_from_profile(&profile);
+
+            assert!(config.sap.is_ready());
+            assert_eq!(
+                config
+                    .sap
+                    .password
+                    .as_ref()
+                    .map(|value| value.value.as_str()),
+                Some("qas-pass")
+            );
+            assert_eq!(
+                config.sap.password.as_ref().map(|value| value.source),
+                Some("profile.passwordEnv")
+            );
+            assert_eq!(config.sap.timeout_secs, DEFAULT_ADT_TIMEOUT_SECS);
+            assert_eq!(config.sap.timeout_source, None);
+            assert_eq!(config.sap.search_objects_path.source, "default");
+            assert_eq!(config.ws.timeout_secs, 5);
+            assert!(config.safety.read_only);
+        });
+    }
+
+    #[test]
+    fn secret_store_values_fill_gaps_without_leaking_into_metadata() {
+        with_env_overrides(
+            &[
+                ("NEURO_SAP_URL", Some("https://neuro.local")),
+                ("NEURO_SAP_USER", Some("neuro-user")),
+                ("NEURO_SAP_PASSWORD", None),
+                ("SAP_PASSWORD", None),
+                ("SAP_PASS", None),
+            ],
+            || {
+                let mut config = resolve_runtime_config().expect("runtime config should resolve");
+                apply_connection_secrets(
+                    &mut config,
+                    ConnectionSecrets {
+                        sap_password: Some("vault-password".to_string()),
+                        ws_headers: BTreeMap::from([(
+                            "x-api-key".to_string(),
+                            "vault-token".to_string(),
+                        )]),
+                    },
+                );
+
+                assert!(config.sap.is_ready());
+                assert_eq!(
+                    config.sap.password.as_ref().map(|value| value.source),
+                    Some("secret_store")
+                );
+
+                let metadata = serde_json::to_string(&config_metadata(&config)).unwrap();
+                assert!(metadata.contains("\"sapPasswordSource\":\"secret_store\""));
+                assert!(metadata.contains("x-api-key"));
+                assert!(!metadata.contains("vault-password"));
+                assert!(!metadata.contains("vault-token"));
+            },
+        );
+    }
+
+    #[test]
+    fn unlocking_the_secret_store_retires_runtimes_built_while_locked() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        let secrets_path = env::temp_dir()
+            .join(format!("neuro-unlock-{}", std::process::id()))
+            .join("secrets.json");
+        let _ = std::fs::remove_file(&secrets_path);
+
+        with_fake_sap_env(
+            adt.base_url(),
+            None,
+            FAKE_SAP_PASSWORD,
+            &[
+                ("NEURO_SAP_PASSWORD", None),
+                ("SAP_PASSWORD", None),
+                ("SAP_PASS", None),
+                ("NEURO_SECRETS_PATH", secrets_path.to_str()),
+            ],
+            || {
+                let app = mock_app_with_state();
+                let state = app.state::<AppState>();
+                rt.block_on(async {
+                    neuro_secret_create_impl(app.state(), "master-pass".to_string())
+                        .await
+                        .expect("secret store should be created");
+                    neuro_secret_set_impl(
+                        app.state(),
+                        NeuroSecretSetRequest {
+                            connection_id: None,
+                            name: "sap_password".to_string(),
+                            value: FAKE_SAP_PASSWORD.to_string(),
+                        },
+                    )
+                    .await
+                    .expect("password should be stored");
+                    neuro_secret_lock_impl(app.state())
+                        .await
+                        .expect("secret store should lock");
+
+                    let locked = acquire_runtime(state.inner(), None)
+                        .await
+                        .expect("runtime should initialize while locked");
+                    assert!(locked.config.sap.password.is_none());
+                    drop(locked);
+
+                    neuro_secret_unlock_impl(app.state(), "master-pass".to_string())
+                        .await
+                        .expect("secret store should unlock");
+                    let unlocked = acquire_runtime(state.inner(), None)
+                        .await
+                        .expect("runtime should reinitialize after unlock");
+                    assert_eq!(
+                        unlocked
+                            .config
+                            .sap
+                            .password
+                            .as_ref()
+                            .map(|value| value.source),
+                        Some("secret_store")
+                    );
+                });
+            },
+        );
+        let _ = std::fs::remove_file(&secrets_path);
+    }
+
+    #[tokio::test]
+    async fn drain_waits_for_in_flight_leases() {
+        let in_flight = Arc::new(RwLock::new(()));
+        let lease = Arc::clone(&in_flight).read_owned().await;
+
+        assert!(!drain_in_flight(&in_flight, Duration::from_millis(20)).await);
+
+        drop(lease);
+        assert!(drain_in_flight(&in_flight, Duration::from_millis(20)).await);
+    }
+
+    #[test]
+    fn locked_updates_apply_runtime_safety_policy() {
+        let mut safety = SafetyConfig {
+            read_only: false,
+            read_only_source: None,
+            blocked_source_patterns: vec!["DELETE FROM".to_string()],
+            blocked_source_patterns_source: Some("NEURO_SAFETY_BLOCKED_PATTERNS"),
+            blocked_entity_patterns: Vec::new(),
+            blocked_entity_patterns_source: None,
+            allowed_ws_domains: Vec::new(),
+            allowed_ws_domains_source: None,
+            require_etag_for_updates: true,
+            require_etag_for_updates_source: Some("NEURO_UPDATE_REQUIRE_ETAG"),
+            syntax_check_before_update: false,
+            syntax_check_before_update_source: None,
+            require_transport_for_updates: false,
+            require_transport_for_updates_source: None,
+            require_confirmation_for_updates: false,
+            require_confirmation_for_updates_source: None,
+        };
+        let request = |source: &str, etag: Option<&str>| neuro_types::AdtUpdateSourceRequest {
+            object_uri: "/sap/bc/adt/programs/programs/ztest".to_string(),
+            source: source.to_string(),
+            etag: etag.map(str::to_string),
+        };
+
+        let policy = safety_policy(&safety);
+        assert!(check_update_safety(&policy, &request("WRITE 'ok'.", Some("etag-1"))).is_ok());
+
+        let error = check_update_safety(&policy, &request("WRITE 'ok'.", None))
+            .expect_err("missing etag must fail");
+        assert_eq!(error.code, NeuroRuntimeErrorCode::SafetyViolation);
+
+        let error = check_update_safety(&policy, &request("delete from ztab.", Some("etag-1")))
+            .expect_err("blocked pattern must fail");
+        assert!(error.message.contains("DELETE FROM"));
+
+        safety.read_only = true;
+        assert!(check_update_safety(
+            &safety_policy(&safety),
+            &request("WRITE 'ok'.", Some("etag-1"))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn recognizes_declined_writes() {
+        let declined = runtime_error(
+            NeuroRuntimeErrorCode::SafetyViolation,
+            "write to `/sap/bc/adt/programs/programs/ztest` was declined".to_string(),
+            Some(
+                json!({ "objectUri": "/sap/bc/adt/programs/programs/ztest", "confirmation": "declined" }),
+            ),
+        );
+        assert!(is_declined_write(&declined));
+        let failed = runtime_error(
+            NeuroRuntimeErrorCode::SafetyViolation,
+            "blocked".to_string(),
+            None,
+        );
+        assert!(!is_declined_write(&failed));
+    }
+
     #[tokio::test]
     async fn telemetry_reports_success_without_changing_result() {
         let _guard = telemetry_lock().lock().expect("telemetry lock poisoned");
@@ -1101,4 +3972,864 @@ mod tests {
             Some(&error_code_value(&NeuroRuntimeErrorCode::RuntimeInitError))
         );
     }
+
+    fn fixture_runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("test runtime should build")
+    }
+
+    /// Points the default connection at the fake servers for the duration of `test`.
+    fn with_fake_sap<T>(
+        adt_url: &str,
+        ws_url: Option<&str>,
+        password: &str,
+        test: impl FnOnce() -> T,
+    ) -> T {
+        with_fake_sap_env(adt_url, ws_url, password, &[], test)
+    }
+
+    fn with_fake_sap_env<T>(
+        adt_url: &str,
+        ws_url: Option<&str>,
+        password: &str,
+        extra: &[(&str, Option<&str>)],
+        test: impl FnOnce() -> T,
+    ) -> T {
+        let _telemetry = telemetry_lock().lock().expect("telemetry lock poisoned");
+        let scratch = env::temp_dir().join(format!("neuro-fixture-{}", std::process::id()));
+        let secrets_path = scratch.join("secrets.json");
+        let audit_path = scratch.join("audit.jsonl");
+        let mut pairs = vec![
+            ("NEURO_SAP_URL", Some(adt_url)),
+            ("SAP_URL", None),
+            ("NEURO_SAP_USER", Some(FAKE_SAP_USER)),
+            ("NEURO_SAP_PASSWORD", Some(password)),
+            ("NEURO_SAP_CLIENT", None),
+            ("NEURO_WS_URL", ws_url),
+            ("SAP_WS_URL", None),
+            ("NEURO_WS_TIMEOUT_SECS", Some("1")),
+            ("NEURO_SAFETY_READ_ONLY", Some("false")),
+            ("NEURO_SECRET_BACKEND", Some("file")),
+            ("NEURO_SECRET_PASSPHRASE", None),
+            ("NEURO_SECRETS_PATH", secrets_path.to_str()),
+            ("NEURO_AUDIT_LOG_PATH", audit_path.to_str()),
+            ("NEURO_CACHE_TTL_SECS", None),
+            ("NEURO_CACHE_MAX_ENTRIES", None),
+        ];
+        pairs.extend_from_slice(extra);
+        let result = with_env_overrides(&pairs, test);
+        drain_test_telemetry_events();
+        result
+    }
+
+    fn mock_app_with_state() -> tauri::App<tauri::test::MockRuntime> {
+        let app = tauri::test::mock_app();
+        app.manage(AppState::default());
+        app
+    }
+
+    fn status_code(error: &NeuroRuntimeError) -> Option<u64> {
+        error
+            .details
+            .as_ref()
+            .and_then(|details| details.get("statusCode"))
+            .and_then(Value::as_u64)
+    }
+
+    #[test]
+    fn search_and_get_source_run_against_fake_adt() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        adt.route(
+            "GET",
+            FAKE_SEARCH_PATH,
+            FakeResponse::ok(FAKE_SEARCH_RESULTS_XML),
+        );
+        adt.route(
+            "GET",
+            &format!("{FAKE_PROGRAM_URI}/source/main"),
+            FakeResponse::ok(FAKE_PROGRAM_SOURCE).etag(FAKE_PROGRAM_ETAG),
+        );
+
+        with_fake_sap(adt.base_url(), None, FAKE_SAP_PASSWORD, || {
+            let app = mock_app_with_state();
+            let results = rt
+                .block_on(neuro_search_objects_impl(
+                    app.state(),
+                    None,
+                    "ZTEST*".to_string(),
+                    Some(10),
+                ))
+                .expect("search should succeed");
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].name, "ZTEST");
+            assert_eq!(results[0].uri, FAKE_PROGRAM_URI);
+
+            let source = rt
+                .block_on(neuro_get_source_impl(
+                    app.state(),
+                    None,
+                    FAKE_PROGRAM_URI.to_string(),
+                ))
+                .expect("get_source should succeed");
+            assert_eq!(source.source, FAKE_PROGRAM_SOURCE);
+            assert_eq!(source.etag.as_deref(), Some(FAKE_PROGRAM_ETAG));
+        });
+
+        let search = adt
+            .requests()
+            .into_iter()
+            .find(|request| request.path == FAKE_SEARCH_PATH)
+            .expect("search request should reach the fake server");
+        assert!(search.query.contains("ZTEST"));
+    }
+
+    #[test]
+    fn retiring_a_runtime_drains_without_holding_the_init_gate() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+
+        with_fake_sap(adt.base_url(), None, FAKE_SAP_PASSWORD, || {
+            let app = mock_app_with_state();
+            let state = app.state::<AppState>();
+            let state = state.inner();
+            rt.block_on(async {
+                let lease = acquire_runtime(state, None)
+                    .await
+                    .expect("runtime should initialize");
+                let retired = Arc::clone(&lease.runtime);
+
+                let (outcome, fresh) = tokio::join!(retire_cached_runtime(state, "env"), async {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    assert!(state.neuro_runtime_init_gate.try_lock().is_ok());
+                    let fresh = get_or_init(state, None)
+                        .await
+                        .expect("a fresh runtime should initialize while draining");
+                    drop(lease);
+                    fresh
+                });
+
+                assert_eq!(outcome, (true, true));
+                assert!(!Arc::ptr_eq(&retired, &fresh));
+            });
+        });
+    }
+
+    #[test]
+    fn get_sources_revalidates_cached_sources_with_etag() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        adt.route(
+            "GET",
+            &format!("{FAKE_PROGRAM_URI}/source/main"),
+            FakeResponse::ok(FAKE_PROGRAM_SOURCE).etag(FAKE_PROGRAM_ETAG),
+        );
+        let missing_uri = "/sap/bc/adt/oo/classes/zcl_missing";
+
+        with_fake_sap_env(
+            adt.base_url(),
+            None,
+            FAKE_SAP_PASSWORD,
+            &[("NEURO_CACHE_TTL_SECS", Some("300"))],
+            || {
+                let app = mock_app_with_state();
+                let entries = rt
+                    .block_on(neuro_get_sources_impl(
+                        app.state(),
+                        None,
+                        vec![FAKE_PROGRAM_URI.to_string(), missing_uri.to_string()],
+                    ))
+                    .expect("batch should succeed even if one object fails");
+                assert_eq!(entries.len(), 2);
+                assert_eq!(entries[0].object_uri, FAKE_PROGRAM_URI);
+                assert!(!entries[0].cached);
+                assert_eq!(
+                    entries[0]
+                        .source
+                        .as_ref()
+                        .and_then(|source| source.etag.as_deref()),
+                    Some(FAKE_PROGRAM_ETAG)
+                );
+                assert_eq!(entries[1].object_uri, missing_uri);
+                assert_eq!(
+                    entries[1].error.as_ref().map(|error| &error.code),
+                    Some(&NeuroRuntimeErrorCode::AdtHttpError)
+                );
+
+                let entries = rt
+                    .block_on(neuro_get_sources_impl(
+                        app.state(),
+                        None,
+                        vec![format!("{FAKE_PROGRAM_URI}/source/main")],
+                    ))
+                    .expect("second read should succeed");
+                assert!(entries[0].cached);
+                assert_eq!(
+                    entries[0]
+                        .source
+                        .as_ref()
+                        .map(|source| source.source.as_str()),
+                    Some(FAKE_PROGRAM_SOURCE)
+                );
+
+                let error = rt
+                    .block_on(neuro_get_sources_impl(app.state(), None, Vec::new()))
+                    .expect_err("empty batch should be rejected");
+                assert_eq!(error.code, NeuroRuntimeErrorCode::InvalidArgument);
+            },
+        );
+
+        let revalidation = adt
+            .requests()
+            .into_iter()
+            .find(|request| request.header("if-none-match").is_some())
+            .expect("fresh entries should be revalidated too");
+        assert_eq!(
+            revalidation.header("if-none-match"),
+            Some(FAKE_PROGRAM_ETAG)
+        );
+    }
+
+    #[test]
+    fn versions_and_source_diff_run_against_fake_adt() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        let version_id = "20260105093000/00001";
+        adt.route(
+            "GET",
+            &format!("{FAKE_PROGRAM_URI}/source/main/versions"),
+            FakeResponse::ok(format!(
+                r#"<atom:feed xmlns:atom="http://www.w3.org/2005/Atom">
+                  <atom:entry>
+                    <atom:author><atom:name>{FAKE_SAP_USER}</atom:name></atom:author>
+                    <atom:content type="text/plain" src="{FAKE_PROGRAM_URI}/source/main/versions/{version_id}/content"/>
+                    <atom:title>Initial version</atom:title>
+                  </atom:entry>
+                </atom:feed>"#
+            )),
+        );
+        adt.route(
+            "GET",
+            &format!("{FAKE_PROGRAM_URI}/source/main/versions/{version_id}/content"),
+            FakeResponse::ok("REPORT ztest.\n"),
+        );
+        adt.route(
+            "GET",
+            &format!("{FAKE_PROGRAM_URI}/source/main"),
+            FakeResponse::ok(FAKE_PROGRAM_SOURCE).etag(FAKE_PROGRAM_ETAG),
+        );
+
+        with_fake_sap(adt.base_url(), None, FAKE_SAP_PASSWORD, || {
+            let app = mock_app_with_state();
+            let list = rt
+                .block_on(neuro_list_versions_impl(
+                    app.state(),
+                    None,
+                    FAKE_PROGRAM_URI.to_string(),
+                ))
+                .expect("versions feed should parse");
+            assert_eq!(list.versions.len(), 1);
+            assert_eq!(list.versions[0].version_id, version_id);
+            assert_eq!(list.versions[0].author.as_deref(), Some(FAKE_SAP_USER));
+
+            let version = rt
+                .block_on(neuro_get_version_impl(
+                    app.state(),
+                    None,
+                    FAKE_PROGRAM_URI.to_string(),
+                    version_id.to_string(),
+                ))
+                .expect("version content should load");
+            assert_eq!(version.source, "REPORT ztest.\n");
+
+            let unchanged = rt
+                .block_on(neuro_diff_source_impl(
+                    app.state(),
+                    None,
+                    FAKE_PROGRAM_URI.to_string(),
+                    FAKE_PROGRAM_SOURCE.to_string(),
+                    Some(NeuroServerVersion::Active),
+                ))
+                .expect("diff should succeed");
+            assert!(!unchanged.changed);
+            assert!(unchanged.diff.is_empty());
+            assert_eq!(unchanged.etag.as_deref(), Some(FAKE_PROGRAM_ETAG));
+
+            let changed = rt
+                .block_on(neuro_diff_source_impl(
+                    app.state(),
+                    None,
+                    FAKE_PROGRAM_URI.to_string(),
+                    format!("{FAKE_PROGRAM_SOURCE}* local change\n"),
+                    None,
+                ))
+                .expect("diff should succeed");
+            assert!(changed.changed);
+            assert_eq!(changed.version, NeuroServerVersion::Inactive);
+            assert!(changed
+                .diff
+                .starts_with(&format!("--- a{FAKE_PROGRAM_URI}\n")));
+            assert!(changed.diff.contains("\n+* local change\n"));
+        });
+
+        let queries = adt
+            .requests()
+            .into_iter()
+            .filter(|request| request.path == format!("{FAKE_PROGRAM_URI}/source/main"))
+            .map(|request| request.query)
+            .collect::<Vec<_>>();
+        assert_eq!(queries, ["version=active", "version=inactive"]);
+    }
+
+    #[test]
+    fn where_used_and_navigation_run_against_fake_adt() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        adt.route(
+            "POST",
+            WHERE_USED_PATH,
+            FakeResponse::ok(
+                r#"<usageReferences:usageReferenceResult xmlns:usageReferences="http://www.sap.com/adt/ris/usageReferences" xmlns:adtcore="http://www.sap.com/adt/core">
+                  <usageReferences:referencedObjects>
+                    <usageReferences:referencedObject uri="/sap/bc/adt/programs/programs/zcaller" isResult="true" usageInformation="gradeDirect">
+                      <usageReferences:adtObject adtcore:name="ZCALLER" adtcore:type="PROG/P"/>
+                    </usageReferences:referencedObject>
+                  </usageReferences:referencedObjects>
+                </usageReferences:usageReferenceResult>"#,
+            ),
+        );
+        adt.route(
+            "GET",
+            &format!("{FAKE_PROGRAM_URI}/source/main"),
+            FakeResponse::ok(FAKE_PROGRAM_SOURCE).etag(FAKE_PROGRAM_ETAG),
+        );
+        adt.route(
+            "POST",
+            NAVIGATION_PATH,
+            FakeResponse::ok(format!(
+                r#"<adtcore:objectReference xmlns:adtcore="http://www.sap.com/adt/core" adtcore:uri="{FAKE_PROGRAM_URI}/source/main#start=1,7" adtcore:type="PROG/P" adtcore:name="ZTEST"/>"#
+            )),
+        );
+
+        with_fake_sap(adt.base_url(), None, FAKE_SAP_PASSWORD, || {
+            let app = mock_app_with_state();
+            let used = rt
+                .block_on(neuro_where_used_impl(
+                    app.state(),
+                    None,
+                    format!("{FAKE_PROGRAM_URI}/source/main"),
+                ))
+                .expect("where-used list should parse");
+            assert_eq!(used.object_uri, FAKE_PROGRAM_URI);
+            assert_eq!(used.references.len(), 1);
+            assert_eq!(used.references[0].name.as_deref(), Some("ZCALLER"));
+
+            let navigation = rt
+                .block_on(neuro_navigate_definition_impl(
+                    app.state(),
+                    None,
+                    NeuroNavigateRequest {
+                        object_uri: FAKE_PROGRAM_URI.to_string(),
+                        line: 2,
+                        column: 0,
+                        source: None,
+                    },
+                ))
+                .expect("navigation should resolve");
+            let target = navigation.target.expect("definition should be found");
+            assert_eq!(target.position.object_uri, FAKE_PROGRAM_URI);
+            assert_eq!(target.position.line, Some(1));
+        });
+
+        let requests = adt.requests();
+        let where_used = requests
+            .iter()
+            .find(|request| request.path == WHERE_USED_PATH)
+            .expect("where-used request should be sent");
+        assert!(where_used
+            .query
+            .contains("uri=%2Fsap%2Fbc%2Fadt%2Fprograms%2Fprograms%2Fztest"));
+        let navigation = requests
+            .iter()
+            .find(|request| request.path == NAVIGATION_PATH)
+            .expect("navigation request should be sent");
+        assert!(navigation.query.contains("filter=definition"));
+        assert!(navigation.query.contains("%23start%3D2%2C0"));
+        assert_eq!(navigation.body, FAKE_PROGRAM_SOURCE);
+    }
+
+    #[test]
+    fn ddic_table_falls_back_to_structure_against_fake_adt() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        adt.route(
+            "GET",
+            "/sap/bc/adt/ddic/structures/zorder_admin/source/main",
+            FakeResponse::ok(
+                "@EndUserText.label : 'Admin fields'\ndefine structure zorder_admin {\n  created_by : syuname;\n  created_at : timestampl;\n}\n",
+            ),
+        );
+
+        with_fake_sap(adt.base_url(), None, FAKE_SAP_PASSWORD, || {
+            let app = mock_app_with_state();
+            let structure = rt
+                .block_on(neuro_ddic_table_impl(
+                    app.state(),
+                    None,
+                    "zorder_admin".to_string(),
+                ))
+                .expect("structure should be read after the table lookup misses");
+            assert_eq!(structure.name, "ZORDER_ADMIN");
+            assert_eq!(structure.kind, NeuroDdicTableKind::Structure);
+            assert_eq!(structure.description.as_deref(), Some("Admin fields"));
+            assert_eq!(structure.fields.len(), 2);
+            assert_eq!(structure.fields[0].data_element.as_deref(), Some("SYUNAME"));
+
+            let error = rt
+                .block_on(neuro_ddic_domain_impl(
+                    app.state(),
+                    None,
+                    "zmissing".to_string(),
+                ))
+                .expect_err("unknown domain should fail");
+            assert_eq!(error.code, NeuroRuntimeErrorCode::AdtHttpError);
+            assert_eq!(status_code(&error), Some(404));
+
+            let error = rt
+                .block_on(neuro_cds_view_impl(app.state(), None, "../x".to_string()))
+                .expect_err("invalid name should be rejected before any request");
+            assert_eq!(error.code, NeuroRuntimeErrorCode::InvalidArgument);
+        });
+
+        let paths = adt
+            .requests()
+            .into_iter()
+            .filter(|request| !request.is_csrf_fetch())
+            .map(|request| request.path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "/sap/bc/adt/ddic/tables/zorder_admin/source/main",
+                "/sap/bc/adt/ddic/structures/zorder_admin/source/main",
+                "/sap/bc/adt/ddic/domains/zmissing",
+            ]
+        );
+    }
+
+    #[test]
+    fn data_preview_is_capped_and_obeys_blocked_entities() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        adt.route(
+            "POST",
+            "/sap/bc/adt/datapreview/freestyle",
+            FakeResponse::ok(
+                r#"<dataPreview:tableData xmlns:dataPreview="http://www.sap.com/adt/dataPreview">
+                  <dataPreview:totalRows>1</dataPreview:totalRows>
+                  <dataPreview:columns>
+                    <dataPreview:metadata dataPreview:name="COUNT" dataPreview:colType="INT8"/>
+                    <dataPreview:dataSet><dataPreview:data>42</dataPreview:data></dataPreview:dataSet>
+                  </dataPreview:columns>
+                </dataPreview:tableData>"#,
+            ),
+        );
+
+        with_fake_sap_env(
+            adt.base_url(),
+            None,
+            FAKE_SAP_PASSWORD,
+            &[
+                ("NEURO_SAFETY_BLOCKED_ENTITIES", Some("PA*")),
+                ("NEURO_SAFETY_BLOCKED_PATTERNS", Some("vbak")),
+                ("NEURO_DATA_PREVIEW_MAX_ROWS", Some("50")),
+            ],
+            || {
+                let app = mock_app_with_state();
+                let preview = rt
+                    .block_on(neuro_data_preview_impl(
+                        app.state(),
+                        None,
+                        NeuroDataPreviewRequest {
+                            query: Some("SELECT COUNT(*) AS count FROM vbak".to_string()),
+                            entity: None,
+                            max_rows: Some(10_000),
+                        },
+                    ))
+                    .expect("select should run");
+                assert_eq!(preview.columns[0].name, "COUNT");
+                assert_eq!(preview.rows, vec![vec!["42".to_string()]]);
+                assert!(!preview.truncated);
+
+                let error = rt
+                    .block_on(neuro_data_preview_impl(
+                        app.state(),
+                        None,
+                        NeuroDataPreviewRequest {
+                            query: None,
+                            entity: Some("pa0008".to_string()),
+                            max_rows: None,
+                        },
+                    ))
+                    .expect_err("blocked entity should be refused");
+                assert_eq!(error.code, NeuroRuntimeErrorCode::SafetyViolation);
+
+                let error = rt
+                    .block_on(neuro_data_preview_impl(
+                        app.state(),
+                        None,
+                        NeuroDataPreviewRequest {
+                            query: Some(
+                                "SELECT * FROM vbak AS v INNER JOIN pa0001 AS p ON v~ernam = p~uname"
+                                    .to_string(),
+                            ),
+                            entity: None,
+                            max_rows: None,
+                        },
+                    ))
+                    .expect_err("joined blocked entity should be refused");
+                assert_eq!(error.code, NeuroRuntimeErrorCode::SafetyViolation);
+
+                let error = rt
+                    .block_on(neuro_data_preview_impl(
+                        app.state(),
+                        None,
+                        NeuroDataPreviewRequest {
+                            query: Some(
+                                "SELECT FROM zcds_emp AS e FIELDS e~pernr, \\_payroll-amount"
+                                    .to_string(),
+                            ),
+                            entity: None,
+                            max_rows: None,
+                        },
+                    ))
+                    .expect_err("association paths should be refused");
+                assert_eq!(error.code, NeuroRuntimeErrorCode::SafetyViolation);
+                assert_eq!(
+                    error
+                        .details
+                        .as_ref()
+                        .and_then(|details| details.get("pathExpression")),
+                    Some(&json!(true))
+                );
+
+                let error = rt
+                    .block_on(neuro_data_preview_impl(
+                        app.state(),
+                        None,
+                        NeuroDataPreviewRequest {
+                            query: Some("UPDATE vbak SET gbstk = 'C'".to_string()),
+                            entity: None,
+                            max_rows: None,
+                        },
+                    ))
+                    .expect_err("non-SELECT statements should be refused");
+                assert_eq!(error.code, NeuroRuntimeErrorCode::InvalidArgument);
+            },
+        );
+
+        let previews = adt
+            .requests()
+            .into_iter()
+            .filter(|request| request.path.starts_with("/sap/bc/adt/datapreview"))
+            .collect::<Vec<_>>();
+        assert_eq!(previews.len(), 1);
+        assert!(previews[0].query.contains("rowNumber=50"));
+        assert_eq!(previews[0].body, "SELECT COUNT(*) AS count FROM vbak");
+    }
+
+    #[test]
+    fn rejected_logon_and_forbidden_objects_map_to_adt_auth_error() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        adt.route(
+            "GET",
+            FAKE_SEARCH_PATH,
+            FakeResponse::ok(FAKE_SEARCH_RESULTS_XML),
+        );
+        adt.route(
+            "GET",
+            &format!("{FAKE_PROGRAM_URI}/source/main"),
+            FakeResponse::status(403).body("No authorization for S_DEVELOP"),
+        );
+
+        with_fake_sap(adt.base_url(), None, "wrong-password", || {
+            let app = mock_app_with_state();
+            let error = rt
+                .block_on(neuro_search_objects_impl(
+                    app.state(),
+                    None,
+                    "ZTEST*".to_string(),
+                    None,
+                ))
+                .expect_err("wrong credentials should fail");
+            assert_eq!(error.code, NeuroRuntimeErrorCode::AdtAuthError);
+            assert_eq!(status_code(&error), Some(401));
+
+            let runtime = rt
+                .block_on(get_or_init(app.state().inner(), None))
+                .expect("runtime should initialize");
+            let error = rt
+                .block_on(runtime.locks.lock(&runtime.adt, FAKE_PROGRAM_URI))
+                .map_err(map_adt_rest_error)
+                .expect_err("CSRF fetch should be rejected");
+            assert_eq!(error.code, NeuroRuntimeErrorCode::AdtAuthError);
+            assert_eq!(error.details.as_ref().unwrap()["operation"], "csrf_fetch");
+        });
+
+        with_fake_sap(adt.base_url(), None, FAKE_SAP_PASSWORD, || {
+            let app = mock_app_with_state();
+            let error = rt
+                .block_on(neuro_get_source_impl(
+                    app.state(),
+                    None,
+                    FAKE_PROGRAM_URI.to_string(),
+                ))
+                .expect_err("missing authorization should fail");
+            assert_eq!(error.code, NeuroRuntimeErrorCode::AdtAuthError);
+            assert_eq!(status_code(&error), Some(403));
+        });
+    }
+
+    #[test]
+    fn etag_conflict_on_update_maps_to_adt_http_error() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        adt.route(
+            "POST",
+            FAKE_PROGRAM_URI,
+            FakeResponse::ok(fake_lock_result_xml(Some("DEVK900123"))),
+        );
+        adt.route(
+            "PUT",
+            &format!("{FAKE_PROGRAM_URI}/source/main"),
+            FakeResponse::status(412).body("Resource was modified in the meantime"),
+        );
+
+        with_fake_sap_env(
+            adt.base_url(),
+            None,
+            FAKE_SAP_PASSWORD,
+            &[
+                ("NEURO_UPDATE_SYNTAX_CHECK", Some("false")),
+                ("NEURO_UPDATE_REQUIRE_TRANSPORT", Some("false")),
+                ("NEURO_UPDATE_REQUIRE_CONFIRMATION", Some("false")),
+            ],
+            || {
+                let app = mock_app_with_state();
+                let lock = rt
+                    .block_on(neuro_lock_object_impl(
+                        app.state(),
+                        None,
+                        FAKE_PROGRAM_URI.to_string(),
+                    ))
+                    .expect("lock should succeed");
+                assert_eq!(lock.lock_handle, FAKE_LOCK_HANDLE);
+                assert_eq!(lock.transport.as_deref(), Some("DEVK900123"));
+
+                let error = rt
+                    .block_on(neuro_update_source_impl(
+                        app.state(),
+                        None,
+                        NeuroUpdateSourceRequest {
+                            update: neuro_types::AdtUpdateSourceRequest {
+                                object_uri: FAKE_PROGRAM_URI.to_string(),
+                                source: "REPORT ztest.\n".to_string(),
+                                etag: Some("20250101000000001".to_string()),
+                            },
+                            transport: None,
+                        },
+                        None,
+                        SapWriteConfirmation::Caller { confirmed: false },
+                    ))
+                    .expect_err("stale ETag should be rejected");
+                assert_eq!(error.code, NeuroRuntimeErrorCode::AdtHttpError);
+                assert_eq!(status_code(&error), Some(412));
+                assert_eq!(
+                    error.details.as_ref().unwrap()["operation"],
+                    "update_source"
+                );
+            },
+        );
+
+        let put = adt
+            .requests()
+            .into_iter()
+            .find(|request| request.method == "PUT")
+            .expect("update should reach the fake server");
+        assert_eq!(put.header("if-match"), Some("20250101000000001"));
+        assert!(put
+            .query
+            .contains(&format!("lockHandle={FAKE_LOCK_HANDLE}")));
+        assert!(put.query.contains("corrNr=DEVK900123"));
+        assert_eq!(put.body, "REPORT ztest.\n");
+    }
+
+    #[test]
+    fn expired_csrf_token_is_fetched_again_once() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::ExpireOnce));
+        adt.route(
+            "POST",
+            FAKE_PROGRAM_URI,
+            FakeResponse::ok(fake_lock_result_xml(None)),
+        );
+
+        let state = AppState::default();
+        with_fake_sap(adt.base_url(), None, FAKE_SAP_PASSWORD, || {
+            let runtime = rt
+                .block_on(get_or_init(&state, None))
+                .expect("runtime should initialize");
+            let lock = rt
+                .block_on(runtime.locks.lock(&runtime.adt, FAKE_PROGRAM_URI))
+                .expect("lock should succeed after refetching the token");
+            assert!(lock.is_local);
+        });
+
+        // Only the stateful session used for locks; the engine keeps its own session.
+        let stateful_fetches = adt
+            .requests()
+            .iter()
+            .filter(|request| {
+                request.is_csrf_fetch()
+                    && request.header("x-sap-adt-sessiontype") == Some("stateful")
+            })
+            .count();
+        assert_eq!(stateful_fetches, 2);
+        let lock_calls = adt
+            .requests()
+            .into_iter()
+            .filter(|request| request.method == "POST")
+            .collect::<Vec<_>>();
+        assert_eq!(lock_calls.len(), 2);
+        assert!(lock_calls[0].query.contains("_action=LOCK"));
+        assert_eq!(lock_calls[1].header("x-csrf-token"), Some("fake-csrf-2"));
+    }
+
+    #[test]
+    fn missing_csrf_token_maps_to_adt_csrf_error() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Missing));
+
+        let state = AppState::default();
+        with_fake_sap(adt.base_url(), None, FAKE_SAP_PASSWORD, || {
+            let runtime = rt
+                .block_on(get_or_init(&state, None))
+                .expect("runtime should initialize");
+            let error = rt
+                .block_on(runtime.locks.lock(&runtime.adt, FAKE_PROGRAM_URI))
+                .map_err(map_adt_rest_error)
+                .expect_err("lock without CSRF token should fail");
+            assert_eq!(error.code, NeuroRuntimeErrorCode::AdtCsrfError);
+        });
+        assert!(adt.requests().iter().all(|request| request.method == "GET"));
+    }
+
+    fn ws_ping() -> WsDomainRequest {
+        WsDomainRequest {
+            domain: "abap".to_string(),
+            action: "ping".to_string(),
+            payload: json!({ "probe": "fixture" }),
+        }
+    }
+
+    #[test]
+    fn silent_ws_server_maps_to_ws_timeout() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        let ws = rt.block_on(FakeWsServer::start(FakeWsMode::Silent));
+
+        with_fake_sap(adt.base_url(), Some(ws.url()), FAKE_SAP_PASSWORD, || {
+            let app = mock_app_with_state();
+            let error = rt
+                .block_on(neuro_ws_request_impl(app.state(), None, ws_ping()))
+                .expect_err("silent server should time out");
+            assert_eq!(error.code, NeuroRuntimeErrorCode::WsTimeout);
+            assert_eq!(error.details.as_ref().unwrap()["timeoutSecs"], json!(1));
+        });
+        assert!(ws.received().iter().any(|frame| frame.contains("fixture")));
+    }
+
+    #[test]
+    fn closed_refused_or_missing_ws_maps_to_ws_unavailable() {
+        let rt = fixture_runtime();
+        let adt = rt.block_on(FakeAdtServer::start(CsrfMode::Issue));
+        let closing = rt.block_on(FakeWsServer::start(FakeWsMode::CloseImmediately));
+        let refused = rt.block_on(unused_local_url("ws"));
+
+        for ws_url in [Some(closing.url()), Some(refused.as_str()), None] {
+            with_fake_sap(adt.base_url(), ws_url, FAKE_SAP_PASSWORD, || {
+                let app = mock_app_with_state();
+                let error = rt
+                    .block_on(neuro_ws_request_impl(app.state(), None, ws_ping()))
+                    .expect_err("request without a usable WebSocket should fail");
+                assert_eq!(
+                    error.code,
+                    NeuroRuntimeErrorCode::WsUnavailable,
+                    "unexpected error for {ws_url:?}: {}",
+                    error.message
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn ws_subscriber_receives_pushed_events_after_each_resubscribe() {
+        let rt = fixture_runtime();
+        let ws = rt.block_on(FakeWsServer::start(FakeWsMode::PushThenClose));
+        let registry = NeuroWsSubscriptionRegistry::default();
+        let outcome = registry.subscribe(
+            "fixture",
+            "locks".to_string(),
+            Some(json!({ "action": FAKE_WS_PUSH_ACTION })),
+        );
+        let other = registry.subscribe(
+            "fixture",
+            "locks".to_string(),
+            Some(json!({ "action": "released" })),
+        );
+
+        // The fake server drops the socket after every push, like a reconnecting SAP side.
+        for _ in 0..2 {
+            let socket = rt
+                .block_on(connect_subscriber(&WsClientConfig {
+                    url: ws.url().to_string(),
+                    request_timeout_secs: 1,
+                    connect_headers: BTreeMap::new(),
+                }))
+                .expect("fake WS should accept the subscriber");
+            let (writer, mut reader) = socket.split();
+            *outcome.sink.blocking_lock() = Some(writer);
+            let sent = rt
+                .block_on(send_frame(
+                    &outcome.sink,
+                    subscription_frame(&outcome.subscription, "subscribe"),
+                ))
+                .expect("subscribe frame should be sent");
+            assert!(sent);
+
+            let Some(Ok(Message::Text(text))) = rt.block_on(reader.next()) else {
+                panic!("fake WS should push an event");
+            };
+            let envelope: Value = serde_json::from_str(text.as_str()).unwrap();
+            assert_eq!(
+                registry.matching("fixture", &envelope),
+                vec![outcome.subscription.subscription_id.clone()]
+            );
+            rt.block_on(close_sink(&outcome.sink));
+        }
+
+        let subscribes = ws
+            .received()
+            .into_iter()
+            .filter(|frame| frame.contains(&outcome.subscription.subscription_id))
+            .count();
+        assert_eq!(subscribes, 2);
+        assert_eq!(registry.subscriptions("fixture").len(), 2);
+        assert!(registry
+            .unsubscribe(&other.subscription.subscription_id)
+            .is_some());
+    }
 }
@@ -0,0 +1,626 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const SECRET_SAP_PASSWORD: &str = "sap_password";
+pub const SECRET_WS_HEADER_PREFIX: &str = "ws_header.";
+const SECRETS_FILE_NAME: &str = "neuro-secrets.json";
+const KEYRING_SERVICE: &str = "alicia-neuro";
+const KEYRING_PROBE_USER: &str = "__alicia_probe__";
+const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+const PASSPHRASE_CHECK_NAME: &str = "__check__";
+const PASSPHRASE_CHECK_VALUE: &str = "alicia-neuro-secrets";
+const KEY_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NeuroSecretBackend {
+    File,
+    Keyring,
+}
+
+/// Holds the vault key derived from the master passphrase while the store is unlocked.
+#[derive(Clone, Default)]
+pub struct NeuroSecretSession {
+    key: Option<[u8; KEY_LEN]>,
+}
+
+#[derive(Debug, Default)]
+pub struct ConnectionSecrets {
+    pub sap_password: Option<String>,
+    pub ws_headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSecretStatus {
+    pub backend: NeuroSecretBackend,
+    pub initialized: bool,
+    pub unlocked: bool,
+    pub path: String,
+    pub names: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSecretSetRequest {
+    pub connection_id: Option<String>,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSecretClearRequest {
+    pub connection_id: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedSecret {
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SecretVault {
+    kdf_iterations: u32,
+    salt: String,
+    check: EncryptedSecret,
+    #[serde(default)]
+    secrets: BTreeMap<String, EncryptedSecret>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SecretStoreFile {
+    #[serde(default)]
+    vault: Option<SecretVault>,
+    /// Names of entries kept in the OS keyring, which cannot be enumerated portably.
+    #[serde(default)]
+    keyring_entries: Vec<String>,
+}
+
+pub fn secret_store_path() -> PathBuf {
+    if let Some(path) = env::var_os("NEURO_SECRETS_PATH") {
+        if !path.is_empty() {
+            return PathBuf::from(path);
+        }
+    }
+    crate::config_runtime::resolve_codex_home().join(SECRETS_FILE_NAME)
+}
+
+fn keyring_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_PROBE_USER)
+            .and_then(|entry| match entry.get_password() {
+                Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(error) => Err(error),
+            })
+            .is_ok()
+    })
+}
+
+pub fn resolve_secret_backend() -> Result<NeuroSecretBackend, String> {
+    let requested = env::var("NEURO_SECRET_BACKEND").unwrap_or_default();
+    match requested.trim().to_ascii_lowercase().as_str() {
+        "" | "auto" => Ok(if keyring_available() {
+            NeuroSecretBackend::Keyring
+        } else {
+            NeuroSecretBackend::File
+        }),
+        "file" => Ok(NeuroSecretBackend::File),
+        "keyring" if keyring_available() => Ok(NeuroSecretBackend::Keyring),
+        "keyring" => Err("NEURO_SECRET_BACKEND=keyring but no OS keyring is available".to_string()),
+        other => Err(format!(
+            "invalid NEURO_SECRET_BACKEND `{other}`: expected auto, file or keyring"
+        )),
+    }
+}
+
+pub fn validate_secret_name(name: &str) -> Result<String, String> {
+    let name = name.trim().to_ascii_lowercase();
+    if name == SECRET_SAP_PASSWORD {
+        return Ok(name);
+    }
+    if let Some(header) = name.strip_prefix(SECRET_WS_HEADER_PREFIX) {
+        let header = header.replace('_', "-");
+        if !header.is_empty()
+            && header
+                .chars()
+                .all(|entry| entry.is_ascii_alphanumeric() || entry == '-')
+        {
+            return Ok(format!("{SECRET_WS_HEADER_PREFIX}{header}"));
+        }
+    }
+    Err(format!(
+        "unsupported secret name `{name}`: expected `{SECRET_SAP_PASSWORD}` or `{SECRET_WS_HEADER_PREFIX}<header>`"
+    ))
+}
+
+pub fn scoped_secret_name(connection_id: &str, name: &str) -> String {
+    format!("{connection_id}/{name}")
+}
+
+fn load_store_file(path: &Path) -> Result<SecretStoreFile, String> {
+    if !path.is_file() {
+        return Ok(SecretStoreFile::default());
+    }
+
+    let raw = fs::read_to_string(path)
+        .map_err(|error| format!("failed to read `{}`: {error}", path.display()))?;
+    if raw.trim().is_empty() {
+        return Ok(SecretStoreFile::default());
+    }
+    serde_json::from_str(&raw)
+        .map_err(|error| format!("failed to parse `{}`: {error}", path.display()))
+}
+
+fn save_store_file(path: &Path, file: &SecretStoreFile) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create `{}`: {error}", parent.display()))?;
+    }
+
+    let serialized = serde_json::to_string_pretty(file)
+        .map_err(|error| format!("failed to serialize neuro secrets: {error}"))?;
+    let staging = path.with_extension("json.tmp");
+    fs::write(&staging, serialized)
+        .map_err(|error| format!("failed to write `{}`: {error}", staging.display()))?;
+    fs::rename(&staging, path)
+        .map_err(|error| format!("failed to replace `{}`: {error}", path.display()))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<[u8; KEY_LEN], String> {
+    let iterations = NonZeroU32::new(iterations)
+        .ok_or_else(|| "secret store has an invalid KDF iteration count".to_string())?;
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    Ok(key)
+}
+
+fn seal(key: &[u8; KEY_LEN], name: &str, plaintext: &str) -> Result<EncryptedSecret, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "failed to generate secret nonce".to_string())?;
+    let sealing_key = UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| "invalid secret store key".to_string())?;
+
+    let mut in_out = plaintext.as_bytes().to_vec();
+    sealing_key
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(name.as_bytes()),
+            &mut in_out,
+        )
+        .map_err(|_| format!("failed to encrypt secret `{name}`"))?;
+
+    Ok(EncryptedSecret {
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(in_out),
+    })
+}
+
+fn open(key: &[u8; KEY_LEN], name: &str, secret: &EncryptedSecret) -> Result<String, String> {
+    let nonce: [u8; NONCE_LEN] = BASE64
+        .decode(&secret.nonce)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("secret `{name}` has a malformed nonce"))?;
+    let mut in_out = BASE64
+        .decode(&secret.ciphertext)
+        .map_err(|_| format!("secret `{name}` has malformed ciphertext"))?;
+    let opening_key = UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| "invalid secret store key".to_string())?;
+
+    let plaintext = opening_key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(name.as_bytes()),
+            &mut in_out,
+        )
+        .map_err(|_| format!("failed to decrypt secret `{name}`"))?;
+    String::from_utf8(plaintext.to_vec()).map_err(|_| format!("secret `{name}` is not valid UTF-8"))
+}
+
+fn unlock_vault(path: &Path, passphrase: &str) -> Result<[u8; KEY_LEN], String> {
+    if passphrase.is_empty() {
+        return Err("master passphrase must not be empty".to_string());
+    }
+
+    let file = load_store_file(path)?;
+    let Some(vault) = file.vault.as_ref() else {
+        return Err(
+            "secret store is not initialized: create it with a master passphrase first".to_string(),
+        );
+    };
+    let salt = BASE64
+        .decode(&vault.salt)
+        .map_err(|_| "secret store has a malformed salt".to_string())?;
+    let key = derive_key(passphrase, &salt, vault.kdf_iterations)?;
+    match open(&key, PASSPHRASE_CHECK_NAME, &vault.check) {
+        Ok(check) if check == PASSPHRASE_CHECK_VALUE => Ok(key),
+        _ => Err("incorrect master passphrase".to_string()),
+    }
+}
+
+fn create_vault_with_iterations(
+    path: &Path,
+    passphrase: &str,
+    iterations: u32,
+) -> Result<[u8; KEY_LEN], String> {
+    if passphrase.is_empty() {
+        return Err("master passphrase must not be empty".to_string());
+    }
+
+    let mut file = load_store_file(path)?;
+    if file.vault.is_some() {
+        return Err("secret store already exists: unlock it instead".to_string());
+    }
+
+    let mut salt = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| "failed to generate secret store salt".to_string())?;
+    let key = derive_key(passphrase, &salt, iterations)?;
+    file.vault = Some(SecretVault {
+        kdf_iterations: iterations,
+        salt: BASE64.encode(salt),
+        check: seal(&key, PASSPHRASE_CHECK_NAME, PASSPHRASE_CHECK_VALUE)?,
+        secrets: BTreeMap::new(),
+    });
+    save_store_file(path, &file)?;
+    Ok(key)
+}
+
+fn vault_set(path: &Path, key: &[u8; KEY_LEN], name: &str, value: &str) -> Result<(), String> {
+    let mut file = load_store_file(path)?;
+    let Some(vault) = file.vault.as_mut() else {
+        return Err("secret store is not initialized".to_string());
+    };
+    vault
+        .secrets
+        .insert(name.to_string(), seal(key, name, value)?);
+    save_store_file(path, &file)
+}
+
+fn vault_clear(path: &Path, name: &str) -> Result<bool, String> {
+    let mut file = load_store_file(path)?;
+    let removed = file
+        .vault
+        .as_mut()
+        .map(|vault| vault.secrets.remove(name).is_some())
+        .unwrap_or(false);
+    if removed {
+        save_store_file(path, &file)?;
+    }
+    Ok(removed)
+}
+
+fn vault_read_prefixed(
+    path: &Path,
+    key: &[u8; KEY_LEN],
+    prefix: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    let file = load_store_file(path)?;
+    let Some(vault) = file.vault else {
+        return Ok(BTreeMap::new());
+    };
+
+    vault
+        .secrets
+        .iter()
+        .filter(|(name, _)| name.starts_with(prefix))
+        .map(|(name, secret)| Ok((name[prefix.len()..].to_string(), open(key, name, secret)?)))
+        .collect()
+}
+
+fn keyring_entry(name: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, name)
+        .map_err(|error| format!("failed to open keyring entry `{name}`: {error}"))
+}
+
+fn keyring_set(path: &Path, name: &str, value: &str) -> Result<(), String> {
+    keyring_entry(name)?
+        .set_password(value)
+        .map_err(|error| format!("failed to store `{name}` in the OS keyring: {error}"))?;
+
+    let mut file = load_store_file(path)?;
+    if !file.keyring_entries.iter().any(|entry| entry == name) {
+        file.keyring_entries.push(name.to_string());
+        file.keyring_entries.sort();
+        save_store_file(path, &file)?;
+    }
+    Ok(())
+}
+
+fn keyring_clear(path: &Path, name: &str) -> Result<bool, String> {
+    let removed = match keyring_entry(name)?.delete_credential() {
+        Ok(()) => true,
+        Err(keyring::Error::NoEntry) => false,
+        Err(error) => {
+            return Err(format!(
+                "failed to remove `{name}` from the OS keyring: {error}"
+            ))
+        }
+    };
+
+    let mut file = load_store_file(path)?;
+    let before = file.keyring_entries.len();
+    file.keyring_entries.retain(|entry| entry != name);
+    if file.keyring_entries.len() != before {
+        save_store_file(path, &file)?;
+    }
+    Ok(removed)
+}
+
+fn keyring_read_prefixed(path: &Path, prefix: &str) -> Result<BTreeMap<String, String>, String> {
+    let file = load_store_file(path)?;
+    let mut secrets = BTreeMap::new();
+    for name in file
+        .keyring_entries
+        .iter()
+        .filter(|name| name.starts_with(prefix))
+    {
+        match keyring_entry(name)?.get_password() {
+            Ok(value) => {
+                secrets.insert(name[prefix.len()..].to_string(), value);
+            }
+            Err(keyring::Error::NoEntry) => {}
+            Err(error) => {
+                return Err(format!(
+                    "failed to read `{name}` from the OS keyring: {error}"
+                ))
+            }
+        }
+    }
+    Ok(secrets)
+}
+
+fn ensure_unlocked_from_env(session: &mut NeuroSecretSession, path: &Path) -> Result<(), String> {
+    if session.key.is_some() {
+        return Ok(());
+    }
+    let Some(passphrase) = env::var("NEURO_SECRET_PASSPHRASE")
+        .ok()
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(());
+    };
+    if load_store_file(path)?.vault.is_some() {
+        session.key = Some(unlock_vault(path, &passphrase)?);
+    }
+    Ok(())
+}
+
+pub fn secret_status(session: &NeuroSecretSession) -> Result<NeuroSecretStatus, String> {
+    let backend = resolve_secret_backend()?;
+    let path = secret_store_path();
+    let file = load_store_file(&path)?;
+
+    let (initialized, unlocked, names) = match backend {
+        NeuroSecretBackend::Keyring => (true, true, file.keyring_entries),
+        NeuroSecretBackend::File => match file.vault {
+            Some(vault) => (
+                true,
+                session.key.is_some(),
+                vault.secrets.into_keys().collect(),
+            ),
+            None => (false, false, Vec::new()),
+        },
+    };
+
+    Ok(NeuroSecretStatus {
+        backend,
+        initialized,
+        unlocked,
+        path: path.display().to_string(),
+        names,
+    })
+}
+
+/// Creates the encrypted file store; fails when one already exists.
+pub fn create_secret_store(
+    session: &mut NeuroSecretSession,
+    passphrase: &str,
+) -> Result<(), String> {
+    session.key = Some(create_vault_with_iterations(
+        &secret_store_path(),
+        passphrase,
+        DEFAULT_KDF_ITERATIONS,
+    )?);
+    Ok(())
+}
+
+pub fn unlock_secret_store(
+    session: &mut NeuroSecretSession,
+    passphrase: &str,
+) -> Result<(), String> {
+    session.key = Some(unlock_vault(&secret_store_path(), passphrase)?);
+    Ok(())
+}
+
+pub fn lock_secret_store(session: &mut NeuroSecretSession) {
+    session.key = None;
+}
+
+pub fn set_secret(
+    session: &mut NeuroSecretSession,
+    connection_id: &str,
+    name: &str,
+    value: &str,
+) -> Result<(), String> {
+    let name = scoped_secret_name(connection_id, &validate_secret_name(name)?);
+    if value.is_empty() {
+        return Err(format!("secret `{name}` must not be empty"));
+    }
+
+    let path = secret_store_path();
+    match resolve_secret_backend()? {
+        NeuroSecretBackend::Keyring => keyring_set(&path, &name, value),
+        NeuroSecretBackend::File => {
+            ensure_unlocked_from_env(session, &path)?;
+            let key = session.key.as_ref().ok_or_else(|| {
+                "secret store is locked: unlock it with the master passphrase first".to_string()
+            })?;
+            vault_set(&path, key, &name, value)
+        }
+    }
+}
+
+pub fn clear_secret(connection_id: &str, name: &str) -> Result<bool, String> {
+    let name = scoped_secret_name(connection_id, &validate_secret_name(name)?);
+    let path = secret_store_path();
+    match resolve_secret_backend()? {
+        NeuroSecretBackend::Keyring => keyring_clear(&path, &name),
+        NeuroSecretBackend::File => vault_clear(&path, &name),
+    }
+}
+
+/// Returns the stored secrets for a connection, or nothing while the file store is locked.
+pub fn read_connection_secrets(
+    session: &mut NeuroSecretSession,
+    connection_id: &str,
+) -> Result<ConnectionSecrets, String> {
+    let path = secret_store_path();
+    let prefix = scoped_secret_name(connection_id, "");
+    let entries = match resolve_secret_backend()? {
+        NeuroSecretBackend::Keyring => keyring_read_prefixed(&path, &prefix)?,
+        NeuroSecretBackend::File => {
+            ensure_unlocked_from_env(session, &path)?;
+            match session.key.as_ref() {
+                Some(key) => vault_read_prefixed(&path, key, &prefix)?,
+                None => BTreeMap::new(),
+            }
+        }
+    };
+
+    Ok(split_connection_secrets(entries))
+}
+
+fn split_connection_secrets(entries: BTreeMap<String, String>) -> ConnectionSecrets {
+    let mut secrets = ConnectionSecrets::default();
+    for (name, value) in entries {
+        if name == SECRET_SAP_PASSWORD {
+            secrets.sap_password = Some(value);
+        } else if let Some(header) = name.strip_prefix(SECRET_WS_HEADER_PREFIX) {
+            secrets.ws_headers.insert(header.to_string(), value);
+        }
+    }
+    secrets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        create_vault_with_iterations, load_store_file, split_connection_secrets, unlock_vault,
+        validate_secret_name, vault_clear, vault_read_prefixed, vault_set,
+    };
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn temp_store_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "alicia-neuro-secrets-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("neuro-secrets.json")
+    }
+
+    #[test]
+    fn validates_secret_names() {
+        assert_eq!(
+            validate_secret_name(" SAP_PASSWORD ").as_deref(),
+            Ok("sap_password")
+        );
+        assert_eq!(
+            validate_secret_name("ws_header.X_API_KEY").as_deref(),
+            Ok("ws_header.x-api-key")
+        );
+        assert!(validate_secret_name("ws_header.").is_err());
+        assert!(validate_secret_name("sap_user").is_err());
+    }
+
+    #[test]
+    fn vault_encrypts_values_and_rejects_wrong_passphrase() {
+        let path = temp_store_path("vault");
+        assert!(unlock_vault(&path, "correct horse").is_err());
+        let key = create_vault_with_iterations(&path, "correct horse", 1_000).expect("create");
+        assert!(create_vault_with_iterations(&path, "correct horse", 1_000).is_err());
+        vault_set(&path, &key, "dev/sap_password", "s3cret-value").expect("set");
+        vault_set(&path, &key, "dev/ws_header.x-token", "token").expect("set header");
+        vault_set(&path, &key, "qas/sap_password", "other").expect("set qas");
+
+        let raw = std::fs::read_to_string(&path).expect("store file");
+        assert!(!raw.contains("s3cret-value"));
+        assert!(!raw.contains("correct horse"));
+
+        assert!(unlock_vault(&path, "wrong").is_err());
+        let reopened = unlock_vault(&path, "correct horse").expect("open");
+        let dev = vault_read_prefixed(&path, &reopened, "dev/").expect("read dev");
+        assert_eq!(
+            dev.get("sap_password").map(String::as_str),
+            Some("s3cret-value")
+        );
+        assert_eq!(dev.len(), 2);
+
+        assert!(vault_clear(&path, "dev/sap_password").expect("clear"));
+        assert!(!vault_clear(&path, "dev/sap_password").expect("clear again"));
+        let vault = load_store_file(&path).unwrap().vault.expect("vault");
+        assert_eq!(vault.secrets.len(), 2);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn tampered_ciphertext_fails_to_decrypt() {
+        let path = temp_store_path("tamper");
+        let key = create_vault_with_iterations(&path, "pass", 1_000).expect("create");
+        vault_set(&path, &key, "env/sap_password", "value").expect("set");
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        let swapped = raw.replace("env/sap_password", "qas/sap_password");
+        std::fs::write(&path, swapped).unwrap();
+        assert!(vault_read_prefixed(&path, &key, "qas/").is_err());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn splits_connection_secrets_by_kind() {
+        let secrets = split_connection_secrets(BTreeMap::from([
+            ("sap_password".to_string(), "pw".to_string()),
+            (
+                "ws_header.authorization".to_string(),
+                "Bearer x".to_string(),
+            ),
+        ]));
+        assert_eq!(secrets.sap_password.as_deref(), Some("pw"));
+        assert_eq!(
+            secrets.ws_headers.get("authorization").map(String::as_str),
+            Some("Bearer x")
+        );
+    }
+}
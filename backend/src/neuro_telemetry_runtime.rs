@@ -0,0 +1,407 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::events_runtime::emit_neuro_telemetry_event;
+
+const TELEMETRY_FILE_NAME: &str = "neuro-telemetry.jsonl";
+const DEFAULT_TELEMETRY_MAX_BYTES: u64 = 5 * 1024 * 1024;
+const DEFAULT_TELEMETRY_MAX_FILES: usize = 3;
+/// Percentiles cover the most recent samples of each command only.
+const LATENCY_WINDOW: usize = 1024;
+/// Cache lookups are counted separately and not as commands.
+pub(crate) const NEURO_CACHE_TELEMETRY_EVENT: &str = "neuro.cache";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroCommandMetrics {
+    /// Command name, or the event name for events without one (e.g. `neuro.runtime_init`).
+    pub command: String,
+    pub count: u64,
+    pub error_count: u64,
+    pub error_rate: f64,
+    pub p50_latency_ms: Option<u64>,
+    pub p95_latency_ms: Option<u64>,
+    pub error_codes: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroErrorCodeMetrics {
+    pub error_code: String,
+    pub count: u64,
+    /// Share of all recorded events.
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroCacheMetrics {
+    /// `search` or `source`.
+    pub cache: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroTelemetrySummary {
+    pub since_epoch_secs: u64,
+    pub store_path: Option<String>,
+    pub total_count: u64,
+    pub error_count: u64,
+    pub error_rate: f64,
+    pub commands: Vec<NeuroCommandMetrics>,
+    pub error_codes: Vec<NeuroErrorCodeMetrics>,
+    pub caches: Vec<NeuroCacheMetrics>,
+}
+
+/// JSONL file that is rotated to `<path>.1`, `<path>.2`, ... once it reaches `max_bytes`.
+#[derive(Debug, Clone)]
+pub(crate) struct TelemetryStore {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+}
+
+impl TelemetryStore {
+    pub(crate) fn new(path: PathBuf, max_bytes: u64, max_files: usize) -> Self {
+        Self {
+            path,
+            max_bytes: max_bytes.max(1),
+            max_files,
+        }
+    }
+
+    fn from_env() -> Self {
+        let path = env::var_os("NEURO_TELEMETRY_PATH")
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                crate::config_runtime::resolve_codex_home().join(TELEMETRY_FILE_NAME)
+            });
+        let max_bytes = env::var("NEURO_TELEMETRY_MAX_BYTES")
+            .ok()
+            .and_then(|raw| raw.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TELEMETRY_MAX_BYTES);
+        let max_files = env::var("NEURO_TELEMETRY_MAX_FILES")
+            .ok()
+            .and_then(|raw| raw.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_TELEMETRY_MAX_FILES);
+        Self::new(path, max_bytes, max_files)
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+        let _ = fs::remove_file(self.rotated_path(self.max_files));
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))
+    }
+
+    pub(crate) fn append(&self, line: &str) -> Result<(), String> {
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("failed to create telemetry directory: {error}"))?;
+        }
+        let current_len = fs::metadata(&self.path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        if current_len > 0 && current_len + line.len() as u64 + 1 > self.max_bytes {
+            self.rotate()
+                .map_err(|error| format!("failed to rotate telemetry store: {error}"))?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{line}"))
+            .map_err(|error| {
+                format!(
+                    "failed to append to telemetry store {}: {error}",
+                    self.path.display()
+                )
+            })
+    }
+}
+
+#[derive(Debug, Default)]
+struct CommandStats {
+    count: u64,
+    error_count: u64,
+    latencies: VecDeque<u64>,
+    error_codes: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct TelemetryAggregates {
+    commands: BTreeMap<String, CommandStats>,
+    /// Cache name -> (hits, misses).
+    caches: BTreeMap<String, (u64, u64)>,
+}
+
+fn rate(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[u64], percent: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
+
+impl TelemetryAggregates {
+    pub(crate) fn record(&mut self, payload: &Value) {
+        if payload.get("event").and_then(Value::as_str) == Some(NEURO_CACHE_TELEMETRY_EVENT) {
+            let cache = payload
+                .get("cache")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown");
+            let counts = self.caches.entry(cache.to_string()).or_default();
+            counts.0 += payload.get("hits").and_then(Value::as_u64).unwrap_or(0);
+            counts.1 += payload.get("misses").and_then(Value::as_u64).unwrap_or(0);
+            return;
+        }
+        let Some(key) = payload
+            .get("command")
+            .or_else(|| payload.get("event"))
+            .and_then(Value::as_str)
+        else {
+            return;
+        };
+        let stats = self.commands.entry(key.to_string()).or_default();
+        stats.count += 1;
+        if let Some(latency) = payload.get("latencyMs").and_then(Value::as_u64) {
+            if stats.latencies.len() == LATENCY_WINDOW {
+                stats.latencies.pop_front();
+            }
+            stats.latencies.push_back(latency);
+        }
+        if payload.get("success").and_then(Value::as_bool) == Some(false) {
+            stats.error_count += 1;
+            let code = payload
+                .get("errorCode")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown");
+            *stats.error_codes.entry(code.to_string()).or_default() += 1;
+        }
+    }
+
+    pub(crate) fn summary(
+        &self,
+        since_epoch_secs: u64,
+        store_path: Option<String>,
+    ) -> NeuroTelemetrySummary {
+        let mut error_codes = BTreeMap::<String, u64>::new();
+        let commands = self
+            .commands
+            .iter()
+            .map(|(command, stats)| {
+                let mut latencies = stats.latencies.iter().copied().collect::<Vec<_>>();
+                latencies.sort_unstable();
+                for (code, count) in &stats.error_codes {
+                    *error_codes.entry(code.clone()).or_default() += count;
+                }
+                NeuroCommandMetrics {
+                    command: command.clone(),
+                    count: stats.count,
+                    error_count: stats.error_count,
+                    error_rate: rate(stats.error_count, stats.count),
+                    p50_latency_ms: percentile(&latencies, 50),
+                    p95_latency_ms: percentile(&latencies, 95),
+                    error_codes: stats.error_codes.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let total_count = commands.iter().map(|metrics| metrics.count).sum();
+        let error_count = commands.iter().map(|metrics| metrics.error_count).sum();
+        NeuroTelemetrySummary {
+            since_epoch_secs,
+            store_path,
+            total_count,
+            error_count,
+            error_rate: rate(error_count, total_count),
+            commands,
+            error_codes: error_codes
+                .into_iter()
+                .map(|(error_code, count)| NeuroErrorCodeMetrics {
+                    error_code,
+                    count,
+                    rate: rate(count, total_count),
+                })
+                .collect(),
+            caches: self
+                .caches
+                .iter()
+                .map(|(cache, (hits, misses))| NeuroCacheMetrics {
+                    cache: cache.clone(),
+                    hits: *hits,
+                    misses: *misses,
+                    hit_rate: rate(*hits, hits + misses),
+                })
+                .collect(),
+        }
+    }
+}
+
+struct TelemetrySink {
+    app: AppHandle,
+    started_at_epoch_secs: u64,
+    store: TelemetryStore,
+    aggregates: Mutex<TelemetryAggregates>,
+}
+
+static TELEMETRY_SINK: OnceLock<TelemetrySink> = OnceLock::new();
+
+fn now_epoch_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Telemetry emitted before this is only printed to stderr.
+pub(crate) fn install_telemetry_sink(app: AppHandle) {
+    let _ = TELEMETRY_SINK.set(TelemetrySink {
+        app,
+        started_at_epoch_secs: now_epoch_secs(),
+        store: TelemetryStore::from_env(),
+        aggregates: Mutex::new(TelemetryAggregates::default()),
+    });
+}
+
+pub(crate) fn record_telemetry(payload: &Value, line: &str) {
+    let Some(sink) = TELEMETRY_SINK.get() else {
+        return;
+    };
+    // The store is appended under the same lock so rotation never races a write.
+    if let Ok(mut aggregates) = sink.aggregates.lock() {
+        aggregates.record(payload);
+        if let Err(message) = sink.store.append(line) {
+            eprintln!("[neuro-telemetry] {message}");
+        }
+    }
+    emit_neuro_telemetry_event(&sink.app, payload);
+}
+
+pub(crate) fn telemetry_summary() -> NeuroTelemetrySummary {
+    match TELEMETRY_SINK.get() {
+        Some(sink) => sink
+            .aggregates
+            .lock()
+            .map(|aggregates| {
+                aggregates.summary(
+                    sink.started_at_epoch_secs,
+                    Some(sink.store.path.display().to_string()),
+                )
+            })
+            .unwrap_or_else(|_| {
+                TelemetryAggregates::default().summary(sink.started_at_epoch_secs, None)
+            }),
+        None => TelemetryAggregates::default().summary(now_epoch_secs(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{percentile, TelemetryAggregates, TelemetryStore};
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn aggregates_latency_percentiles_and_error_rates() {
+        let mut aggregates = TelemetryAggregates::default();
+        for latency in 1..=100u64 {
+            aggregates.record(&json!({
+                "event": "neuro.command",
+                "command": "neuro_get_source",
+                "success": latency % 10 != 0,
+                "latencyMs": latency,
+                "errorCode": if latency % 10 == 0 { Some("ws_timeout") } else { None },
+            }));
+        }
+        aggregates.record(&json!({
+            "event": "neuro.lock_refresh",
+            "success": false,
+            "errorCode": "adt_http_error",
+        }));
+        for (hits, misses) in [(3, 1), (0, 4)] {
+            aggregates.record(&json!({
+                "event": "neuro.cache",
+                "cache": "source",
+                "hits": hits,
+                "misses": misses,
+            }));
+        }
+
+        let summary = aggregates.summary(0, None);
+        assert_eq!(summary.total_count, 101);
+        assert_eq!(summary.error_count, 11);
+        let get_source = &summary.commands[1];
+        assert_eq!(get_source.command, "neuro_get_source");
+        assert_eq!(get_source.p50_latency_ms, Some(50));
+        assert_eq!(get_source.p95_latency_ms, Some(95));
+        assert!((get_source.error_rate - 0.1).abs() < f64::EPSILON);
+        assert_eq!(get_source.error_codes.get("ws_timeout"), Some(&10));
+        let lock_refresh = &summary.commands[0];
+        assert_eq!(lock_refresh.command, "neuro.lock_refresh");
+        assert_eq!(lock_refresh.p50_latency_ms, None);
+        assert_eq!(summary.error_codes.len(), 2);
+        assert_eq!(percentile(&[7], 95), Some(7));
+        assert_eq!(summary.caches.len(), 1);
+        assert_eq!(summary.caches[0].hits, 3);
+        assert_eq!(summary.caches[0].misses, 5);
+        assert!((summary.caches[0].hit_rate - 0.375).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn rotates_store_when_full() {
+        let dir = std::env::temp_dir().join(format!("neuro-telemetry-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("neuro-telemetry.jsonl");
+        let store = TelemetryStore::new(path.clone(), 20, 2);
+
+        for index in 0..4 {
+            store.append(&format!("{{\"index\":{index}}}")).unwrap();
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"index\":3}\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("neuro-telemetry.jsonl.1")).unwrap(),
+            "{\"index\":2}\n"
+        );
+        assert!(dir.join("neuro-telemetry.jsonl.2").exists());
+        assert!(!dir.join("neuro-telemetry.jsonl.3").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
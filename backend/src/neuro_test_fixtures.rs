@@ -0,0 +1,399 @@
+//! Local stand-ins for an SAP system, used by the Neuro runtime tests.
+
+use base64::Engine as _;
+use futures_util::{SinkExt, StreamExt};
+use reqwest::StatusCode;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+
+pub(crate) const FAKE_SAP_USER: &str = "DEVELOPER";
+pub(crate) const FAKE_SAP_PASSWORD: &str = "fake-password";
+pub(crate) const FAKE_SEARCH_PATH: &str = "/sap/bc/adt/repository/informationsystem/search";
+pub(crate) const FAKE_PROGRAM_URI: &str = "/sap/bc/adt/programs/programs/ztest";
+pub(crate) const FAKE_PROGRAM_SOURCE: &str = "REPORT ztest.\nWRITE / 'Hello'.\n";
+pub(crate) const FAKE_PROGRAM_ETAG: &str = "20260101120000001";
+pub(crate) const FAKE_LOCK_HANDLE: &str = "FAKE-LOCK-0001";
+pub(crate) const FAKE_WS_PUSH_ACTION: &str = "lockChanged";
+
+pub(crate) const FAKE_SEARCH_RESULTS_XML: &str = concat!(
+    "<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+    "<adtcore:objectReferences xmlns:adtcore=\"http://www.sap.com/adt/core\">",
+    "<adtcore:objectReference adtcore:uri=\"/sap/bc/adt/programs/programs/ztest\" ",
+    "adtcore:type=\"PROG/P\" adtcore:name=\"ZTEST\" adtcore:packageName=\"$TMP\" ",
+    "adtcore:description=\"Fixture report\"/>",
+    "</adtcore:objectReferences>"
+);
+
+pub(crate) fn fake_lock_result_xml(transport: Option<&str>) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?><asx:abap xmlns:asx=\"http://www.sap.com/abapxml\" version=\"1.0\"><asx:values><DATA><LOCK_HANDLE>{FAKE_LOCK_HANDLE}</LOCK_HANDLE><CORRNR>{}</CORRNR><IS_LOCAL>{}</IS_LOCAL></DATA></asx:values></asx:abap>",
+        transport.unwrap_or_default(),
+        if transport.is_some() { "" } else { "X" }
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CsrfMode {
+    /// Hands out a token on `x-csrf-token: Fetch` and requires it on writes.
+    Issue,
+    /// Answers the fetch without a token.
+    Missing,
+    /// Rejects the first write with `403` + `x-csrf-token: Required`, as an expired session does.
+    ExpireOnce,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FakeResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl FakeResponse {
+    pub(crate) fn ok(body: impl Into<String>) -> Self {
+        Self::status(200).body(body)
+    }
+
+    pub(crate) fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    pub(crate) fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    pub(crate) fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub(crate) fn etag(self, etag: &str) -> Self {
+        self.header("etag", etag)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedRequest {
+    pub method: String,
+    /// Path without the query string.
+    pub path: String,
+    pub query: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    pub(crate) fn is_csrf_fetch(&self) -> bool {
+        self.header("x-csrf-token")
+            .is_some_and(|value| value.eq_ignore_ascii_case("fetch"))
+    }
+}
+
+struct FakeAdtState {
+    csrf_mode: CsrfMode,
+    csrf_expired: bool,
+    issued_tokens: u32,
+    /// `method path` -> queued responses; the last one repeats.
+    routes: HashMap<String, VecDeque<FakeResponse>>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// Minimal HTTP/1.1 server answering like the ADT REST API: basic auth, CSRF tokens and
+/// canned responses per route.
+pub(crate) struct FakeAdtServer {
+    base_url: String,
+    state: Arc<Mutex<FakeAdtState>>,
+}
+
+impl FakeAdtServer {
+    pub(crate) async fn start(csrf_mode: CsrfMode) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("fake ADT server should bind");
+        let address = listener.local_addr().expect("fake ADT server address");
+        let state = Arc::new(Mutex::new(FakeAdtState {
+            csrf_mode,
+            csrf_expired: csrf_mode == CsrfMode::ExpireOnce,
+            issued_tokens: 0,
+            routes: HashMap::new(),
+            requests: Vec::new(),
+        }));
+
+        let server_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = Arc::clone(&server_state);
+                tokio::spawn(async move {
+                    let _ = serve_adt_connection(stream, state).await;
+                });
+            }
+        });
+
+        Self {
+            base_url: format!("http://{address}"),
+            state,
+        }
+    }
+
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Queues `response` for `method path`; the last queued response answers all later calls.
+    pub(crate) fn route(&self, method: &str, path: &str, response: FakeResponse) {
+        self.lock()
+            .routes
+            .entry(format!("{} {path}", method.to_ascii_uppercase()))
+            .or_default()
+            .push_back(response);
+    }
+
+    pub(crate) fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FakeAdtState> {
+        self.state.lock().expect("fake ADT state lock poisoned")
+    }
+}
+
+fn basic_auth_matches(request: &RecordedRequest) -> bool {
+    let expected = base64::engine::general_purpose::STANDARD
+        .encode(format!("{FAKE_SAP_USER}:{FAKE_SAP_PASSWORD}"));
+    request
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Basic "))
+        .is_some_and(|value| value.trim() == expected)
+}
+
+fn respond(state: &Mutex<FakeAdtState>, request: RecordedRequest) -> FakeResponse {
+    let mut state = state.lock().expect("fake ADT state lock poisoned");
+    state.requests.push(request.clone());
+
+    if !basic_auth_matches(&request) {
+        return FakeResponse::status(401)
+            .header(
+                "www-authenticate",
+                "Basic realm=\"SAP NetWeaver Application Server\"",
+            )
+            .body("Logon failed");
+    }
+
+    if request.is_csrf_fetch() {
+        return match state.csrf_mode {
+            CsrfMode::Missing => FakeResponse::status(200),
+            CsrfMode::Issue | CsrfMode::ExpireOnce => {
+                state.issued_tokens += 1;
+                FakeResponse::status(200)
+                    .header("x-csrf-token", format!("fake-csrf-{}", state.issued_tokens))
+            }
+        };
+    }
+
+    if request.method != "GET" && request.method != "HEAD" {
+        let current = format!("fake-csrf-{}", state.issued_tokens);
+        let token_valid =
+            state.issued_tokens > 0 && request.header("x-csrf-token") == Some(current.as_str());
+        if !token_valid || state.csrf_expired {
+            state.csrf_expired = false;
+            return FakeResponse::status(403)
+                .header("x-csrf-token", "Required")
+                .body("CSRF token validation failed");
+        }
+    }
+
+    let key = format!("{} {}", request.method, request.path);
+    let response = match state.routes.get_mut(&key) {
+        Some(queue) if queue.len() > 1 => queue.pop_front().expect("queued response"),
+        Some(queue) => queue
+            .front()
+            .cloned()
+            .unwrap_or_else(|| FakeResponse::status(404)),
+        None => FakeResponse::status(404).body(format!("no fake route for {key}")),
+    };
+    let etag = response
+        .headers
+        .iter()
+        .find(|(name, _)| name == "etag")
+        .map(|(_, value)| value.as_str());
+    if etag.is_some() && request.header("if-none-match") == etag {
+        return FakeResponse::status(304);
+    }
+    response
+}
+
+async fn serve_adt_connection(
+    stream: TcpStream,
+    state: Arc<Mutex<FakeAdtState>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let (path, query) = (path.to_string(), query.to_string());
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+
+    let response = respond(
+        &state,
+        RecordedRequest {
+            method,
+            path,
+            query,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        },
+    );
+
+    let reason = StatusCode::from_u16(response.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("Unknown");
+    let mut raw = format!(
+        "HTTP/1.1 {} {reason}\r\ncontent-length: {}\r\nconnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        raw.push_str(&format!("{name}: {value}\r\n"));
+    }
+    raw.push_str("\r\n");
+    raw.push_str(&response.body);
+
+    let mut stream = reader.into_inner();
+    stream.write_all(raw.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FakeWsMode {
+    /// Accepts frames and never answers, so requests run into their timeout.
+    Silent,
+    /// Closes every connection right after the handshake.
+    CloseImmediately,
+    /// Answers each `subscribe` frame with one [`FAKE_WS_PUSH_ACTION`] event, then drops the connection.
+    PushThenClose,
+}
+
+/// WebSocket endpoint standing in for the Neuro domain server.
+pub(crate) struct FakeWsServer {
+    url: String,
+    received: Arc<Mutex<Vec<String>>>,
+}
+
+impl FakeWsServer {
+    pub(crate) async fn start(mode: FakeWsMode) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("fake WS server should bind");
+        let address = listener.local_addr().expect("fake WS server address");
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let server_received = Arc::clone(&received);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let received = Arc::clone(&server_received);
+                tokio::spawn(async move {
+                    let Ok(mut socket) = tokio_tungstenite::accept_async(stream).await else {
+                        return;
+                    };
+                    if mode == FakeWsMode::CloseImmediately {
+                        let _ = socket.close(None).await;
+                        return;
+                    }
+                    while let Some(Ok(message)) = socket.next().await {
+                        if let Message::Text(text) = message {
+                            received
+                                .lock()
+                                .expect("fake WS lock poisoned")
+                                .push(text.to_string());
+                            if mode == FakeWsMode::PushThenClose {
+                                if let Some(event) = fake_push_event(text.as_str()) {
+                                    let _ = socket.send(Message::text(event)).await;
+                                    let _ = socket.close(None).await;
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        Self {
+            url: format!("ws://{address}"),
+            received,
+        }
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub(crate) fn received(&self) -> Vec<String> {
+        self.received.lock().expect("fake WS lock poisoned").clone()
+    }
+}
+
+fn fake_push_event(frame: &str) -> Option<String> {
+    let frame: serde_json::Value = serde_json::from_str(frame).ok()?;
+    if frame.get("action")?.as_str()? != "subscribe" {
+        return None;
+    }
+    Some(
+        serde_json::json!({
+            "id": "push-1",
+            "domain": frame.get("domain")?,
+            "action": FAKE_WS_PUSH_ACTION,
+            "payload": { "objectUri": FAKE_PROGRAM_URI },
+            "ok": true,
+            "error": null,
+        })
+        .to_string(),
+    )
+}
+
+/// Address nothing listens on, for connection-refused cases.
+pub(crate) async fn unused_local_url(scheme: &str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("probe listener should bind");
+    let address = listener.local_addr().expect("probe listener address");
+    drop(listener);
+    format!("{scheme}://{address}")
+}
@@ -0,0 +1,317 @@
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tokio::sync::Mutex;
+
+use crate::neuro_adt_rest::{collect_xml_text, xml_attribute};
+
+pub const TRANSPORT_REQUESTS_PATH: &str = "/sap/bc/adt/cts/transportrequests";
+pub const TRANSPORT_REQUESTS_ACCEPT: &str = "application/vnd.sap.adt.transportorganizertree.v1+xml";
+pub const CREATE_TRANSPORT_PATH: &str = "/sap/bc/adt/cts/transports";
+pub const CREATE_TRANSPORT_CONTENT_TYPE: &str =
+    "application/vnd.sap.as+xml; charset=UTF-8; dataname=com.sap.adt.CreateCorrectionRequest";
+pub const TRANSPORT_CHECK_PATH: &str = "/sap/bc/adt/cts/transportchecks";
+pub const TRANSPORT_CHECK_CONTENT_TYPE: &str =
+    "application/vnd.sap.as+xml; charset=UTF-8; dataname=com.sap.adt.transport.service.checkData";
+
+/// Updates sent through the ADT REST path instead of the engine, with the request to record in.
+#[derive(Debug, Deserialize)]
+pub struct NeuroUpdateSourceRequest {
+    #[serde(flatten)]
+    pub update: neuro_types::AdtUpdateSourceRequest,
+    #[serde(default)]
+    pub transport: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroTransportTask {
+    pub number: String,
+    pub owner: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroTransportRequest {
+    pub number: String,
+    pub owner: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub target: Option<String>,
+    /// `workbench` or `customizing`.
+    pub kind: String,
+    pub tasks: Vec<NeuroTransportTask>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroTransportCreateRequest {
+    pub object_uri: String,
+    pub package: String,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroTransportAssignRequest {
+    pub object_uri: String,
+    pub transport: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroTransportAssignment {
+    pub object_uri: String,
+    pub transport: String,
+    pub package: Option<String>,
+    /// False for local (`$`) packages, where the transport is not used.
+    pub recording_required: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct TransportCheck {
+    pub package: Option<String>,
+    pub recording_required: bool,
+    /// Request the object is already locked in, if any.
+    pub locked_transport: Option<String>,
+}
+
+/// Transport numbers look like `DEVK900123`: system id, `K`, six digits.
+pub(crate) fn normalize_transport_number(value: &str) -> Result<String, String> {
+    let number = value.trim().to_ascii_uppercase();
+    let bytes = number.as_bytes();
+    let valid = bytes.len() == 10
+        && bytes[..3].iter().all(u8::is_ascii_alphanumeric)
+        && bytes[3] == b'K'
+        && bytes[4..].iter().all(u8::is_ascii_digit);
+    if !valid {
+        return Err(format!(
+            "invalid transport request number `{}`",
+            value.trim()
+        ));
+    }
+    Ok(number)
+}
+
+fn asx_values(data: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><asx:abap xmlns:asx=\"http://www.sap.com/abapxml\" version=\"1.0\"><asx:values><DATA>{data}</DATA></asx:values></asx:abap>"
+    )
+}
+
+pub(crate) fn transport_check_request_xml(object_uri: &str) -> String {
+    asx_values(&format!(
+        "<PGMID/><OBJECT/><OBJECTNAME/><DEVCLASS/><SUPER_PACKAGE/><OPERATION/><URI>{}</URI>",
+        escape(object_uri)
+    ))
+}
+
+pub(crate) fn create_transport_request_xml(
+    object_uri: &str,
+    package: &str,
+    description: &str,
+) -> String {
+    asx_values(&format!(
+        "<OPERATION>I</OPERATION><DEVCLASS>{}</DEVCLASS><REQUEST_TEXT>{}</REQUEST_TEXT><REF>{}</REF>",
+        escape(package.trim().to_ascii_uppercase().as_str()),
+        escape(description.trim()),
+        escape(object_uri)
+    ))
+}
+
+/// The create endpoint answers with a URI whose last segment is the new request number.
+pub(crate) fn parse_created_transport(body: &str) -> Result<String, String> {
+    let segment = body.trim().rsplit('/').next().unwrap_or_default();
+    normalize_transport_number(segment)
+}
+
+pub(crate) fn parse_transport_check(xml: &str) -> Result<TransportCheck, String> {
+    let values = collect_xml_text(xml, &["DEVCLASS", "DLVUNIT", "RECORDING", "TRKORR"])?;
+    let value = |name: &str| values.get(name).filter(|value| !value.is_empty()).cloned();
+    let is_local = value("DLVUNIT").is_some_and(|unit| unit.eq_ignore_ascii_case("LOCAL"))
+        || value("DEVCLASS").is_some_and(|package| package.starts_with('$'));
+    Ok(TransportCheck {
+        package: value("DEVCLASS"),
+        recording_required: !is_local
+            && value("RECORDING").is_some_and(|recording| recording.eq_ignore_ascii_case("x")),
+        locked_transport: value("TRKORR"),
+    })
+}
+
+/// Reads the modifiable requests and their tasks from a transport organizer tree.
+pub(crate) fn parse_transport_requests(xml: &str) -> Result<Vec<NeuroTransportRequest>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut requests = Vec::new();
+    let mut kind = String::new();
+    let mut target: Option<String> = None;
+    let mut modifiable = false;
+    let mut request: Option<NeuroTransportRequest> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        let (element, is_empty) = match &event {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(element) => {
+                match element.local_name().as_ref() {
+                    b"modifiable" => modifiable = false,
+                    b"target" => target = None,
+                    b"request" => requests.extend(request.take()),
+                    _ => {}
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match element.local_name().as_ref() {
+            b"workbench" => kind = "workbench".to_string(),
+            b"customizing" => kind = "customizing".to_string(),
+            b"target" => target = xml_attribute(element, "name"),
+            b"modifiable" => modifiable = !is_empty,
+            b"request" if modifiable => {
+                let Some(number) = xml_attribute(element, "number") else {
+                    continue;
+                };
+                let parsed = NeuroTransportRequest {
+                    number,
+                    owner: xml_attribute(element, "owner"),
+                    description: xml_attribute(element, "desc"),
+                    status: xml_attribute(element, "status"),
+                    target: target.clone(),
+                    kind: kind.clone(),
+                    tasks: Vec::new(),
+                };
+                if is_empty {
+                    requests.push(parsed);
+                } else {
+                    request = Some(parsed);
+                }
+            }
+            b"task" => {
+                if let (Some(request), Some(number)) =
+                    (request.as_mut(), xml_attribute(element, "number"))
+                {
+                    request.tasks.push(NeuroTransportTask {
+                        number,
+                        owner: xml_attribute(element, "owner"),
+                        description: xml_attribute(element, "desc"),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(requests)
+}
+
+/// Transport requests chosen per object for later updates on one runtime.
+#[derive(Default)]
+pub(crate) struct TransportAssignments {
+    assignments: Mutex<BTreeMap<String, String>>,
+}
+
+impl TransportAssignments {
+    pub(crate) async fn get(&self, object_uri: &str) -> Option<String> {
+        self.assignments.lock().await.get(object_uri).cloned()
+    }
+
+    pub(crate) async fn assign(&self, object_uri: &str, transport: &str) {
+        self.assignments
+            .lock()
+            .await
+            .insert(object_uri.to_string(), transport.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        create_transport_request_xml, normalize_transport_number, parse_created_transport,
+        parse_transport_check, parse_transport_requests,
+    };
+
+    #[test]
+    fn validates_transport_numbers() {
+        assert_eq!(
+            normalize_transport_number(" devk900123 ").unwrap(),
+            "DEVK900123"
+        );
+        assert!(normalize_transport_number("DEVK90012").is_err());
+        assert!(normalize_transport_number("DEVX900123").is_err());
+        assert_eq!(
+            parse_created_transport("/com.sap.cts/object_record/DEVK900124\n").unwrap(),
+            "DEVK900124"
+        );
+        assert!(create_transport_request_xml(
+            "/sap/bc/adt/programs/programs/ztest",
+            "zdemo",
+            "Fix <bug>"
+        )
+        .contains("<DEVCLASS>ZDEMO</DEVCLASS><REQUEST_TEXT>Fix &lt;bug&gt;</REQUEST_TEXT>"));
+    }
+
+    #[test]
+    fn parses_transport_checks() {
+        let transportable = parse_transport_check(
+            "<asx:abap xmlns:asx=\"http://www.sap.com/abapxml\"><asx:values><DATA><DEVCLASS>ZDEMO</DEVCLASS><DLVUNIT>HOME</DLVUNIT><RECORDING>X</RECORDING><LOCKS><CTS_OBJECT_LOCK><LOCK_HOLDER><REQ_HEADER><TRKORR>DEVK900123</TRKORR></REQ_HEADER></LOCK_HOLDER></CTS_OBJECT_LOCK></LOCKS></DATA></asx:values></asx:abap>",
+        )
+        .unwrap();
+        assert_eq!(transportable.package.as_deref(), Some("ZDEMO"));
+        assert!(transportable.recording_required);
+        assert_eq!(
+            transportable.locked_transport.as_deref(),
+            Some("DEVK900123")
+        );
+
+        let local = parse_transport_check(
+            "<DATA><DEVCLASS>$TMP</DEVCLASS><DLVUNIT>LOCAL</DLVUNIT><RECORDING>X</RECORDING></DATA>",
+        )
+        .unwrap();
+        assert!(!local.recording_required);
+        assert_eq!(local.locked_transport, None);
+    }
+
+    #[test]
+    fn lists_only_modifiable_requests() {
+        let xml = r#"<tm:root xmlns:tm="http://www.sap.com/cts/adt/tm">
+            <tm:workbench>
+              <tm:target tm:name="/QAS/">
+                <tm:modifiable>
+                  <tm:request tm:number="DEVK900123" tm:owner="DEVELOPER" tm:desc="Fix pricing" tm:status="D">
+                    <tm:task tm:number="DEVK900124" tm:owner="DEVELOPER" tm:desc="Fix pricing"/>
+                  </tm:request>
+                </tm:modifiable>
+                <tm:released>
+                  <tm:request tm:number="DEVK900001" tm:owner="DEVELOPER" tm:desc="Old" tm:status="R"/>
+                </tm:released>
+              </tm:target>
+            </tm:workbench>
+            <tm:customizing>
+              <tm:target tm:name="/QAS/">
+                <tm:modifiable>
+                  <tm:request tm:number="DEVK900200" tm:owner="DEVELOPER" tm:desc="Config" tm:status="D"/>
+                </tm:modifiable>
+              </tm:target>
+            </tm:customizing>
+          </tm:root>"#;
+        let requests = parse_transport_requests(xml).unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].number, "DEVK900123");
+        assert_eq!(requests[0].kind, "workbench");
+        assert_eq!(requests[0].target.as_deref(), Some("/QAS/"));
+        assert_eq!(requests[0].tasks[0].number, "DEVK900124");
+        assert_eq!(requests[1].kind, "customizing");
+        assert!(requests[1].tasks.is_empty());
+    }
+}
@@ -0,0 +1,572 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+use crate::neuro_adt_rest::{adt_object_set_xml, xml_attribute, xml_reference_text};
+use crate::neuro_check_runtime::parse_uri_position;
+use crate::neuro_lock_runtime::normalize_object_uri;
+
+pub const UNIT_TEST_RUNS_PATH: &str = "/sap/bc/adt/abapunit/testruns";
+pub const UNIT_TEST_RUN_CONTENT_TYPE: &str =
+    "application/vnd.sap.adt.abapunit.testruns.config.v4+xml";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroUnitTestRequest {
+    pub object_uris: Vec<String>,
+    #[serde(default)]
+    pub with_coverage: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroUnitTestStackEntry {
+    pub uri: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroUnitTestAlert {
+    pub kind: String,
+    pub severity: String,
+    pub title: String,
+    pub details: Vec<String>,
+    pub stack: Vec<NeuroUnitTestStackEntry>,
+}
+
+impl NeuroUnitTestAlert {
+    /// Tolerable alerts (e.g. risk level warnings) do not fail a test.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.severity.as_str(), "critical" | "fatal")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroUnitTestMethod {
+    pub name: String,
+    pub uri: Option<String>,
+    pub execution_time_secs: Option<f64>,
+    pub passed: bool,
+    pub alerts: Vec<NeuroUnitTestAlert>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroUnitTestClass {
+    pub name: String,
+    pub uri: Option<String>,
+    pub risk_level: Option<String>,
+    pub duration_category: Option<String>,
+    pub passed: bool,
+    pub alerts: Vec<NeuroUnitTestAlert>,
+    pub methods: Vec<NeuroUnitTestMethod>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroUnitTestProgram {
+    pub object_uri: Option<String>,
+    pub name: Option<String>,
+    pub object_type: Option<String>,
+    pub passed: bool,
+    pub classes: Vec<NeuroUnitTestClass>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroCoverageMetric {
+    pub executed: u64,
+    pub total: u64,
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroUnitTestCoverage {
+    pub object_uri: Option<String>,
+    pub name: Option<String>,
+    pub object_type: Option<String>,
+    pub statement: Option<NeuroCoverageMetric>,
+    pub branch: Option<NeuroCoverageMetric>,
+    pub procedure: Option<NeuroCoverageMetric>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroUnitTestResponse {
+    pub passed: bool,
+    pub total_methods: usize,
+    pub failed_methods: usize,
+    pub programs: Vec<NeuroUnitTestProgram>,
+    /// Alerts not tied to a test class, e.g. "no test classes found".
+    pub alerts: Vec<NeuroUnitTestAlert>,
+    pub coverage: Vec<NeuroUnitTestCoverage>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct UnitTestRunResult {
+    pub programs: Vec<NeuroUnitTestProgram>,
+    pub alerts: Vec<NeuroUnitTestAlert>,
+    pub coverage_uri: Option<String>,
+}
+
+pub(crate) fn unit_test_object_uris(object_uris: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized = Vec::new();
+    for object_uri in object_uris {
+        let object_uri = normalize_object_uri(object_uri)?;
+        if !normalized.contains(&object_uri) {
+            normalized.push(object_uri);
+        }
+    }
+    if normalized.is_empty() {
+        return Err("unit test run requires at least one object URI".to_string());
+    }
+    Ok(normalized)
+}
+
+/// Builds the run configuration; `harmless_only` skips dangerous and critical tests.
+pub(crate) fn unit_test_run_xml(
+    object_uris: &[String],
+    with_coverage: bool,
+    harmless_only: bool,
+) -> String {
+    let risky = !harmless_only;
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><aunit:runConfiguration xmlns:aunit=\"http://www.sap.com/adt/aunit\"><external><coverage active=\"{with_coverage}\"/></external><options><uriType value=\"semantic\"/><testDeterminationStrategy sameProgram=\"true\" assignedTests=\"false\"/><testRiskLevels harmless=\"true\" dangerous=\"{risky}\" critical=\"{risky}\"/><testDurations short=\"true\" medium=\"true\" long=\"true\"/><withNavigationUri enabled=\"true\"/></options><adtcore:objectSets xmlns:adtcore=\"http://www.sap.com/adt/core\">{}</adtcore:objectSets></aunit:runConfiguration>",
+        adt_object_set_xml(object_uris)
+    )
+}
+
+pub(crate) fn coverage_query_xml(object_uris: &[String]) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><cov:query xmlns:cov=\"http://www.sap.com/adt/cov\" xmlns:adtcore=\"http://www.sap.com/adt/core\"><adtcore:objectSets>{}</adtcore:objectSets></cov:query>",
+        adt_object_set_xml(object_uris)
+    )
+}
+
+fn attribute(element: &BytesStart<'_>, name: &str) -> Option<String> {
+    xml_attribute(element, name).filter(|value| !value.trim().is_empty())
+}
+
+fn stack_entry(element: &BytesStart<'_>) -> NeuroUnitTestStackEntry {
+    let uri = attribute(element, "uri");
+    let line = uri
+        .as_deref()
+        .and_then(|uri| parse_uri_position(uri).1)
+        .or_else(|| {
+            attribute(element, "description")
+                .and_then(|description| line_from_description(&description))
+        });
+    NeuroUnitTestStackEntry {
+        uri,
+        name: attribute(element, "name"),
+        description: attribute(element, "description"),
+        line,
+    }
+}
+
+/// Stack descriptions look like `Include: <ZCL_DEMO=====CCAU> Line: <21> (TEST_SUM)`.
+fn line_from_description(description: &str) -> Option<u32> {
+    let (_, rest) = description.split_once("Line: <")?;
+    rest.split('>').next()?.trim().parse().ok()
+}
+
+fn finish_method(mut method: NeuroUnitTestMethod) -> NeuroUnitTestMethod {
+    method.passed = !method.alerts.iter().any(NeuroUnitTestAlert::is_failure);
+    method
+}
+
+fn finish_class(mut class: NeuroUnitTestClass) -> NeuroUnitTestClass {
+    class.methods = class.methods.into_iter().map(finish_method).collect();
+    class.passed = !class.alerts.iter().any(NeuroUnitTestAlert::is_failure)
+        && class.methods.iter().all(|method| method.passed);
+    class
+}
+
+fn finish_program(mut program: NeuroUnitTestProgram) -> NeuroUnitTestProgram {
+    program.passed = program.classes.iter().all(|class| class.passed);
+    program
+}
+
+pub(crate) fn parse_unit_test_result(xml: &str) -> Result<UnitTestRunResult, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut result = UnitTestRunResult::default();
+    let mut program: Option<NeuroUnitTestProgram> = None;
+    let mut class: Option<NeuroUnitTestClass> = None;
+    let mut method: Option<NeuroUnitTestMethod> = None;
+    let mut alert: Option<NeuroUnitTestAlert> = None;
+    let mut title: Option<String> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        let (element, is_empty) = match &event {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::Text(text) => {
+                if let Some(title) = title.as_mut() {
+                    title.push_str(&text.decode().map_err(|error| error.to_string())?);
+                }
+                continue;
+            }
+            Event::GeneralRef(reference) => {
+                if let Some(title) = title.as_mut() {
+                    title.push_str(&xml_reference_text(reference));
+                }
+                continue;
+            }
+            Event::End(element) => {
+                match element.local_name().as_ref() {
+                    b"title" => {
+                        if let (Some(alert), Some(text)) = (alert.as_mut(), title.take()) {
+                            alert.title = text.trim().to_string();
+                        }
+                    }
+                    b"alert" => {
+                        if let Some(finished) = alert.take() {
+                            push_alert(&mut result, &mut class, &mut method, finished);
+                        }
+                    }
+                    b"testMethod" => {
+                        if let (Some(finished), Some(class)) = (method.take(), class.as_mut()) {
+                            class.methods.push(finished);
+                        }
+                    }
+                    b"testClass" => {
+                        if let (Some(finished), Some(program)) = (class.take(), program.as_mut()) {
+                            program.classes.push(finish_class(finished));
+                        }
+                    }
+                    b"program" => {
+                        if let Some(finished) = program.take() {
+                            result.programs.push(finish_program(finished));
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match element.local_name().as_ref() {
+            b"coverage" if program.is_none() => {
+                result.coverage_uri = attribute(element, "uri");
+            }
+            b"program" => {
+                let parsed = NeuroUnitTestProgram {
+                    object_uri: attribute(element, "uri"),
+                    name: attribute(element, "name"),
+                    object_type: attribute(element, "type"),
+                    passed: true,
+                    classes: Vec::new(),
+                };
+                if is_empty {
+                    result.programs.push(parsed);
+                } else {
+                    program = Some(parsed);
+                }
+            }
+            b"testClass" => {
+                let parsed = NeuroUnitTestClass {
+                    name: attribute(element, "name").unwrap_or_default(),
+                    uri: attribute(element, "navigationUri").or_else(|| attribute(element, "uri")),
+                    risk_level: attribute(element, "riskLevel"),
+                    duration_category: attribute(element, "durationCategory"),
+                    passed: true,
+                    alerts: Vec::new(),
+                    methods: Vec::new(),
+                };
+                match program.as_mut() {
+                    Some(program) if is_empty => program.classes.push(parsed),
+                    _ => class = Some(parsed),
+                }
+            }
+            b"testMethod" => {
+                let parsed = NeuroUnitTestMethod {
+                    name: attribute(element, "name").unwrap_or_default(),
+                    uri: attribute(element, "navigationUri").or_else(|| attribute(element, "uri")),
+                    execution_time_secs: attribute(element, "executionTime")
+                        .and_then(|value| value.trim().parse().ok()),
+                    passed: true,
+                    alerts: Vec::new(),
+                };
+                match class.as_mut() {
+                    Some(class) if is_empty => class.methods.push(parsed),
+                    _ => method = Some(parsed),
+                }
+            }
+            b"alert" => {
+                let parsed = NeuroUnitTestAlert {
+                    kind: attribute(element, "kind").unwrap_or_default(),
+                    severity: attribute(element, "severity").unwrap_or_default(),
+                    title: String::new(),
+                    details: Vec::new(),
+                    stack: Vec::new(),
+                };
+                if is_empty {
+                    push_alert(&mut result, &mut class, &mut method, parsed);
+                } else {
+                    alert = Some(parsed);
+                }
+            }
+            b"title" if alert.is_some() && !is_empty => title = Some(String::new()),
+            b"detail" => {
+                if let (Some(alert), Some(text)) = (alert.as_mut(), attribute(element, "text")) {
+                    alert.details.push(text);
+                }
+            }
+            b"stackEntry" => {
+                if let Some(alert) = alert.as_mut() {
+                    alert.stack.push(stack_entry(element));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(result)
+}
+
+fn push_alert(
+    result: &mut UnitTestRunResult,
+    class: &mut Option<NeuroUnitTestClass>,
+    method: &mut Option<NeuroUnitTestMethod>,
+    alert: NeuroUnitTestAlert,
+) {
+    if let Some(method) = method.as_mut() {
+        method.alerts.push(alert);
+    } else if let Some(class) = class.as_mut() {
+        class.alerts.push(alert);
+    } else {
+        result.alerts.push(alert);
+    }
+}
+
+fn coverage_metric(element: &BytesStart<'_>) -> Option<NeuroCoverageMetric> {
+    let executed = attribute(element, "executed")?.trim().parse::<u64>().ok()?;
+    let total = attribute(element, "total")?.trim().parse::<u64>().ok()?;
+    let percent = if total == 0 {
+        0.0
+    } else {
+        (executed as f64 * 10_000.0 / total as f64).round() / 100.0
+    };
+    Some(NeuroCoverageMetric {
+        executed,
+        total,
+        percent,
+    })
+}
+
+/// Reads coverage for the requested objects, or for the top-level nodes if none match.
+pub(crate) fn parse_coverage_result(
+    xml: &str,
+    object_uris: &[String],
+) -> Result<Vec<NeuroUnitTestCoverage>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut open_nodes: Vec<NeuroUnitTestCoverage> = Vec::new();
+    let mut nodes: Vec<(usize, NeuroUnitTestCoverage)> = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        match event {
+            Event::Start(element) if element.local_name().as_ref() == b"node" => {
+                open_nodes.push(NeuroUnitTestCoverage {
+                    object_uri: attribute(&element, "uri")
+                        .map(|uri| normalize_object_uri(&uri).unwrap_or(uri)),
+                    name: attribute(&element, "name"),
+                    object_type: attribute(&element, "type"),
+                    statement: None,
+                    branch: None,
+                    procedure: None,
+                });
+            }
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == b"coverage" =>
+            {
+                let Some(node) = open_nodes.last_mut() else {
+                    continue;
+                };
+                let metric = coverage_metric(&element);
+                match attribute(&element, "type").as_deref() {
+                    Some("statement") => node.statement = metric,
+                    Some("branch") => node.branch = metric,
+                    Some("procedure") => node.procedure = metric,
+                    _ => {}
+                }
+            }
+            Event::End(element) if element.local_name().as_ref() == b"node" => {
+                if let Some(node) = open_nodes.pop() {
+                    nodes.push((open_nodes.len(), node));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let requested = nodes
+        .iter()
+        .filter(|(_, node)| {
+            node.object_uri
+                .as_ref()
+                .is_some_and(|uri| object_uris.contains(uri))
+        })
+        .map(|(_, node)| node.clone())
+        .collect::<Vec<_>>();
+    if !requested.is_empty() {
+        return Ok(requested);
+    }
+    Ok(nodes
+        .into_iter()
+        .filter(|(depth, _)| *depth == 0)
+        .map(|(_, node)| node)
+        .collect())
+}
+
+pub(crate) fn unit_test_response(
+    run: UnitTestRunResult,
+    coverage: Vec<NeuroUnitTestCoverage>,
+) -> NeuroUnitTestResponse {
+    let methods = run
+        .programs
+        .iter()
+        .flat_map(|program| &program.classes)
+        .flat_map(|class| &class.methods);
+    let total_methods = methods.clone().count();
+    let failed_methods = methods.filter(|method| !method.passed).count();
+    let passed = run.programs.iter().all(|program| program.passed)
+        && !run.alerts.iter().any(NeuroUnitTestAlert::is_failure);
+    NeuroUnitTestResponse {
+        passed,
+        total_methods,
+        failed_methods,
+        programs: run.programs,
+        alerts: run.alerts,
+        coverage,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_coverage_result, parse_unit_test_result, unit_test_response, unit_test_run_xml,
+    };
+
+    const CLASS: &str = "/sap/bc/adt/oo/classes/zcl_demo";
+
+    #[test]
+    fn builds_run_configuration() {
+        let body = unit_test_run_xml(&[CLASS.to_string()], true, true);
+        assert!(body.contains("<coverage active=\"true\"/>"));
+        assert!(body.contains("harmless=\"true\" dangerous=\"false\" critical=\"false\""));
+        assert!(body.contains(&format!("adtcore:uri=\"{CLASS}\"")));
+    }
+
+    #[test]
+    fn parses_class_and_method_tree_with_alerts() {
+        let xml = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <aunit:runResult xmlns:aunit="http://www.sap.com/adt/aunit" xmlns:adtcore="http://www.sap.com/adt/core">
+              <external><coverage adtcore:uri="/sap/bc/adt/runtime/traces/coverage/measurements/ABC"/></external>
+              <program adtcore:uri="{CLASS}" adtcore:type="CLAS/OC" adtcore:name="ZCL_DEMO">
+                <testClasses>
+                  <testClass adtcore:uri="{CLASS}/includes/testclasses#type=CLAS%2FOL;name=LTCL_DEMO" adtcore:name="LTCL_DEMO" riskLevel="harmless" durationCategory="short">
+                    <testMethods>
+                      <testMethod adtcore:name="TEST_SUM" executionTime="0.012"/>
+                      <testMethod adtcore:name="TEST_DIV" executionTime="0">
+                        <alerts>
+                          <alert kind="failedAssertion" severity="critical">
+                            <title>Critical Assertion Error: &apos;Sum&apos;</title>
+                            <details><detail text="Expected [3] Actual [4]"><details><detail text="Test 'TEST_DIV'"/></details></detail></details>
+                            <stack>
+                              <stackEntry adtcore:uri="{CLASS}/includes/testclasses#start=21,4" adtcore:name="LTCL_DEMO" adtcore:description="Include: &lt;ZCL_DEMO=====CCAU&gt; Line: &lt;21&gt;"/>
+                              <stackEntry adtcore:description="Include: &lt;ZCL_DEMO=====CM001&gt; Line: &lt;7&gt; (DIVIDE)"/>
+                            </stack>
+                          </alert>
+                        </alerts>
+                      </testMethod>
+                    </testMethods>
+                  </testClass>
+                </testClasses>
+              </program>
+            </aunit:runResult>"#
+        );
+        let run = parse_unit_test_result(&xml).expect("valid run result");
+        assert_eq!(
+            run.coverage_uri.as_deref(),
+            Some("/sap/bc/adt/runtime/traces/coverage/measurements/ABC")
+        );
+
+        let class = &run.programs[0].classes[0];
+        assert_eq!(class.name, "LTCL_DEMO");
+        assert!(!class.passed);
+        assert!(class.methods[0].passed);
+        assert_eq!(class.methods[0].execution_time_secs, Some(0.012));
+
+        let alert = &class.methods[1].alerts[0];
+        assert_eq!(alert.title, "Critical Assertion Error: 'Sum'");
+        assert_eq!(
+            alert.details,
+            vec!["Expected [3] Actual [4]", "Test 'TEST_DIV'"]
+        );
+        assert_eq!(alert.stack[0].line, Some(21));
+        assert_eq!(alert.stack[1].line, Some(7));
+
+        let response = unit_test_response(run, Vec::new());
+        assert!(!response.passed);
+        assert_eq!((response.total_methods, response.failed_methods), (2, 1));
+    }
+
+    #[test]
+    fn parses_coverage_for_requested_objects() {
+        let xml = format!(
+            r#"<cov:result xmlns:cov="http://www.sap.com/adt/cov" xmlns:adtcore="http://www.sap.com/adt/core">
+              <nodes>
+                <node adtcore:name="$DEMO" adtcore:type="DEVC/K" adtcore:uri="/sap/bc/adt/packages/%24demo">
+                  <coverages><coverage type="statement" total="10" executed="5"/></coverages>
+                  <nodes>
+                    <node adtcore:name="ZCL_DEMO" adtcore:type="CLAS/OC" adtcore:uri="{CLASS}">
+                      <coverages>
+                        <coverage type="branch" total="4" executed="1"/>
+                        <coverage type="procedure" total="0" executed="0"/>
+                        <coverage type="statement" total="3" executed="2"/>
+                      </coverages>
+                    </node>
+                  </nodes>
+                </node>
+              </nodes>
+            </cov:result>"#
+        );
+        let coverage = parse_coverage_result(&xml, &[CLASS.to_string()]).unwrap();
+        assert_eq!(coverage.len(), 1);
+        assert_eq!(coverage[0].name.as_deref(), Some("ZCL_DEMO"));
+        assert_eq!(
+            coverage[0].statement.map(|metric| metric.percent),
+            Some(66.67)
+        );
+        assert_eq!(coverage[0].branch.map(|metric| metric.percent), Some(25.0));
+        assert_eq!(
+            coverage[0].procedure.map(|metric| metric.percent),
+            Some(0.0)
+        );
+
+        let fallback =
+            parse_coverage_result(&xml, &["/sap/bc/adt/oo/classes/zcl_other".to_string()]).unwrap();
+        assert_eq!(fallback[0].name.as_deref(), Some("$DEMO"));
+    }
+}
@@ -0,0 +1,251 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+use crate::neuro_adt_rest::{xml_attribute, xml_reference_text};
+
+pub const VERSION_FEED_ACCEPT: &str = "application/atom+xml;type=feed";
+const VERSIONS_SEGMENT: &str = "/versions/";
+const TRANSPORT_RELATION_SUFFIX: &str = "/transport/request";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroObjectVersion {
+    /// Path below `source/main/versions/`, e.g. `20260105093000/00000`.
+    pub version_id: String,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// ISO 8601 timestamp as sent by SAP.
+    pub updated: Option<String>,
+    pub transport: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroVersionListResponse {
+    pub object_uri: String,
+    /// Newest first, in feed order.
+    pub versions: Vec<NeuroObjectVersion>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroVersionSourceResponse {
+    pub object_uri: String,
+    pub version_id: String,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NeuroServerVersion {
+    Active,
+    /// Latest saved source; SAP returns the active one when nothing inactive exists.
+    #[default]
+    Inactive,
+}
+
+impl NeuroServerVersion {
+    pub(crate) fn as_query(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Inactive => "inactive",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroSourceDiffResponse {
+    pub object_uri: String,
+    pub version: NeuroServerVersion,
+    /// ETag of the server source the diff was computed against.
+    pub etag: Option<String>,
+    /// Unified diff from the server source to `localSource`; empty when both are equal.
+    pub diff: String,
+    pub changed: bool,
+}
+
+pub(crate) fn versions_feed_path(object_uri: &str) -> String {
+    format!("{object_uri}/source/main/versions")
+}
+
+pub(crate) fn version_content_path(object_uri: &str, version_id: &str) -> Result<String, String> {
+    let version_id = version_id.trim().trim_matches('/');
+    let valid = !version_id.is_empty()
+        && version_id.split('/').all(|segment| {
+            !segment.is_empty() && segment.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+    if !valid {
+        return Err(format!(
+            "invalid version id `{version_id}`: use a versionId from neuro_list_versions"
+        ));
+    }
+    Ok(format!(
+        "{object_uri}/source/main/versions/{version_id}/content"
+    ))
+}
+
+fn version_id_from_content_src(src: &str) -> Option<String> {
+    let start = src.find(VERSIONS_SEGMENT)? + VERSIONS_SEGMENT.len();
+    let version_id = src[start..]
+        .trim_end_matches('/')
+        .trim_end_matches("/content");
+    (!version_id.is_empty()).then(|| version_id.to_string())
+}
+
+#[derive(Default)]
+struct FeedEntry {
+    id: Option<String>,
+    content_src: Option<String>,
+    title: Option<String>,
+    author: Option<String>,
+    updated: Option<String>,
+    transport: Option<String>,
+}
+
+impl FeedEntry {
+    fn into_version(self) -> Option<NeuroObjectVersion> {
+        let version_id = self
+            .content_src
+            .as_deref()
+            .and_then(version_id_from_content_src)
+            .or(self.id)?;
+        Some(NeuroObjectVersion {
+            version_id,
+            title: self.title,
+            author: self.author,
+            updated: self.updated,
+            transport: self.transport,
+        })
+    }
+}
+
+/// Parses the Atom feed of `source/main/versions`.
+pub(crate) fn parse_version_feed(xml: &str) -> Result<Vec<NeuroObjectVersion>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut versions = Vec::new();
+    let mut entry: Option<FeedEntry> = None;
+    let mut text_field: Option<&'static str> = None;
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event().map_err(|error| {
+            format!(
+                "malformed XML at position {}: {error}",
+                reader.error_position()
+            )
+        })?;
+        match event {
+            Event::Start(element) | Event::Empty(element) => {
+                let Some(current) = entry.as_mut() else {
+                    if element.local_name().as_ref() == b"entry" {
+                        entry = Some(FeedEntry::default());
+                    }
+                    continue;
+                };
+                match element.local_name().as_ref() {
+                    b"content" => current.content_src = xml_attribute(&element, "src"),
+                    b"link" => {
+                        let is_transport = xml_attribute(&element, "rel")
+                            .is_some_and(|rel| rel.ends_with(TRANSPORT_RELATION_SUFFIX));
+                        if is_transport {
+                            current.transport = xml_attribute(&element, "name")
+                                .filter(|name| !name.trim().is_empty())
+                                .or_else(|| {
+                                    xml_attribute(&element, "href").and_then(|href| {
+                                        href.rsplit('/').next().map(str::to_string)
+                                    })
+                                });
+                        }
+                    }
+                    b"id" => text_field = Some("id"),
+                    b"title" => text_field = Some("title"),
+                    b"updated" => text_field = Some("updated"),
+                    b"name" => text_field = Some("name"),
+                    _ => {}
+                }
+                text.clear();
+            }
+            Event::Text(value) if text_field.is_some() => {
+                text.push_str(&value.decode().map_err(|error| error.to_string())?);
+            }
+            Event::GeneralRef(reference) if text_field.is_some() => {
+                text.push_str(&xml_reference_text(&reference));
+            }
+            Event::End(element) => {
+                if element.local_name().as_ref() == b"entry" {
+                    versions.extend(entry.take().and_then(FeedEntry::into_version));
+                    continue;
+                }
+                if let (Some(field), Some(current)) = (text_field.take(), entry.as_mut()) {
+                    let value = Some(text.trim().to_string()).filter(|value| !value.is_empty());
+                    match field {
+                        "id" => current.id = value,
+                        "title" => current.title = value,
+                        "updated" => current.updated = value,
+                        _ => current.author = value,
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_version_feed, version_content_path};
+
+    const PROGRAM: &str = "/sap/bc/adt/programs/programs/ztest";
+
+    #[test]
+    fn parses_version_feed_entries() {
+        let xml = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <atom:feed xmlns:atom="http://www.w3.org/2005/Atom" xmlns:adtcore="http://www.sap.com/adt/core">
+              <atom:title>Version List of ZTEST (REPS)</atom:title>
+              <atom:entry>
+                <atom:author><atom:name>DEVELOPER</atom:name></atom:author>
+                <atom:content type="text/plain" src="{PROGRAM}/source/main/versions/20260105093000/00001/content"/>
+                <atom:id>00001</atom:id>
+                <atom:link href="/sap/bc/adt/cts/transportrequests/DEVK900123" rel="http://www.sap.com/adt/relations/transport/request" adtcore:name="DEVK900123"/>
+                <atom:title>DEVK900123 : Fix rounding &amp; totals</atom:title>
+                <atom:updated>2026-01-05T09:30:00Z</atom:updated>
+              </atom:entry>
+              <atom:entry>
+                <atom:author><atom:name>OTHER</atom:name></atom:author>
+                <atom:content type="text/plain" src="{PROGRAM}/source/main/versions/20251201080000/00000/content"/>
+                <atom:id>00000</atom:id>
+                <atom:title>Initial version</atom:title>
+                <atom:updated>2025-12-01T08:00:00Z</atom:updated>
+              </atom:entry>
+            </atom:feed>"#
+        );
+
+        let versions = parse_version_feed(&xml).unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version_id, "20260105093000/00001");
+        assert_eq!(versions[0].author.as_deref(), Some("DEVELOPER"));
+        assert_eq!(
+            versions[0].title.as_deref(),
+            Some("DEVK900123 : Fix rounding & totals")
+        );
+        assert_eq!(versions[0].transport.as_deref(), Some("DEVK900123"));
+        assert_eq!(versions[1].transport, None);
+        assert_eq!(versions[1].updated.as_deref(), Some("2025-12-01T08:00:00Z"));
+    }
+
+    #[test]
+    fn builds_content_path_only_for_feed_ids() {
+        assert_eq!(
+            version_content_path(PROGRAM, "20260105093000/00001").unwrap(),
+            format!("{PROGRAM}/source/main/versions/20260105093000/00001/content")
+        );
+        assert!(version_content_path(PROGRAM, "../../../oo/classes/zcl_x").is_err());
+        assert!(version_content_path(PROGRAM, "").is_err());
+    }
+}
@@ -0,0 +1,354 @@
+use futures_util::stream::SplitSink;
+use futures_util::SinkExt;
+use neuro_types::WsClientConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+pub(crate) const WS_RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+pub(crate) const WS_RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+const SUBSCRIPTION_ID_PREFIX: &str = "ws-sub-";
+
+pub(crate) type WsSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type WsSink = SplitSink<WsSocket, Message>;
+/// Write half of the subscriber socket; `None` while disconnected.
+pub(crate) type WsSinkSlot = Arc<tokio::sync::Mutex<Option<WsSink>>>;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroWsSubscribeRequest {
+    pub domain: String,
+    /// JSON object every forwarded envelope must contain, e.g. `{ "action": "jobFinished" }`.
+    pub filter: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroWsSubscription {
+    pub subscription_id: String,
+    pub connection_id: String,
+    pub domain: String,
+    pub filter: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroWsUnsubscribeResponse {
+    pub subscription_id: String,
+    pub removed: bool,
+}
+
+/// Payload of the `neuro://ws` event.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeuroWsEvent<'a> {
+    pub connection_id: &'a str,
+    pub subscription_ids: Vec<String>,
+    pub envelope: &'a Value,
+}
+
+struct ConnectionSubscriptions {
+    generation: u64,
+    subscriptions: BTreeMap<String, NeuroWsSubscription>,
+    sink: WsSinkSlot,
+}
+
+/// Outcome of [`NeuroWsSubscriptionRegistry::subscribe`].
+pub(crate) struct SubscribeOutcome {
+    pub subscription: NeuroWsSubscription,
+    pub sink: WsSinkSlot,
+    /// Set when the connection had no subscriber socket yet and a worker must be started.
+    pub start_worker: Option<u64>,
+}
+
+/// Subscriptions per connection, kept across runtime reloads so workers can resubscribe.
+#[derive(Default)]
+pub struct NeuroWsSubscriptionRegistry {
+    connections: Mutex<HashMap<String, ConnectionSubscriptions>>,
+    next_id: AtomicU64,
+}
+
+impl NeuroWsSubscriptionRegistry {
+    fn connections(&self) -> std::sync::MutexGuard<'_, HashMap<String, ConnectionSubscriptions>> {
+        self.connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn subscribe(
+        &self,
+        connection_id: &str,
+        domain: String,
+        filter: Option<Value>,
+    ) -> SubscribeOutcome {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let subscription = NeuroWsSubscription {
+            subscription_id: format!("{SUBSCRIPTION_ID_PREFIX}{id}"),
+            connection_id: connection_id.to_string(),
+            domain,
+            filter,
+        };
+        let mut connections = self.connections();
+        let mut start_worker = None;
+        let entry = connections
+            .entry(connection_id.to_string())
+            .or_insert_with(|| {
+                start_worker = Some(id);
+                ConnectionSubscriptions {
+                    generation: id,
+                    subscriptions: BTreeMap::new(),
+                    sink: Arc::new(tokio::sync::Mutex::new(None)),
+                }
+            });
+        entry
+            .subscriptions
+            .insert(subscription.subscription_id.clone(), subscription.clone());
+        SubscribeOutcome {
+            subscription,
+            sink: Arc::clone(&entry.sink),
+            start_worker,
+        }
+    }
+
+    /// Removes a subscription; the sink is returned with `true` once the connection has none left.
+    pub(crate) fn unsubscribe(
+        &self,
+        subscription_id: &str,
+    ) -> Option<(NeuroWsSubscription, WsSinkSlot, bool)> {
+        let mut connections = self.connections();
+        let connection_id = connections
+            .iter()
+            .find(|(_, entry)| entry.subscriptions.contains_key(subscription_id))
+            .map(|(connection_id, _)| connection_id.clone())?;
+        let entry = connections.get_mut(&connection_id)?;
+        let subscription = entry.subscriptions.remove(subscription_id)?;
+        let sink = Arc::clone(&entry.sink);
+        let last = entry.subscriptions.is_empty();
+        if last {
+            connections.remove(&connection_id);
+        }
+        Some((subscription, sink, last))
+    }
+
+    /// Whether the worker started for `generation` should keep running.
+    pub(crate) fn is_current(&self, connection_id: &str, generation: u64) -> bool {
+        self.connections()
+            .get(connection_id)
+            .is_some_and(|entry| entry.generation == generation)
+    }
+
+    pub(crate) fn subscriptions(&self, connection_id: &str) -> Vec<NeuroWsSubscription> {
+        self.connections()
+            .get(connection_id)
+            .map(|entry| entry.subscriptions.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn matching(&self, connection_id: &str, envelope: &Value) -> Vec<String> {
+        self.connections()
+            .get(connection_id)
+            .map(|entry| {
+                entry
+                    .subscriptions
+                    .values()
+                    .filter(|subscription| subscription_matches(subscription, envelope))
+                    .map(|subscription| subscription.subscription_id.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Accepts a missing or `null` filter, or a JSON object.
+pub(crate) fn normalize_subscription_filter(
+    filter: Option<Value>,
+) -> Result<Option<Value>, String> {
+    match filter {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Object(fields)) if fields.is_empty() => Ok(None),
+        Some(filter @ Value::Object(_)) => Ok(Some(filter)),
+        Some(_) => Err("websocket subscription `filter` must be a JSON object".to_string()),
+    }
+}
+
+/// Every field of `filter` is present in `value`; objects are compared recursively.
+pub(crate) fn json_contains(value: &Value, filter: &Value) -> bool {
+    match (value, filter) {
+        (Value::Object(value), Value::Object(filter)) => filter.iter().all(|(key, expected)| {
+            value
+                .get(key)
+                .is_some_and(|actual| json_contains(actual, expected))
+        }),
+        _ => value == filter,
+    }
+}
+
+pub(crate) fn subscription_matches(subscription: &NeuroWsSubscription, envelope: &Value) -> bool {
+    let domain = envelope.get("domain").and_then(Value::as_str);
+    let id = envelope
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    // Replies to our own subscribe and unsubscribe frames carry a subscription id.
+    domain.is_some_and(|domain| domain.eq_ignore_ascii_case(&subscription.domain))
+        && !id.starts_with(SUBSCRIPTION_ID_PREFIX)
+        && subscription
+            .filter
+            .as_ref()
+            .is_none_or(|filter| json_contains(envelope, filter))
+}
+
+pub(crate) fn subscription_frame(subscription: &NeuroWsSubscription, action: &str) -> String {
+    json!({
+        "id": subscription.subscription_id,
+        "domain": subscription.domain,
+        "action": action,
+        "payload": { "filter": subscription.filter },
+    })
+    .to_string()
+}
+
+pub(crate) async fn send_frame(sink: &WsSinkSlot, frame: String) -> Result<bool, String> {
+    let mut sink = sink.lock().await;
+    let Some(writer) = sink.as_mut() else {
+        return Ok(false);
+    };
+    writer
+        .send(Message::text(frame))
+        .await
+        .map(|()| true)
+        .map_err(|error| error.to_string())
+}
+
+/// Closes the subscriber socket so its worker stops reading.
+pub(crate) async fn close_sink(sink: &WsSinkSlot) {
+    if let Some(mut writer) = sink.lock().await.take() {
+        let _ = writer.close().await;
+    }
+}
+
+pub(crate) fn next_reconnect_delay(current: Duration) -> Duration {
+    (current * 2).min(WS_RECONNECT_MAX_DELAY)
+}
+
+pub(crate) async fn connect_subscriber(config: &WsClientConfig) -> Result<WsSocket, String> {
+    let mut request = config
+        .url
+        .as_str()
+        .into_client_request()
+        .map_err(|error| format!("invalid websocket URL: {error}"))?;
+    for (name, value) in &config.connect_headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|error| format!("invalid websocket header `{name}`: {error}"))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|error| format!("invalid websocket header value for `{name}`: {error}"))?;
+        request.headers_mut().insert(name, value);
+    }
+    let timeout = Duration::from_secs(config.request_timeout_secs);
+    match tokio::time::timeout(timeout, tokio_tungstenite::connect_async(request)).await {
+        Ok(Ok((socket, _))) => Ok(socket),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(_) => Err(format!(
+            "websocket connect timed out after {} seconds",
+            timeout.as_secs()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        json_contains, next_reconnect_delay, normalize_subscription_filter, subscription_frame,
+        NeuroWsSubscriptionRegistry, WS_RECONNECT_MAX_DELAY,
+    };
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn routes_envelopes_to_matching_subscriptions() {
+        let registry = NeuroWsSubscriptionRegistry::default();
+        let jobs = registry.subscribe("dev", "jobs".to_string(), None);
+        assert!(jobs.start_worker.is_some());
+        let finished = registry.subscribe(
+            "dev",
+            "JOBS".to_string(),
+            Some(json!({ "action": "finished", "payload": { "client": "100" } })),
+        );
+        assert!(finished.start_worker.is_none());
+        let jobs_id = jobs.subscription.subscription_id.clone();
+        let finished_id = finished.subscription.subscription_id.clone();
+
+        let event = json!({
+            "id": "evt-1",
+            "domain": "jobs",
+            "action": "finished",
+            "payload": { "client": "100", "job": "ZNIGHTLY" },
+        });
+        assert_eq!(
+            registry.matching("dev", &event),
+            vec![jobs_id.clone(), finished_id.clone()]
+        );
+        assert_eq!(
+            registry.matching(
+                "dev",
+                &json!({ "id": "evt-2", "domain": "jobs", "action": "started" })
+            ),
+            vec![jobs_id.clone()]
+        );
+        assert!(registry
+            .matching(
+                "dev",
+                &json!({ "id": finished_id, "domain": "jobs", "ok": true })
+            )
+            .is_empty());
+        assert!(registry.matching("qa", &event).is_empty());
+
+        let generation = jobs.start_worker.unwrap();
+        let (_, _, last) = registry.unsubscribe(&jobs_id).unwrap();
+        assert!(!last);
+        assert!(registry.is_current("dev", generation));
+        let (_, _, last) = registry.unsubscribe(&finished_id).unwrap();
+        assert!(last);
+        assert!(!registry.is_current("dev", generation));
+        assert!(registry.unsubscribe(&finished_id).is_none());
+    }
+
+    #[test]
+    fn validates_filters_and_builds_frames() {
+        assert_eq!(normalize_subscription_filter(Some(json!({}))), Ok(None));
+        assert!(normalize_subscription_filter(Some(json!(["a"]))).is_err());
+        assert!(json_contains(
+            &json!({ "a": [1, 2], "b": 1 }),
+            &json!({ "a": [1, 2] })
+        ));
+        assert!(!json_contains(
+            &json!({ "a": [1, 2] }),
+            &json!({ "a": [1] })
+        ));
+
+        let registry = NeuroWsSubscriptionRegistry::default();
+        let outcome = registry.subscribe("dev", "locks".to_string(), None);
+        let frame: serde_json::Value =
+            serde_json::from_str(&subscription_frame(&outcome.subscription, "subscribe")).unwrap();
+        assert_eq!(frame["domain"], "locks");
+        assert_eq!(frame["action"], "subscribe");
+        assert_eq!(frame["payload"]["filter"], serde_json::Value::Null);
+
+        assert_eq!(
+            next_reconnect_delay(Duration::from_secs(4)),
+            Duration::from_secs(8)
+        );
+        assert_eq!(
+            next_reconnect_delay(WS_RECONNECT_MAX_DELAY),
+            WS_RECONNECT_MAX_DELAY
+        );
+    }
+}
@@ -50,6 +50,8 @@ async fn start_native_session(
                 active_turns: std::collections::HashMap::new(),
                 pending_approvals: std::collections::HashMap::new(),
                 pending_user_inputs: std::collections::HashMap::new(),
+                local_approvals: std::collections::HashMap::new(),
+                approved_dynamic_tools: std::collections::HashSet::new(),
                 next_approval_id: 1,
                 next_user_input_id: 1,
             }),
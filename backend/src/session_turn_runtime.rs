@@ -5,7 +5,7 @@ use std::fs::{FileTimes, OpenOptions};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 #[cfg(feature = "native-codex-runtime")]
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Manager, State};
 
 #[cfg(feature = "native-codex-runtime")]
@@ -52,9 +52,15 @@ use codex_protocol::ThreadId;
 use toml::map::Map as TomlMap;
 
 #[cfg(feature = "native-codex-runtime")]
-use crate::codex_event_translator::NativeCodexEventTranslator;
+use crate::codex_event_translator::{next_approval_action_id, NativeCodexEventTranslator};
+#[cfg(feature = "native-codex-runtime")]
+use crate::dynamic_tool_runtime::{native_dynamic_tool_specs, respond_to_native_dynamic_tool_call};
 #[cfg(feature = "native-codex-runtime")]
 use crate::emit_codex_event;
+#[cfg(feature = "native-codex-runtime")]
+use crate::neuro_agent_runtime::{
+    neuro_agent_approval_reason, neuro_agent_tool_confirms_write, neuro_agent_tool_writes,
+};
 use crate::status_runtime::{fetch_rate_limits_for_status, format_non_tui_status};
 use crate::{
     emit_lifecycle, emit_stderr, emit_stdout, lock_active_session, lock_runtime_config, AppState,
@@ -119,13 +125,17 @@ fn runtime_model_override(model: &str) -> Option<String> {
 
 #[cfg(feature = "native-codex-runtime")]
 const ALICIA_NATIVE_INTERNAL_PROFILE: &str = "__alicia_native_internal";
+#[cfg(feature = "native-codex-runtime")]
+const EPHEMERAL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[cfg(feature = "native-codex-runtime")]
 fn native_internal_profile_cli_overrides() -> Vec<(String, toml::Value)> {
-    vec![(
+    let mut overrides = vec![(
         format!("profiles.{ALICIA_NATIVE_INTERNAL_PROFILE}"),
         toml::Value::Table(TomlMap::new()),
-    )]
+    )];
+    overrides.extend(crate::neuro_mcp_server_runtime::neuro_mcp_server_cli_overrides());
+    overrides
 }
 
 #[cfg(feature = "native-codex-runtime")]
@@ -423,10 +433,11 @@ async fn resolve_native_thread(
                 }
                 let resumed = runtime
                     .thread_manager
-                    .resume_thread_from_rollout(
+                    .resume_thread_from_rollout_with_tools(
                         config,
                         rollout_path,
                         Arc::clone(&runtime.auth_manager),
+                        native_dynamic_tool_specs(),
                     )
                     .await
                     .map_err(|error| format!("failed to load thread `{thread_id}`: {error}"))?;
@@ -474,7 +485,7 @@ async fn resolve_native_thread(
     }
     let created = runtime
         .thread_manager
-        .start_thread(config)
+        .start_thread_with_tools(config, native_dynamic_tool_specs())
         .await
         .map_err(|error| format!("failed to start native thread: {error}"))?;
     let thread_id = created.thread_id.to_string();
@@ -553,7 +564,12 @@ async fn load_native_thread_from_active_session(
                 .map_err(|error| format!("failed to build native thread config: {error}"))?;
             let resumed = runtime
                 .thread_manager
-                .resume_thread_from_rollout(config, rollout_path, Arc::clone(&runtime.auth_manager))
+                .resume_thread_from_rollout_with_tools(
+                    config,
+                    rollout_path,
+                    Arc::clone(&runtime.auth_manager),
+                    native_dynamic_tool_specs(),
+                )
                 .await
                 .map_err(|error| {
                     format!("failed to load thread `{normalized_thread_id}`: {error}")
@@ -758,6 +774,207 @@ fn clear_native_pending_actions_for_threads(
     native
         .pending_user_inputs
         .retain(|_, pending| !thread_id_set.contains(pending.thread_id.as_str()));
+    // Dropping the sender declines the tool call that is still waiting on it.
+    native
+        .local_approvals
+        .retain(|action_id, _| native.pending_approvals.contains_key(action_id));
+}
+
+#[cfg(feature = "native-codex-runtime")]
+fn approval_requested_event(
+    kind: &str,
+    thread_id: &str,
+    turn_id: &str,
+    item_id: &str,
+    reason: String,
+    command: &str,
+) -> Value {
+    json!({
+        "type": "approval.requested",
+        "kind": kind,
+        "thread_id": thread_id,
+        "turn_id": turn_id,
+        "item_id": item_id,
+        "reason": reason,
+        "command": command,
+        "cwd": "",
+        "command_actions": [],
+        "proposed_execpolicy_amendment": [],
+        "grant_root": "",
+    })
+}
+
+/// Registers an approval answered by the app itself and waits for `codex_approval_respond`.
+#[cfg(feature = "native-codex-runtime")]
+async fn await_local_approval(
+    app: &AppHandle,
+    session_id: u64,
+    pending: crate::NativePendingApproval,
+    mut event: Value,
+) -> Result<ReviewDecision, String> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let action_id = with_native_handles_mut(app, session_id, |native| {
+        let action_id = next_approval_action_id(native);
+        native.pending_approvals.insert(action_id.clone(), pending);
+        native.local_approvals.insert(action_id.clone(), sender);
+        action_id
+    })
+    .ok_or_else(|| "codex session ended before the approval was requested".to_string())?;
+
+    event["action_id"] = Value::String(action_id);
+    let event_seq = Arc::clone(&app.state::<AppState>().next_event_seq);
+    emit_codex_event(app, session_id, event, &event_seq);
+
+    receiver
+        .await
+        .map_err(|_| "approval was dismissed without a decision".to_string())
+}
+
+/// Write tools served by the `neuro` MCP server ask for approval like core does for commands,
+/// unless the policy is `never`, the user already approved the tool for this session, or the
+/// write itself is confirmed with a diff. Without an active session writes are declined.
+#[cfg(feature = "native-codex-runtime")]
+pub(crate) async fn approve_neuro_tool_call(
+    app: &AppHandle,
+    tool: &str,
+    arguments: &Value,
+) -> bool {
+    if neuro_agent_tool_writes(tool) != Some(true) {
+        return true;
+    }
+    let approval_policy = {
+        let state = app.state::<AppState>();
+        match lock_runtime_config(state.inner()) {
+            Ok(config) => config.approval_policy.clone(),
+            Err(_) => return false,
+        }
+    };
+    if matches!(
+        runtime_approval_policy(&approval_policy),
+        AskForApproval::Never
+    ) {
+        return true;
+    }
+    if neuro_agent_tool_confirms_write(app, tool, arguments).await {
+        return true;
+    }
+    let (session_id, thread_id, turn_id, approved_for_session) = {
+        let state = app.state::<AppState>();
+        let Ok(guard) = lock_active_session(state.inner()) else {
+            return false;
+        };
+        let Some(active) = guard.as_ref() else {
+            return false;
+        };
+        let crate::ActiveSessionTransport::Native(native) = &active.transport;
+        let thread_id = active.thread_id.clone().unwrap_or_default();
+        let turn_id = native
+            .active_turns
+            .get(&thread_id)
+            .cloned()
+            .unwrap_or_default();
+        (
+            active.session_id,
+            thread_id,
+            turn_id,
+            native.approved_dynamic_tools.contains(tool),
+        )
+    };
+    if approved_for_session {
+        return true;
+    }
+
+    let pending = crate::NativePendingApproval {
+        thread_id: thread_id.clone(),
+        turn_id: turn_id.clone(),
+        call_id: tool.to_string(),
+        kind: crate::NativeApprovalKind::DynamicToolCall,
+    };
+    let event = approval_requested_event(
+        "dynamic_tool_call",
+        &thread_id,
+        &turn_id,
+        tool,
+        neuro_agent_approval_reason(tool, arguments),
+        tool,
+    );
+    match await_local_approval(app, session_id, pending, event).await {
+        Ok(ReviewDecision::Approved) => true,
+        Ok(ReviewDecision::ApprovedForSession) => {
+            let _ = with_native_handles_mut(app, session_id, |native| {
+                native.approved_dynamic_tools.insert(tool.to_string())
+            });
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(not(feature = "native-codex-runtime"))]
+pub(crate) async fn approve_neuro_tool_call(
+    _app: &AppHandle,
+    _tool: &str,
+    _arguments: &Value,
+) -> bool {
+    false
+}
+
+/// Asks the user to confirm a SAP write, showing `diff`, through the active session's
+/// approval flow. Every write is confirmed on its own: the prompt does not offer
+/// `acceptForSession`, and a session approval only covers this write.
+#[cfg(feature = "native-codex-runtime")]
+pub(crate) async fn request_neuro_write_approval(
+    app: &AppHandle,
+    object_uri: &str,
+    reason: String,
+    diff: String,
+) -> Result<bool, String> {
+    let (session_id, thread_id, turn_id) = {
+        let state = app.state::<AppState>();
+        let guard = lock_active_session(state.inner())?;
+        let active = guard
+            .as_ref()
+            .ok_or_else(|| "no active codex session to confirm the write".to_string())?;
+        let crate::ActiveSessionTransport::Native(native) = &active.transport;
+        let thread_id = active.thread_id.clone().unwrap_or_default();
+        let turn_id = native
+            .active_turns
+            .get(&thread_id)
+            .cloned()
+            .unwrap_or_default();
+        (active.session_id, thread_id, turn_id)
+    };
+
+    let pending = crate::NativePendingApproval {
+        thread_id: thread_id.clone(),
+        turn_id: turn_id.clone(),
+        call_id: object_uri.to_string(),
+        kind: crate::NativeApprovalKind::NeuroWrite,
+    };
+    let mut event = approval_requested_event(
+        "neuro_write",
+        &thread_id,
+        &turn_id,
+        object_uri,
+        reason,
+        object_uri,
+    );
+    event["diff"] = Value::String(diff);
+    let decision = await_local_approval(app, session_id, pending, event).await?;
+    Ok(matches!(
+        decision,
+        ReviewDecision::Approved | ReviewDecision::ApprovedForSession
+    ))
+}
+
+#[cfg(not(feature = "native-codex-runtime"))]
+pub(crate) async fn request_neuro_write_approval(
+    _app: &AppHandle,
+    _object_uri: &str,
+    _reason: String,
+    _diff: String,
+) -> Result<bool, String> {
+    Err("write confirmation requires native runtime support in this build".to_string())
 }
 
 #[cfg(feature = "native-codex-runtime")]
@@ -1467,6 +1684,14 @@ async fn schedule_turn_run_native(
                     .next_event()
                     .await
                     .map_err(|error| format!("native event stream failed: {error}"))?;
+                if let EventMsg::DynamicToolCallRequest(request) = &event.msg {
+                    for translated in
+                        respond_to_native_dynamic_tool_call(&thread, &cwd, request).await?
+                    {
+                        emit_codex_event(&app_for_task, session_id, translated, &event_seq);
+                    }
+                    continue;
+                }
                 let is_terminal = matches!(
                     event.msg,
                     EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_)
@@ -1620,6 +1845,14 @@ async fn schedule_review_start_native(
                     .next_event()
                     .await
                     .map_err(|error| format!("native event stream failed: {error}"))?;
+                if let EventMsg::DynamicToolCallRequest(request) = &event.msg {
+                    for translated in
+                        respond_to_native_dynamic_tool_call(&thread, &cwd, request).await?
+                    {
+                        emit_codex_event(&app_for_task, session_id, translated, &event_seq);
+                    }
+                    continue;
+                }
                 let is_terminal = matches!(
                     event.msg,
                     EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_)
@@ -1694,6 +1927,115 @@ pub(crate) async fn codex_review_start_impl(
     schedule_review_start(app, state, request).await
 }
 
+#[cfg(feature = "native-codex-runtime")]
+pub(crate) async fn run_ephemeral_structured_turn(
+    runtime: Arc<crate::codex_native_runtime::NativeCodexRuntime>,
+    runtime_config: &RuntimeCodexConfig,
+    cwd: &Path,
+    prompt: String,
+    output_schema: Value,
+) -> Result<String, String> {
+    if !output_schema.is_object() {
+        return Err("output_schema must be a plain JSON object".to_string());
+    }
+
+    let mut config = native_config_builder(runtime.codex_home.clone(), cwd)
+        .harness_overrides(ConfigOverrides {
+            approval_policy: Some(AskForApproval::Never),
+            sandbox_mode: Some(SandboxModeConfig::ReadOnly),
+            ..runtime_config_harness_overrides(runtime_config, cwd)
+        })
+        .build()
+        .await
+        .map_err(|error| format!("failed to build ephemeral thread config: {error}"))?;
+    apply_runtime_config_bootstrap_overrides(&mut config, runtime_config)?;
+
+    let created = runtime
+        .thread_manager
+        .start_thread(config)
+        .await
+        .map_err(|error| format!("failed to start ephemeral thread: {error}"))?;
+    let thread = Arc::clone(&created.thread);
+
+    let result: Result<String, String> = async {
+        let model = match runtime_model_override(runtime_config.model.as_str()) {
+            Some(model) => model,
+            None => thread.config_snapshot().await.model,
+        };
+
+        thread
+            .submit(Op::UserTurn {
+                items: vec![UserInput::Text {
+                    text: prompt,
+                    text_elements: Vec::new(),
+                }],
+                cwd: cwd.to_path_buf(),
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::new_read_only_policy(),
+                model,
+                effort: runtime_reasoning_effort(&runtime_config.reasoning),
+                summary: ReasoningSummaryConfig::default(),
+                final_output_json_schema: Some(output_schema),
+                collaboration_mode: None,
+                personality: None,
+            })
+            .await
+            .map_err(|error| format!("failed to submit ephemeral turn: {error}"))?;
+
+        let mut last_agent_message: Option<String> = None;
+        loop {
+            let event = thread
+                .next_event()
+                .await
+                .map_err(|error| format!("ephemeral event stream failed: {error}"))?;
+            match event.msg {
+                EventMsg::AgentMessage(message) => {
+                    last_agent_message = Some(message.message);
+                }
+                EventMsg::Error(error) => {
+                    return Err(format!("ephemeral turn failed: {}", error.message));
+                }
+                EventMsg::TurnComplete(complete) => {
+                    if let Some(message) = complete.last_agent_message {
+                        last_agent_message = Some(message);
+                    }
+                    break;
+                }
+                EventMsg::TurnAborted(_) => {
+                    return Err("ephemeral turn was aborted".to_string());
+                }
+                _ => {}
+            }
+        }
+
+        last_agent_message
+            .filter(|message| !message.trim().is_empty())
+            .ok_or_else(|| "ephemeral turn finished without an agent message".to_string())
+    }
+    .await;
+
+    let rollout_path = thread.rollout_path();
+    let _ = runtime
+        .thread_manager
+        .remove_thread(&created.thread_id)
+        .await;
+    let _ = thread.submit(Op::Shutdown).await;
+    // Wait for the recorder to flush before deleting, so the thread never shows up in history.
+    let _ = tokio::time::timeout(EPHEMERAL_SHUTDOWN_TIMEOUT, async {
+        while let Ok(event) = thread.next_event().await {
+            if matches!(event.msg, EventMsg::ShutdownComplete) {
+                break;
+            }
+        }
+    })
+    .await;
+    if let Some(rollout_path) = rollout_path {
+        let _ = tokio::fs::remove_file(rollout_path).await;
+    }
+
+    result
+}
+
 pub(crate) async fn codex_thread_open_impl(
     app: AppHandle,
     state: State<'_, AppState>,
@@ -2781,12 +3123,12 @@ pub(crate) async fn codex_approval_respond_impl(
             .ok_or_else(|| format!("approval action not found: {action_id}"))?;
 
         let thread = native.threads.get(&pending_approval.thread_id).cloned();
-        let Some(thread) = thread else {
+        if thread.is_none() && !pending_approval.kind.is_local() {
             native
                 .pending_approvals
                 .insert(action_id.to_string(), pending_approval.clone());
             return Err(format!("thread not found: {}", pending_approval.thread_id));
-        };
+        }
 
         (
             session_id,
@@ -2811,32 +3153,48 @@ pub(crate) async fn codex_approval_respond_impl(
     })?;
     let decision_label = review_decision.to_opaque_string().to_string();
     let op = match pending_approval.kind {
-        crate::NativeApprovalKind::CommandExecution => Op::ExecApproval {
+        crate::NativeApprovalKind::CommandExecution => Some(Op::ExecApproval {
             id: pending_approval.call_id.clone(),
             turn_id: Some(pending_approval.turn_id.clone())
                 .filter(|value| !value.trim().is_empty()),
             decision: review_decision,
-        },
-        crate::NativeApprovalKind::FileChange => Op::PatchApproval {
+        }),
+        crate::NativeApprovalKind::FileChange => Some(Op::PatchApproval {
             id: pending_approval.call_id.clone(),
             decision: review_decision,
-        },
+        }),
+        crate::NativeApprovalKind::DynamicToolCall | crate::NativeApprovalKind::NeuroWrite => {
+            let sender = with_native_handles_mut(&app, session_id, |native| {
+                native.local_approvals.remove(action_id)
+            })
+            .flatten();
+            if !sender.is_some_and(|sender| sender.send(review_decision).is_ok()) {
+                return Err(format!(
+                    "tool call is no longer waiting for approval: {action_id}"
+                ));
+            }
+            None
+        }
     };
 
-    if let Err(error) = thread.submit(op).await {
-        let _ = with_native_handles_mut(&app, session_id, |native| {
-            native
-                .pending_approvals
-                .insert(action_id.to_string(), pending_approval.clone());
-        });
-        return Err(format!(
-            "failed to submit native approval response: {error}"
-        ));
+    if let (Some(op), Some(thread)) = (op, thread) {
+        if let Err(error) = thread.submit(op).await {
+            let _ = with_native_handles_mut(&app, session_id, |native| {
+                native
+                    .pending_approvals
+                    .insert(action_id.to_string(), pending_approval.clone());
+            });
+            return Err(format!(
+                "failed to submit native approval response: {error}"
+            ));
+        }
     }
 
     let kind_label = match pending_approval.kind {
         crate::NativeApprovalKind::CommandExecution => "command_execution",
         crate::NativeApprovalKind::FileChange => "file_change",
+        crate::NativeApprovalKind::DynamicToolCall => "dynamic_tool_call",
+        crate::NativeApprovalKind::NeuroWrite => "neuro_write",
     };
     emit_codex_event(
         &app,
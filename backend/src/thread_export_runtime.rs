@@ -0,0 +1,415 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::CodexThreadSummary;
+
+const THREAD_COMMITS_FILE_NAME: &str = "alicia-thread-commits.json";
+
+/// Serializes updates of the commits file, which are a read-modify-write of the whole file.
+static THREAD_COMMITS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitThreadExportRequest {
+    pub thread_id: String,
+    pub format: String,
+    pub output_dir: String,
+    pub revision_range: Option<String>,
+    #[serde(default = "default_include_summary")]
+    pub include_summary: bool,
+}
+
+fn default_include_summary() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitThreadExportResponse {
+    pub thread_id: String,
+    pub format: String,
+    pub output_dir: String,
+    pub files: Vec<String>,
+    pub commits: Vec<GitExportedCommit>,
+    pub summary_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitExportedCommit {
+    pub sha: String,
+    pub subject: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitThreadExportFormat {
+    Patch,
+    Bundle,
+    Diff,
+}
+
+impl GitThreadExportFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "patch" | "format-patch" => Ok(Self::Patch),
+            "bundle" => Ok(Self::Bundle),
+            "diff" => Ok(Self::Diff),
+            other => Err(format!(
+                "unsupported export format `{other}` (expected patch, bundle or diff)"
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Patch => "patch",
+            Self::Bundle => "bundle",
+            Self::Diff => "diff",
+        }
+    }
+}
+
+pub fn is_safe_revision_range(range: &str) -> bool {
+    !range.is_empty()
+        && !range.starts_with('-')
+        && !range
+            .chars()
+            .any(|entry| entry.is_whitespace() || entry.is_control())
+}
+
+pub fn export_file_stem(thread_id: &str) -> String {
+    let sanitized = thread_id
+        .chars()
+        .map(|entry| {
+            if entry.is_ascii_alphanumeric() || entry == '-' || entry == '_' {
+                entry
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("thread-{sanitized}")
+}
+
+pub fn parse_exported_commits(log_output: &str) -> Vec<GitExportedCommit> {
+    log_output
+        .lines()
+        .filter_map(|line| {
+            let (sha, subject) = line.split_once('\t')?;
+            let sha = sha.trim();
+            if sha.is_empty() {
+                return None;
+            }
+            Some(GitExportedCommit {
+                sha: sha.to_string(),
+                subject: subject.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Commits created through `git_commit_approved_review`, per thread, in creation order.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadCommitsFile {
+    #[serde(default)]
+    threads: BTreeMap<String, Vec<String>>,
+}
+
+pub fn thread_commits_path() -> PathBuf {
+    crate::config_runtime::resolve_codex_home().join(THREAD_COMMITS_FILE_NAME)
+}
+
+fn load_thread_commits_file(path: &Path) -> Result<ThreadCommitsFile, String> {
+    if !path.is_file() {
+        return Ok(ThreadCommitsFile::default());
+    }
+    let raw = fs::read_to_string(path)
+        .map_err(|error| format!("failed to read `{}`: {error}", path.display()))?;
+    if raw.trim().is_empty() {
+        return Ok(ThreadCommitsFile::default());
+    }
+    serde_json::from_str(&raw)
+        .map_err(|error| format!("failed to parse `{}`: {error}", path.display()))
+}
+
+pub fn load_thread_commits(path: &Path, thread_id: &str) -> Result<Vec<String>, String> {
+    Ok(load_thread_commits_file(path)?
+        .threads
+        .remove(thread_id)
+        .unwrap_or_default())
+}
+
+/// Appends `sha` to the thread; an amended commit replaces the one it rewrote.
+pub fn record_thread_commit(
+    path: &Path,
+    thread_id: &str,
+    sha: &str,
+    replaced: Option<&str>,
+) -> Result<(), String> {
+    let _guard = THREAD_COMMITS_LOCK
+        .lock()
+        .map_err(|_| "thread commits lock poisoned".to_string())?;
+    let mut file = load_thread_commits_file(path)?;
+    let commits = file.threads.entry(thread_id.to_string()).or_default();
+    match replaced.and_then(|old| commits.iter().position(|entry| entry == old)) {
+        Some(index) => commits[index] = sha.to_string(),
+        None if !commits.iter().any(|entry| entry == sha) => commits.push(sha.to_string()),
+        None => {}
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create `{}`: {error}", parent.display()))?;
+    }
+    let serialized = serde_json::to_string_pretty(&file)
+        .map_err(|error| format!("failed to serialize thread commits: {error}"))?;
+    let staging = path.with_extension("json.tmp");
+    fs::write(&staging, serialized)
+        .map_err(|error| format!("failed to write `{}`: {error}", staging.display()))?;
+    fs::rename(&staging, path)
+        .map_err(|error| format!("failed to replace `{}`: {error}", path.display()))
+}
+
+pub fn format_epoch_seconds_utc(epoch_seconds: i64) -> String {
+    let days = epoch_seconds.div_euclid(86_400);
+    let seconds_of_day = epoch_seconds.rem_euclid(86_400);
+
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        seconds_of_day / 3_600,
+        (seconds_of_day % 3_600) / 60,
+        seconds_of_day % 60
+    )
+}
+
+fn role_heading(role: &str) -> String {
+    let mut chars = role.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Message".to_string(),
+    }
+}
+
+pub fn build_thread_export_summary(
+    thread: &CodexThreadSummary,
+    format: GitThreadExportFormat,
+    commits: &[GitExportedCommit],
+    files: &[String],
+) -> String {
+    let title = thread.preview.lines().next().unwrap_or("").trim();
+    let mut summary = format!(
+        "# Thread export: {}\n\n",
+        if title.is_empty() {
+            thread.id.as_str()
+        } else {
+            title
+        }
+    );
+
+    summary.push_str(&format!("- Thread: `{}`\n", thread.id));
+    if let Some(codex_thread_id) = thread.codex_thread_id.as_deref() {
+        summary.push_str(&format!("- Codex thread: `{codex_thread_id}`\n"));
+    }
+    summary.push_str(&format!("- Workspace: `{}`\n", thread.cwd));
+    summary.push_str(&format!("- Model provider: {}\n", thread.model_provider));
+    summary.push_str(&format!(
+        "- Created: {}\n",
+        format_epoch_seconds_utc(thread.created_at)
+    ));
+    summary.push_str(&format!(
+        "- Updated: {}\n",
+        format_epoch_seconds_utc(thread.updated_at)
+    ));
+    summary.push_str(&format!("- Turns: {}\n", thread.turn_count));
+    summary.push_str(&format!("- Export format: {}\n", format.as_str()));
+
+    if !commits.is_empty() {
+        summary.push_str("\n## Commits\n\n");
+        for commit in commits {
+            let short_sha = commit.sha.get(..12).unwrap_or(&commit.sha);
+            summary.push_str(&format!("- `{short_sha}` {}\n", commit.subject));
+        }
+    }
+
+    if !files.is_empty() {
+        summary.push_str("\n## Files\n\n");
+        for file in files {
+            summary.push_str(&format!("- `{file}`\n"));
+        }
+    }
+
+    if !thread.turns.is_empty() {
+        summary.push_str("\n## Conversation\n");
+        for (index, turn) in thread.turns.iter().enumerate() {
+            summary.push_str(&format!("\n### Turn {} ({})\n", index + 1, turn.status));
+            for message in &turn.messages {
+                let content = message.content.trim();
+                if content.is_empty() {
+                    continue;
+                }
+                summary.push_str(&format!(
+                    "\n**{}:**\n\n{content}\n",
+                    role_heading(&message.role)
+                ));
+            }
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_thread_export_summary, export_file_stem, format_epoch_seconds_utc,
+        is_safe_revision_range, load_thread_commits, parse_exported_commits, record_thread_commit,
+        GitExportedCommit, GitThreadExportFormat,
+    };
+    use crate::{CodexThreadSummary, CodexThreadTurnHistoryMessage, CodexThreadTurnSummary};
+
+    #[test]
+    fn parses_export_formats() {
+        assert_eq!(
+            GitThreadExportFormat::parse("Format-Patch"),
+            Ok(GitThreadExportFormat::Patch)
+        );
+        assert_eq!(
+            GitThreadExportFormat::parse("bundle"),
+            Ok(GitThreadExportFormat::Bundle)
+        );
+        assert!(GitThreadExportFormat::parse("zip").is_err());
+    }
+
+    #[test]
+    fn validates_revision_ranges() {
+        assert!(is_safe_revision_range("main..HEAD"));
+        assert!(is_safe_revision_range("HEAD~3"));
+        assert!(!is_safe_revision_range("--output=/tmp/x"));
+        assert!(!is_safe_revision_range("main HEAD"));
+        assert!(!is_safe_revision_range(""));
+    }
+
+    #[test]
+    fn sanitizes_export_file_stem() {
+        assert_eq!(export_file_stem("019a/../x y"), "thread-019a____x_y");
+    }
+
+    #[test]
+    fn parses_exported_commits_from_log() {
+        assert_eq!(
+            parse_exported_commits("abc\tfix: one\n\ndef\tfeat: two\n"),
+            vec![
+                GitExportedCommit {
+                    sha: "abc".to_string(),
+                    subject: "fix: one".to_string(),
+                },
+                GitExportedCommit {
+                    sha: "def".to_string(),
+                    subject: "feat: two".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn records_thread_commits_and_replaces_amended_ones() {
+        let dir = std::env::temp_dir().join(format!(
+            "alicia-thread-commits-{}-{}",
+            std::process::id(),
+            line!()
+        ));
+        let path = dir.join("commits.json");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(load_thread_commits(&path, "t1").unwrap().is_empty());
+        record_thread_commit(&path, "t1", "aaa", None).unwrap();
+        record_thread_commit(&path, "t1", "bbb", None).unwrap();
+        record_thread_commit(&path, "t1", "bbb", None).unwrap();
+        record_thread_commit(&path, "t2", "ccc", None).unwrap();
+        record_thread_commit(&path, "t1", "ddd", Some("bbb")).unwrap();
+
+        assert_eq!(
+            load_thread_commits(&path, "t1").unwrap(),
+            vec!["aaa", "ddd"]
+        );
+        assert_eq!(load_thread_commits(&path, "t2").unwrap(), vec!["ccc"]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn formats_epoch_seconds_as_utc() {
+        assert_eq!(format_epoch_seconds_utc(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(
+            format_epoch_seconds_utc(1_709_210_096),
+            "2024-02-29 12:34:56 UTC"
+        );
+    }
+
+    #[test]
+    fn summary_includes_metadata_commits_and_turns() {
+        let thread = CodexThreadSummary {
+            id: "thread-1".to_string(),
+            codex_thread_id: None,
+            preview: "Fix the parser\nmore".to_string(),
+            model_provider: "openai".to_string(),
+            created_at: 0,
+            updated_at: 60,
+            cwd: "/repo".to_string(),
+            path: None,
+            source: "app".to_string(),
+            turn_count: 1,
+            turns: vec![CodexThreadTurnSummary {
+                id: "turn-1".to_string(),
+                status: "completed".to_string(),
+                item_count: 2,
+                messages: vec![
+                    CodexThreadTurnHistoryMessage {
+                        role: "user".to_string(),
+                        content: "Please fix it".to_string(),
+                    },
+                    CodexThreadTurnHistoryMessage {
+                        role: "assistant".to_string(),
+                        content: "Done".to_string(),
+                    },
+                ],
+            }],
+        };
+
+        let summary = build_thread_export_summary(
+            &thread,
+            GitThreadExportFormat::Patch,
+            &[GitExportedCommit {
+                sha: "0123456789abcdef".to_string(),
+                subject: "fix: parser".to_string(),
+            }],
+            &["0001-fix-parser.patch".to_string()],
+        );
+
+        assert!(summary.starts_with("# Thread export: Fix the parser\n"));
+        assert!(summary.contains("- Updated: 1970-01-01 00:01:00 UTC\n"));
+        assert!(summary.contains("- `0123456789ab` fix: parser\n"));
+        assert!(summary.contains("- `0001-fix-parser.patch`\n"));
+        assert!(summary.contains("### Turn 1 (completed)"));
+        assert!(summary.contains("**User:**\n\nPlease fix it\n"));
+        assert!(summary.contains("**Assistant:**\n\nDone\n"));
+    }
+}
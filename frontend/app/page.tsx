@@ -21,18 +21,30 @@ import {
   codexRuntimeSessionStop,
   codexUserInputRespond,
   isTauriRuntime,
+  listenToNeuroHealthEvents,
+  NEURO_ENV_CONNECTION_ID,
   pickImageFile,
   pickMentionFile,
   gitCommitApprovedReview,
   codexWorkspaceChanges,
   codexWorkspaceReadFile,
   codexWorkspaceWriteFile,
+  neuroActivate,
+  neuroAtcRun,
   neuroGetSource,
+  neuroHealthHistory,
+  neuroLockObject,
+  neuroSyntaxCheck,
+  neuroUnlockObject,
   neuroUpdateSource,
   terminalResize,
   terminalWrite,
   type ApprovalDecision,
   type CodexModel,
+  type NeuroAtcFinding,
+  type NeuroCheckMessage,
+  type NeuroDiagnoseStatus,
+  type NeuroObjectLock,
   type NeuroRuntimeCommandError,
   type RuntimeCodexConfig,
 } from "@/lib/tauri-bridge"
@@ -81,6 +93,7 @@ import {
   resolveAbapSourceRef,
 } from "@/lib/abap-source-resolver"
 import { routeSourceEditorRef, type SourceEditorRefKind } from "@/lib/source-editor-ref-routing"
+import { atcFindingsToDiagnostics, buildAtcFindingsPrompt } from "@/lib/neuro-atc-findings"
 
 interface SourceEditorState {
   visible: boolean
@@ -91,9 +104,13 @@ interface SourceEditorState {
   language: string
   source: string
   etag: string | null
+  lock: NeuroObjectLock | null
+  diagnostics: NeuroCheckMessage[]
+  atcFindings: NeuroAtcFinding[]
   dirty: boolean
   loading: boolean
   saving: boolean
+  checking: boolean
   error: string | null
 }
 
@@ -106,9 +123,13 @@ const INITIAL_SOURCE_EDITOR_STATE: SourceEditorState = {
   language: "plaintext",
   source: "",
   etag: null,
+  lock: null,
+  diagnostics: [],
+  atcFindings: [],
   dirty: false,
   loading: false,
   saving: false,
+  checking: false,
   error: null,
 }
 
@@ -177,9 +198,46 @@ export default function AliciaTerminal() {
   const wasReviewThinkingRef = useRef(false)
   const sourceOperationSeqRef = useRef(0)
   const sourceBaselineRef = useRef("")
+  const sourceLockRef = useRef<string | null>(null)
   const sourceEditorRef = useRef<SourceEditorState>(INITIAL_SOURCE_EDITOR_STATE)
 
   const [isReviewComplete, setIsReviewComplete] = useState(false)
+  const [sapHealth, setSapHealth] = useState<{
+    status: NeuroDiagnoseStatus
+    detail: string | null
+  } | null>(null)
+
+  useEffect(() => {
+    if (!isTauriRuntime()) {
+      return
+    }
+
+    let disposed = false
+    let unlisten: (() => void) | null = null
+    void neuroHealthHistory()
+      .then(({ status }) => {
+        if (!disposed && status) {
+          setSapHealth((previous) => previous ?? { status, detail: null })
+        }
+      })
+      .catch(() => undefined)
+    void listenToNeuroHealthEvents((event) => {
+      if (event.connectionId === NEURO_ENV_CONNECTION_ID) {
+        setSapHealth({ status: event.status, detail: event.detail })
+      }
+    }).then((dispose) => {
+      if (disposed) {
+        dispose()
+      } else {
+        unlisten = dispose
+      }
+    })
+
+    return () => {
+      disposed = true
+      unlisten?.()
+    }
+  }, [])
 
   useEffect(() => {
     const cached = readModelsCache()
@@ -781,12 +839,19 @@ export default function AliciaTerminal() {
           paths: expandedPaths,
           message: commitMessage,
           cwd: activeSessionCwd ?? undefined,
+          threadId: threadIdRef.current ?? undefined,
         })
 
         if (!response.success) {
           const addError = response.add.stderr.trim()
           const commitError = response.commit.stderr.trim()
-          const failure = addError || commitError || "git add/commit failed"
+          const validationErrors = response.validation?.errors.join("; ") ?? ""
+          const failedHook = response.hooks?.failedHook
+          const failure =
+            addError ||
+            (validationErrors ? `${commitError}: ${validationErrors}` : "") ||
+            (failedHook ? `${failedHook} hook failed: ${commitError}` : commitError) ||
+            "git add/commit failed"
           throw new Error(failure)
         }
 
@@ -798,6 +863,9 @@ export default function AliciaTerminal() {
         if (reviewNotes.length > 0) {
           addMessage("system", `[review] notes\n${reviewNotes.join("\n")}`, "review")
         }
+        for (const warning of response.warnings) {
+          addMessage("system", `[review] warning: ${warning}`, "review")
+        }
       } catch (error) {
         addMessage("system", `[review] commit failed: ${String(error)}`, "review")
       } finally {
@@ -845,6 +913,66 @@ export default function AliciaTerminal() {
     return parts.at(-1) ?? normalized
   }, [])
 
+  const releaseSourceLock = useCallback(() => {
+    const lockedObjectUri = sourceLockRef.current
+    if (!lockedObjectUri) {
+      return
+    }
+    sourceLockRef.current = null
+    void neuroUnlockObject(lockedObjectUri).catch(() => undefined)
+  }, [])
+
+  const ensureSourceLock = useCallback(() => {
+    const currentEditor = sourceEditorRef.current
+    if (
+      currentEditor.refKind !== "abap" ||
+      !currentEditor.objectUri ||
+      sourceLockRef.current === currentEditor.objectUri
+    ) {
+      return
+    }
+    const objectUri = currentEditor.objectUri
+    sourceLockRef.current = objectUri
+
+    void (async () => {
+      try {
+        const lock = await neuroLockObject(objectUri)
+        if (sourceLockRef.current !== objectUri) {
+          void neuroUnlockObject(objectUri).catch(() => undefined)
+          return
+        }
+        setSourceEditorWithRef((previous) =>
+          previous.objectUri === objectUri ? { ...previous, lock } : previous,
+        )
+      } catch (error) {
+        setSourceEditorWithRef((previous) =>
+          previous.objectUri === objectUri
+            ? {
+                ...previous,
+                error: `Falha ao bloquear objeto para edicao: ${toEditorErrorMessage(error)}`,
+              }
+            : previous,
+        )
+      }
+    })()
+  }, [setSourceEditorWithRef, toEditorErrorMessage])
+
+  const handleChangeSource = useCallback(
+    (value: string) => {
+      const dirty = Boolean(sourceEditorRef.current.objectUri) && value !== sourceBaselineRef.current
+      setSourceEditorWithRef((previous) => ({
+        ...previous,
+        source: value,
+        dirty: Boolean(previous.objectUri) && value !== sourceBaselineRef.current,
+        error: null,
+      }))
+      if (dirty) {
+        ensureSourceLock()
+      }
+    },
+    [ensureSourceLock, setSourceEditorWithRef],
+  )
+
   const handleOpenSourceFromRef = useCallback(
     (ref: string): boolean => {
       const currentEditor = sourceEditorRef.current
@@ -858,6 +986,7 @@ export default function AliciaTerminal() {
         return false
       }
 
+      releaseSourceLock()
       const refRoute = routeSourceEditorRef(ref)
       const activeWorkspace = runtime.workspace
       if (refRoute.kind === "workspace" && runtime.state !== "running") {
@@ -870,9 +999,13 @@ export default function AliciaTerminal() {
           language: refRoute.monacoLanguage,
           source: "",
           etag: null,
+          lock: null,
+          diagnostics: [],
+          atcFindings: [],
           dirty: false,
           loading: false,
           saving: false,
+          checking: false,
           error:
             "Inicie uma sessao ativa antes de abrir arquivos do workspace no editor de codigo.",
         })
@@ -894,9 +1027,13 @@ export default function AliciaTerminal() {
         language: refRoute.monacoLanguage,
         source: "",
         etag: null,
+        lock: null,
+        diagnostics: [],
+        atcFindings: [],
         dirty: false,
         loading: true,
         saving: false,
+        checking: false,
         error: null,
       }))
 
@@ -919,9 +1056,13 @@ export default function AliciaTerminal() {
               language: "abap",
               source: loaded.source,
               etag: loaded.etag ?? null,
+              lock: null,
+              diagnostics: [],
+              atcFindings: [],
               dirty: false,
               loading: false,
               saving: false,
+              checking: false,
               error: null,
             })
             sourceBaselineRef.current = loaded.source
@@ -945,9 +1086,13 @@ export default function AliciaTerminal() {
             language: refRoute.monacoLanguage,
             source: loaded.source,
             etag: null,
+            lock: null,
+            diagnostics: [],
+            atcFindings: [],
             dirty: false,
             loading: false,
             saving: false,
+            checking: false,
             error: null,
           })
           sourceBaselineRef.current = loaded.source
@@ -968,9 +1113,13 @@ export default function AliciaTerminal() {
             language: refRoute.monacoLanguage,
             source: "",
             etag: null,
+            lock: null,
+            diagnostics: [],
+            atcFindings: [],
             dirty: false,
             loading: false,
             saving: false,
+            checking: false,
             error: toEditorErrorMessage(error),
           })
           sourceBaselineRef.current = ""
@@ -982,6 +1131,7 @@ export default function AliciaTerminal() {
     [
       beginSourceOperation,
       inferDisplayNameFromPath,
+      releaseSourceLock,
       runtime.state,
       runtime.workspace,
       setSourceEditorWithRef,
@@ -1042,9 +1192,13 @@ export default function AliciaTerminal() {
     const objectUri = currentEditor.objectUri
     const refKind = currentEditor.refKind
     const requestId = beginSourceOperation()
+    releaseSourceLock()
 
     setSourceEditorWithRef((previous) => ({
       ...previous,
+      lock: null,
+      diagnostics: [],
+      atcFindings: [],
       loading: true,
       error: null,
     }))
@@ -1105,6 +1259,7 @@ export default function AliciaTerminal() {
     }
   }, [
     beginSourceOperation,
+    releaseSourceLock,
     runtime.state,
     runtime.workspace,
     setSourceEditorWithRef,
@@ -1156,10 +1311,20 @@ export default function AliciaTerminal() {
 
     try {
       if (refKind === "abap") {
-        const result = await neuroUpdateSource({
-          objectUri,
-          source: sourceToSave,
-          etag,
+        const update = { objectUri, source: sourceToSave, etag }
+        const result = await neuroUpdateSource(update).catch((error: unknown) => {
+          const details =
+            error && typeof error === "object"
+              ? (error as Partial<NeuroRuntimeCommandError>).details
+              : undefined
+          if (details?.confirmation !== "required") {
+            throw error
+          }
+          const diff = typeof details.diff === "string" ? details.diff : ""
+          if (!window.confirm(`Gravar estas alteracoes no SAP?\n\n${diff.slice(0, 4000)}`)) {
+            throw new Error("gravacao no SAP cancelada.")
+          }
+          return neuroUpdateSource(update, undefined, { confirmed: true })
         })
 
         if (
@@ -1177,6 +1342,8 @@ export default function AliciaTerminal() {
         setSourceEditorWithRef((previous) => ({
           ...previous,
           etag: result.etag ?? previous.etag,
+          diagnostics: [],
+          atcFindings: [],
           dirty: sourceStillMatches ? false : previous.dirty,
           saving: false,
           error: null,
@@ -1215,8 +1382,15 @@ export default function AliciaTerminal() {
       ) {
         return
       }
+      const syntaxMessages =
+        error && typeof error === "object"
+          ? (error as Partial<NeuroRuntimeCommandError>).details?.messages
+          : undefined
       setSourceEditorWithRef((previous) => ({
         ...previous,
+        diagnostics: Array.isArray(syntaxMessages)
+          ? (syntaxMessages as NeuroCheckMessage[])
+          : previous.diagnostics,
         saving: false,
         error: `Falha ao salvar codigo: ${toEditorErrorMessage(error)}`,
       }))
@@ -1229,6 +1403,165 @@ export default function AliciaTerminal() {
     toEditorErrorMessage,
   ])
 
+  const handleCheckSource = useCallback(async () => {
+    const currentEditor = sourceEditorRef.current
+    if (
+      currentEditor.refKind !== "abap" ||
+      !currentEditor.objectUri ||
+      currentEditor.loading ||
+      currentEditor.saving ||
+      currentEditor.checking
+    ) {
+      return
+    }
+    const objectUri = currentEditor.objectUri
+
+    setSourceEditorWithRef((previous) => ({
+      ...previous,
+      checking: true,
+      error: null,
+    }))
+
+    try {
+      const result = await neuroSyntaxCheck({ objectUri, source: currentEditor.source })
+      if (sourceEditorRef.current.objectUri !== objectUri) {
+        return
+      }
+      setSourceEditorWithRef((previous) => ({
+        ...previous,
+        diagnostics: result.messages,
+        checking: false,
+      }))
+    } catch (error) {
+      if (sourceEditorRef.current.objectUri !== objectUri) {
+        return
+      }
+      setSourceEditorWithRef((previous) => ({
+        ...previous,
+        checking: false,
+        error: `Falha na verificacao de sintaxe: ${toEditorErrorMessage(error)}`,
+      }))
+    }
+  }, [setSourceEditorWithRef, toEditorErrorMessage])
+
+  const handleActivateSource = useCallback(async () => {
+    const currentEditor = sourceEditorRef.current
+    if (
+      currentEditor.refKind !== "abap" ||
+      !currentEditor.objectUri ||
+      currentEditor.dirty ||
+      currentEditor.loading ||
+      currentEditor.saving ||
+      currentEditor.checking
+    ) {
+      return
+    }
+    const objectUri = currentEditor.objectUri
+
+    setSourceEditorWithRef((previous) => ({
+      ...previous,
+      checking: true,
+      error: null,
+    }))
+
+    try {
+      const activation = { objectUris: [objectUri] }
+      const result = await neuroActivate(activation).catch((error: unknown) => {
+        const details =
+          error && typeof error === "object"
+            ? (error as Partial<NeuroRuntimeCommandError>).details
+            : undefined
+        if (details?.confirmation !== "required") {
+          throw error
+        }
+        if (!window.confirm(`Ativar ${objectUri} no SAP?`)) {
+          throw new Error("ativacao no SAP cancelada.")
+        }
+        return neuroActivate(activation, undefined, { confirmed: true })
+      })
+      if (sourceEditorRef.current.objectUri !== objectUri) {
+        return
+      }
+      const messages = [
+        ...result.objects.flatMap((object) => object.messages),
+        ...result.messages,
+      ]
+      const firstError = messages.find((message) => message.severity === "error")
+      setSourceEditorWithRef((previous) => ({
+        ...previous,
+        diagnostics: messages,
+        checking: false,
+        error: result.activated
+          ? null
+          : `Ativacao falhou${firstError ? `: ${firstError.text}` : "."}`,
+      }))
+    } catch (error) {
+      if (sourceEditorRef.current.objectUri !== objectUri) {
+        return
+      }
+      setSourceEditorWithRef((previous) => ({
+        ...previous,
+        checking: false,
+        error: `Falha ao ativar objeto: ${toEditorErrorMessage(error)}`,
+      }))
+    }
+  }, [setSourceEditorWithRef, toEditorErrorMessage])
+
+  const handleRunAtc = useCallback(async () => {
+    const currentEditor = sourceEditorRef.current
+    if (
+      currentEditor.refKind !== "abap" ||
+      !currentEditor.objectUri ||
+      currentEditor.dirty ||
+      currentEditor.loading ||
+      currentEditor.saving ||
+      currentEditor.checking
+    ) {
+      return
+    }
+    const objectUri = currentEditor.objectUri
+
+    setSourceEditorWithRef((previous) => ({
+      ...previous,
+      checking: true,
+      error: null,
+    }))
+
+    try {
+      const result = await neuroAtcRun({ objectUris: [objectUri] })
+      if (sourceEditorRef.current.objectUri !== objectUri) {
+        return
+      }
+      setSourceEditorWithRef((previous) => ({
+        ...previous,
+        diagnostics: atcFindingsToDiagnostics(result.findings),
+        atcFindings: result.findings,
+        checking: false,
+        error: result.complete
+          ? null
+          : "ATC nao concluiu dentro do tempo limite; os achados podem estar incompletos.",
+      }))
+    } catch (error) {
+      if (sourceEditorRef.current.objectUri !== objectUri) {
+        return
+      }
+      setSourceEditorWithRef((previous) => ({
+        ...previous,
+        checking: false,
+        error: `Falha ao executar ATC: ${toEditorErrorMessage(error)}`,
+      }))
+    }
+  }, [setSourceEditorWithRef, toEditorErrorMessage])
+
+  const handleFixAtcFindings = useCallback(() => {
+    const findings = sourceEditorRef.current.atcFindings
+    if (findings.length === 0) {
+      return
+    }
+    setActiveMainTab("chat")
+    void handleSubmit(buildAtcFindingsPrompt(findings))
+  }, [handleSubmit])
+
   const handleCloseEditor = useCallback(() => {
     const currentEditor = sourceEditorRef.current
     if (
@@ -1242,8 +1575,9 @@ export default function AliciaTerminal() {
 
     sourceOperationSeqRef.current += 1
     sourceBaselineRef.current = ""
+    releaseSourceLock()
     setSourceEditorWithRef(INITIAL_SOURCE_EDITOR_STATE)
-  }, [setSourceEditorWithRef])
+  }, [releaseSourceLock, setSourceEditorWithRef])
 
   if (initializing) {
     return (
@@ -1411,24 +1745,43 @@ export default function AliciaTerminal() {
                   language={sourceEditor.language}
                   source={sourceEditor.source}
                   etag={sourceEditor.etag}
+                  lock={sourceEditor.lock}
+                  diagnostics={sourceEditor.diagnostics}
+                  atcFindingCount={sourceEditor.atcFindings.length}
                   dirty={sourceEditor.dirty}
                   loading={sourceEditor.loading}
                   saving={sourceEditor.saving}
+                  checking={sourceEditor.checking}
                   error={sourceEditor.error}
-                  onChangeSource={(value) => {
-                    setSourceEditorWithRef((previous) => ({
-                      ...previous,
-                      source: value,
-                      dirty: Boolean(previous.objectUri) && value !== sourceBaselineRef.current,
-                      error: null,
-                    }))
-                  }}
+                  onChangeSource={handleChangeSource}
                   onReload={() => {
                     void handleReloadSource()
                   }}
                   onSave={() => {
                     void handleSaveSource()
                   }}
+                  onCheck={
+                    sourceEditor.refKind === "abap"
+                      ? () => {
+                          void handleCheckSource()
+                        }
+                      : undefined
+                  }
+                  onActivate={
+                    sourceEditor.refKind === "abap"
+                      ? () => {
+                          void handleActivateSource()
+                        }
+                      : undefined
+                  }
+                  onRunAtc={
+                    sourceEditor.refKind === "abap"
+                      ? () => {
+                          void handleRunAtc()
+                        }
+                      : undefined
+                  }
+                  onFixAtcFindings={sourceEditor.atcFindings.length > 0 ? handleFixAtcFindings : undefined}
                   onClose={handleCloseEditor}
                 />
               ) : (
@@ -1534,24 +1887,43 @@ export default function AliciaTerminal() {
                 language={sourceEditor.language}
                 source={sourceEditor.source}
                 etag={sourceEditor.etag}
+                lock={sourceEditor.lock}
+                diagnostics={sourceEditor.diagnostics}
+                atcFindingCount={sourceEditor.atcFindings.length}
                 dirty={sourceEditor.dirty}
                 loading={sourceEditor.loading}
                 saving={sourceEditor.saving}
+                checking={sourceEditor.checking}
                 error={sourceEditor.error}
-                onChangeSource={(value) => {
-                  setSourceEditorWithRef((previous) => ({
-                    ...previous,
-                    source: value,
-                    dirty: Boolean(previous.objectUri) && value !== sourceBaselineRef.current,
-                    error: null,
-                  }))
-                }}
+                onChangeSource={handleChangeSource}
                 onReload={() => {
                   void handleReloadSource()
                 }}
                 onSave={() => {
                   void handleSaveSource()
                 }}
+                onCheck={
+                  sourceEditor.refKind === "abap"
+                    ? () => {
+                        void handleCheckSource()
+                      }
+                    : undefined
+                }
+                onActivate={
+                  sourceEditor.refKind === "abap"
+                    ? () => {
+                        void handleActivateSource()
+                      }
+                    : undefined
+                }
+                onRunAtc={
+                  sourceEditor.refKind === "abap"
+                    ? () => {
+                        void handleRunAtc()
+                      }
+                    : undefined
+                }
+                onFixAtcFindings={sourceEditor.atcFindings.length > 0 ? handleFixAtcFindings : undefined}
                 onClose={handleCloseEditor}
               />
             </ResizablePanel>
@@ -1626,6 +1998,7 @@ export default function AliciaTerminal() {
         usage={statusSignals.usage}
         reasoning={statusSignals.reasoning}
         isThinking={isThinking}
+        sapHealth={sapHealth}
         onOpenPanel={(panel) => {
           if (panel === "model") {
             void openModelPanel(true)
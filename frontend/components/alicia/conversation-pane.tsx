@@ -76,6 +76,10 @@ function approvalRisk(
     return "high"
   }
 
+  if (approval.kind === "neuro_write") {
+    return "high"
+  }
+
   if (approval.kind === "file_change" || approval.command.trim().length > 0) {
     return "medium"
   }
@@ -295,28 +299,41 @@ export function ConversationPane({
         )}
 
         {pendingApprovals.map((approval) => (
-          <ApprovalRequest
-            key={approval.actionId}
-            toolName={
-              approval.kind === "file_change"
-                ? `fileChange:${approval.itemId || "pending"}`
-                : approval.command || `command:${approval.itemId || "pending"}`
-            }
-            description={approvalDescription(approval)}
-            risk={approvalRisk(approval)}
-            onApprove={() => {
-              void onApprovalDecision(approval.actionId, "accept")
-            }}
-            onAlwaysApprove={() => {
-              void onApprovalDecision(approval.actionId, "acceptForSession")
-            }}
-            onDeny={() => {
-              void onApprovalDecision(approval.actionId, "decline")
-            }}
-            onCancel={() => {
-              void onApprovalDecision(approval.actionId, "cancel")
-            }}
-          />
+          <div key={approval.actionId}>
+            <ApprovalRequest
+              toolName={
+                approval.kind === "file_change"
+                  ? `fileChange:${approval.itemId || "pending"}`
+                  : approval.kind === "neuro_write"
+                    ? `neuroWrite:${approval.itemId || "pending"}`
+                    : approval.command || `command:${approval.itemId || "pending"}`
+              }
+              description={approvalDescription(approval)}
+              risk={approvalRisk(approval)}
+              onApprove={() => {
+                void onApprovalDecision(approval.actionId, "accept")
+              }}
+              onAlwaysApprove={
+                approval.kind === "neuro_write"
+                  ? undefined
+                  : () => {
+                      void onApprovalDecision(approval.actionId, "acceptForSession")
+                    }
+              }
+              onDeny={() => {
+                void onApprovalDecision(approval.actionId, "decline")
+              }}
+              onCancel={() => {
+                void onApprovalDecision(approval.actionId, "cancel")
+              }}
+            />
+            {approval.diff &&
+              parseUnifiedDiffFiles(approval.diff).map((file, index) => (
+                <div key={`${file.filename}-${index}`} className="px-5 pb-2 ml-9">
+                  <DiffViewer filename={file.filename} lines={file.lines} />
+                </div>
+              ))}
+          </div>
         ))}
 
         {pendingUserInput && (
@@ -54,6 +54,13 @@ const serverIcons: Record<string, typeof Server> = {
   search: Search,
 }
 
+/** Served by the app itself and registered on every native thread; not in `config.toml`. */
+const APP_MANAGED_SERVER = "neuro"
+
+function isAppManaged(server: McpServer): boolean {
+  return server.name === APP_MANAGED_SERVER
+}
+
 const statusConfig = {
   connected: { dot: "bg-terminal-green", text: "text-terminal-green" },
   disconnected: { dot: "bg-muted-foreground/50", text: "text-muted-foreground/60" },
@@ -100,6 +107,9 @@ function commandFailureMessage(result: {
 }
 
 function authBadgeLabel(server: McpServer): string {
+  if (isAppManaged(server)) {
+    return "app"
+  }
   const authStatus = server.authStatus ?? "not_logged_in"
   if (authStatus === "not_logged_in") {
     return "oauth required"
@@ -115,12 +125,17 @@ function authBadgeLabel(server: McpServer): string {
 
 function canLogin(server: McpServer): boolean {
   const authStatus = server.authStatus ?? "not_logged_in"
-  return server.transport === "streamable-http" && authStatus === "not_logged_in"
+  return (
+    !isAppManaged(server) &&
+    server.transport === "streamable-http" &&
+    authStatus === "not_logged_in"
+  )
 }
 
 function canLogout(server: McpServer): boolean {
   const authStatus = server.authStatus ?? "not_logged_in"
   return (
+    !isAppManaged(server) &&
     server.transport === "streamable-http" &&
     (authStatus === "oauth" || authStatus === "bearer_token")
   )
@@ -153,6 +168,7 @@ function ServerRow({
   const Icon = serverIcons[server.id] || Server
   const effectiveStatus = isReconnecting ? "connecting" : server.status
   const status = statusConfig[effectiveStatus]
+  const configurable = !isAppManaged(server)
 
   return (
     <div className="border border-panel-border rounded-lg overflow-hidden bg-background/20">
@@ -229,14 +245,16 @@ function ServerRow({
           )}
 
           <div className="flex flex-wrap items-center gap-2 px-3 py-2 border-t border-panel-border bg-background/20">
-            <button
-              disabled={isBusy}
-              onClick={() => onGet(server)}
-              className="flex items-center gap-1 text-[10px] text-terminal-cyan/80 hover:text-terminal-cyan disabled:opacity-40 transition-colors"
-            >
-              <ListChecks className="w-3 h-3" />
-              Get
-            </button>
+            {configurable && (
+              <button
+                disabled={isBusy}
+                onClick={() => onGet(server)}
+                className="flex items-center gap-1 text-[10px] text-terminal-cyan/80 hover:text-terminal-cyan disabled:opacity-40 transition-colors"
+              >
+                <ListChecks className="w-3 h-3" />
+                Get
+              </button>
+            )}
 
             {canLogin(server) && (
               <button
@@ -260,23 +278,27 @@ function ServerRow({
               </button>
             )}
 
-            <button
-              disabled={isBusy}
-              onClick={() => onReconnect(server)}
-              className="flex items-center gap-1 text-[10px] text-terminal-blue/80 hover:text-terminal-blue disabled:opacity-40 transition-colors"
-            >
-              <RefreshCw className={`w-3 h-3 ${isReconnecting ? "animate-spin" : ""}`} />
-              Reconnect
-            </button>
+            {configurable && (
+              <>
+                <button
+                  disabled={isBusy}
+                  onClick={() => onReconnect(server)}
+                  className="flex items-center gap-1 text-[10px] text-terminal-blue/80 hover:text-terminal-blue disabled:opacity-40 transition-colors"
+                >
+                  <RefreshCw className={`w-3 h-3 ${isReconnecting ? "animate-spin" : ""}`} />
+                  Reconnect
+                </button>
 
-            <button
-              disabled={isBusy}
-              onClick={() => onRemove(server)}
-              className="flex items-center gap-1 text-[10px] text-terminal-red/80 hover:text-terminal-red disabled:opacity-40 transition-colors"
-            >
-              <Trash2 className="w-3 h-3" />
-              Remove
-            </button>
+                <button
+                  disabled={isBusy}
+                  onClick={() => onRemove(server)}
+                  className="flex items-center gap-1 text-[10px] text-terminal-red/80 hover:text-terminal-red disabled:opacity-40 transition-colors"
+                >
+                  <Trash2 className="w-3 h-3" />
+                  Remove
+                </button>
+              </>
+            )}
 
             {server.url && (
               <span className="ml-auto text-[10px] text-muted-foreground/30 font-mono truncate max-w-[220px]">
@@ -1,7 +1,10 @@
 "use client"
 
+import { useEffect, useRef, useState } from "react"
 import dynamic from "next/dynamic"
-import { Loader2, RefreshCw, Save, X } from "lucide-react"
+import type { OnMount } from "@monaco-editor/react"
+import { CheckCircle2, Loader2, Lock, RefreshCw, Save, ShieldCheck, Sparkles, X, Zap } from "lucide-react"
+import type { NeuroCheckMessage, NeuroObjectLock } from "@/lib/tauri-bridge"
 
 const MonacoEditor = dynamic(() => import("@monaco-editor/react"), {
   ssr: false,
@@ -18,36 +21,93 @@ interface SourceEditorPanelProps {
   language: string
   source: string
   etag: string | null
+  lock: NeuroObjectLock | null
+  diagnostics: NeuroCheckMessage[]
+  atcFindingCount: number
   dirty: boolean
   loading: boolean
   saving: boolean
+  checking: boolean
   error: string | null
   onChangeSource: (value: string) => void
   onReload: () => void
   onSave: () => void
+  onCheck?: () => void
+  onActivate?: () => void
+  onRunAtc?: () => void
+  onFixAtcFindings?: () => void
   onClose: () => void
 }
 
+type MonacoEditorInstance = Parameters<OnMount>[0]
+type MonacoInstance = Parameters<OnMount>[1]
+
+const DIAGNOSTICS_OWNER = "neuro"
+
 export function SourceEditorPanel({
   objectUri,
   displayName,
   language,
   source,
   etag,
+  lock,
+  diagnostics,
+  atcFindingCount,
   dirty,
   loading,
   saving,
+  checking,
   error,
   onChangeSource,
   onReload,
   onSave,
+  onCheck,
+  onActivate,
+  onRunAtc,
+  onFixAtcFindings,
   onClose,
 }: SourceEditorPanelProps) {
+  const editorRef = useRef<MonacoEditorInstance | null>(null)
+  const monacoRef = useRef<MonacoInstance | null>(null)
+  const [editorMountCount, setEditorMountCount] = useState(0)
+
+  useEffect(() => {
+    const monaco = monacoRef.current
+    const model = editorRef.current?.getModel()
+    if (!monaco || !model) {
+      return
+    }
+    monaco.editor.setModelMarkers(
+      model,
+      DIAGNOSTICS_OWNER,
+      diagnostics.map((diagnostic) => {
+        const line = Math.min(Math.max(diagnostic.line ?? 1, 1), model.getLineCount())
+        return {
+          severity:
+            diagnostic.severity === "error"
+              ? monaco.MarkerSeverity.Error
+              : diagnostic.severity === "warning"
+                ? monaco.MarkerSeverity.Warning
+                : monaco.MarkerSeverity.Info,
+          message: diagnostic.code ? `${diagnostic.text} (${diagnostic.code})` : diagnostic.text,
+          startLineNumber: line,
+          startColumn: Math.max((diagnostic.column ?? 0) + 1, 1),
+          endLineNumber: line,
+          endColumn: model.getLineMaxColumn(line),
+        }
+      }),
+    )
+  }, [diagnostics, editorMountCount, source])
+
+  const errorCount = diagnostics.filter((diagnostic) => diagnostic.severity === "error").length
+  const warningCount = diagnostics.filter((diagnostic) => diagnostic.severity === "warning").length
   const statusText = loading
     ? "Carregando..."
     : saving
       ? "Salvando..."
-      : error
+      : checking
+        ? "Verificando..."
+        : error
         ? "Erro"
         : dirty
           ? "Alterado"
@@ -86,6 +146,36 @@ export function SourceEditorPanel({
             <RefreshCw className="h-3 w-3" />
             Recarregar
           </button>
+          {onCheck ? (
+            <button
+              onClick={onCheck}
+              disabled={!objectUri || loading || saving || checking}
+              className="inline-flex items-center gap-1 rounded px-2 py-1 text-xs bg-background/60 text-muted-foreground hover:text-terminal-fg disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              {checking ? <Loader2 className="h-3 w-3 animate-spin" /> : <CheckCircle2 className="h-3 w-3" />}
+              Verificar
+            </button>
+          ) : null}
+          {onActivate ? (
+            <button
+              onClick={onActivate}
+              disabled={!objectUri || loading || saving || checking || dirty}
+              className="inline-flex items-center gap-1 rounded px-2 py-1 text-xs bg-background/60 text-muted-foreground hover:text-terminal-fg disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              <Zap className="h-3 w-3" />
+              Ativar
+            </button>
+          ) : null}
+          {onRunAtc ? (
+            <button
+              onClick={onRunAtc}
+              disabled={!objectUri || loading || saving || checking || dirty}
+              className="inline-flex items-center gap-1 rounded px-2 py-1 text-xs bg-background/60 text-muted-foreground hover:text-terminal-fg disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              <ShieldCheck className="h-3 w-3" />
+              ATC
+            </button>
+          ) : null}
           <button
             onClick={onClose}
             className="ml-auto inline-flex items-center gap-1 rounded px-2 py-1 text-xs bg-background/60 text-muted-foreground hover:text-terminal-fg"
@@ -95,6 +185,29 @@ export function SourceEditorPanel({
           </button>
         </div>
         {etag ? <p className="mt-1 truncate text-[10px] text-muted-foreground">ETag: {etag}</p> : null}
+        {lock ? (
+          <p className="mt-1 inline-flex items-center gap-1 truncate text-[10px] text-terminal-green">
+            <Lock className="h-3 w-3" />
+            Objeto bloqueado para edicao
+            {lock.transport ? ` · Transporte ${lock.transport}` : lock.isLocal ? " · Objeto local" : ""}
+          </p>
+        ) : null}
+        {diagnostics.length > 0 ? (
+          <p className="mt-1 text-[10px] text-muted-foreground">
+            {errorCount} erro(s), {warningCount} aviso(s)
+            {atcFindingCount > 0 ? ` em ${atcFindingCount} achado(s) ATC` : " na verificacao de sintaxe"}
+          </p>
+        ) : null}
+        {atcFindingCount > 0 && onFixAtcFindings ? (
+          <button
+            onClick={onFixAtcFindings}
+            disabled={checking}
+            className="mt-1 inline-flex items-center gap-1 rounded px-2 py-1 text-[10px] bg-terminal-green/15 text-terminal-green hover:bg-terminal-green/25 disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            <Sparkles className="h-3 w-3" />
+            Corrigir achados ATC com o agente
+          </button>
+        ) : null}
         {error ? <p className="mt-1 text-xs text-terminal-red">{error}</p> : null}
       </div>
       <div className="min-h-0 flex-1">
@@ -105,13 +218,18 @@ export function SourceEditorPanel({
             theme="vs-dark"
             value={source}
             onChange={(value) => onChangeSource(value ?? "")}
+            onMount={(editor, monaco) => {
+              editorRef.current = editor
+              monacoRef.current = monaco
+              setEditorMountCount((count) => count + 1)
+            }}
             options={{
               automaticLayout: true,
               minimap: { enabled: false },
               fontSize: 13,
               scrollBeyondLastLine: false,
               tabSize: 2,
-              readOnly: loading || saving,
+              readOnly: loading || saving || checking,
             }}
           />
         ) : (
@@ -16,6 +16,7 @@ import {
   Clock,
   Gauge,
   AppWindow,
+  Database,
 } from "lucide-react"
 import {
   type AliciaState,
@@ -23,6 +24,7 @@ import {
   APPROVAL_PRESETS,
 } from "@/lib/alicia-types"
 import { type UsageStats } from "@/lib/runtime-statusline"
+import { type NeuroDiagnoseStatus } from "@/lib/tauri-bridge"
 
 interface RuntimeStatus {
   connected: boolean
@@ -37,6 +39,7 @@ interface StatusBarProps {
   usage: UsageStats | null
   reasoning: string | null
   isThinking: boolean
+  sapHealth?: { status: NeuroDiagnoseStatus; detail: string | null } | null
   onOpenPanel: (panel: AliciaState["activePanel"]) => void
 }
 
@@ -52,6 +55,12 @@ const approvalColors: Record<ApprovalPreset, string> = {
   "full-access": "text-terminal-red",
 }
 
+const sapHealthColors: Record<NeuroDiagnoseStatus, string> = {
+  healthy: "text-terminal-green",
+  degraded: "text-terminal-gold",
+  unavailable: "text-terminal-red",
+}
+
 const effortColors: Record<string, string> = {
   none: "#77797c",
   minimal: "#77797c",
@@ -194,6 +203,7 @@ export function StatusBar({
   usage,
   reasoning,
   isThinking,
+  sapHealth,
   onOpenPanel,
 }: StatusBarProps) {
   const ApprovalIcon = approvalIcons[state.approvalPreset]
@@ -297,6 +307,20 @@ export function StatusBar({
           <span className="text-muted-foreground/30">({totalTools})</span>
         </button>
 
+        {sapHealth && (
+          <>
+            <Separator />
+
+            <div
+              title={sapHealth.detail ?? `SAP ${sapHealth.status}`}
+              className={`flex items-center gap-1 px-1.5 py-0.5 rounded ${sapHealthColors[sapHealth.status]}`}
+            >
+              <Database className="w-3 h-3" />
+              <span>sap</span>
+            </div>
+          </>
+        )}
+
         <Separator />
 
         <button
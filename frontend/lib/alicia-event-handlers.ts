@@ -306,7 +306,13 @@ export function createCodexEventHandler({
       const nextApproval: ApprovalRequestState = {
         actionId: String(payload.action_id ?? ""),
         kind:
-          payload.kind === "file_change" ? "file_change" : "command_execution",
+          payload.kind === "file_change"
+            ? "file_change"
+            : payload.kind === "dynamic_tool_call"
+              ? "dynamic_tool_call"
+              : payload.kind === "neuro_write"
+                ? "neuro_write"
+                : "command_execution",
         threadId: String(payload.thread_id ?? ""),
         turnId: String(payload.turn_id ?? ""),
         itemId: String(payload.item_id ?? ""),
@@ -322,6 +328,7 @@ export function createCodexEventHandler({
         )
           ? payload.proposed_execpolicy_amendment.map((entry) => String(entry))
           : [],
+        diff: typeof payload.diff === "string" ? payload.diff : undefined,
       }
 
       if (!nextApproval.actionId) {
@@ -29,7 +29,11 @@ export interface Message {
   timestamp: string
 }
 
-export type ApprovalRequestKind = "command_execution" | "file_change"
+export type ApprovalRequestKind =
+  | "command_execution"
+  | "file_change"
+  | "dynamic_tool_call"
+  | "neuro_write"
 
 export interface ApprovalRequestState {
   actionId: string
@@ -43,6 +47,7 @@ export interface ApprovalRequestState {
   grantRoot: string
   commandActions: unknown[]
   proposedExecpolicyAmendment: string[]
+  diff?: string
 }
 
 export interface UserInputQuestionOptionState {
@@ -697,6 +702,9 @@ export function formatStructuredItem(
   if (itemType === "mcp_tool_call") {
     return `[mcp:${String(item.status ?? "in_progress")}] ${String(item.tool ?? "tool")}`
   }
+  if (itemType === "dynamic_tool_call") {
+    return `[tool:${String(item.status ?? "in_progress")}] ${String(item.tool ?? "tool")}`
+  }
   if (itemType === "file_change") {
     return "[file_change] changes applied"
   }
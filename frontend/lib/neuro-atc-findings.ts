@@ -0,0 +1,51 @@
+import type { NeuroAtcFinding, NeuroCheckMessage, NeuroCheckSeverity } from "@/lib/tauri-bridge"
+
+export function atcPrioritySeverity(priority: number): NeuroCheckSeverity {
+  if (priority <= 1) {
+    return "error"
+  }
+  return priority === 2 ? "warning" : "info"
+}
+
+export function atcFindingsToDiagnostics(findings: NeuroAtcFinding[]): NeuroCheckMessage[] {
+  return findings.map((finding) => ({
+    objectUri: finding.objectUri,
+    line: finding.line,
+    column: finding.column,
+    severity: atcPrioritySeverity(finding.priority),
+    text: finding.message,
+    code: finding.checkId,
+  }))
+}
+
+function describeQuickFixes(finding: NeuroAtcFinding): string | null {
+  const kinds = [
+    finding.quickFixes.automatic ? "automatico" : null,
+    finding.quickFixes.manual ? "manual" : null,
+  ].filter((kind): kind is string => kind !== null)
+  return kinds.length > 0 ? `quick fix ${kinds.join("/")} disponivel` : null
+}
+
+export function buildAtcFindingsPrompt(findings: NeuroAtcFinding[]): string {
+  const lines = findings.map((finding, index) => {
+    const objectLabel = finding.objectName ?? finding.objectUri ?? "objeto desconhecido"
+    const location = finding.line !== null ? `${objectLabel}:${finding.line}` : objectLabel
+    const check = finding.checkTitle ?? finding.checkId
+    const details = [
+      `prioridade ${finding.priority}`,
+      check ? `check ${check}` : null,
+      describeQuickFixes(finding),
+    ].filter((detail): detail is string => detail !== null)
+    const uri = finding.locationUri ?? finding.objectUri
+    return `${index + 1}. [${location}] ${finding.message} (${details.join(", ")})${
+      uri ? `\n   ${uri}` : ""
+    }`
+  })
+
+  return [
+    "Corrija estes achados do ATC (ABAP Test Cockpit).",
+    "Use as ferramentas Neuro para ler e atualizar o codigo e rode a verificacao de sintaxe antes de salvar.",
+    "",
+    ...lines,
+  ].join("\n")
+}
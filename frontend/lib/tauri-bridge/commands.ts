@@ -50,20 +50,73 @@ import type {
   CodexWorkspaceReadFileResponse,
   CodexWorkspaceWriteFileRequest,
   CodexWorkspaceWriteFileResponse,
+  GitBlameRequest,
+  GitBlameResponse,
   GitCommitApprovedReviewRequest,
   GitCommitApprovedReviewResponse,
+  GitConflictReadRequest,
+  GitConflictReadResponse,
+  GitConflictResolveRequest,
+  GitConflictResolveResponse,
+  GitFileHistoryRequest,
+  GitFileHistoryResponse,
+  GitThreadExportRequest,
+  GitThreadExportResponse,
   RuntimeCodexConfig,
   RuntimeStatusResponse,
   RuntimeCapabilitiesResponse,
+  NeuroActivateRequest,
+  NeuroActivateResponse,
+  NeuroAtcRunRequest,
+  NeuroAtcRunResponse,
   NeuroAdtObjectSummary,
   NeuroAdtSourceResponse,
   NeuroAdtUpdateSourceRequest,
   NeuroAdtUpdateSourceResponse,
+  NeuroCdsViewResponse,
+  NeuroConnectionProfile,
+  NeuroDataElementResponse,
+  NeuroDataPreviewRequest,
+  NeuroDataPreviewResponse,
+  NeuroDdicTableResponse,
+  NeuroDomainResponse,
+  NeuroHealthHistory,
+  NeuroNavigateRequest,
+  NeuroNavigationResponse,
+  NeuroObjectLock,
+  NeuroObjectStructureResponse,
+  NeuroObjectUnlockResponse,
+  NeuroPushChangesRequest,
+  NeuroPushChangesResponse,
   NeuroRuntimeCommandError,
   NeuroRuntimeDiagnoseResponse,
+  NeuroRuntimeLifecycleResponse,
+  NeuroSecretClearRequest,
+  NeuroSecretSetRequest,
+  NeuroSecretStatus,
+  NeuroServerVersion,
+  NeuroSourceDiffResponse,
+  NeuroSourceEntry,
+  NeuroSyntaxCheckRequest,
+  NeuroSyntaxCheckResponse,
+  NeuroSyncPackageRequest,
+  NeuroSyncPackageResponse,
+  NeuroTelemetrySummary,
   NeuroToolSpec,
+  NeuroTransportAssignment,
+  NeuroTransportAssignRequest,
+  NeuroTransportCreateRequest,
+  NeuroTransportRequest,
+  NeuroUnitTestRequest,
+  NeuroUnitTestResponse,
+  NeuroVersionListResponse,
+  NeuroVersionSourceResponse,
+  NeuroWhereUsedResponse,
   NeuroWsDomainRequest,
   NeuroWsMessageEnvelope,
+  NeuroWsSubscribeRequest,
+  NeuroWsSubscription,
+  NeuroWsUnsubscribeResponse,
   StartCodexSessionConfig,
   StartCodexSessionResponse,
   TerminalCreateRequest,
@@ -99,10 +152,18 @@ interface RawNeuroAdtSourceResponse {
   etag?: string | null
 }
 
+interface RawNeuroSourceEntry {
+  objectUri: string
+  source?: RawNeuroAdtSourceResponse | null
+  error?: RawNeuroRuntimeCommandError | null
+  cached: boolean
+}
+
 interface RawNeuroAdtUpdateSourceRequest {
   object_uri: string
   source: string
   etag?: string | null
+  transport?: string | null
 }
 
 interface RawNeuroAdtUpdateSourceResponse {
@@ -288,10 +349,13 @@ export async function codexRuntimeCapabilities(): Promise<RuntimeCapabilitiesRes
   return invoke<RuntimeCapabilitiesResponse>('codex_runtime_capabilities')
 }
 
-export async function neuroRuntimeDiagnose(): Promise<NeuroRuntimeDiagnoseResponse> {
+export async function neuroRuntimeDiagnose(
+  connectionId?: string,
+): Promise<NeuroRuntimeDiagnoseResponse> {
   try {
     const response = await invoke<RawNeuroCommandResponse<RawNeuroRuntimeDiagnoseResponse>>(
       'neuro_runtime_diagnose',
+      { connectionId },
     )
     return mapNeuroDiagnoseResponse(unwrapNeuroResponse(response))
   } catch (error) {
@@ -302,11 +366,13 @@ export async function neuroRuntimeDiagnose(): Promise<NeuroRuntimeDiagnoseRespon
 export async function neuroSearchObjects(
   query: string,
   maxResults?: number,
+  connectionId?: string,
 ): Promise<NeuroAdtObjectSummary[]> {
   try {
     const response = await invoke<RawNeuroCommandResponse<RawNeuroAdtObjectSummary[]>>(
       'neuro_search_objects',
       {
+        connectionId,
         query,
         maxResults,
       },
@@ -318,11 +384,14 @@ export async function neuroSearchObjects(
   }
 }
 
-export async function neuroGetSource(objectUri: string): Promise<NeuroAdtSourceResponse> {
+export async function neuroGetSource(
+  objectUri: string,
+  connectionId?: string,
+): Promise<NeuroAdtSourceResponse> {
   try {
     const response = await invoke<RawNeuroCommandResponse<RawNeuroAdtSourceResponse>>(
       'neuro_get_source',
-      { objectUri },
+      { connectionId, objectUri },
     )
     const raw = unwrapNeuroResponse(response)
     return mapNeuroSourceResponse(raw)
@@ -331,19 +400,168 @@ export async function neuroGetSource(objectUri: string): Promise<NeuroAdtSourceR
   }
 }
 
+export async function neuroGetSources(
+  objectUris: string[],
+  connectionId?: string,
+): Promise<NeuroSourceEntry[]> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<RawNeuroSourceEntry[]>>(
+      'neuro_get_sources',
+      { connectionId, objectUris },
+    )
+    return unwrapNeuroResponse(response).map(entry => ({
+      objectUri: entry.objectUri,
+      source: entry.source ? mapNeuroSourceResponse(entry.source) : null,
+      error: entry.error ? normalizeRuntimeCommandError(entry.error) : null,
+      cached: entry.cached,
+    }))
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroListVersions(
+  objectUri: string,
+  connectionId?: string,
+): Promise<NeuroVersionListResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroVersionListResponse>>(
+      'neuro_list_versions',
+      { connectionId, objectUri },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroGetVersion(
+  objectUri: string,
+  versionId: string,
+  connectionId?: string,
+): Promise<NeuroVersionSourceResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroVersionSourceResponse>>(
+      'neuro_get_version',
+      { connectionId, objectUri, versionId },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroDiffSource(
+  objectUri: string,
+  localSource: string,
+  connectionId?: string,
+  options: { version?: NeuroServerVersion } = {},
+): Promise<NeuroSourceDiffResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroSourceDiffResponse>>(
+      'neuro_diff_source',
+      { connectionId, objectUri, localSource, version: options.version ?? null },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroDdicTable(
+  name: string,
+  connectionId?: string,
+): Promise<NeuroDdicTableResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroDdicTableResponse>>(
+      'neuro_ddic_table',
+      { connectionId, name },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroDdicDataElement(
+  name: string,
+  connectionId?: string,
+): Promise<NeuroDataElementResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroDataElementResponse>>(
+      'neuro_ddic_data_element',
+      { connectionId, name },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroDdicDomain(
+  name: string,
+  connectionId?: string,
+): Promise<NeuroDomainResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroDomainResponse>>(
+      'neuro_ddic_domain',
+      { connectionId, name },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroCdsView(
+  name: string,
+  connectionId?: string,
+): Promise<NeuroCdsViewResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroCdsViewResponse>>(
+      'neuro_cds_view',
+      { connectionId, name },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroDataPreview(
+  request: NeuroDataPreviewRequest,
+  connectionId?: string,
+): Promise<NeuroDataPreviewResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroDataPreviewResponse>>(
+      'neuro_data_preview',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
 export async function neuroUpdateSource(
   request: NeuroAdtUpdateSourceRequest,
+  connectionId?: string,
+  options: { syntaxCheck?: boolean; confirmed?: boolean } = {},
 ): Promise<NeuroAdtUpdateSourceResponse> {
   try {
     const payload: RawNeuroAdtUpdateSourceRequest = {
       object_uri: request.objectUri,
       source: request.source,
       etag: request.etag ?? null,
+      transport: request.transport ?? null,
     }
     const response = await invoke<RawNeuroCommandResponse<RawNeuroAdtUpdateSourceResponse>>(
       'neuro_update_source',
       {
+        connectionId,
         request: payload,
+        syntaxCheck: options.syntaxCheck,
+        confirmed: options.confirmed,
       },
     )
     const raw = unwrapNeuroResponse(response)
@@ -353,13 +571,179 @@ export async function neuroUpdateSource(
   }
 }
 
+export async function neuroSyntaxCheck(
+  request: NeuroSyntaxCheckRequest,
+  connectionId?: string,
+): Promise<NeuroSyntaxCheckResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroSyntaxCheckResponse>>(
+      'neuro_syntax_check',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroActivate(
+  request: NeuroActivateRequest,
+  connectionId?: string,
+  options: { confirmed?: boolean } = {},
+): Promise<NeuroActivateResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroActivateResponse>>(
+      'neuro_activate',
+      { connectionId, request, confirmed: options.confirmed },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroAtcRun(
+  request: NeuroAtcRunRequest,
+  connectionId?: string,
+): Promise<NeuroAtcRunResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroAtcRunResponse>>(
+      'neuro_atc_run',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroRunUnitTests(
+  request: NeuroUnitTestRequest,
+  connectionId?: string,
+): Promise<NeuroUnitTestResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroUnitTestResponse>>(
+      'neuro_run_unit_tests',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroListTransports(
+  connectionId?: string,
+): Promise<NeuroTransportRequest[]> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroTransportRequest[]>>(
+      'neuro_list_transports',
+      { connectionId },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroCreateTransport(
+  request: NeuroTransportCreateRequest,
+  connectionId?: string,
+): Promise<NeuroTransportAssignment> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroTransportAssignment>>(
+      'neuro_create_transport',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroAssignTransport(
+  request: NeuroTransportAssignRequest,
+  connectionId?: string,
+): Promise<NeuroTransportAssignment> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroTransportAssignment>>(
+      'neuro_assign_transport',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroSyncPackage(
+  request: NeuroSyncPackageRequest,
+  connectionId?: string,
+): Promise<NeuroSyncPackageResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroSyncPackageResponse>>(
+      'neuro_sync_package',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroPushChanges(
+  request: NeuroPushChangesRequest,
+  connectionId?: string,
+): Promise<NeuroPushChangesResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroPushChangesResponse>>(
+      'neuro_push_changes',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroLockObject(
+  objectUri: string,
+  connectionId?: string,
+): Promise<NeuroObjectLock> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroObjectLock>>(
+      'neuro_lock_object',
+      { connectionId, objectUri },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroUnlockObject(
+  objectUri: string,
+  connectionId?: string,
+): Promise<NeuroObjectUnlockResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroObjectUnlockResponse>>(
+      'neuro_unlock_object',
+      { connectionId, objectUri },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
 export async function neuroWsRequest(
   request: NeuroWsDomainRequest,
+  connectionId?: string,
 ): Promise<NeuroWsMessageEnvelope> {
   try {
     const response = await invoke<RawNeuroCommandResponse<RawNeuroWsMessageEnvelope>>(
       'neuro_ws_request',
-      { request },
+      { connectionId, request },
     )
     const raw = unwrapNeuroResponse(response)
     return mapNeuroWsMessageEnvelope(raw)
@@ -368,10 +752,40 @@ export async function neuroWsRequest(
   }
 }
 
-export async function neuroListTools(): Promise<NeuroToolSpec[]> {
+export async function neuroWsSubscribe(
+  request: NeuroWsSubscribeRequest,
+  connectionId?: string,
+): Promise<NeuroWsSubscription> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroWsSubscription>>(
+      'neuro_ws_subscribe',
+      { connectionId, request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroWsUnsubscribe(
+  subscriptionId: string,
+): Promise<NeuroWsUnsubscribeResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroWsUnsubscribeResponse>>(
+      'neuro_ws_unsubscribe',
+      { subscriptionId },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroListTools(connectionId?: string): Promise<NeuroToolSpec[]> {
   try {
     const response = await invoke<RawNeuroCommandResponse<NeuroToolSpec[]>>(
       'neuro_list_tools',
+      { connectionId },
     )
     return unwrapNeuroResponse(response)
   } catch (error) {
@@ -382,13 +796,17 @@ export async function neuroListTools(): Promise<NeuroToolSpec[]> {
 export async function neuroInvokeTool(
   toolName: string,
   argumentsPayload: Record<string, unknown>,
+  connectionId?: string,
+  options: { confirmed?: boolean } = {},
 ): Promise<Record<string, unknown>> {
   try {
     const response = await invoke<RawNeuroCommandResponse<Record<string, unknown>>>(
       'neuro_invoke_tool',
       {
+        connectionId,
         toolName,
         arguments: argumentsPayload,
+        confirmed: options.confirmed,
       },
     )
     return unwrapNeuroResponse(response)
@@ -397,6 +815,178 @@ export async function neuroInvokeTool(
   }
 }
 
+export async function neuroRuntimeReload(
+  connectionId?: string,
+): Promise<NeuroRuntimeLifecycleResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroRuntimeLifecycleResponse>>(
+      'neuro_runtime_reload',
+      { connectionId },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroRuntimeReset(
+  connectionId?: string,
+): Promise<NeuroRuntimeLifecycleResponse> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroRuntimeLifecycleResponse>>(
+      'neuro_runtime_reset',
+      { connectionId },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroConnectionList(): Promise<NeuroConnectionProfile[]> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroConnectionProfile[]>>(
+      'neuro_connection_list',
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroConnectionCreate(
+  profile: NeuroConnectionProfile,
+): Promise<NeuroConnectionProfile> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroConnectionProfile>>(
+      'neuro_connection_create',
+      { profile },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroConnectionUpdate(
+  profile: NeuroConnectionProfile,
+): Promise<NeuroConnectionProfile> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroConnectionProfile>>(
+      'neuro_connection_update',
+      { profile },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroConnectionDelete(connectionId: string): Promise<boolean> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<boolean>>(
+      'neuro_connection_delete',
+      { connectionId },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroHealthHistory(connectionId?: string): Promise<NeuroHealthHistory> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroHealthHistory>>(
+      'neuro_health_history',
+      { connectionId },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroTelemetrySummary(): Promise<NeuroTelemetrySummary> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroTelemetrySummary>>(
+      'neuro_telemetry_summary',
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroSecretStatus(): Promise<NeuroSecretStatus> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroSecretStatus>>(
+      'neuro_secret_status',
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroSecretCreate(passphrase: string): Promise<NeuroSecretStatus> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroSecretStatus>>(
+      'neuro_secret_create',
+      { passphrase },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroSecretUnlock(passphrase: string): Promise<NeuroSecretStatus> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroSecretStatus>>(
+      'neuro_secret_unlock',
+      { passphrase },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroSecretLock(): Promise<NeuroSecretStatus> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroSecretStatus>>(
+      'neuro_secret_lock',
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroSecretSet(request: NeuroSecretSetRequest): Promise<NeuroSecretStatus> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<NeuroSecretStatus>>(
+      'neuro_secret_set',
+      { request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
+export async function neuroSecretClear(request: NeuroSecretClearRequest): Promise<boolean> {
+  try {
+    const response = await invoke<RawNeuroCommandResponse<boolean>>(
+      'neuro_secret_clear',
+      { request },
+    )
+    return unwrapNeuroResponse(response)
+  } catch (error) {
+    throw normalizeNeuroRuntimeError(error)
+  }
+}
+
 export async function loadCodexDefaultConfig(): Promise<RuntimeCodexConfig> {
   return invoke<RuntimeCodexConfig>('load_codex_default_config')
 }
@@ -575,6 +1165,34 @@ export async function gitCommitApprovedReview(
   })
 }
 
+export async function gitConflictRead(
+  request: GitConflictReadRequest = {},
+): Promise<GitConflictReadResponse> {
+  return invoke<GitConflictReadResponse>('git_conflict_read', { request })
+}
+
+export async function gitConflictResolve(
+  request: GitConflictResolveRequest,
+): Promise<GitConflictResolveResponse> {
+  return invoke<GitConflictResolveResponse>('git_conflict_resolve', { request })
+}
+
+export async function gitThreadExport(
+  request: GitThreadExportRequest,
+): Promise<GitThreadExportResponse> {
+  return invoke<GitThreadExportResponse>('git_thread_export', { request })
+}
+
+export async function gitBlame(request: GitBlameRequest): Promise<GitBlameResponse> {
+  return invoke<GitBlameResponse>('git_blame', { request })
+}
+
+export async function gitFileHistory(
+  request: GitFileHistoryRequest,
+): Promise<GitFileHistoryResponse> {
+  return invoke<GitFileHistoryResponse>('git_file_history', { request })
+}
+
 export async function codexModelsList(): Promise<CodexModelListResponse> {
   return invoke<CodexModelListResponse>('codex_models_list')
 }
@@ -5,6 +5,10 @@ import type {
   CodexRuntimeTimelineEvent,
   CodexStructuredEventPayload,
   LifecycleEventPayload,
+  NeuroHealthEventPayload,
+  NeuroLifecycleEventPayload,
+  NeuroTelemetryEventPayload,
+  NeuroWsEventPayload,
   StreamEventPayload,
   TerminalDataPayload,
   TerminalExitPayload,
@@ -96,6 +100,15 @@ function normalizeTerminalExitPayload(payload: unknown): TerminalExitPayload {
   }
 }
 
+function normalizeNeuroLifecyclePayload(payload: unknown): NeuroLifecycleEventPayload {
+  const source = (payload ?? {}) as Record<string, unknown>
+  return {
+    connectionId: String(source.connectionId ?? source.connection_id ?? 'env'),
+    status: String(source.status ?? 'failed') as NeuroLifecycleEventPayload['status'],
+    message: source.message == null ? null : String(source.message),
+  }
+}
+
 export function isTauriRuntime(): boolean {
   if (typeof window === 'undefined') {
     return false
@@ -155,3 +168,67 @@ export async function listenToTerminalEvents(
     }
   }
 }
+
+export async function listenToNeuroLifecycleEvents(
+  onEvent: (payload: NeuroLifecycleEventPayload) => void,
+): Promise<() => void> {
+  if (!isTauriRuntime()) {
+    return () => undefined
+  }
+
+  const unlisten = await listen('neuro://lifecycle', (event) => {
+    onEvent(normalizeNeuroLifecyclePayload(event.payload))
+  })
+
+  return () => {
+    unlisten()
+  }
+}
+
+export async function listenToNeuroHealthEvents(
+  onEvent: (payload: NeuroHealthEventPayload) => void,
+): Promise<() => void> {
+  if (!isTauriRuntime()) {
+    return () => undefined
+  }
+
+  const unlisten = await listen<NeuroHealthEventPayload>('neuro://health', (event) => {
+    onEvent(event.payload)
+  })
+
+  return () => {
+    unlisten()
+  }
+}
+
+export async function listenToNeuroTelemetryEvents(
+  onEvent: (payload: NeuroTelemetryEventPayload) => void,
+): Promise<() => void> {
+  if (!isTauriRuntime()) {
+    return () => undefined
+  }
+
+  const unlisten = await listen<NeuroTelemetryEventPayload>('neuro://telemetry', (event) => {
+    onEvent(event.payload)
+  })
+
+  return () => {
+    unlisten()
+  }
+}
+
+export async function listenToNeuroWsEvents(
+  onEvent: (payload: NeuroWsEventPayload) => void,
+): Promise<() => void> {
+  if (!isTauriRuntime()) {
+    return () => undefined
+  }
+
+  const unlisten = await listen<NeuroWsEventPayload>('neuro://ws', (event) => {
+    onEvent(event.payload)
+  })
+
+  return () => {
+    unlisten()
+  }
+}
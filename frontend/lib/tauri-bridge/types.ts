@@ -123,10 +123,204 @@ export interface NeuroAdtSourceResponse {
   etag?: string | null
 }
 
+export interface NeuroSourceEntry {
+  objectUri: string
+  source: NeuroAdtSourceResponse | null
+  error: NeuroRuntimeCommandError | null
+  cached: boolean
+}
+
+export interface NeuroObjectVersion {
+  versionId: string
+  title?: string | null
+  author?: string | null
+  updated?: string | null
+  transport?: string | null
+}
+
+export interface NeuroVersionListResponse {
+  objectUri: string
+  versions: NeuroObjectVersion[]
+}
+
+export interface NeuroVersionSourceResponse {
+  objectUri: string
+  versionId: string
+  source: string
+}
+
+export type NeuroServerVersion = 'active' | 'inactive'
+
+export interface NeuroSourceDiffResponse {
+  objectUri: string
+  version: NeuroServerVersion
+  etag?: string | null
+  diff: string
+  changed: boolean
+}
+
+export interface NeuroWhereUsedReference {
+  objectUri: string
+  name?: string | null
+  objectType?: string | null
+  description?: string | null
+  package?: string | null
+  usage: string[]
+}
+
+export interface NeuroWhereUsedResponse {
+  objectUri: string
+  references: NeuroWhereUsedReference[]
+}
+
+export interface NeuroNavigateRequest {
+  objectUri: string
+  line: number
+  column: number
+  source?: string | null
+}
+
+export interface NeuroSourcePosition {
+  objectUri: string
+  line?: number | null
+  column?: number | null
+}
+
+export interface NeuroNavigationResponse {
+  objectUri: string
+  target: {
+    position: NeuroSourcePosition
+    name?: string | null
+    objectType?: string | null
+  } | null
+}
+
+export type NeuroStructureKind = 'method' | 'attribute' | 'include' | 'other'
+
+export interface NeuroStructureElement {
+  name: string
+  objectType?: string | null
+  kind: NeuroStructureKind
+  visibility?: string | null
+  level?: string | null
+  description?: string | null
+  definition?: NeuroSourcePosition | null
+  implementation?: NeuroSourcePosition | null
+}
+
+export interface NeuroObjectStructureResponse {
+  objectUri: string
+  name?: string | null
+  objectType?: string | null
+  methods: NeuroStructureElement[]
+  attributes: NeuroStructureElement[]
+  includes: NeuroStructureElement[]
+  other: NeuroStructureElement[]
+}
+
+export interface NeuroDdicAnnotation {
+  name: string
+  value: string
+}
+
+export interface NeuroDdicField {
+  name: string
+  key: boolean
+  dataType: string
+  dataElement?: string | null
+  builtinType?: string | null
+  length?: number | null
+  decimals?: number | null
+  notNull: boolean
+  annotations: NeuroDdicAnnotation[]
+}
+
+export interface NeuroDdicTableResponse {
+  objectUri: string
+  name: string
+  kind: 'table' | 'structure'
+  description?: string | null
+  annotations: NeuroDdicAnnotation[]
+  fields: NeuroDdicField[]
+  includes: string[]
+  source: string
+}
+
+export interface NeuroDataElementResponse {
+  objectUri: string
+  name: string
+  description?: string | null
+  typeKind?: string | null
+  typeName?: string | null
+  dataType?: string | null
+  length?: number | null
+  decimals?: number | null
+  shortLabel?: string | null
+  mediumLabel?: string | null
+  longLabel?: string | null
+  headingLabel?: string | null
+  searchHelp?: string | null
+}
+
+export interface NeuroDomainResponse {
+  objectUri: string
+  name: string
+  description?: string | null
+  dataType?: string | null
+  length?: number | null
+  decimals?: number | null
+  outputLength?: number | null
+  conversionExit?: string | null
+  signed: boolean
+  lowercase: boolean
+  valueTable?: string | null
+  fixedValues: Array<{ low: string; high?: string | null; text?: string | null }>
+}
+
+export interface NeuroCdsElement {
+  name: string
+  key: boolean
+  expression: string
+  annotations: NeuroDdicAnnotation[]
+}
+
+export interface NeuroCdsViewResponse {
+  objectUri: string
+  name: string
+  description?: string | null
+  annotations: NeuroDdicAnnotation[]
+  elements: NeuroCdsElement[]
+  source: string
+}
+
+export interface NeuroDataPreviewRequest {
+  query?: string | null
+  entity?: string | null
+  maxRows?: number | null
+}
+
+export interface NeuroDataPreviewColumn {
+  name: string
+  description?: string | null
+  dataType?: string | null
+  length?: number | null
+  key: boolean
+}
+
+export interface NeuroDataPreviewResponse {
+  columns: NeuroDataPreviewColumn[]
+  rows: string[][]
+  totalRows?: number | null
+  truncated: boolean
+  executedQuery?: string | null
+  executionTimeMs?: number | null
+}
+
 export interface NeuroAdtUpdateSourceRequest {
   objectUri: string
   source: string
   etag?: string | null
+  transport?: string | null
 }
 
 export interface NeuroAdtUpdateSourceResponse {
@@ -135,6 +329,262 @@ export interface NeuroAdtUpdateSourceResponse {
   etag?: string | null
 }
 
+export interface NeuroObjectLock {
+  objectUri: string
+  transport?: string | null
+  isLocal: boolean
+  acquiredAtEpochSecs: number
+  refreshedAtEpochSecs: number
+  expiresAtEpochSecs: number
+}
+
+export interface NeuroObjectUnlockResponse {
+  objectUri: string
+  released: boolean
+}
+
+export type NeuroCheckSeverity = 'error' | 'warning' | 'info'
+
+export interface NeuroCheckMessage {
+  objectUri?: string | null
+  line?: number | null
+  column?: number | null
+  severity: NeuroCheckSeverity
+  text: string
+  code?: string | null
+}
+
+export interface NeuroSyntaxCheckRequest {
+  objectUri: string
+  source?: string | null
+}
+
+export interface NeuroSyntaxCheckResponse {
+  objectUri: string
+  hasErrors: boolean
+  messages: NeuroCheckMessage[]
+}
+
+export interface NeuroActivateRequest {
+  objectUris: string[]
+}
+
+export interface NeuroActivationObjectResult {
+  objectUri: string
+  activated: boolean
+  messages: NeuroCheckMessage[]
+}
+
+export interface NeuroActivateResponse {
+  activated: boolean
+  objects: NeuroActivationObjectResult[]
+  messages: NeuroCheckMessage[]
+}
+
+export interface NeuroAtcRunRequest {
+  objectUris?: string[]
+  package?: string
+  checkVariant?: string
+  maxFindings?: number
+}
+
+export interface NeuroAtcQuickFixHints {
+  automatic: boolean
+  manual: boolean
+  pseudo: boolean
+}
+
+export interface NeuroAtcFinding {
+  findingUri: string | null
+  objectUri: string | null
+  objectName: string | null
+  objectType: string | null
+  priority: number
+  checkId: string | null
+  checkTitle: string | null
+  messageId: string | null
+  message: string
+  locationUri: string | null
+  line: number | null
+  column: number | null
+  quickFixes: NeuroAtcQuickFixHints
+  documentationUri: string | null
+}
+
+export interface NeuroAtcRunResponse {
+  worklistId: string
+  checkVariant: string
+  complete: boolean
+  findings: NeuroAtcFinding[]
+}
+
+export interface NeuroUnitTestRequest {
+  objectUris: string[]
+  withCoverage?: boolean
+}
+
+export interface NeuroUnitTestStackEntry {
+  uri: string | null
+  name: string | null
+  description: string | null
+  line: number | null
+}
+
+export interface NeuroUnitTestAlert {
+  kind: string
+  severity: string
+  title: string
+  details: string[]
+  stack: NeuroUnitTestStackEntry[]
+}
+
+export interface NeuroUnitTestMethod {
+  name: string
+  uri: string | null
+  executionTimeSecs: number | null
+  passed: boolean
+  alerts: NeuroUnitTestAlert[]
+}
+
+export interface NeuroUnitTestClass {
+  name: string
+  uri: string | null
+  riskLevel: string | null
+  durationCategory: string | null
+  passed: boolean
+  alerts: NeuroUnitTestAlert[]
+  methods: NeuroUnitTestMethod[]
+}
+
+export interface NeuroUnitTestProgram {
+  objectUri: string | null
+  name: string | null
+  objectType: string | null
+  passed: boolean
+  classes: NeuroUnitTestClass[]
+}
+
+export interface NeuroCoverageMetric {
+  executed: number
+  total: number
+  percent: number
+}
+
+export interface NeuroUnitTestCoverage {
+  objectUri: string | null
+  name: string | null
+  objectType: string | null
+  statement: NeuroCoverageMetric | null
+  branch: NeuroCoverageMetric | null
+  procedure: NeuroCoverageMetric | null
+}
+
+export interface NeuroUnitTestResponse {
+  passed: boolean
+  totalMethods: number
+  failedMethods: number
+  programs: NeuroUnitTestProgram[]
+  alerts: NeuroUnitTestAlert[]
+  coverage: NeuroUnitTestCoverage[]
+}
+
+export interface NeuroTransportTask {
+  number: string
+  owner: string | null
+  description: string | null
+}
+
+export interface NeuroTransportRequest {
+  number: string
+  owner: string | null
+  description: string | null
+  status: string | null
+  target: string | null
+  kind: "workbench" | "customizing"
+  tasks: NeuroTransportTask[]
+}
+
+export interface NeuroTransportCreateRequest {
+  objectUri: string
+  package: string
+  description: string
+}
+
+export interface NeuroTransportAssignRequest {
+  objectUri: string
+  transport: string
+}
+
+export interface NeuroTransportAssignment {
+  objectUri: string
+  transport: string
+  package: string | null
+  recordingRequired: boolean
+}
+
+export interface NeuroSyncPackageRequest {
+  package: string
+  targetDir: string
+  includeSubpackages?: boolean
+}
+
+export interface NeuroPackageManifestEntry {
+  objectUri: string
+  objectType: string
+  objectName: string
+  path: string
+  etag: string | null
+  sha256: string
+}
+
+export interface NeuroPackageSkippedObject {
+  objectUri: string
+  objectType: string
+  reason: string
+}
+
+export interface NeuroPackageSyncFailure {
+  objectUri: string
+  objectType: string
+  error: NeuroRuntimeCommandError
+}
+
+export interface NeuroSyncPackageResponse {
+  package: string
+  targetDir: string
+  manifestPath: string
+  objects: NeuroPackageManifestEntry[]
+  skipped: NeuroPackageSkippedObject[]
+  failed: NeuroPackageSyncFailure[]
+}
+
+export interface NeuroPushChangesRequest {
+  targetDir: string
+  dryRun?: boolean
+  syntaxCheck?: boolean
+  transport?: string
+  confirmed?: boolean
+}
+
+export interface NeuroPushedObject {
+  objectUri: string
+  path: string
+  etag: string | null
+}
+
+export interface NeuroPushFailure {
+  objectUri: string
+  path: string
+  error: NeuroRuntimeCommandError
+}
+
+export interface NeuroPushChangesResponse {
+  dryRun: boolean
+  pushed: NeuroPushedObject[]
+  unchanged: number
+  failed: NeuroPushFailure[]
+}
+
 export interface NeuroWsDomainRequest {
   domain: string
   action: string
@@ -150,11 +600,167 @@ export interface NeuroWsMessageEnvelope {
   error?: string | null
 }
 
+export interface NeuroWsSubscribeRequest {
+  domain: string
+  filter?: Record<string, unknown> | null
+}
+
+export interface NeuroWsSubscription {
+  subscriptionId: string
+  connectionId: string
+  domain: string
+  filter?: Record<string, unknown> | null
+}
+
+export interface NeuroWsUnsubscribeResponse {
+  subscriptionId: string
+  removed: boolean
+}
+
+export interface NeuroWsEventPayload {
+  connectionId: string
+  subscriptionIds: string[]
+  envelope: NeuroWsMessageEnvelope
+}
+
 export interface NeuroToolSpec {
   name: string
   description: string
 }
 
+export const NEURO_ENV_CONNECTION_ID = 'env'
+
+export interface NeuroConnectionSafety {
+  readOnly: boolean
+  blockedSourcePatterns: string[]
+  blockedEntityPatterns?: string[]
+  allowedWsDomains: string[]
+  requireEtagForUpdates: boolean
+  syntaxCheckBeforeUpdate?: boolean
+  requireTransportForUpdates?: boolean
+  requireConfirmationForUpdates?: boolean
+}
+
+export interface NeuroConnectionProfile {
+  id: string
+  name: string
+  sapUrl: string
+  user?: string | null
+  passwordEnv?: string | null
+  client?: string | null
+  language?: string | null
+  insecureTls: boolean
+  timeoutSecs?: number | null
+  csrfFetchPath?: string | null
+  searchObjectsPath?: string | null
+  wsUrl?: string | null
+  wsTimeoutSecs?: number | null
+  safety: NeuroConnectionSafety
+}
+
+export type NeuroRuntimeLifecycleStatus = 'reloading' | 'reloaded' | 'reset' | 'failed'
+
+export interface NeuroRuntimeLifecycleResponse {
+  connectionId: string
+  status: NeuroRuntimeLifecycleStatus
+  hadRuntime: boolean
+  drained: boolean
+}
+
+export interface NeuroLifecycleEventPayload {
+  connectionId: string
+  status: NeuroRuntimeLifecycleStatus
+  message?: string | null
+}
+
+export interface NeuroHealthSample {
+  checkedAtEpochSecs: number
+  status: NeuroDiagnoseStatus
+  latencyMs: number
+}
+
+export interface NeuroHealthEventPayload {
+  connectionId: string
+  status: NeuroDiagnoseStatus
+  previousStatus: NeuroDiagnoseStatus | null
+  checkedAtEpochSecs: number
+  detail: string | null
+}
+
+export interface NeuroHealthHistory {
+  connectionId: string
+  status: NeuroDiagnoseStatus | null
+  sinceEpochSecs: number | null
+  availability: number | null
+  samples: NeuroHealthSample[]
+}
+
+export interface NeuroCommandMetrics {
+  command: string
+  count: number
+  errorCount: number
+  errorRate: number
+  p50LatencyMs: number | null
+  p95LatencyMs: number | null
+  errorCodes: Record<string, number>
+}
+
+export interface NeuroErrorCodeMetrics {
+  errorCode: string
+  count: number
+  rate: number
+}
+
+export interface NeuroCacheMetrics {
+  cache: string
+  hits: number
+  misses: number
+  hitRate: number
+}
+
+export interface NeuroTelemetrySummary {
+  sinceEpochSecs: number
+  storePath: string | null
+  totalCount: number
+  errorCount: number
+  errorRate: number
+  commands: NeuroCommandMetrics[]
+  errorCodes: NeuroErrorCodeMetrics[]
+  caches: NeuroCacheMetrics[]
+}
+
+export interface NeuroTelemetryEventPayload {
+  event: string
+  timestampMs: number
+  command?: string
+  success: boolean
+  latencyMs?: number
+  errorCode?: string
+  errorMessage?: string
+  [key: string]: unknown
+}
+
+export type NeuroSecretBackend = 'file' | 'keyring'
+
+export interface NeuroSecretStatus {
+  backend: NeuroSecretBackend
+  initialized: boolean
+  unlocked: boolean
+  path: string
+  names: string[]
+}
+
+export interface NeuroSecretSetRequest {
+  connectionId?: string | null
+  name: string
+  value: string
+}
+
+export interface NeuroSecretClearRequest {
+  connectionId?: string | null
+  name: string
+}
+
 export type NeuroRuntimeErrorCode =
   | 'adt_http_error'
   | 'adt_auth_error'
@@ -243,10 +849,52 @@ export interface CodexWorkspaceWriteFileResponse {
   path: string
 }
 
+export interface ConventionalCommitRules {
+  types?: string[]
+  scopes?: string[]
+  requireScope?: boolean
+  maxSubjectLength?: number
+  allowBreaking?: boolean
+  requireBody?: boolean
+}
+
 export interface GitCommitApprovedReviewRequest {
   paths: string[]
-  message: string
+  message?: string
   cwd?: string
+  generateMessage?: boolean
+  conventionalCommits?: ConventionalCommitRules
+  signing?: GitCommitSigningOptions
+  author?: string
+  coAuthors?: string[]
+  noVerify?: boolean
+  amend?: boolean
+  threadId?: string
+}
+
+export interface GitCommitSigningOptions {
+  format?: 'gpg' | 'ssh' | 'x509'
+  key?: string
+}
+
+export interface GitHookCheck {
+  name: string
+  status: 'passed' | 'failed' | 'skipped'
+  hookId: string | null
+  exitCode: number | null
+  details: string[]
+}
+
+export interface GitCommitHookReport {
+  installed: string[]
+  verifySkipped: boolean
+  failedHook: string | null
+  checks: GitHookCheck[]
+}
+
+export interface CommitMessageValidation {
+  valid: boolean
+  errors: string[]
 }
 
 export interface GitStepResult {
@@ -260,6 +908,112 @@ export interface GitCommitApprovedReviewResponse {
   success: boolean
   add: GitStepResult
   commit: GitStepResult
+  message: string | null
+  generatedMessage: boolean
+  validation: CommitMessageValidation | null
+  hooks: GitCommitHookReport | null
+  warnings: string[]
+}
+
+export interface GitConflictReadRequest {
+  paths?: string[]
+  startAgentTurn?: boolean
+  threadId?: string
+}
+
+export interface GitConflictFile {
+  path: string
+  base: string | null
+  ours: string | null
+  theirs: string | null
+  working: string | null
+  binary: boolean
+}
+
+export interface GitConflictReadResponse {
+  cwd: string
+  files: GitConflictFile[]
+  turn: CodexTurnRunResponse | null
+}
+
+export interface GitConflictResolveRequest {
+  path: string
+  content: string
+}
+
+export interface GitConflictResolveResponse {
+  path: string
+  resolved: boolean
+  add: GitStepResult
+  remaining: string[]
+}
+
+export type GitThreadExportFormat = 'patch' | 'bundle' | 'diff'
+
+export interface GitThreadExportRequest {
+  threadId: string
+  format: GitThreadExportFormat
+  outputDir: string
+  revisionRange?: string
+  includeSummary?: boolean
+}
+
+export interface GitExportedCommit {
+  sha: string
+  subject: string
+}
+
+export interface GitThreadExportResponse {
+  threadId: string
+  format: GitThreadExportFormat
+  outputDir: string
+  files: string[]
+  commits: GitExportedCommit[]
+  summaryPath: string | null
+}
+
+export interface GitLineRange {
+  start: number
+  end: number
+}
+
+export interface GitBlameRequest {
+  path: string
+  range?: GitLineRange
+}
+
+export interface GitBlameLine {
+  line: number
+  commit: string
+  author: string
+  authorEmail: string
+  authoredAt: number
+  summary: string
+  content: string
+}
+
+export interface GitBlameResponse {
+  path: string
+  lines: GitBlameLine[]
+}
+
+export interface GitFileHistoryRequest {
+  path: string
+  limit?: number
+}
+
+export interface GitFileHistoryEntry {
+  commit: string
+  author: string
+  authorEmail: string
+  authoredAt: number
+  subject: string
+  path: string | null
+}
+
+export interface GitFileHistoryResponse {
+  path: string
+  entries: GitFileHistoryEntry[]
 }
 
 export interface McpStartupWarmupResponse {
@@ -672,7 +1426,7 @@ export interface CodexUserInputOption {
 
 export interface ApprovalRequest {
   actionId: string
-  kind: "command_execution" | "file_change"
+  kind: "command_execution" | "file_change" | "dynamic_tool_call" | "neuro_write"
   threadId: string
   turnId: string
   itemId?: string
@@ -682,6 +1436,7 @@ export interface ApprovalRequest {
   grantRoot?: string
   commandActions?: string[]
   proposedExecpolicyAmendment?: string[]
+  diff?: string
 }
 
 export interface TurnDiffUpdate {
@@ -775,7 +1530,7 @@ export interface TurnPlanUpdatedRuntimeEvent extends RuntimeEventEnvelopeBase {
 export interface ApprovalRequestedRuntimeEvent extends RuntimeEventEnvelopeBase {
   type: "approval.requested"
   action_id?: string
-  kind?: "command_execution" | "file_change"
+  kind?: "command_execution" | "file_change" | "dynamic_tool_call" | "neuro_write"
   thread_id?: string
   turn_id?: string
   item_id?: string
@@ -785,6 +1540,7 @@ export interface ApprovalRequestedRuntimeEvent extends RuntimeEventEnvelopeBase
   grant_root?: string
   command_actions?: string[]
   proposed_execpolicy_amendment?: string[]
+  diff?: string
 }
 
 export interface ApprovalResolvedRuntimeEvent extends RuntimeEventEnvelopeBase {
@@ -0,0 +1,48 @@
+import { describe, expect, it } from "vitest"
+
+import { atcFindingsToDiagnostics, buildAtcFindingsPrompt } from "@/lib/neuro-atc-findings"
+import type { NeuroAtcFinding } from "@/lib/tauri-bridge"
+
+const finding: NeuroAtcFinding = {
+  findingUri: "/sap/bc/adt/atc/findings/itemid/1",
+  objectUri: "/sap/bc/adt/programs/programs/ztest",
+  objectName: "ZTEST",
+  objectType: "PROG/P",
+  priority: 2,
+  checkId: "CL_CI_TEST_SELECT",
+  checkTitle: "Search problematic statements",
+  messageId: "0001",
+  message: "SELECT * in loop",
+  locationUri: "/sap/bc/adt/programs/programs/ztest/source/main#start=14,2",
+  line: 14,
+  column: 2,
+  quickFixes: { automatic: true, manual: false, pseudo: false },
+  documentationUri: null,
+}
+
+describe("atcFindingsToDiagnostics", () => {
+  it("mapeia prioridade ATC para severidade do editor", () => {
+    const diagnostics = atcFindingsToDiagnostics([
+      { ...finding, priority: 1 },
+      finding,
+      { ...finding, priority: 3 },
+    ])
+    expect(diagnostics.map((diagnostic) => diagnostic.severity)).toEqual([
+      "error",
+      "warning",
+      "info",
+    ])
+    expect(diagnostics[1]).toMatchObject({ line: 14, column: 2, code: "CL_CI_TEST_SELECT" })
+  })
+})
+
+describe("buildAtcFindingsPrompt", () => {
+  it("descreve local, check e quick fix de cada achado", () => {
+    const prompt = buildAtcFindingsPrompt([finding])
+    expect(prompt).toContain("Corrija estes achados do ATC")
+    expect(prompt).toContain(
+      "1. [ZTEST:14] SELECT * in loop (prioridade 2, check Search problematic statements, quick fix automatico disponivel)",
+    )
+    expect(prompt).toContain(finding.locationUri)
+  })
+})